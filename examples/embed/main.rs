@@ -0,0 +1,32 @@
+use std::error::Error as StdError;
+use std::fs;
+
+gltf::include_glb! {
+    /// The asset displayed when no file is given on the command line.
+    fn fallback_asset = "../Box.glb";
+}
+
+fn summarize(gltf: &gltf::GltfSlice) {
+    println!(
+        "{} mesh(es), {} node(s), {} byte(s) of binary payload",
+        gltf.meshes().count(),
+        gltf.nodes().count(),
+        gltf.blob.map_or(0, <[u8]>::len),
+    );
+}
+
+fn run(path: &str) -> Result<(), Box<dyn StdError>> {
+    let bytes = fs::read(path)?;
+    let gltf = gltf::GltfSlice::from_slice(&bytes)?;
+    summarize(&gltf);
+    Ok(())
+}
+
+fn main() {
+    if let Some(path) = std::env::args().nth(1) {
+        run(&path).expect("runtime error");
+    } else {
+        println!("no file given, using the embedded fallback asset");
+        summarize(fallback_asset());
+    }
+}
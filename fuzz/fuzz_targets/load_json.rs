@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (gltf::json::Root, Vec<u8>)| {
+    let (root, bin) = input;
+    let _ = gltf::fuzz::load_json(root, &bin);
+});
@@ -1,13 +1,16 @@
 use crate::validation::{Checked, Error, Validate};
 use crate::{buffer, extensions, Extras, Index, Path, Root};
+#[cfg(feature = "names")]
+use alloc::string::String;
+use core::fmt;
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fmt;
 
 /// The component data type.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ComponentType {
     /// Corresponds to `GL_BYTE`.
     I8 = 1,
@@ -25,6 +28,7 @@ pub enum ComponentType {
 
 /// Specifies whether an attribute, vector, or matrix.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Type {
     /// Scalar quantity.
     Scalar = 1,
@@ -84,6 +88,7 @@ pub mod sparse {
 
     /// Indices of those attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct Indices {
         /// The parent buffer view containing the sparse indices.
         ///
@@ -102,17 +107,20 @@ pub mod sparse {
 
         /// Extension specific data.
         #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extensions: Option<extensions::accessor::sparse::Indices>,
 
         /// Optional application specific data.
         #[serde(default)]
         #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
         #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extras: Extras,
     }
 
     /// Sparse storage of attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct Sparse {
         /// The number of attributes encoded in this sparse accessor.
         pub count: u32,
@@ -132,18 +140,21 @@ pub mod sparse {
 
         /// Extension specific data.
         #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extensions: Option<extensions::accessor::sparse::Sparse>,
 
         /// Optional application specific data.
         #[serde(default)]
         #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
         #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extras: Extras,
     }
 
     /// Array of size `count * number_of_components` storing the displaced
     /// accessor attributes pointed by `accessor::sparse::Indices`.
     #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct Values {
         /// The parent buffer view containing the sparse indices.
         ///
@@ -158,22 +169,26 @@ pub mod sparse {
 
         /// Extension specific data.
         #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extensions: Option<extensions::accessor::sparse::Values>,
 
         /// Optional application specific data.
         #[serde(default)]
         #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
         #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+        #[cfg_attr(feature = "arbitrary", arbitrary(default))]
         pub extras: Extras,
     }
 }
 
 /// A typed view into a buffer view.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Accessor {
     /// The parent buffer view this accessor reads from.
     ///
-    /// This field can be omitted in sparse accessors.
+    /// When omitted, the accessor is initialized with zeros, which is commonly
+    /// used as the base of sparse accessors.
     #[serde(rename = "bufferView")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub buffer_view: Option<Index<buffer::View>>,
@@ -195,12 +210,14 @@ pub struct Accessor {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::accessor::Accessor>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// Specifies if the attribute is a scalar, vector, or matrix.
@@ -209,10 +226,12 @@ pub struct Accessor {
 
     /// Minimum value of each component in this attribute.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_bounds))]
     pub min: Option<Value>,
 
     /// Maximum value of each component in this attribute.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_bounds))]
     pub max: Option<Value>,
 
     /// Optional user-defined name for this object.
@@ -237,12 +256,6 @@ impl Validate for Accessor {
         P: Fn() -> Path,
         R: FnMut(&dyn Fn() -> Path, Error),
     {
-        if self.sparse.is_none() && self.buffer_view.is_none() {
-            // If sparse is missing, then bufferView must be present. Report that bufferView is
-            // missing since it is the more common one to require.
-            report(&|| path().field("bufferView"), Error::Missing);
-        }
-
         self.buffer_view
             .validate(root, || path().field("bufferView"), report);
         self.byte_offset
@@ -265,16 +278,32 @@ impl Validate for Accessor {
 }
 
 // Help serde avoid serializing this glTF 2.0 default value.
+/// Generates `min` or `max` as an array of up to 16 numbers.
+#[cfg(feature = "arbitrary")]
+fn arbitrary_bounds(u: &mut arbitrary::Unstructured) -> arbitrary::Result<Option<Value>> {
+    if !u.arbitrary::<bool>()? {
+        return Ok(None);
+    }
+    let len = u.int_in_range(0..=16)?;
+    let mut values = Vec::with_capacity(len);
+    for _ in 0..len {
+        values.push(Value::from(u.arbitrary::<f32>()?));
+    }
+    Ok(Some(Value::Array(values)))
+}
+
 fn is_normalized_default(b: &bool) -> bool {
     !*b
 }
 
 /// The data type of an index.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IndexComponentType(pub ComponentType);
 
 /// The data type of a generic vertex attribute.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GenericComponentType(pub ComponentType);
 
 impl<'de> de::Deserialize<'de> for Checked<GenericComponentType> {
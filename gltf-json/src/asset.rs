@@ -1,9 +1,11 @@
+use crate::prelude::*;
 use crate::{extensions, Extras};
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 
 /// Metadata about the glTF asset.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Asset {
     /// A copyright message suitable for display to credit the content creator.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,12 +13,14 @@ pub struct Asset {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::asset::Asset>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// Tool that generated this glTF model.
@@ -1,9 +1,10 @@
+use crate::prelude::*;
 use crate::validation::Checked;
 use crate::{extensions, Extras, Index};
+use core::fmt;
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
-use std::fmt;
 
 /// Corresponds to `GL_ARRAY_BUFFER`.
 pub const ARRAY_BUFFER: u32 = 34_962;
@@ -22,6 +23,7 @@ pub const VALID_TARGETS: &[u32] = &[ARRAY_BUFFER, ELEMENT_ARRAY_BUFFER];
 
 /// Specifies the target a GPU buffer should be bound to.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Target {
     /// Corresponds to `GL_ARRAY_BUFFER`.
     ArrayBuffer = 1,
@@ -44,6 +46,7 @@ impl ser::Serialize for Target {
 
 /// A buffer points to binary data representing geometry, animations, or skins.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Buffer {
     /// The length of the buffer in bytes.
     #[serde(default, rename = "byteLength")]
@@ -61,12 +64,14 @@ pub struct Buffer {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::buffer::Buffer>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 }
 
@@ -75,6 +80,7 @@ pub struct Buffer {
 /// <https://github.com/KhronosGroup/glTF/tree/master/specification/2.0#reference-bufferview>
 ///
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct View {
     /// The parent `Buffer`.
     pub buffer: Index<Buffer>,
@@ -109,12 +115,14 @@ pub struct View {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::buffer::View>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 }
 
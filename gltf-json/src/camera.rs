@@ -1,9 +1,11 @@
 use crate::validation::{Checked, Error, Validate};
 use crate::{extensions, Extras, Path, Root};
+#[cfg(feature = "names")]
+use alloc::string::String;
+use core::fmt;
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
-use std::fmt;
 
 /// All valid camera types.
 pub const VALID_CAMERA_TYPES: &[&str] = &["perspective", "orthographic"];
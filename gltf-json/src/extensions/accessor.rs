@@ -1,3 +1,5 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -9,16 +11,28 @@ pub mod sparse {
 
     /// Indices of those attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Indices {}
+    pub struct Indices {
+        #[cfg(feature = "extensions")]
+        #[serde(default, flatten)]
+        pub others: Map<String, Value>,
+    }
 
     /// Sparse storage of attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Sparse {}
+    pub struct Sparse {
+        #[cfg(feature = "extensions")]
+        #[serde(default, flatten)]
+        pub others: Map<String, Value>,
+    }
 
     /// Array of size `count * number_of_components` storing the displaced
     /// accessor attributes pointed by `accessor::sparse::Indices`.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Values {}
+    pub struct Values {
+        #[cfg(feature = "extensions")]
+        #[serde(default, flatten)]
+        pub others: Map<String, Value>,
+    }
 }
 
 /// A typed view into a buffer view.
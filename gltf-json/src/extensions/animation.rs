@@ -1,3 +1,5 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -13,12 +15,24 @@ pub struct Animation {
 
 /// Targets an animation's sampler at a node's property.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct Channel {}
+pub struct Channel {
+    #[cfg(feature = "extensions")]
+    #[serde(default, flatten)]
+    pub others: Map<String, Value>,
+}
 
 /// The index of the node and TRS property that an animation channel targets.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Target {}
+pub struct Target {
+    #[cfg(feature = "extensions")]
+    #[serde(default, flatten)]
+    pub others: Map<String, Value>,
+}
 
 /// Defines a keyframe graph but not its target.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Sampler {}
+pub struct Sampler {
+    #[cfg(feature = "extensions")]
+    #[serde(default, flatten)]
+    pub others: Map<String, Value>,
+}
@@ -1,6 +1,14 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "extensions")]
+use serde_json::{Map, Value};
 
 /// Metadata about the glTF asset.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Asset {}
+pub struct Asset {
+    #[cfg(feature = "extensions")]
+    #[serde(default, flatten)]
+    pub others: Map<String, Value>,
+}
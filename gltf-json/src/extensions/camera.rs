@@ -1,3 +1,5 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
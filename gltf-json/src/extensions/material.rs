@@ -1,5 +1,7 @@
 #[allow(unused_imports)] // different features use different imports
 use crate::{material::StrengthFactor, texture, validation::Validate, Extras};
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -64,6 +66,14 @@ pub struct Material {
     )]
     pub emissive_strength: Option<EmissiveStrength>,
 
+    #[cfg(feature = "KHR_materials_iridescence")]
+    #[serde(
+        default,
+        rename = "KHR_materials_iridescence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub iridescence: Option<Iridescence>,
+
     #[cfg(feature = "extensions")]
     #[serde(default, flatten)]
     pub others: Map<String, Value>,
@@ -415,3 +425,98 @@ pub struct Specular {
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
     pub extras: Extras,
 }
+
+/// A number in the inclusive range [0.0, 1.0] with a default value of 0.0.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IridescenceFactor(pub f32);
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Default for IridescenceFactor {
+    fn default() -> Self {
+        IridescenceFactor(0.0)
+    }
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Validate for IridescenceFactor {}
+
+/// The refractive index of the thin-film layer, with a default value of 1.3.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IridescenceIor(pub f32);
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Default for IridescenceIor {
+    fn default() -> Self {
+        IridescenceIor(1.3)
+    }
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Validate for IridescenceIor {}
+
+/// The minimum thickness of the thin-film layer in nanometers, with a default value of 100.0.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IridescenceThicknessMinimum(pub f32);
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Default for IridescenceThicknessMinimum {
+    fn default() -> Self {
+        IridescenceThicknessMinimum(100.0)
+    }
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Validate for IridescenceThicknessMinimum {}
+
+/// The maximum thickness of the thin-film layer in nanometers, with a default value of 400.0.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IridescenceThicknessMaximum(pub f32);
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Default for IridescenceThicknessMaximum {
+    fn default() -> Self {
+        IridescenceThicknessMaximum(400.0)
+    }
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+impl Validate for IridescenceThicknessMaximum {}
+
+/// A thin-film iridescence effect, where the hue depends on the viewing angle
+/// and the thickness of the film.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Iridescence {
+    /// The iridescence intensity factor.
+    pub iridescence_factor: IridescenceFactor,
+
+    /// The iridescence intensity texture, stored in the red (`R`) channel.
+    /// This will be multiplied by `iridescence_factor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iridescence_texture: Option<texture::Info>,
+
+    /// The index of refraction of the dielectric thin-film layer.
+    pub iridescence_ior: IridescenceIor,
+
+    /// The minimum thickness of the thin-film layer given in nanometers.
+    pub iridescence_thickness_minimum: IridescenceThicknessMinimum,
+
+    /// The maximum thickness of the thin-film layer given in nanometers.
+    pub iridescence_thickness_maximum: IridescenceThicknessMaximum,
+
+    /// The thickness texture of the thin-film layer, stored in the green
+    /// (`G`) channel. The thickness is interpolated between the minimum and
+    /// maximum thickness.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iridescence_thickness_texture: Option<texture::Info>,
+
+    /// Optional application specific data.
+    #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    pub extras: Extras,
+}
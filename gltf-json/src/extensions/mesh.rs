@@ -1,3 +1,7 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
+#[cfg(feature = "KHR_materials_variants")]
+use alloc::vec::Vec;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -38,6 +38,8 @@ pub use self::root::Root;
 
 /// Names of glTF 2.0 extensions enabled by the user.
 pub const ENABLED_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "EXT_lights_image_based")]
+    "EXT_lights_image_based",
     #[cfg(feature = "KHR_lights_punctual")]
     "KHR_lights_punctual",
     #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
@@ -52,10 +54,17 @@ pub const ENABLED_EXTENSIONS: &[&str] = &[
     "KHR_materials_ior",
     #[cfg(feature = "KHR_materials_emissive_strength")]
     "KHR_materials_emissive_strength",
+    #[cfg(feature = "KHR_materials_volume")]
+    "KHR_materials_volume",
+    #[cfg(feature = "KHR_materials_specular")]
+    "KHR_materials_specular",
+    #[cfg(feature = "KHR_materials_iridescence")]
+    "KHR_materials_iridescence",
 ];
 
 /// Names of glTF 2.0 extensions supported by the library.
 pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "EXT_lights_image_based",
     "KHR_lights_punctual",
     "KHR_materials_pbrSpecularGlossiness",
     "KHR_materials_unlit",
@@ -63,4 +72,7 @@ pub const SUPPORTED_EXTENSIONS: &[&str] = &[
     "KHR_materials_transmission",
     "KHR_materials_ior",
     "KHR_materials_emissive_strength",
+    "KHR_materials_volume",
+    "KHR_materials_specular",
+    "KHR_materials_iridescence",
 ];
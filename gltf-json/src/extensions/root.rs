@@ -1,3 +1,11 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
+#[cfg(any(
+    feature = "EXT_lights_image_based",
+    feature = "KHR_lights_punctual",
+    feature = "KHR_materials_variants"
+))]
+use alloc::vec::Vec;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -6,6 +14,14 @@ use serde_json::{Map, Value};
 /// The root object of a glTF 2.0 asset.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Root {
+    #[cfg(feature = "EXT_lights_image_based")]
+    #[serde(
+        default,
+        rename = "EXT_lights_image_based",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ext_lights_image_based: Option<ExtLightsImageBased>,
+
     #[cfg(feature = "KHR_lights_punctual")]
     #[serde(
         default,
@@ -27,6 +43,30 @@ pub struct Root {
     pub others: Map<String, Value>,
 }
 
+#[cfg(feature = "EXT_lights_image_based")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct ExtLightsImageBased {
+    /// Image based lights of the asset.
+    pub lights: Vec<crate::extensions::scene::ext_lights_image_based::ImageBasedLight>,
+}
+
+#[cfg(feature = "EXT_lights_image_based")]
+impl crate::root::Get<crate::extensions::scene::ext_lights_image_based::ImageBasedLight>
+    for crate::Root
+{
+    fn get(
+        &self,
+        id: crate::Index<crate::extensions::scene::ext_lights_image_based::ImageBasedLight>,
+    ) -> Option<&crate::extensions::scene::ext_lights_image_based::ImageBasedLight> {
+        self.extensions
+            .as_ref()?
+            .ext_lights_image_based
+            .as_ref()?
+            .lights
+            .get(id.value())
+    }
+}
+
 #[cfg(feature = "KHR_lights_punctual")]
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct KhrLightsPunctual {
@@ -1,3 +1,5 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "extensions")]
@@ -32,10 +34,12 @@ pub struct Node {
 pub mod khr_lights_punctual {
     use crate::validation::{Checked, Error, Validate};
     use crate::{Extras, Index, Path, Root};
+    #[cfg(feature = "names")]
+    use alloc::string::String;
+    use core::fmt;
     use gltf_derive::Validate;
     use serde::{de, ser};
     use serde_derive::{Deserialize, Serialize};
-    use std::fmt;
 
     /// All valid light types.
     pub const VALID_TYPES: &[&str] = &["directional", "point", "spot"];
@@ -84,7 +88,7 @@ pub mod khr_lights_punctual {
 
         /// Extension specific data.
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        pub extensions: Option<std::boxed::Box<serde_json::value::RawValue>>,
+        pub extensions: Option<alloc::boxed::Box<serde_json::value::RawValue>>,
 
         /// Optional application specific data.
         #[serde(default)]
@@ -158,7 +162,7 @@ pub mod khr_lights_punctual {
     }
 
     fn outer_cone_angle_default() -> f32 {
-        std::f32::consts::FRAC_PI_4
+        core::f32::consts::FRAC_PI_4
     }
 
     impl<'de> de::Deserialize<'de> for Checked<Type> {
@@ -206,10 +210,97 @@ pub mod khr_lights_punctual {
     }
 }
 
+#[cfg(feature = "EXT_lights_image_based")]
+pub mod ext_lights_image_based {
+    use crate::validation::{Error, Validate};
+    use crate::{Extras, Image, Index, Path, Root};
+    #[cfg(feature = "names")]
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use gltf_derive::Validate;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+    pub struct ExtLightsImageBased {
+        pub light: Index<ImageBasedLight>,
+    }
+
+    /// An environment light defined by prefiltered specular cubemaps and
+    /// spherical harmonic irradiance coefficients.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ImageBasedLight {
+        /// Extension specific data.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub extensions: Option<alloc::boxed::Box<serde_json::value::RawValue>>,
+
+        /// Optional application specific data.
+        #[serde(default)]
+        #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
+        #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+        pub extras: Extras,
+
+        /// Brightness multiplier of the environment.
+        #[serde(default = "intensity_default")]
+        pub intensity: f32,
+
+        /// Declares the spherical harmonic coefficients for irradiance up to
+        /// l=2, as nine RGB values.
+        pub irradiance_coefficients: [[f32; 3]; 9],
+
+        /// Optional user-defined name for this object.
+        #[cfg(feature = "names")]
+        #[cfg_attr(feature = "names", serde(skip_serializing_if = "Option::is_none"))]
+        pub name: Option<String>,
+
+        /// XYZW unit quaternion rotating the environment.
+        #[serde(default = "rotation_default")]
+        pub rotation: [f32; 4],
+
+        /// The width and height in pixels of the largest specular mip level.
+        pub specular_image_size: u32,
+
+        /// The cube faces of every specular mip level, largest first, in the
+        /// order +X, -X, +Y, -Y, +Z, -Z.
+        pub specular_images: Vec<[Index<Image>; 6]>,
+    }
+
+    impl Validate for ImageBasedLight {
+        fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where
+            P: Fn() -> Path,
+            R: FnMut(&dyn Fn() -> Path, Error),
+        {
+            for (level, faces) in self.specular_images.iter().enumerate() {
+                for (face, image) in faces.iter().enumerate() {
+                    image.validate(
+                        root,
+                        || path().field("specularImages").index(level).index(face),
+                        report,
+                    );
+                }
+            }
+            self.extensions
+                .validate(root, || path().field("extensions"), report);
+            self.extras
+                .validate(root, || path().field("extras"), report);
+        }
+    }
+
+    fn intensity_default() -> f32 {
+        1.0
+    }
+
+    fn rotation_default() -> [f32; 4] {
+        [0.0, 0.0, 0.0, 1.0]
+    }
+}
+
 #[cfg(feature = "KHR_materials_variants")]
 pub mod khr_materials_variants {
     use crate::validation::{Error, Validate};
     use crate::{Path, Root};
+    use alloc::string::String;
     use serde_derive::{Deserialize, Serialize};
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -231,6 +322,14 @@ pub mod khr_materials_variants {
 /// The root `Node`s of a scene.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Scene {
+    #[cfg(feature = "EXT_lights_image_based")]
+    #[serde(
+        default,
+        rename = "EXT_lights_image_based",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ext_lights_image_based: Option<ext_lights_image_based::ExtLightsImageBased>,
+
     #[cfg(feature = "extensions")]
     #[serde(default, flatten)]
     pub others: Map<String, Value>,
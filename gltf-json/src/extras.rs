@@ -1,13 +1,13 @@
+use core::fmt;
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
-use std::fmt;
 
 #[cfg(feature = "extras")]
 pub use serde_json::value::RawValue;
 
 /// Data type of the `extras` attribute on all glTF objects.
 #[cfg(feature = "extras")]
-pub type Extras = Option<::std::boxed::Box<RawValue>>;
+pub type Extras = Option<alloc::boxed::Box<RawValue>>;
 
 /// Data type of the `extras` attribute on all glTF objects.
 #[cfg(not(feature = "extras"))]
@@ -1,3 +1,8 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
 /// Contains `Accessor` and other related data structures.
 pub mod accessor;
 
@@ -29,6 +34,9 @@ pub mod material;
 /// Contains `Mesh` and other related data structures.
 pub mod mesh;
 
+/// Contains `Metadata`.
+pub mod metadata;
+
 /// Contains `Path`.
 pub mod path;
 
@@ -38,6 +46,10 @@ pub mod root;
 /// Contains `Scene`, `Node`, and other related data structures.
 pub mod scene;
 
+/// Contains functions that validate raw glTF JSON against the glTF JSON schema.
+#[cfg(feature = "schema-validate")]
+pub mod schema;
+
 /// Contains `Skin` and other related data structures.
 pub mod skin;
 
@@ -47,6 +59,12 @@ pub mod texture;
 /// Contains functions that validate glTF JSON data against the specification.
 pub mod validation;
 
+/// The subset of the standard prelude provided by `alloc`.
+mod prelude {
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
+
 #[doc(inline)]
 pub use accessor::Accessor;
 #[doc(inline)]
@@ -64,6 +82,8 @@ pub use material::Material;
 #[doc(inline)]
 pub use mesh::Mesh;
 #[doc(inline)]
+pub use metadata::Metadata;
+#[doc(inline)]
 pub use scene::Node;
 #[doc(inline)]
 pub use scene::Scene;
@@ -92,7 +112,9 @@ pub use serde_json::Value;
 /// so that one can deserialize data structures other than `Root` without
 /// being bound to a specific version of `serde_json`.
 pub mod deserialize {
-    pub use serde_json::{from_reader, from_slice, from_str, from_value};
+    #[cfg(feature = "std")]
+    pub use serde_json::from_reader;
+    pub use serde_json::{from_slice, from_str, from_value};
 }
 
 /// Re-exports of `serde_json` serialization functions.
@@ -101,7 +123,7 @@ pub mod deserialize {
 /// so that one can serialize data structures other than `Root` without
 /// being bound to a specific version of `serde_json`.
 pub mod serialize {
-    pub use serde_json::{
-        to_string, to_string_pretty, to_value, to_vec, to_vec_pretty, to_writer, to_writer_pretty,
-    };
+    pub use serde_json::{to_string, to_string_pretty, to_value, to_vec, to_vec_pretty};
+    #[cfg(feature = "std")]
+    pub use serde_json::{to_writer, to_writer_pretty};
 }
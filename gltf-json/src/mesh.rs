@@ -1,11 +1,12 @@
+use crate::prelude::*;
 use crate::validation::{Checked, Error, Validate};
 use crate::{accessor, extensions, material, Extras, Index};
+use alloc::collections::BTreeMap;
+use core::fmt;
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
 use serde_json::from_value;
-use std::collections::BTreeMap;
-use std::fmt;
 
 /// Corresponds to `GL_POINTS`.
 pub const POINTS: u32 = 0;
@@ -44,6 +45,7 @@ pub const VALID_MORPH_TARGETS: &[&str] = &["POSITION", "NORMAL", "TANGENT"];
 
 /// The type of primitives to render.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Mode {
     /// Corresponds to `GL_POINTS`.
     Points = 1,
@@ -72,15 +74,18 @@ pub enum Mode {
 /// A node can contain one or more meshes and its transform places the meshes in
 /// the scene.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Mesh {
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::mesh::Mesh>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// Optional user-defined name for this object.
@@ -98,6 +103,7 @@ pub struct Mesh {
 
 /// Geometry to be rendered with the given material.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Primitive {
     /// Maps attribute semantic names to the `Accessor`s containing the
     /// corresponding attribute data.
@@ -105,12 +111,14 @@ pub struct Primitive {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::mesh::Primitive>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// The index of the accessor that contains the indices.
@@ -162,7 +170,11 @@ impl Validate for Primitive {
         if let Some(pos_accessor_index) = self.attributes.get(&Checked::Valid(Semantic::Positions))
         {
             // spec: POSITION accessor **must** have `min` and `max` properties defined.
-            let pos_accessor = &root.accessors[pos_accessor_index.value()];
+            // An out of bounds index has already been reported above.
+            let pos_accessor = match root.accessors.get(pos_accessor_index.value()) {
+                Some(pos_accessor) => pos_accessor,
+                None => return,
+            };
 
             let min_path = &|| position_path().field("min");
             if let Some(ref min) = pos_accessor.min {
@@ -189,6 +201,7 @@ impl Validate for Primitive {
 
 /// A dictionary mapping attributes to their deviations in the Morph Target.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MorphTarget {
     /// XYZ vertex position displacements of type `[f32; 3]`.
     #[serde(rename = "POSITION")]
@@ -208,6 +221,7 @@ pub struct MorphTarget {
 
 /// Vertex attribute semantic name.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Semantic {
     /// Extra attribute name.
     #[cfg(feature = "extras")]
@@ -341,28 +355,28 @@ impl ser::Serialize for Semantic {
     }
 }
 
-impl ToString for Semantic {
-    fn to_string(&self) -> String {
+impl fmt::Display for Semantic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Semantic::*;
         match *self {
-            Positions => "POSITION".into(),
-            Normals => "NORMAL".into(),
-            Tangents => "TANGENT".into(),
-            Colors(set) => format!("COLOR_{}", set),
-            TexCoords(set) => format!("TEXCOORD_{}", set),
-            Joints(set) => format!("JOINTS_{}", set),
-            Weights(set) => format!("WEIGHTS_{}", set),
+            Positions => write!(f, "POSITION"),
+            Normals => write!(f, "NORMAL"),
+            Tangents => write!(f, "TANGENT"),
+            Colors(set) => write!(f, "COLOR_{}", set),
+            TexCoords(set) => write!(f, "TEXCOORD_{}", set),
+            Joints(set) => write!(f, "JOINTS_{}", set),
+            Weights(set) => write!(f, "WEIGHTS_{}", set),
             #[cfg(feature = "extras")]
-            Extras(ref name) => format!("_{}", name),
+            Extras(ref name) => write!(f, "_{}", name),
         }
     }
 }
 
-impl ToString for Checked<Semantic> {
-    fn to_string(&self) -> String {
+impl fmt::Display for Checked<Semantic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Checked::Valid(ref semantic) => semantic.to_string(),
-            Checked::Invalid => "<invalid semantic name>".into(),
+            Checked::Valid(ref semantic) => semantic.fmt(f),
+            Checked::Invalid => write!(f, "<invalid semantic name>"),
         }
     }
 }
@@ -0,0 +1,50 @@
+use crate::prelude::*;
+use crate::{scene, Asset, Index};
+use serde_derive::{Deserialize, Serialize};
+
+/// The subset of `Root` needed to index an asset without loading it.
+///
+/// All other top-level properties, such as accessors and materials, are
+/// skipped while parsing instead of being deserialized.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Metadata {
+    /// Metadata about the glTF asset.
+    pub asset: Asset,
+
+    /// Names of glTF extensions used somewhere in this asset.
+    #[serde(default, rename = "extensionsUsed")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions_used: Vec<String>,
+
+    /// Names of glTF extensions required to properly load this asset.
+    #[serde(default, rename = "extensionsRequired")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions_required: Vec<String>,
+
+    /// The meshes, of which only the names are retained.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub meshes: Vec<Named>,
+
+    /// The nodes, of which only the names are retained.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Named>,
+
+    /// The default scene.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scene: Option<Index<scene::Scene>>,
+
+    /// The scenes, of which only the names are retained.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scenes: Vec<Named>,
+}
+
+/// A glTF object of which only the name is retained.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Named {
+    /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
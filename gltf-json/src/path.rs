@@ -1,4 +1,5 @@
-use std::fmt;
+use crate::prelude::*;
+use core::fmt;
 
 /// An immutable JSON source path.
 #[derive(Default, Clone, Debug, PartialEq)]
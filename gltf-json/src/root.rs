@@ -1,10 +1,13 @@
 use crate::buffer;
 use crate::extensions;
+use crate::prelude::*;
 use crate::texture;
 use crate::validation;
+use core::{fmt, marker};
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
-use std::{self, fmt, io, marker};
+#[cfg(feature = "std")]
+use std::io;
 
 use crate::path::Path;
 use crate::{
@@ -24,6 +27,7 @@ pub struct Index<T>(u32, marker::PhantomData<fn() -> T>);
 
 /// The root object of a glTF 2.0 asset.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Root {
     /// An array of accessors.
     #[serde(default)]
@@ -33,6 +37,7 @@ pub struct Root {
     /// An array of keyframe animations.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub animations: Vec<Animation>,
 
     /// Metadata about the glTF asset.
@@ -54,12 +59,14 @@ pub struct Root {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::root::Root>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// Names of glTF extensions used somewhere in this asset.
@@ -75,16 +82,19 @@ pub struct Root {
     /// An array of cameras.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub cameras: Vec<Camera>,
 
     /// An array of images.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub images: Vec<Image>,
 
     /// An array of materials.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub materials: Vec<Material>,
 
     /// An array of meshes.
@@ -100,6 +110,7 @@ pub struct Root {
     /// An array of samplers.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub samplers: Vec<texture::Sampler>,
 
     /// An array of scenes.
@@ -115,6 +126,7 @@ pub struct Root {
     /// An array of textures.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub textures: Vec<Texture>,
 }
 
@@ -139,6 +151,7 @@ impl Root {
     }
 
     /// Deserialize from a stream of JSON.
+    #[cfg(feature = "std")]
     pub fn from_reader<R>(reader: R) -> Result<Self, Error>
     where
         R: io::Read,
@@ -172,6 +185,7 @@ impl Root {
     }
 
     /// Serialize as a JSON byte writertor.
+    #[cfg(feature = "std")]
     pub fn to_writer<W>(&self, writer: W) -> Result<(), Error>
     where
         W: io::Write,
@@ -180,6 +194,7 @@ impl Root {
     }
 
     /// Serialize as a pretty-printed JSON byte writertor.
+    #[cfg(feature = "std")]
     pub fn to_writer_pretty<W>(&self, writer: W) -> Result<(), Error>
     where
         W: io::Write,
@@ -191,7 +206,7 @@ impl Root {
 impl<T> Index<T> {
     /// Creates a new `Index` representing an offset into an array containing `T`.
     pub fn new(value: u32) -> Self {
-        Index(value, std::marker::PhantomData)
+        Index(value, core::marker::PhantomData)
     }
 
     /// Returns the internal offset value.
@@ -242,12 +257,12 @@ impl<T> Clone for Index<T> {
 impl<T> Copy for Index<T> {}
 
 impl<T> Ord for Index<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.0.cmp(&other.0)
     }
 }
 impl<T> PartialOrd for Index<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -259,8 +274,8 @@ impl<T> PartialEq for Index<T> {
     }
 }
 
-impl<T> std::hash::Hash for Index<T> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl<T> core::hash::Hash for Index<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.0.hash(state);
     }
 }
@@ -277,6 +292,19 @@ impl<T> fmt::Display for Index<T> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'u, T> arbitrary::Arbitrary<'u> for Index<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'u>) -> arbitrary::Result<Self> {
+        // Mostly generate small indices, which are likely to be in bounds.
+        let value = if u.ratio(1, 16)? {
+            u.arbitrary()?
+        } else {
+            u.int_in_range(0..=7)?
+        };
+        Ok(Index::new(value))
+    }
+}
+
 impl<T: Validate> Validate for Index<T>
 where
     Root: Get<T>,
@@ -1,7 +1,10 @@
+use crate::prelude::*;
 use crate::validation::Validate;
 use crate::{camera, extensions, mesh, scene, skin, Extras, Index};
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "extras")]
+use serde_json::{value::RawValue, Map, Value};
 
 /// A node in the node hierarchy.  When the node contains `skin`, all
 /// `mesh.primitives` must contain `JOINTS_0` and `WEIGHTS_0` attributes.
@@ -14,6 +17,7 @@ use serde_derive::{Deserialize, Serialize};
 /// animation.channel.target), only TRS properties may be present; `matrix` will not
 /// be present.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Node {
     /// The index of the camera referenced by this node.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -25,12 +29,14 @@ pub struct Node {
 
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::scene::Node>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// 4x4 column-major transformation matrix.
@@ -80,15 +86,18 @@ pub struct Node {
 
 /// The root `Node`s of a scene.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Scene {
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extensions: Option<extensions::scene::Scene>,
 
     /// Optional application specific data.
     #[serde(default)]
     #[cfg_attr(feature = "extras", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(not(feature = "extras"), serde(skip_serializing))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub extras: Extras,
 
     /// Optional user-defined name for this object.
@@ -101,8 +110,102 @@ pub struct Scene {
     pub nodes: Vec<Index<Node>>,
 }
 
+/// The background of a scene, stored in the `background` property of the
+/// scene extras.
+///
+/// glTF does not define scene backgrounds, so this crate uses the following
+/// convention:
+///
+/// ```json
+/// "extras": {
+///     "background": {
+///         "color": [0.1, 0.1, 0.1],
+///         "skybox": 0,
+///         "intensity": 1.0
+///     }
+/// }
+/// ```
+///
+/// All properties are optional. The skybox is a texture holding an
+/// equirectangular panorama, drawn in place of the color when present.
+#[cfg(feature = "extras")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Background {
+    /// Linear RGB background color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[f32; 3]>,
+
+    /// Brightness multiplier of the skybox.
+    #[serde(default = "background_intensity_default")]
+    pub intensity: f32,
+
+    /// The index of the equirectangular skybox texture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skybox: Option<Index<crate::Texture>>,
+}
+
+#[cfg(feature = "extras")]
+fn background_intensity_default() -> f32 {
+    1.0
+}
+
+#[cfg(feature = "extras")]
+impl Default for Background {
+    fn default() -> Self {
+        Self {
+            color: None,
+            intensity: background_intensity_default(),
+            skybox: None,
+        }
+    }
+}
+
+#[cfg(feature = "extras")]
+impl Scene {
+    /// Returns the scene background stored in the extras, if any.
+    ///
+    /// Returns `None` if the extras have no `background` property or if it
+    /// does not follow the convention of [`Background`].
+    pub fn background(&self) -> Option<Background> {
+        let extras = serde_json::from_str::<Value>(self.extras.as_ref()?.get()).ok()?;
+        serde_json::from_value(extras.get("background")?.clone()).ok()
+    }
+
+    /// Stores the scene background in the extras, or removes it if
+    /// `background` is `None`.
+    ///
+    /// Other properties of the extras are kept. Extras which are not a JSON
+    /// object are replaced.
+    pub fn set_background(&mut self, background: Option<Background>) {
+        let mut extras = match self
+            .extras
+            .as_ref()
+            .and_then(|extras| serde_json::from_str::<Value>(extras.get()).ok())
+        {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        match background {
+            Some(background) => {
+                let value = serde_json::to_value(background).expect("background is serializable");
+                extras.insert("background".to_string(), value);
+            }
+            None => {
+                extras.remove("background");
+            }
+        }
+        self.extras = if extras.is_empty() {
+            None
+        } else {
+            let json = Value::Object(extras).to_string();
+            Some(RawValue::from_string(json).expect("extras are valid JSON"))
+        };
+    }
+}
+
 /// Unit quaternion rotation in the order (x, y, z, w), where w is the scalar.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UnitQuaternion(pub [f32; 4]);
 
 impl Default for UnitQuaternion {
@@ -0,0 +1,618 @@
+use crate::prelude::*;
+use crate::{Path, Value};
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// The bundled glTF 2.0 JSON schema files, keyed by file name.
+const SCHEMAS: &[(&str, &str)] = &[
+    (
+        "accessor.schema.json",
+        include_str!("../schema/accessor.schema.json"),
+    ),
+    (
+        "accessor.sparse.indices.schema.json",
+        include_str!("../schema/accessor.sparse.indices.schema.json"),
+    ),
+    (
+        "accessor.sparse.schema.json",
+        include_str!("../schema/accessor.sparse.schema.json"),
+    ),
+    (
+        "accessor.sparse.values.schema.json",
+        include_str!("../schema/accessor.sparse.values.schema.json"),
+    ),
+    (
+        "animation.channel.schema.json",
+        include_str!("../schema/animation.channel.schema.json"),
+    ),
+    (
+        "animation.channel.target.schema.json",
+        include_str!("../schema/animation.channel.target.schema.json"),
+    ),
+    (
+        "animation.sampler.schema.json",
+        include_str!("../schema/animation.sampler.schema.json"),
+    ),
+    (
+        "animation.schema.json",
+        include_str!("../schema/animation.schema.json"),
+    ),
+    (
+        "asset.schema.json",
+        include_str!("../schema/asset.schema.json"),
+    ),
+    (
+        "buffer.schema.json",
+        include_str!("../schema/buffer.schema.json"),
+    ),
+    (
+        "bufferView.schema.json",
+        include_str!("../schema/bufferView.schema.json"),
+    ),
+    (
+        "camera.orthographic.schema.json",
+        include_str!("../schema/camera.orthographic.schema.json"),
+    ),
+    (
+        "camera.perspective.schema.json",
+        include_str!("../schema/camera.perspective.schema.json"),
+    ),
+    (
+        "camera.schema.json",
+        include_str!("../schema/camera.schema.json"),
+    ),
+    (
+        "extension.schema.json",
+        include_str!("../schema/extension.schema.json"),
+    ),
+    (
+        "extras.schema.json",
+        include_str!("../schema/extras.schema.json"),
+    ),
+    (
+        "glTF.schema.json",
+        include_str!("../schema/glTF.schema.json"),
+    ),
+    (
+        "glTFChildOfRootProperty.schema.json",
+        include_str!("../schema/glTFChildOfRootProperty.schema.json"),
+    ),
+    (
+        "glTFProperty.schema.json",
+        include_str!("../schema/glTFProperty.schema.json"),
+    ),
+    (
+        "glTFid.schema.json",
+        include_str!("../schema/glTFid.schema.json"),
+    ),
+    (
+        "image.schema.json",
+        include_str!("../schema/image.schema.json"),
+    ),
+    (
+        "material.normalTextureInfo.schema.json",
+        include_str!("../schema/material.normalTextureInfo.schema.json"),
+    ),
+    (
+        "material.occlusionTextureInfo.schema.json",
+        include_str!("../schema/material.occlusionTextureInfo.schema.json"),
+    ),
+    (
+        "material.pbrMetallicRoughness.schema.json",
+        include_str!("../schema/material.pbrMetallicRoughness.schema.json"),
+    ),
+    (
+        "material.schema.json",
+        include_str!("../schema/material.schema.json"),
+    ),
+    (
+        "mesh.primitive.schema.json",
+        include_str!("../schema/mesh.primitive.schema.json"),
+    ),
+    (
+        "mesh.schema.json",
+        include_str!("../schema/mesh.schema.json"),
+    ),
+    (
+        "node.schema.json",
+        include_str!("../schema/node.schema.json"),
+    ),
+    (
+        "sampler.schema.json",
+        include_str!("../schema/sampler.schema.json"),
+    ),
+    (
+        "scene.schema.json",
+        include_str!("../schema/scene.schema.json"),
+    ),
+    (
+        "skin.schema.json",
+        include_str!("../schema/skin.schema.json"),
+    ),
+    (
+        "texture.schema.json",
+        include_str!("../schema/texture.schema.json"),
+    ),
+    (
+        "textureInfo.schema.json",
+        include_str!("../schema/textureInfo.schema.json"),
+    ),
+];
+
+/// The schema of the root glTF object.
+const ROOT: &str = "glTF.schema.json";
+
+/// A violation of the glTF JSON schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// The JSON path of the offending value.
+    pub path: Path,
+
+    /// The JSON schema keyword that was violated, for example `required`.
+    pub keyword: &'static str,
+
+    /// A human readable description of the violation.
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.as_str().is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Validates raw glTF JSON against the bundled glTF 2.0 JSON schema.
+///
+/// The schema files are transcribed from the official glTF 2.0 schema with
+/// the long-form descriptions removed. Only the keywords used by the glTF
+/// schema are supported; the `format` keyword is not checked.
+///
+/// This complements [`Validate`](../validation/trait.Validate.html), which
+/// checks the semantic rules a schema cannot express, such as indices being
+/// in bounds.
+#[derive(Clone, Debug)]
+pub struct Validator {
+    /// The parsed schema files, keyed by file name.
+    schemas: BTreeMap<&'static str, Value>,
+}
+
+impl Validator {
+    /// Parses the bundled schema files.
+    pub fn new() -> Self {
+        let schemas = SCHEMAS
+            .iter()
+            .map(|(name, source)| {
+                let schema = serde_json::from_str(source).expect("bundled schema is valid JSON");
+                (*name, schema)
+            })
+            .collect();
+        Self { schemas }
+    }
+
+    /// Returns every violation of the glTF 2.0 schema in `value`, which
+    /// should be the root glTF object.
+    pub fn validate(&self, value: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.check(&self.schemas[ROOT], value, &Path::new(), &mut violations);
+        violations
+    }
+
+    /// Checks `value` against `schema`, appending any violations.
+    fn check(&self, schema: &Value, value: &Value, path: &Path, out: &mut Vec<Violation>) {
+        let schema = match schema.as_object() {
+            Some(schema) => schema,
+            // Boolean and empty schemas accept every value.
+            None => return,
+        };
+        if let Some(name) = schema.get("$ref").and_then(Value::as_str) {
+            match self.schemas.get(name) {
+                Some(referenced) => self.check(referenced, value, path, out),
+                None => panic!("bundled schema references unknown schema {}", name),
+            }
+        }
+        if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+            for schema in schemas {
+                self.check(schema, value, path, out);
+            }
+        }
+
+        if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+            if !schemas
+                .iter()
+                .any(|schema| self.accepts(schema, value, path))
+            {
+                let message = match allowed_constants(schemas) {
+                    Some(constants) => format!("expected one of {}, found {}", constants, value),
+                    None => "does not match any of the allowed schemas".to_string(),
+                };
+                out.push(Violation {
+                    path: path.clone(),
+                    keyword: "anyOf",
+                    message,
+                });
+            }
+        }
+        if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+            let matches = schemas
+                .iter()
+                .filter(|schema| self.accepts(schema, value, path))
+                .count();
+            if matches != 1 {
+                out.push(Violation {
+                    path: path.clone(),
+                    keyword: "oneOf",
+                    message: format!("matches {} of the exclusive schemas, expected 1", matches),
+                });
+            }
+        }
+        if let Some(schema) = schema.get("not") {
+            if self.accepts(schema, value, path) {
+                out.push(Violation {
+                    path: path.clone(),
+                    keyword: "not",
+                    message: format!("matches the forbidden schema {}", schema),
+                });
+            }
+        }
+
+        let mut report = |keyword: &'static str, message: String| {
+            out.push(Violation {
+                path: path.clone(),
+                keyword,
+                message,
+            })
+        };
+        if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+            if !has_type(value, expected) {
+                report(
+                    "type",
+                    format!("expected {}, found {}", expected, type_name(value)),
+                );
+                // The remaining keywords are meaningless for the wrong type.
+                return;
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if value != constant {
+                report("const", format!("expected {}, found {}", constant, value));
+            }
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            if !values.contains(value) {
+                report(
+                    "enum",
+                    format!("expected one of {:?}, found {}", values, value),
+                );
+            }
+        }
+
+        match value {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or(0.0);
+                let limit = |keyword| schema.get(keyword).and_then(Value::as_f64);
+                if let Some(minimum) = limit("minimum") {
+                    if number < minimum {
+                        report("minimum", format!("{} is less than {}", number, minimum));
+                    }
+                }
+                if let Some(maximum) = limit("maximum") {
+                    if number > maximum {
+                        report("maximum", format!("{} is greater than {}", number, maximum));
+                    }
+                }
+                if let Some(minimum) = limit("exclusiveMinimum") {
+                    if number <= minimum {
+                        let message = format!("{} is not greater than {}", number, minimum);
+                        report("exclusiveMinimum", message);
+                    }
+                }
+                if let Some(divisor) = limit("multipleOf") {
+                    if number % divisor != 0.0 {
+                        let message = format!("{} is not a multiple of {}", number, divisor);
+                        report("multipleOf", message);
+                    }
+                }
+            }
+            Value::String(string) => {
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    if !matches_pattern(pattern, string) {
+                        let message =
+                            format!("{:?} does not match the pattern {}", string, pattern);
+                        report("pattern", message);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let limit = |keyword| schema.get(keyword).and_then(Value::as_u64);
+                if let Some(minimum) = limit("minItems") {
+                    if (items.len() as u64) < minimum {
+                        let message =
+                            format!("expected at least {} items, found {}", minimum, items.len());
+                        report("minItems", message);
+                    }
+                }
+                if let Some(maximum) = limit("maxItems") {
+                    if (items.len() as u64) > maximum {
+                        let message =
+                            format!("expected at most {} items, found {}", maximum, items.len());
+                        report("maxItems", message);
+                    }
+                }
+                if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+                    let duplicate = items
+                        .iter()
+                        .enumerate()
+                        .any(|(i, item)| items[..i].contains(item));
+                    if duplicate {
+                        report("uniqueItems", "items are not unique".to_string());
+                    }
+                }
+                if let Some(schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.check(schema, item, &path.index(index), out);
+                    }
+                }
+            }
+            Value::Object(object) => {
+                if let Some(minimum) = schema.get("minProperties").and_then(Value::as_u64) {
+                    if (object.len() as u64) < minimum {
+                        let message = format!(
+                            "expected at least {} properties, found {}",
+                            minimum,
+                            object.len()
+                        );
+                        report("minProperties", message);
+                    }
+                }
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            report("required", format!("missing required property `{}`", name));
+                        }
+                    }
+                }
+                if let Some(dependencies) =
+                    schema.get("dependentRequired").and_then(Value::as_object)
+                {
+                    for (name, required) in dependencies {
+                        if !object.contains_key(name) {
+                            continue;
+                        }
+                        for dependency in required.as_array().into_iter().flatten() {
+                            let dependency = dependency.as_str().unwrap_or_default();
+                            if !object.contains_key(dependency) {
+                                let message = format!(
+                                    "property `{}` requires property `{}`",
+                                    name, dependency
+                                );
+                                report("dependentRequired", message);
+                            }
+                        }
+                    }
+                }
+
+                let properties = schema.get("properties").and_then(Value::as_object);
+                let additional = schema.get("additionalProperties");
+                for (name, property) in object {
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(schema) => self.check(schema, property, &path.field(name), out),
+                        None => {
+                            if let Some(schema) = additional {
+                                if schema == &Value::Bool(false) {
+                                    out.push(Violation {
+                                        path: path.key(name),
+                                        keyword: "additionalProperties",
+                                        message: "unexpected property".to_string(),
+                                    });
+                                } else {
+                                    self.check(schema, property, &path.key(name), out);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if `value` satisfies `schema`.
+    fn accepts(&self, schema: &Value, value: &Value, path: &Path) -> bool {
+        let mut violations = Vec::new();
+        self.check(schema, value, path, &mut violations);
+        violations.is_empty()
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validates raw glTF JSON against the bundled glTF 2.0 JSON schema.
+///
+/// This is a shorthand for [`Validator::validate`], parsing the bundled
+/// schema files on every call.
+///
+/// [`Validator::validate`]: struct.Validator.html#method.validate
+pub fn validate(value: &Value) -> Vec<Violation> {
+    Validator::new().validate(value)
+}
+
+/// Returns the constants of an `anyOf` of constants, as used by the glTF
+/// schema for enumerations.
+fn allowed_constants(schemas: &[Value]) -> Option<String> {
+    let constants = schemas
+        .iter()
+        .filter_map(|schema| schema.get("const"))
+        .map(Value::to_string)
+        .collect::<Vec<_>>();
+    if constants.is_empty() {
+        None
+    } else {
+        Some(constants.join(", "))
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => match value {
+            Value::Number(number) => {
+                number.is_i64()
+                    || number.is_u64()
+                    || number.as_f64().map_or(false, |n| n % 1.0 == 0.0)
+            }
+            _ => false,
+        },
+        _ => type_name(value) == expected,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// A single element of a pattern.
+#[derive(Debug)]
+enum Atom {
+    /// Matches the given character.
+    Char(char),
+
+    /// Matches any character in one of the inclusive ranges.
+    Class(Vec<(char, char)>),
+
+    /// Matches any character.
+    Any,
+}
+
+impl Atom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Char(expected) => c == *expected,
+            Atom::Class(ranges) => ranges.iter().any(|&(low, high)| low <= c && c <= high),
+            Atom::Any => true,
+        }
+    }
+}
+
+/// Matches `string` against the regular expression subset used by the glTF
+/// schema: literals, escapes, `.`, character classes, the `?`, `*`, and `+`
+/// quantifiers, and the `^` and `$` anchors.
+fn matches_pattern(pattern: &str, string: &str) -> bool {
+    let mut chars = pattern.chars().peekable();
+    let anchored_start = chars.next_if_eq(&'^').is_some();
+    let mut atoms = Vec::new();
+    let mut anchored_end = false;
+    while let Some(c) = chars.next() {
+        let atom = match c {
+            '$' if chars.peek().is_none() => {
+                anchored_end = true;
+                break;
+            }
+            '\\' => Atom::Char(chars.next().unwrap_or('\\')),
+            '.' => Atom::Any,
+            '[' => {
+                let mut ranges = Vec::new();
+                while let Some(low) = chars.next() {
+                    if low == ']' {
+                        break;
+                    }
+                    let low = if low == '\\' {
+                        chars.next().unwrap_or('\\')
+                    } else {
+                        low
+                    };
+                    let high = if chars.next_if_eq(&'-').is_some() {
+                        chars.next().unwrap_or(low)
+                    } else {
+                        low
+                    };
+                    ranges.push((low, high));
+                }
+                Atom::Class(ranges)
+            }
+            c => Atom::Char(c),
+        };
+        let (min, max) = match chars.peek() {
+            Some('?') => (0, Some(1)),
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            _ => (1, Some(1)),
+        };
+        if min != 1 || max != Some(1) {
+            chars.next();
+        }
+        atoms.push((atom, min, max));
+    }
+
+    let string = string.chars().collect::<Vec<_>>();
+    if anchored_start {
+        match_here(&atoms, &string, anchored_end)
+    } else {
+        (0..=string.len()).any(|start| match_here(&atoms, &string[start..], anchored_end))
+    }
+}
+
+/// Backtracking matcher for `matches_pattern`.
+fn match_here(atoms: &[(Atom, usize, Option<usize>)], string: &[char], anchored_end: bool) -> bool {
+    let ((atom, min, max), rest) = match atoms.split_first() {
+        Some(first) => first,
+        None => return !anchored_end || string.is_empty(),
+    };
+    let available = string.iter().take_while(|&&c| atom.matches(c)).count();
+    let most = max.map_or(available, |max| max.min(available));
+    (*min..=most)
+        .rev()
+        .any(|count| match_here(rest, &string[count..], anchored_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern() {
+        assert!(matches_pattern("^[0-9]+\\.[0-9]+$", "2.0"));
+        assert!(matches_pattern("^[0-9]+\\.[0-9]+$", "10.12"));
+        assert!(!matches_pattern("^[0-9]+\\.[0-9]+$", "2"));
+        assert!(!matches_pattern("^[0-9]+\\.[0-9]+$", "2.0.1"));
+        assert!(matches_pattern("b?c", "abc"));
+    }
+
+    #[test]
+    fn violations() {
+        let value = serde_json::json!({
+            "asset": { "version": "2" },
+            "accessors": [{ "componentType": "float", "count": 0, "type": "VEC3" }],
+            "nodes": [{ "matrix": [1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1], "scale": [1, 1, 1] }],
+            "scene": 0
+        });
+        let mut violations = validate(&value)
+            .into_iter()
+            .map(|violation| (violation.path.0, violation.keyword))
+            .collect::<Vec<_>>();
+        violations.sort();
+        assert_eq!(
+            violations,
+            vec![
+                ("".to_string(), "dependentRequired"),
+                ("accessors[0].componentType".to_string(), "anyOf"),
+                ("accessors[0].count".to_string(), "minimum"),
+                ("asset.version".to_string(), "pattern"),
+                ("nodes[0]".to_string(), "not"),
+            ]
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use crate::validation::Checked;
 use crate::{extensions, image, Extras, Index};
+#[cfg(feature = "names")]
+use alloc::string::String;
+use core::fmt;
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
-use std::fmt;
 
 /// Corresponds to `GL_NEAREST`.
 pub const NEAREST: u32 = 9728;
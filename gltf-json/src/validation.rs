@@ -1,6 +1,7 @@
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use core::hash::Hash;
 use serde::{ser, Serialize, Serializer};
-use std::collections::BTreeMap;
-use std::hash::Hash;
 
 use crate::{Path, Root};
 
@@ -27,6 +28,9 @@ pub enum Error {
 
     /// Some required data has been omitted.
     Missing,
+
+    /// A node is its own ancestor.
+    Cycle,
 }
 
 /// Specifies a type that has been pre-validated during deserialization or otherwise.
@@ -90,6 +94,17 @@ impl<T: Default> Default for Checked<T> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'u, T: arbitrary::Arbitrary<'u>> arbitrary::Arbitrary<'u> for Checked<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'u>) -> arbitrary::Result<Self> {
+        if u.ratio(1, 16)? {
+            Ok(Checked::Invalid)
+        } else {
+            Ok(Checked::Valid(u.arbitrary()?))
+        }
+    }
+}
+
 impl<T> Validate for Checked<T> {
     fn validate<P, R>(&self, _root: &Root, path: P, report: &mut R)
     where
@@ -153,7 +168,7 @@ impl<T: Validate> Validate for Vec<T> {
     }
 }
 
-impl Validate for std::boxed::Box<serde_json::value::RawValue> {
+impl Validate for alloc::boxed::Box<serde_json::value::RawValue> {
     fn validate<P, R>(&self, _: &Root, _: P, _: &mut R)
     where
         P: Fn() -> Path,
@@ -163,10 +178,11 @@ impl Validate for std::boxed::Box<serde_json::value::RawValue> {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "{}",
@@ -174,6 +190,7 @@ impl std::fmt::Display for Error {
                 Error::IndexOutOfBounds => "Index out of bounds",
                 Error::Invalid => "Invalid value",
                 Error::Missing => "Missing data",
+                Error::Cycle => "Cycle in node hierarchy",
             }
         )
     }
@@ -0,0 +1,43 @@
+#![cfg(feature = "lossless")]
+
+use gltf_json::Root;
+
+const LOSSLESS: &str = r#"{
+  "asset": {
+    "version": "2.0",
+    "extensions": { "VENDOR_asset": { "z": 1, "a": [true, null] } }
+  },
+  "accessors": [{
+    "bufferView": 0,
+    "componentType": 5126,
+    "count": 1,
+    "type": "SCALAR",
+    "sparse": {
+      "count": 1,
+      "indices": { "bufferView": 0, "byteOffset": 0, "componentType": 5125, "extensions": { "VENDOR_indices": {} } },
+      "values": { "bufferView": 0, "byteOffset": 0, "extras": { "note": "values" } },
+      "extensions": { "VENDOR_sparse": { "b": 2, "a": 1 } }
+    }
+  }],
+  "buffers": [{ "byteLength": 4 }],
+  "bufferViews": [{ "buffer": 0, "byteLength": 4 }],
+  "nodes": [{
+    "extensions": { "VENDOR_node": { "nested": { "y": "2", "x": "1" } } },
+    "extras": { "z": 1, "a": { "deep": [1, 2, 3] } }
+  }],
+  "extensionsUsed": ["VENDOR_asset", "VENDOR_indices", "VENDOR_node", "VENDOR_sparse"]
+}"#;
+
+#[test]
+fn test_unknown_extensions_and_extras_roundtrip() {
+    let root: Root = serde_json::from_str(LOSSLESS).unwrap();
+    let output = serde_json::to_string(&root).unwrap();
+
+    let expected: serde_json::Value = serde_json::from_str(LOSSLESS).unwrap();
+    let actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(expected, actual);
+
+    // Unknown extensions keep their key order and extras are preserved verbatim.
+    assert!(output.contains(r#"{"VENDOR_asset":{"z":1,"a":[true,null]}}"#));
+    assert!(output.contains(r#"{ "z": 1, "a": { "deep": [1, 2, 3] } }"#));
+}
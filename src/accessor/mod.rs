@@ -55,8 +55,14 @@
 //! # }
 //! ```
 
+#[cfg(feature = "utils")]
+use crate::hash::Hasher;
+#[cfg(feature = "utils")]
+use crate::prelude::*;
 use crate::{buffer, Document};
 
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 pub use json::accessor::ComponentType as DataType;
 pub use json::accessor::Type as Dimensions;
 #[cfg(feature = "extensions")]
@@ -67,6 +73,16 @@ use serde_json::{Map, Value};
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
+/// Compact vertex formats for the accessor writer.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod packed;
+
+/// Write-side counterpart of the accessor utility functions.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod writer;
+
 /// Contains data structures for sparse storage.
 pub mod sparse;
 
@@ -74,6 +90,10 @@ pub mod sparse;
 #[doc(inline)]
 pub use self::util::{Item, Iter};
 
+#[cfg(feature = "utils")]
+#[doc(inline)]
+pub use self::writer::{Component, Element, Writer};
+
 /// A typed view into a buffer view.
 #[derive(Clone, Debug)]
 pub struct Accessor<'a> {
@@ -113,7 +133,7 @@ impl<'a> Accessor<'a> {
 
     /// Returns the buffer view this accessor reads from.
     ///
-    /// This may be `None` if the corresponding accessor is sparse.
+    /// If this is `None`, the accessor is initialized with zeros.
     pub fn view(&self) -> Option<buffer::View<'a>> {
         self.json
             .buffer_view
@@ -188,6 +208,157 @@ impl<'a> Accessor<'a> {
         self.json.normalized
     }
 
+    /// Returns the accessor data as tightly packed elements with any sparse
+    /// substitution applied.
+    ///
+    /// The returned buffer holds `count() * size()` bytes. When the accessor
+    /// has no buffer view the base values are zero. Returns `None` if any of
+    /// the referenced buffer data is unavailable or out of range, or if a
+    /// sparse index exceeds the accessor count.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn dense_data<'s, F>(&self, get_buffer_data: F) -> Option<Vec<u8>>
+    where
+        F: Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let view_slice = |view: &buffer::View<'a>| {
+            let start = view.offset();
+            let end = start + view.length();
+            get_buffer_data(view.buffer()).and_then(|slice| slice.get(start..end))
+        };
+
+        let size = self.size();
+        let count = self.count();
+        let base = match self.view() {
+            Some(view) => {
+                let stride = view.stride().unwrap_or(size);
+                let slice = view_slice(&view)?;
+                // Check the last element before allocating, so that a large
+                // count cannot exhaust memory.
+                if count > 0 {
+                    let end = self.offset() + (count - 1) * stride + size;
+                    slice.get(..end)?;
+                }
+                Some((slice, stride))
+            }
+            None => None,
+        };
+        let mut data = vec![0; size * count];
+        if let Some((slice, stride)) = base {
+            for (i, element) in data.chunks_exact_mut(size).enumerate() {
+                let start = self.offset() + i * stride;
+                element.copy_from_slice(slice.get(start..start + size)?);
+            }
+        }
+
+        if let Some(sparse) = self.sparse() {
+            let sparse_count = sparse.count() as usize;
+            let indices = sparse.indices();
+            let index_type = indices.index_type();
+            let index_size = index_type.size();
+            let start = indices.offset() as usize;
+            let index_data =
+                view_slice(&indices.view())?.get(start..start + sparse_count * index_size)?;
+
+            let values = sparse.values();
+            let start = values.offset() as usize;
+            let value_data = view_slice(&values.view())?.get(start..start + sparse_count * size)?;
+
+            for (index, value) in index_data
+                .chunks_exact(index_size)
+                .zip(value_data.chunks_exact(size))
+            {
+                let index = match index_type {
+                    sparse::IndexType::U8 => index[0] as usize,
+                    sparse::IndexType::U16 => u16::from_le_bytes([index[0], index[1]]) as usize,
+                    sparse::IndexType::U32 => {
+                        u32::from_le_bytes([index[0], index[1], index[2], index[3]]) as usize
+                    }
+                };
+                data.get_mut(index * size..(index + 1) * size)?
+                    .copy_from_slice(value);
+            }
+        }
+
+        Some(data)
+    }
+
+    /// Returns a stable hash of the accessor layout and the data it refers to.
+    ///
+    /// The hash covers the component type, dimensions, normalization, count,
+    /// and the bytes of every element, followed by the sparse indices and
+    /// values if present. Elements are hashed without their stride padding,
+    /// so the same data stored with a different layout produces the same hash.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn content_hash<'s, F>(&self, get_buffer_data: F) -> u64
+    where
+        F: Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut hasher = Hasher::new();
+        self.hash_into(&mut hasher, &get_buffer_data);
+        hasher.finish()
+    }
+
+    /// Feeds the accessor content into `hasher`.
+    #[cfg(feature = "utils")]
+    pub(crate) fn hash_into<'s>(
+        &self,
+        hasher: &mut Hasher,
+        get_buffer_data: &dyn Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
+    ) {
+        let view_slice = |view: &buffer::View<'a>| {
+            let start = view.offset();
+            let end = start + view.length();
+            get_buffer_data(view.buffer()).and_then(|slice| slice.get(start..end))
+        };
+        let write_range =
+            |hasher: &mut Hasher, data: Option<&[u8]>, start: usize, len: usize| match data
+                .and_then(|data| data.get(start..start + len))
+            {
+                Some(bytes) => {
+                    hasher.write(&[1]);
+                    hasher.write(bytes);
+                }
+                None => hasher.write(&[0]),
+            };
+
+        hasher.write_u32(self.data_type().as_gl_enum());
+        hasher.write_u32(self.dimensions() as u32);
+        hasher.write_u32(self.normalized() as u32);
+        hasher.write_u32(self.count() as u32);
+
+        let size = self.size();
+        if let Some(view) = self.view() {
+            let stride = view.stride().unwrap_or(size);
+            let data = view_slice(&view);
+            for i in 0..self.count() {
+                write_range(hasher, data, self.offset() + i * stride, size);
+            }
+        }
+
+        if let Some(sparse) = self.sparse() {
+            let count = sparse.count() as usize;
+            let indices = sparse.indices();
+            let index_size = indices.index_type().size();
+            hasher.write_u32(sparse.count());
+            hasher.write_u32(index_size as u32);
+            write_range(
+                hasher,
+                view_slice(&indices.view()),
+                indices.offset() as usize,
+                count * index_size,
+            );
+            let values = sparse.values();
+            write_range(
+                hasher,
+                view_slice(&values.view()),
+                values.offset() as usize,
+                count * size,
+            );
+        }
+    }
+
     /// Returns sparse storage of attributes that deviate from their initialization
     /// value.
     pub fn sparse(&self) -> Option<sparse::Sparse<'a>> {
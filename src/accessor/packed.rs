@@ -0,0 +1,181 @@
+use crate::accessor::{DataType, Dimensions};
+#[cfg(feature = "extras")]
+use crate::Accessor;
+
+/// A compact vertex format for [`Writer::push_packed`].
+///
+/// The signed normalized formats are standard under the
+/// `KHR_mesh_quantization` extension. Half floats and packed 10-10-10-2
+/// vectors have no glTF component type, so they are stored as raw integer
+/// components, for use as custom attributes whose layout is known to the
+/// renderer. With the `extras` feature, their format is declared in the
+/// accessor extras, see [`Format::of`].
+///
+/// [`Writer::push_packed`]: ../writer/struct.Writer.html#method.push_packed
+/// [`Format::of`]: #method.of
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// Signed normalized 8-bit components.
+    Snorm8,
+
+    /// Signed normalized 16-bit components.
+    Snorm16,
+
+    /// IEEE 754 half precision floats, stored as `UNSIGNED_SHORT` components.
+    Float16,
+
+    /// Three signed normalized 10-bit components followed by a signed
+    /// normalized 2-bit component, packed into 32 bits from the least
+    /// significant bit on, and stored as four `UNSIGNED_BYTE` components.
+    ///
+    /// This matches the `A2B10G10R10_SNORM_PACK32` format of Vulkan and fits
+    /// normals and tangents, whose `w` component is the sign of the bitangent.
+    Snorm10_10_10_2,
+}
+
+impl Format {
+    /// Returns the name of the format as declared in accessor extras.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Format::Snorm8 => "snorm8",
+            Format::Snorm16 => "snorm16",
+            Format::Float16 => "float16",
+            Format::Snorm10_10_10_2 => "snorm10-10-10-2",
+        }
+    }
+
+    /// Returns `true` if the format requires the `KHR_mesh_quantization`
+    /// extension, rather than being a custom layout.
+    pub fn is_quantized(self) -> bool {
+        matches!(self, Format::Snorm8 | Format::Snorm16)
+    }
+
+    /// Returns the component type, dimensions and normalization of the
+    /// accessor storing elements with `dimensions` in this format, or `None`
+    /// if the format cannot store them.
+    pub(crate) fn layout(self, dimensions: Dimensions) -> Option<(DataType, Dimensions, bool)> {
+        match (self, dimensions) {
+            (_, Dimensions::Mat2 | Dimensions::Mat3 | Dimensions::Mat4) => None,
+            (Format::Snorm8, _) => Some((DataType::I8, dimensions, true)),
+            (Format::Snorm16, _) => Some((DataType::I16, dimensions, true)),
+            (Format::Float16, _) => Some((DataType::U16, dimensions, false)),
+            (Format::Snorm10_10_10_2, Dimensions::Vec3 | Dimensions::Vec4) => {
+                Some((DataType::U8, Dimensions::Vec4, false))
+            }
+            (Format::Snorm10_10_10_2, _) => None,
+        }
+    }
+
+    /// Returns the packed format declared in the extras of `accessor`, if
+    /// any.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn of(accessor: &Accessor) -> Option<Self> {
+        let extras = accessor.extras().as_ref()?;
+        let extras: json::Value = json::deserialize::from_str(extras.get()).ok()?;
+        match extras.get("packedFormat")?.as_str()? {
+            "snorm8" => Some(Format::Snorm8),
+            "snorm16" => Some(Format::Snorm16),
+            "float16" => Some(Format::Float16),
+            "snorm10-10-10-2" => Some(Format::Snorm10_10_10_2),
+            _ => None,
+        }
+    }
+}
+
+/// Converts `value` to the signed normalized integer with `max` as its
+/// maximum, rounding to the nearest integer.
+pub(crate) fn to_snorm(value: f32, max: i32) -> i32 {
+    let value = value.clamp(-1.0, 1.0) * max as f32;
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+/// Converts `value` to the nearest half precision float.
+///
+/// Values beyond the range of half floats become infinite.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        // Infinity or NaN.
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent < -10 {
+        return sign;
+    }
+    // Subnormal half floats have an implicit exponent and an explicit
+    // leading one.
+    let (full, shift, half) = if exponent <= 0 {
+        (mantissa | 0x80_0000, (14 - exponent) as u32, 0)
+    } else {
+        (mantissa, 13, (exponent as u32) << 10)
+    };
+    let half = half | (full >> shift);
+    // Round the dropped bits to nearest, ties to even.
+    let remainder = full & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    let round = remainder > halfway || (remainder == halfway && half & 1 == 1);
+    // A carry out of the mantissa correctly increments the exponent.
+    sign | (half + round as u32) as u16
+}
+
+/// Converts the half precision float `bits` to a single precision float.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    let bits = match exponent {
+        0 if mantissa == 0 => sign,
+        0 => {
+            // Normalize the subnormal value.
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3ff;
+            sign | ((113 - shift) << 23) | (mantissa << 13)
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
+/// Packs the signed normalized `value` in the
+/// [`Format::Snorm10_10_10_2`] layout.
+///
+/// [`Format::Snorm10_10_10_2`]: enum.Format.html#variant.Snorm10_10_10_2
+pub fn pack_snorm10(value: [f32; 4]) -> u32 {
+    let [x, y, z, w] = value;
+    let component = |value: f32, max: i32, shift: u32| {
+        let mask = (max as u32) * 2 + 1;
+        ((to_snorm(value, max) as u32) & mask) << shift
+    };
+    component(x, 511, 0) | component(y, 511, 10) | component(z, 511, 20) | component(w, 1, 30)
+}
+
+/// Unpacks a value in the [`Format::Snorm10_10_10_2`] layout.
+///
+/// [`Format::Snorm10_10_10_2`]: enum.Format.html#variant.Snorm10_10_10_2
+pub fn unpack_snorm10(packed: u32) -> [f32; 4] {
+    // Shift each component to the top to sign extend it.
+    let component = |shift: u32, bits: u32| {
+        let value = ((packed << (32 - shift - bits)) as i32) >> (32 - bits);
+        let max = (1 << (bits - 1)) - 1;
+        (value as f32 / max as f32).max(-1.0)
+    };
+    [
+        component(0, 10),
+        component(10, 10),
+        component(20, 10),
+        component(30, 2),
+    ]
+}
@@ -1,6 +1,6 @@
 use byteorder::{ByteOrder, LE};
-use std::marker::PhantomData;
-use std::{iter, mem};
+use core::marker::PhantomData;
+use core::mem;
 
 use crate::{accessor, buffer};
 
@@ -64,6 +64,22 @@ impl<'a, T: Item> Iterator for Iter<'a, T> {
 
 impl<'a, T: Item> ExactSizeIterator for Iter<'a, T> {}
 
+impl<'a, T: Item> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Standard(ref mut iter) => iter.next_back(),
+            Iter::Sparse(ref mut iter) => iter.next_back(),
+        }
+    }
+
+    fn nth_back(&mut self, nth: usize) -> Option<Self::Item> {
+        match self {
+            Iter::Standard(ref mut iter) => iter.nth_back(nth),
+            Iter::Sparse(ref mut iter) => iter.nth_back(nth),
+        }
+    }
+}
+
 /// Iterator over indices of sparse accessor.
 #[derive(Clone, Debug)]
 pub enum SparseIndicesIter<'a> {
@@ -84,6 +100,34 @@ impl<'a> Iterator for SparseIndicesIter<'a> {
             SparseIndicesIter::U32(ref mut iter) => iter.next(),
         }
     }
+
+    fn nth(&mut self, nth: usize) -> Option<Self::Item> {
+        match *self {
+            SparseIndicesIter::U8(ref mut iter) => iter.nth(nth).map(|x| x as u32),
+            SparseIndicesIter::U16(ref mut iter) => iter.nth(nth).map(|x| x as u32),
+            SparseIndicesIter::U32(ref mut iter) => iter.nth(nth),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            SparseIndicesIter::U8(ref iter) => iter.size_hint(),
+            SparseIndicesIter::U16(ref iter) => iter.size_hint(),
+            SparseIndicesIter::U32(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for SparseIndicesIter<'a> {}
+
+impl<'a> DoubleEndedIterator for SparseIndicesIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match *self {
+            SparseIndicesIter::U8(ref mut iter) => iter.next_back().map(|x| x as u32),
+            SparseIndicesIter::U16(ref mut iter) => iter.next_back().map(|x| x as u32),
+            SparseIndicesIter::U32(ref mut iter) => iter.next_back(),
+        }
+    }
 }
 
 /// Iterates over a sparse accessor.
@@ -95,48 +139,102 @@ pub struct SparseIter<'a, T: Item> {
     base: Option<ItemIter<'a, T>>,
 
     /// Sparse indices iterator.
-    indices: iter::Peekable<SparseIndicesIter<'a>>,
+    indices: SparseIndicesIter<'a>,
+
+    /// The next sparse index taken from the front of `indices`.
+    front: Option<u32>,
+
+    /// The next sparse index taken from the back of `indices`.
+    back: Option<u32>,
 
     /// Sparse values iterator.
     values: ItemIter<'a, T>,
 
     /// Iterator counter.
     counter: u32,
+
+    /// Total number of items, i.e. the accessor count, less the number of
+    /// items taken from the back.
+    count: u32,
 }
 
 impl<'a, T: Item> SparseIter<'a, T> {
     /// Constructor.
     ///
     /// Here `base` is allowed to be `None` when the base buffer view is not explicitly specified.
+    /// In that case the number of items cannot be inferred and the iterator is unbounded; prefer
+    /// [`with_count`](#method.with_count).
     pub fn new(
         base: Option<ItemIter<'a, T>>,
         indices: SparseIndicesIter<'a>,
         values: ItemIter<'a, T>,
     ) -> Self {
+        let count = base
+            .as_ref()
+            .map(|iter| iter.len() as u32)
+            .unwrap_or(u32::MAX);
+        Self::with_count(base, indices, values, count)
+    }
+
+    /// Constructor with an explicit item count.
+    ///
+    /// The iterator yields exactly `count` items, substituting zeros for the base values when
+    /// `base` is `None`.
+    pub fn with_count(
+        mut base: Option<ItemIter<'a, T>>,
+        indices: SparseIndicesIter<'a>,
+        values: ItemIter<'a, T>,
+        count: u32,
+    ) -> Self {
+        // Excess base values would otherwise be yielded first when iterating
+        // from the back.
+        if let Some(base) = base.as_mut() {
+            base.truncate(count as usize);
+        }
         SparseIter {
             base,
-            indices: indices.peekable(),
+            indices,
+            front: None,
+            back: None,
             values,
             counter: 0,
+            count,
+        }
+    }
+
+    /// Returns the first sparse index that has not been consumed.
+    fn peek_front(&mut self) -> Option<u32> {
+        if self.front.is_none() {
+            self.front = self.indices.next().or_else(|| self.back.take());
+        }
+        self.front
+    }
+
+    /// Returns the last sparse index that has not been consumed.
+    fn peek_back(&mut self) -> Option<u32> {
+        if self.back.is_none() {
+            self.back = self.indices.next_back().or_else(|| self.front.take());
         }
+        self.back
     }
 }
 
 impl<'a, T: Item> Iterator for SparseIter<'a, T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
+        if self.counter >= self.count {
+            return None;
+        }
+
         let mut next_value = self
             .base
             .as_mut()
             .map(|iter| iter.next())
             .unwrap_or_else(|| Some(T::zero()))?;
 
-        let next_sparse_index = self.indices.peek();
-        if let Some(index) = next_sparse_index {
-            if *index == self.counter {
-                self.indices.next(); // advance
-                next_value = self.values.next().unwrap();
-            }
+        if self.peek_front() == Some(self.counter) {
+            self.front = None;
+            next_value = self.values.next().unwrap();
         }
 
         self.counter += 1;
@@ -144,14 +242,86 @@ impl<'a, T: Item> Iterator for SparseIter<'a, T> {
         Some(next_value)
     }
 
+    fn nth(&mut self, nth: usize) -> Option<Self::Item> {
+        if nth >= self.len() {
+            self.counter = self.count;
+            return None;
+        }
+        if nth > 0 {
+            if let Some(base) = self.base.as_mut() {
+                base.nth(nth - 1)?;
+            }
+        }
+        let target = self.counter + nth as u32;
+        while let Some(index) = self.peek_front() {
+            if index >= target {
+                break;
+            }
+            self.front = None;
+            self.values.next();
+        }
+        self.counter = target;
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let hint = self.values.len() - self.counter as usize;
+        let hint = self.count.saturating_sub(self.counter) as usize;
         (hint, Some(hint))
     }
 }
 
 impl<'a, T: Item> ExactSizeIterator for SparseIter<'a, T> {}
 
+impl<'a, T: Item> DoubleEndedIterator for SparseIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.counter >= self.count {
+            return None;
+        }
+
+        let position = self.count - 1;
+        let mut next_value = self
+            .base
+            .as_mut()
+            .map(|iter| iter.next_back())
+            .unwrap_or_else(|| Some(T::zero()))?;
+
+        if self.peek_back() == Some(position) {
+            self.back = None;
+            next_value = self.values.next_back().unwrap();
+        }
+
+        self.count = position;
+
+        Some(next_value)
+    }
+
+    fn nth_back(&mut self, nth: usize) -> Option<Self::Item> {
+        if nth >= self.len() {
+            self.count = self.counter;
+            return None;
+        }
+        if nth > 0 {
+            if let Some(base) = self.base.as_mut() {
+                base.nth_back(nth - 1)?;
+            }
+        }
+        let target = self.count - nth as u32;
+        while let Some(index) = self.peek_back() {
+            if index < target {
+                break;
+            }
+            self.back = None;
+            self.values.next_back();
+        }
+        self.count = target;
+        self.next_back()
+    }
+}
+
 /// Represents items that can be read by an [`Accessor`].
 ///
 /// [`Accessor`]: struct.Accessor.html
@@ -277,6 +447,27 @@ impl<'a, T: Item> ItemIter<'a, T> {
             _phantom: PhantomData,
         }
     }
+
+    /// Drops all but the first `count` items.
+    fn truncate(&mut self, count: usize) {
+        if count < self.len() {
+            self.data = &self.data[..count * self.stride];
+        }
+    }
+}
+
+impl<'a, T: Item> Iter<'a, T> {
+    /// Drops all items outside of `range`, which is relative to the items
+    /// that have not been consumed yet.
+    pub(crate) fn restrict(&mut self, range: core::ops::Range<usize>) {
+        let len = self.len();
+        if range.end < len {
+            self.nth_back(len - range.end - 1);
+        }
+        if range.start > 0 {
+            self.nth(range.start - 1);
+        }
+    }
 }
 
 impl<'a, 's, T: Item> Iter<'s, T> {
@@ -340,34 +531,49 @@ impl<'a, 's, T: Item> Iter<'s, T> {
                     ItemIter::new(subslice, stride)
                 };
 
-                Some(Iter::Sparse(SparseIter::new(
-                    base_iter, index_iter, value_iter,
+                Some(Iter::Sparse(SparseIter::with_count(
+                    base_iter,
+                    index_iter,
+                    value_iter,
+                    accessor.count() as u32,
                 )))
             }
             None => {
                 debug_assert_eq!(mem::size_of::<T>(), accessor.size());
                 debug_assert!(mem::size_of::<T>() > 0);
 
-                accessor.view().and_then(|view| {
-                    let stride = view.stride().unwrap_or(mem::size_of::<T>());
-                    debug_assert!(
-                        stride >= mem::size_of::<T>(),
-                        "Mismatch in stride, expected at least {} stride but found {}",
-                        mem::size_of::<T>(),
-                        stride
-                    );
-
-                    let start = accessor.offset();
-                    let end = start + stride * (accessor.count() - 1) + mem::size_of::<T>();
-                    let subslice = buffer_view_slice(view, &get_buffer_data)
-                        .and_then(|slice| slice.get(start..end))?;
+                let view = match accessor.view() {
+                    Some(view) => view,
+                    None => {
+                        // Accessors without a buffer view are read as zeros, which is
+                        // equivalent to a sparse accessor without any substitutions.
+                        return Some(Iter::Sparse(SparseIter::with_count(
+                            None,
+                            SparseIndicesIter::U32(ItemIter::new(&[], mem::size_of::<u32>())),
+                            ItemIter::new(&[], mem::size_of::<T>()),
+                            accessor.count() as u32,
+                        )));
+                    }
+                };
 
-                    Some(Iter::Standard(ItemIter {
-                        stride,
-                        data: subslice,
-                        _phantom: PhantomData,
-                    }))
-                })
+                let stride = view.stride().unwrap_or(mem::size_of::<T>());
+                debug_assert!(
+                    stride >= mem::size_of::<T>(),
+                    "Mismatch in stride, expected at least {} stride but found {}",
+                    mem::size_of::<T>(),
+                    stride
+                );
+
+                let start = accessor.offset();
+                let end = start + stride * (accessor.count() - 1) + mem::size_of::<T>();
+                let subslice = buffer_view_slice(view, &get_buffer_data)
+                    .and_then(|slice| slice.get(start..end))?;
+
+                Some(Iter::Standard(ItemIter {
+                    stride,
+                    data: subslice,
+                    _phantom: PhantomData,
+                }))
             }
         }
     }
@@ -429,3 +635,26 @@ impl<'a, T: Item> Iterator for ItemIter<'a, T> {
         (hint, Some(hint))
     }
 }
+
+impl<'a, T: Item> DoubleEndedIterator for ItemIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let start = (len - 1) * self.stride;
+        let val = T::from_slice(&self.data[start..]);
+        self.data = &self.data[..start];
+        Some(val)
+    }
+
+    fn nth_back(&mut self, nth: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if nth >= len {
+            self.data = &[];
+            return None;
+        }
+        self.truncate(len - nth);
+        self.next_back()
+    }
+}
@@ -0,0 +1,599 @@
+use crate::prelude::*;
+use core::mem;
+
+use crate::accessor::packed::{self, Format};
+use crate::accessor::{DataType, Dimensions};
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+
+/// Represents the scalar components that can be written by a [`Writer`].
+///
+/// [`Writer`]: struct.Writer.html
+pub trait Component: Copy + PartialOrd + Into<json::Value> {
+    /// The accessor component type corresponding to this type.
+    const DATA_TYPE: DataType;
+
+    /// Appends the little endian representation of this value to `out`.
+    fn write(self, out: &mut Vec<u8>);
+}
+
+/// Represents items that can be written by a [`Writer`].
+///
+/// This is the write-side counterpart of [`Item`].
+///
+/// [`Writer`]: struct.Writer.html
+/// [`Item`]: ../util/trait.Item.html
+pub trait Element: Copy {
+    /// The scalar component type of this element.
+    type Component: Component;
+
+    /// The accessor dimensions corresponding to this type.
+    const DIMENSIONS: Dimensions;
+
+    /// Visits each component of this element in memory order.
+    fn for_each_component<F: FnMut(Self::Component)>(&self, f: F);
+}
+
+macro_rules! impl_component {
+    ($ty:ty, $data_type:ident) => {
+        impl Component for $ty {
+            const DATA_TYPE: DataType = DataType::$data_type;
+            fn write(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl Element for $ty {
+            type Component = $ty;
+            const DIMENSIONS: Dimensions = Dimensions::Scalar;
+            fn for_each_component<F: FnMut(Self::Component)>(&self, mut f: F) {
+                f(*self)
+            }
+        }
+    };
+}
+
+impl_component!(i8, I8);
+impl_component!(u8, U8);
+impl_component!(i16, I16);
+impl_component!(u16, U16);
+impl_component!(u32, U32);
+impl_component!(f32, F32);
+
+macro_rules! impl_vector {
+    ($n:expr, $dimensions:ident) => {
+        impl<C: Component> Element for [C; $n] {
+            type Component = C;
+            const DIMENSIONS: Dimensions = Dimensions::$dimensions;
+            fn for_each_component<F: FnMut(Self::Component)>(&self, f: F) {
+                self.iter().copied().for_each(f)
+            }
+        }
+    };
+}
+
+impl_vector!(2, Vec2);
+impl_vector!(3, Vec3);
+impl_vector!(4, Vec4);
+
+// Matrices are only provided for `f32` since columns of smaller component
+// types would require per-column padding.
+macro_rules! impl_matrix {
+    ($n:expr, $dimensions:ident) => {
+        impl Element for [[f32; $n]; $n] {
+            type Component = f32;
+            const DIMENSIONS: Dimensions = Dimensions::$dimensions;
+            fn for_each_component<F: FnMut(Self::Component)>(&self, f: F) {
+                self.iter()
+                    .flat_map(|column| column.iter().copied())
+                    .for_each(f)
+            }
+        }
+    };
+}
+
+impl_matrix!(2, Mat2);
+impl_matrix!(3, Mat3);
+impl_matrix!(4, Mat4);
+
+/// Packs typed element slices into a single buffer, creating the
+/// corresponding buffer view and accessor JSON on the way.
+///
+/// The writer appends a new buffer to the given root on construction.
+/// Every call to [`push`] appends one buffer view and one accessor
+/// referencing that buffer, unless there are no elements to write, since
+/// accessors and buffer views may not be empty. [`finish`] sets the final buffer length and
+/// returns the packed binary data, which the caller is expected to store
+/// either as the GLB BIN chunk or at the buffer URI.
+///
+/// [`push`]: #method.push
+/// [`finish`]: #method.finish
+///
+/// # Example
+///
+/// ```
+/// # use gltf::accessor::Writer;
+/// # use gltf::json::buffer::Target;
+/// let mut root = gltf::json::Root::default();
+/// let mut writer = Writer::new(&mut root);
+/// let positions = writer.push(
+///     &[[0.0f32, 0.5, 0.0], [-0.5, -0.5, 0.0], [0.5, -0.5, 0.0]],
+///     Some(Target::ArrayBuffer),
+/// );
+/// let indices = writer.push(&[0u16, 1, 2], Some(Target::ElementArrayBuffer));
+/// let empty = writer.push::<u16>(&[], Some(Target::ElementArrayBuffer));
+/// let bin = writer.finish();
+/// assert_eq!(positions.map(|index| index.value()), Some(0));
+/// assert_eq!(indices.map(|index| index.value()), Some(1));
+/// assert_eq!(empty, None);
+/// assert_eq!(bin.len(), 44);
+/// assert_eq!(root.buffers[0].byte_length, 44);
+/// ```
+#[derive(Debug)]
+pub struct Writer<'a> {
+    /// The root the JSON objects are appended to.
+    root: &'a mut json::Root,
+
+    /// The buffer being written.
+    buffer: json::Index<json::Buffer>,
+
+    /// The packed binary data.
+    data: Vec<u8>,
+}
+
+impl<'a> Writer<'a> {
+    /// Constructs a `Writer`, appending a new empty buffer to `root`.
+    pub fn new(root: &'a mut json::Root) -> Self {
+        let buffer = json::Index::new(root.buffers.len() as u32);
+        root.buffers.push(json::Buffer {
+            byte_length: 0,
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: None,
+            extras: Default::default(),
+        });
+        Self {
+            root,
+            buffer,
+            data: Vec::new(),
+        }
+    }
+
+    /// Returns the index of the buffer being written.
+    pub fn buffer(&self) -> json::Index<json::Buffer> {
+        self.buffer
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if no data has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the root the JSON objects are appended to.
+    pub(crate) fn root_mut(&mut self) -> &mut json::Root {
+        self.root
+    }
+
+    /// Writes `elements` into a new buffer view and returns the index of a new
+    /// accessor describing them.
+    ///
+    /// The buffer view starts at a four byte boundary. For vertex attributes,
+    /// i.e. when `target` is `Target::ArrayBuffer`, elements whose size is not
+    /// a multiple of four are padded and an explicit byte stride is set, as
+    /// required by the specification. The accessor `min` and `max` values are
+    /// always computed.
+    ///
+    /// Returns `None` without writing anything if `elements` is empty.
+    pub fn push<T: Element>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+    ) -> Option<json::Index<json::Accessor>> {
+        self.push_impl(elements, target, false)
+    }
+
+    /// Like [`push`], but marks the accessor as normalized.
+    ///
+    /// This is intended for integer component types, for example `[u8; 4]`
+    /// vertex colors or `[u16; 2]` texture co-ordinates.
+    ///
+    /// [`push`]: #method.push
+    pub fn push_normalized<T: Element>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+    ) -> Option<json::Index<json::Accessor>> {
+        self.push_impl(elements, target, true)
+    }
+
+    /// Writes `elements` in the compact vertex `format` into a new buffer
+    /// view and returns the index of a new accessor describing them.
+    ///
+    /// Elements are padded to four bytes for vertex attributes, like with
+    /// [`push`]. The signed normalized formats declare the
+    /// `KHR_mesh_quantization` extension as used and required, and compute
+    /// the accessor `min` and `max` values. The other formats are custom
+    /// layouts without bounds, and are declared in the accessor extras with
+    /// the `extras` feature. Returns `None` without writing anything if
+    /// `elements` is empty.
+    ///
+    /// ```
+    /// # use gltf::accessor::packed::Format;
+    /// # use gltf::accessor::Writer;
+    /// # use gltf::json::buffer::Target;
+    /// let mut root = gltf::json::Root::default();
+    /// let mut writer = Writer::new(&mut root);
+    /// let normals = [[0.0f32, 0.0, 1.0], [0.0, 1.0, 0.0]];
+    /// writer.push_packed(&normals, Format::Snorm10_10_10_2, Some(Target::ArrayBuffer));
+    /// assert_eq!(writer.finish().len(), 8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a matrix, or if `format` is
+    /// `Format::Snorm10_10_10_2` and `T` is not a three or four component
+    /// vector.
+    ///
+    /// [`push`]: #method.push
+    pub fn push_packed<T: Element<Component = f32>>(
+        &mut self,
+        elements: &[T],
+        format: Format,
+        target: Option<Target>,
+    ) -> Option<json::Index<json::Accessor>> {
+        let (data_type, dimensions, normalized) = format
+            .layout(T::DIMENSIONS)
+            .expect("format does not support the element type");
+        if elements.is_empty() {
+            return None;
+        }
+        let size = data_type.size() * dimensions.multiplicity();
+        let stride = match target {
+            Some(Target::ArrayBuffer) if size % 4 != 0 => Some((size + 3) & !3),
+            _ => None,
+        };
+        self.align();
+        let offset = self.data.len();
+        let mut bounds = Bounds::default();
+        let mut components = Vec::with_capacity(4);
+        for element in elements {
+            let start = self.data.len();
+            components.clear();
+            element.for_each_component(|component| components.push(component));
+            match format {
+                Format::Snorm8 | Format::Snorm16 => {
+                    let limit = if format == Format::Snorm8 { 127 } else { 32767 };
+                    for (i, &component) in components.iter().enumerate() {
+                        let value = packed::to_snorm(component, limit);
+                        if format == Format::Snorm8 {
+                            self.data.push(value as i8 as u8);
+                        } else {
+                            self.data.extend_from_slice(&(value as i16).to_le_bytes());
+                        }
+                        bounds.extend(i, value);
+                    }
+                }
+                Format::Float16 => {
+                    for &component in &components {
+                        let half = packed::f32_to_f16(component);
+                        self.data.extend_from_slice(&half.to_le_bytes());
+                    }
+                }
+                Format::Snorm10_10_10_2 => {
+                    let mut value = [0.0; 4];
+                    value[..components.len()].copy_from_slice(&components);
+                    let packed = packed::pack_snorm10(value);
+                    self.data.extend_from_slice(&packed.to_le_bytes());
+                }
+            }
+            if let Some(stride) = stride {
+                self.data.resize(start + stride, 0);
+            }
+        }
+
+        if format.is_quantized() {
+            let extension = "KHR_mesh_quantization".to_owned();
+            if !self.root.extensions_used.contains(&extension) {
+                self.root.extensions_used.push(extension.clone());
+            }
+            if !self.root.extensions_required.contains(&extension) {
+                self.root.extensions_required.push(extension);
+            }
+        }
+        let view = self.push_view(offset, stride, target);
+        let accessor = json::Index::new(self.root.accessors.len() as u32);
+        self.root.accessors.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: Some(0),
+            count: elements.len() as u32,
+            component_type: Valid(json::accessor::GenericComponentType(data_type)),
+            extensions: None,
+            #[cfg(feature = "extras")]
+            extras: if format.is_quantized() {
+                None
+            } else {
+                let extras = format!(r#"{{"packedFormat":"{}"}}"#, format.as_str());
+                json::extras::RawValue::from_string(extras).ok()
+            },
+            #[cfg(not(feature = "extras"))]
+            extras: Default::default(),
+            type_: Valid(dimensions),
+            min: to_json(bounds.min),
+            max: to_json(bounds.max),
+            #[cfg(feature = "names")]
+            name: None,
+            normalized,
+            sparse: None,
+        });
+        Some(accessor)
+    }
+
+    /// Pads the data to a multiple of four bytes, sets the buffer length, and
+    /// returns the packed binary data.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.align();
+        self.root.buffers[self.buffer.value()].byte_length = self.data.len() as u32;
+        self.data
+    }
+
+    fn align(&mut self) {
+        while self.data.len() % 4 != 0 {
+            self.data.push(0);
+        }
+    }
+
+    /// Like [`push`], but encodes the elements in parallel on the current
+    /// rayon thread pool.
+    ///
+    /// The output is identical to that of [`push`]. The number of threads can
+    /// be limited by calling this function within [`ThreadPool::install`].
+    ///
+    /// ```
+    /// # use gltf::accessor::Writer;
+    /// # use gltf::json::buffer::Target;
+    /// let positions = vec![[1.0f32, 2.0, 3.0]; 100_000];
+    /// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    /// let mut root = gltf::json::Root::default();
+    /// let mut writer = Writer::new(&mut root);
+    /// pool.install(|| writer.par_push(&positions, Some(Target::ArrayBuffer)));
+    /// assert_eq!(writer.finish().len(), 1_200_000);
+    /// ```
+    ///
+    /// [`push`]: #method.push
+    /// [`ThreadPool::install`]: https://docs.rs/rayon/1/rayon/struct.ThreadPool.html#method.install
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_push<T>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+    ) -> Option<json::Index<json::Accessor>>
+    where
+        T: Element + Sync,
+        T::Component: Send,
+    {
+        self.par_push_impl(elements, target, false)
+    }
+
+    /// Like [`par_push`], but marks the accessor as normalized.
+    ///
+    /// [`par_push`]: #method.par_push
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_push_normalized<T>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+    ) -> Option<json::Index<json::Accessor>>
+    where
+        T: Element + Sync,
+        T::Component: Send,
+    {
+        self.par_push_impl(elements, target, true)
+    }
+
+    fn push_impl<T: Element>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+        normalized: bool,
+    ) -> Option<json::Index<json::Accessor>> {
+        if elements.is_empty() {
+            return None;
+        }
+        let stride = stride::<T>(target);
+        self.align();
+        let offset = self.data.len();
+        let bounds = encode(elements, stride, &mut self.data);
+        self.record::<T>(elements.len(), offset, stride, bounds, target, normalized)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_push_impl<T>(
+        &mut self,
+        elements: &[T],
+        target: Option<Target>,
+        normalized: bool,
+    ) -> Option<json::Index<json::Accessor>>
+    where
+        T: Element + Sync,
+        T::Component: Send,
+    {
+        use rayon::prelude::*;
+
+        /// Number of elements encoded per task.
+        const CHUNK_SIZE: usize = 4096;
+
+        if elements.is_empty() {
+            return None;
+        }
+        let stride = stride::<T>(target);
+        self.align();
+        let offset = self.data.len();
+        let chunks: Vec<_> = elements
+            .par_chunks(CHUNK_SIZE)
+            .map(|chunk| {
+                let mut data = Vec::new();
+                let bounds = encode(chunk, stride, &mut data);
+                (data, bounds)
+            })
+            .collect();
+        let mut bounds = Bounds::default();
+        for (data, chunk_bounds) in chunks {
+            self.data.extend_from_slice(&data);
+            bounds.merge(chunk_bounds);
+        }
+        self.record::<T>(elements.len(), offset, stride, bounds, target, normalized)
+    }
+
+    /// Appends the buffer view for the data written from `offset` on.
+    fn push_view(
+        &mut self,
+        offset: usize,
+        stride: Option<usize>,
+        target: Option<Target>,
+    ) -> json::Index<json::buffer::View> {
+        let view = json::Index::new(self.root.buffer_views.len() as u32);
+        self.root.buffer_views.push(json::buffer::View {
+            buffer: self.buffer,
+            byte_length: (self.data.len() - offset) as u32,
+            byte_offset: Some(offset as u32),
+            byte_stride: stride.map(|stride| stride as u32),
+            #[cfg(feature = "names")]
+            name: None,
+            target: target.map(Valid),
+            extensions: None,
+            extras: Default::default(),
+        });
+        view
+    }
+
+    /// Appends the buffer view and accessor for `count` elements written at
+    /// `offset`.
+    ///
+    /// `count` must not be zero.
+    fn record<T: Element>(
+        &mut self,
+        count: usize,
+        offset: usize,
+        stride: Option<usize>,
+        Bounds { min, max }: Bounds<T::Component>,
+        target: Option<Target>,
+        normalized: bool,
+    ) -> Option<json::Index<json::Accessor>> {
+        let view = self.push_view(offset, stride, target);
+        let accessor = json::Index::new(self.root.accessors.len() as u32);
+        self.root.accessors.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: Some(0),
+            count: count as u32,
+            component_type: Valid(json::accessor::GenericComponentType(
+                <T::Component as Component>::DATA_TYPE,
+            )),
+            extensions: None,
+            extras: Default::default(),
+            type_: Valid(T::DIMENSIONS),
+            min: to_json(min),
+            max: to_json(max),
+            #[cfg(feature = "names")]
+            name: None,
+            normalized,
+            sparse: None,
+        });
+        Some(accessor)
+    }
+}
+
+/// Returns the accessor `min` or `max` property for `values`, omitted if
+/// empty.
+fn to_json<C: Into<json::Value>>(values: Vec<C>) -> Option<json::Value> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(json::Value::from(
+            values.into_iter().map(Into::into).collect::<Vec<_>>(),
+        ))
+    }
+}
+
+/// Returns the byte stride of `T` elements written for `target`.
+///
+/// Vertex attributes whose size is not a multiple of four are padded.
+fn stride<T: Element>(target: Option<Target>) -> Option<usize> {
+    let size = mem::size_of::<T>();
+    match target {
+        Some(Target::ArrayBuffer) if size % 4 != 0 => Some((size + 3) & !3),
+        _ => None,
+    }
+}
+
+/// Per-component minimum and maximum values of written elements.
+#[derive(Debug)]
+struct Bounds<C> {
+    min: Vec<C>,
+    max: Vec<C>,
+}
+
+impl<C> Default for Bounds<C> {
+    fn default() -> Self {
+        Self {
+            min: Vec::new(),
+            max: Vec::new(),
+        }
+    }
+}
+
+impl<C: Copy + PartialOrd> Bounds<C> {
+    /// Includes the component at position `i` of an element.
+    fn extend(&mut self, i: usize, component: C) {
+        if i < self.min.len() {
+            if component < self.min[i] {
+                self.min[i] = component;
+            }
+            if component > self.max[i] {
+                self.max[i] = component;
+            }
+        } else {
+            self.min.push(component);
+            self.max.push(component);
+        }
+    }
+
+    /// Includes the bounds of further elements.
+    #[cfg(feature = "parallel")]
+    fn merge(&mut self, other: Self) {
+        for (i, (min, max)) in other.min.into_iter().zip(other.max).enumerate() {
+            self.extend(i, min);
+            self.extend(i, max);
+        }
+    }
+}
+
+/// Appends the little endian representation of `elements` to `data`,
+/// padding every element to `stride` bytes if given.
+fn encode<T: Element>(
+    elements: &[T],
+    stride: Option<usize>,
+    data: &mut Vec<u8>,
+) -> Bounds<T::Component> {
+    let mut bounds = Bounds::default();
+    for element in elements {
+        let start = data.len();
+        let mut i = 0;
+        element.for_each_component(|component| {
+            bounds.extend(i, component);
+            component.write(data);
+            i += 1;
+        });
+        if let Some(stride) = stride {
+            data.resize(start + stride, 0);
+        }
+    }
+    bounds
+}
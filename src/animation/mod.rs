@@ -1,3 +1,9 @@
+#[cfg(feature = "extensions")]
+use alloc::string::String;
+#[cfg(feature = "extensions")]
+use serde_json::{Map, Value};
+
+use crate::prelude::*;
 use crate::{accessor, scene, Document};
 
 #[cfg(feature = "utils")]
@@ -60,6 +66,37 @@ pub struct Target<'a> {
     json: &'a json::animation::Target,
 }
 
+/// An animation channel affecting a node.
+///
+/// Listed per node by [`Document::animation_bindings`].
+///
+/// [`Document::animation_bindings`]: ../struct.Document.html#method.animation_bindings
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Binding {
+    /// The index of the animation.
+    pub animation: usize,
+
+    /// The index of the channel within the animation.
+    pub channel: usize,
+
+    /// The node property modified by the channel.
+    pub property: Property,
+}
+
+/// Table of the animation channels affecting each node.
+///
+/// Created by [`Document::animation_bindings`].
+///
+/// [`Document::animation_bindings`]: ../struct.Document.html#method.animation_bindings
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    /// The start of the bindings of each node, followed by the total count.
+    offsets: Vec<usize>,
+
+    /// The bindings of all nodes, grouped by node.
+    bindings: Vec<Binding>,
+}
+
 impl<'a> Animation<'a> {
     /// Constructs an `Animation`.
     pub(crate) fn new(
@@ -163,6 +200,25 @@ impl<'a> Channel<'a> {
         }
     }
 
+    /// Evaluates the channel at `time` in seconds.
+    ///
+    /// This is a shorthand for reading the channel keyframes with
+    /// [`Reader::read_curve`] and sampling the result. Prefer keeping the
+    /// [`util::Curve`] around when sampling the same channel repeatedly.
+    ///
+    /// [`Reader::read_curve`]: util/struct.Reader.html#method.read_curve
+    /// [`util::Curve`]: util/sampling/struct.Curve.html
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn sample<'s, F>(&self, get_buffer_data: F, time: f32) -> Option<util::PropertyValue>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.reader(get_buffer_data)
+            .read_curve()
+            .map(|curve| curve.sample(time))
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
@@ -240,3 +296,113 @@ impl<'a> Sampler<'a> {
             .unwrap()
     }
 }
+
+impl Binding {
+    /// Returns the bound channel of `document`, or `None` if the indices are
+    /// out of range, e.g. for a table of another document.
+    pub fn channel<'a>(&self, document: &'a Document) -> Option<Channel<'a>> {
+        document
+            .animations()
+            .nth(self.animation)?
+            .channels()
+            .nth(self.channel)
+    }
+}
+
+impl Bindings {
+    /// Returns the bindings of the node with index `node`, ordered by
+    /// animation and channel.
+    ///
+    /// Returns an empty slice for unanimated nodes and out of range indices.
+    pub fn get(&self, node: usize) -> &[Binding] {
+        match (self.offsets.get(node), self.offsets.get(node + 1)) {
+            (Some(&start), Some(&end)) => &self.bindings[start..end],
+            _ => &[],
+        }
+    }
+
+    /// Returns the number of nodes in the table.
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` if the table contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Document {
+    /// Returns the animation channels affecting each node.
+    ///
+    /// The table is computed in a single pass over all channels, so that
+    /// animation systems can build their binding tables without scanning all
+    /// channels per node. Channels with invalid targets are skipped.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (document, _, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+    /// let bindings = document.animation_bindings();
+    /// assert_eq!(bindings.len(), document.nodes().len());
+    /// for node in document.nodes() {
+    ///     for binding in bindings.get(node.index()) {
+    ///         let channel = binding.channel(&document).unwrap();
+    ///         let interpolation = channel.sampler().interpolation();
+    ///         println!("{:?} is {:?} animated", binding.property, interpolation);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #    let _ = run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn animation_bindings(&self) -> Bindings {
+        let node_count = self.0.nodes.len();
+        let targets = || {
+            self.0
+                .animations
+                .iter()
+                .enumerate()
+                .flat_map(|(animation, json)| {
+                    json.channels
+                        .iter()
+                        .enumerate()
+                        .map(move |(channel, json)| (animation, channel, &json.target))
+                })
+                .filter_map(move |(animation, channel, target)| match target.path {
+                    json::validation::Checked::Valid(property)
+                        if target.node.value() < node_count =>
+                    {
+                        let binding = Binding {
+                            animation,
+                            channel,
+                            property,
+                        };
+                        Some((target.node.value(), binding))
+                    }
+                    _ => None,
+                })
+        };
+
+        // Bucket the bindings by node with a counting sort, which keeps them
+        // ordered by animation and channel.
+        let mut offsets = vec![0; node_count + 1];
+        for (node, _) in targets() {
+            offsets[node + 1] += 1;
+        }
+        for node in 0..node_count {
+            offsets[node + 1] += offsets[node];
+        }
+        let mut cursors = offsets.clone();
+        let mut bindings = vec![None; offsets[node_count]];
+        for (node, binding) in targets() {
+            bindings[cursors[node]] = Some(binding);
+            cursors[node] += 1;
+        }
+        Bindings {
+            offsets,
+            bindings: bindings.into_iter().flatten().collect(),
+        }
+    }
+}
@@ -4,6 +4,12 @@ pub mod rotations;
 /// Casting iterator adapters for morph target weights.
 pub mod morph_target_weights;
 
+/// Keyframe interpolation.
+pub mod sampling;
+
+#[doc(inline)]
+pub use self::sampling::{Curve, PropertyValue};
+
 use crate::accessor;
 
 use crate::animation::Channel;
@@ -145,6 +151,29 @@ where
         accessor::Iter::new(self.channel.sampler().input(), self.get_buffer_data.clone())
     }
 
+    /// Reads the keyframes of a channel into a [`Curve`] for sampling.
+    ///
+    /// Returns `None` if the sampler data cannot be read or is inconsistent.
+    ///
+    /// [`Curve`]: sampling/struct.Curve.html
+    pub fn read_curve(&self) -> Option<Curve> {
+        let inputs = self.read_inputs()?.collect();
+        let outputs = match self.read_outputs()? {
+            ReadOutputs::Translations(iter) | ReadOutputs::Scales(iter) => {
+                iter.flat_map(|x| x.to_vec()).collect()
+            }
+            ReadOutputs::Rotations(iter) => iter.into_f32().flat_map(|x| x.to_vec()).collect(),
+            ReadOutputs::MorphTargetWeights(iter) => iter.into_f32().collect(),
+        };
+        let sampler = self.channel.sampler();
+        Curve::new(
+            self.channel.target().property(),
+            sampler.interpolation(),
+            inputs,
+            outputs,
+        )
+    }
+
     /// Visits the output samples of a channel.
     pub fn read_outputs(&self) -> Option<ReadOutputs<'s>> {
         use crate::animation::Property;
@@ -165,7 +194,7 @@ where
                     .map(|x| ReadOutputs::Rotations(Rotations::U16(x))),
                 DataType::F32 => Iter::new(output, self.get_buffer_data.clone())
                     .map(|x| ReadOutputs::Rotations(Rotations::F32(x))),
-                _ => unreachable!(),
+                _ => None,
             },
             Property::Scale => {
                 Iter::new(output, self.get_buffer_data.clone()).map(ReadOutputs::Scales)
@@ -181,7 +210,7 @@ where
                     .map(|x| ReadOutputs::MorphTargetWeights(MorphTargetWeights::U16(x))),
                 DataType::F32 => Iter::new(output, self.get_buffer_data.clone())
                     .map(|x| ReadOutputs::MorphTargetWeights(MorphTargetWeights::F32(x))),
-                _ => unreachable!(),
+                _ => None,
             },
         }
     }
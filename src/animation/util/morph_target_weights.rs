@@ -1,6 +1,6 @@
 use super::MorphTargetWeights;
 use crate::Normalize;
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// Casting iterator for `MorphTargetWeights`.
 #[derive(Clone, Debug)]
@@ -110,6 +110,30 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            MorphTargetWeights::I8(ref mut i) => i.next_back().map(A::cast_i8),
+            MorphTargetWeights::U8(ref mut i) => i.next_back().map(A::cast_u8),
+            MorphTargetWeights::I16(ref mut i) => i.next_back().map(A::cast_i16),
+            MorphTargetWeights::U16(ref mut i) => i.next_back().map(A::cast_u16),
+            MorphTargetWeights::F32(ref mut i) => i.next_back().map(A::cast_f32),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            MorphTargetWeights::I8(ref mut i) => i.nth_back(x).map(A::cast_i8),
+            MorphTargetWeights::U8(ref mut i) => i.nth_back(x).map(A::cast_u8),
+            MorphTargetWeights::I16(ref mut i) => i.nth_back(x).map(A::cast_i16),
+            MorphTargetWeights::U16(ref mut i) => i.nth_back(x).map(A::cast_u16),
+            MorphTargetWeights::F32(ref mut i) => i.nth_back(x).map(A::cast_f32),
+        }
+    }
+}
+
 impl Cast for I8 {
     type Output = i8;
 
@@ -0,0 +1,306 @@
+use crate::animation::{Interpolation, Property};
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::prelude::*;
+
+/// The value of an animated property at a point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// XYZ translation.
+    Translation([f32; 3]),
+
+    /// XYZW unit quaternion rotation.
+    Rotation([f32; 4]),
+
+    /// XYZ scale.
+    Scale([f32; 3]),
+
+    /// Morph target weights.
+    MorphTargetWeights(Vec<f32>),
+}
+
+/// Keyframe data of an animation channel, decoded for repeated sampling.
+///
+/// Reading the keyframes once and sampling this structure avoids re-reading
+/// the accessors every frame.
+#[derive(Clone, Debug)]
+pub struct Curve {
+    /// The animated property.
+    property: Property,
+
+    /// The keyframe interpolation algorithm.
+    interpolation: Interpolation,
+
+    /// Keyframe times in seconds, in ascending order.
+    inputs: Vec<f32>,
+
+    /// Flattened keyframe outputs.
+    ///
+    /// For cubic spline interpolation each keyframe holds an in-tangent, a
+    /// value, and an out-tangent, in that order.
+    outputs: Vec<f32>,
+
+    /// The number of components of a single value.
+    width: usize,
+}
+
+impl Curve {
+    /// Constructs a `Curve` from keyframe times and flattened output values.
+    ///
+    /// Returns `None` if the number of outputs is inconsistent with the number
+    /// of inputs, the property, and the interpolation algorithm.
+    pub fn new(
+        property: Property,
+        interpolation: Interpolation,
+        inputs: Vec<f32>,
+        outputs: Vec<f32>,
+    ) -> Option<Self> {
+        let elements_per_keyframe = match interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+        let width = match property {
+            Property::Translation | Property::Scale => 3,
+            Property::Rotation => 4,
+            Property::MorphTargetWeights => {
+                let divisor = inputs.len() * elements_per_keyframe;
+                if divisor == 0 {
+                    return None;
+                }
+                outputs.len() / divisor
+            }
+        };
+        if inputs.is_empty() || outputs.len() != inputs.len() * elements_per_keyframe * width {
+            return None;
+        }
+        Some(Self {
+            property,
+            interpolation,
+            inputs,
+            outputs,
+            width,
+        })
+    }
+
+    /// Returns the animated property.
+    pub fn property(&self) -> Property {
+        self.property
+    }
+
+    /// Returns the keyframe interpolation algorithm.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Returns the keyframe times in seconds.
+    pub fn inputs(&self) -> &[f32] {
+        &self.inputs
+    }
+
+    /// Returns the time of the first and last keyframes.
+    pub fn time_range(&self) -> (f32, f32) {
+        (self.inputs[0], self.inputs[self.inputs.len() - 1])
+    }
+
+    /// Evaluates the curve at `time` in seconds.
+    ///
+    /// Times outside of the keyframe range are clamped to the first or last
+    /// keyframe. Rotations are interpolated with spherical linear
+    /// interpolation and the result is always normalized.
+    pub fn sample(&self, time: f32) -> PropertyValue {
+        let last = self.inputs.len() - 1;
+        // Index of the first keyframe after `time`.
+        let next = self.inputs.partition_point(|&input| input <= time);
+        let value = if next == 0 {
+            self.value(0).to_vec()
+        } else if next > last {
+            self.value(last).to_vec()
+        } else {
+            let previous = next - 1;
+            let delta = self.inputs[next] - self.inputs[previous];
+            let factor = if delta > 0.0 {
+                (time - self.inputs[previous]) / delta
+            } else {
+                0.0
+            };
+            match self.interpolation {
+                Interpolation::Step => self.value(previous).to_vec(),
+                Interpolation::Linear => {
+                    let a = self.value(previous);
+                    let b = self.value(next);
+                    if self.property == Property::Rotation {
+                        slerp(a, b, factor).to_vec()
+                    } else {
+                        a.iter().zip(b).map(|(a, b)| a + (b - a) * factor).collect()
+                    }
+                }
+                Interpolation::CubicSpline => {
+                    let p0 = self.value(previous);
+                    let m0 = self.element(previous, 2);
+                    let p1 = self.value(next);
+                    let m1 = self.element(next, 0);
+                    let s = factor;
+                    let s2 = s * s;
+                    let s3 = s2 * s;
+                    let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+                    let h10 = s3 - 2.0 * s2 + s;
+                    let h01 = -2.0 * s3 + 3.0 * s2;
+                    let h11 = s3 - s2;
+                    (0..self.width)
+                        .map(|i| {
+                            h00 * p0[i] + h10 * delta * m0[i] + h01 * p1[i] + h11 * delta * m1[i]
+                        })
+                        .collect()
+                }
+            }
+        };
+
+        match self.property {
+            Property::Translation => PropertyValue::Translation([value[0], value[1], value[2]]),
+            Property::Rotation => {
+                PropertyValue::Rotation(normalize([value[0], value[1], value[2], value[3]]))
+            }
+            Property::Scale => PropertyValue::Scale([value[0], value[1], value[2]]),
+            Property::MorphTargetWeights => PropertyValue::MorphTargetWeights(value),
+        }
+    }
+
+    /// Returns the keyframe value, skipping cubic spline tangents.
+    fn value(&self, keyframe: usize) -> &[f32] {
+        match self.interpolation {
+            Interpolation::CubicSpline => self.element(keyframe, 1),
+            _ => self.element(keyframe, 0),
+        }
+    }
+
+    /// Returns the nth output element of a keyframe.
+    fn element(&self, keyframe: usize, n: usize) -> &[f32] {
+        let elements_per_keyframe = match self.interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+        let start = (keyframe * elements_per_keyframe + n) * self.width;
+        &self.outputs[start..start + self.width]
+    }
+}
+
+fn normalize(q: [f32; 4]) -> [f32; 4] {
+    let length = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+    if length > 0.0 {
+        [q[0] / length, q[1] / length, q[2] / length, q[3] / length]
+    } else {
+        [0.0, 0.0, 0.0, 1.0]
+    }
+}
+
+/// Spherical linear interpolation along the shortest path.
+fn slerp(a: &[f32], b: &[f32], factor: f32) -> [f32; 4] {
+    let mut dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3];
+    let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+    dot *= sign;
+
+    let (scale_a, scale_b) = if dot > 0.9995 {
+        // Nearly parallel: fall back to linear interpolation.
+        (1.0 - factor, factor)
+    } else {
+        let theta = dot.acos();
+        let sin_theta = theta.sin();
+        (
+            ((1.0 - factor) * theta).sin() / sin_theta,
+            (factor * theta).sin() / sin_theta,
+        )
+    };
+    let scale_b = scale_b * sign;
+    normalize([
+        scale_a * a[0] + scale_b * b[0],
+        scale_a * a[1] + scale_b * b[1],
+        scale_a * a[2] + scale_b * b[2],
+        scale_a * a[3] + scale_b * b[3],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn step() {
+        let curve = Curve::new(
+            Property::Translation,
+            Interpolation::Step,
+            vec![0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0],
+        )
+        .unwrap();
+        assert_eq!(curve.sample(0.99), PropertyValue::Translation([0.0; 3]));
+        assert_eq!(
+            curve.sample(1.0),
+            PropertyValue::Translation([1.0, 2.0, 3.0])
+        );
+    }
+
+    #[test]
+    fn linear_clamped() {
+        let curve = Curve::new(
+            Property::Scale,
+            Interpolation::Linear,
+            vec![1.0, 3.0],
+            vec![1.0, 1.0, 1.0, 3.0, 5.0, 1.0],
+        )
+        .unwrap();
+        assert_eq!(curve.sample(0.0), PropertyValue::Scale([1.0, 1.0, 1.0]));
+        assert_eq!(curve.sample(2.0), PropertyValue::Scale([2.0, 3.0, 1.0]));
+        assert_eq!(curve.sample(9.0), PropertyValue::Scale([3.0, 5.0, 1.0]));
+    }
+
+    #[test]
+    fn slerp_rotation() {
+        let half = core::f32::consts::FRAC_1_SQRT_2;
+        let curve = Curve::new(
+            Property::Rotation,
+            Interpolation::Linear,
+            vec![0.0, 1.0],
+            // Identity to 180 degrees around Z.
+            vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        )
+        .unwrap();
+        match curve.sample(0.5) {
+            PropertyValue::Rotation(q) => {
+                assert_relative_eq!(q[2], half, epsilon = 1e-6);
+                assert_relative_eq!(q[3], half, epsilon = 1e-6);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn cubic_spline_weights() {
+        // Two weights, zero tangents: the midpoint is the average.
+        let curve = Curve::new(
+            Property::MorphTargetWeights,
+            Interpolation::CubicSpline,
+            vec![0.0, 2.0],
+            vec![
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, //
+                0.0, 0.0, 1.0, 0.0, 0.0, 0.0,
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            curve.sample(1.0),
+            PropertyValue::MorphTargetWeights(vec![0.5, 0.5])
+        );
+    }
+
+    #[test]
+    fn inconsistent_outputs() {
+        assert!(Curve::new(
+            Property::Translation,
+            Interpolation::Linear,
+            vec![0.0, 1.0],
+            vec![0.0; 3],
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::accessor::Writer;
+use crate::binary;
+use crate::{Error, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+use json::validation::Error::{Invalid, Missing};
+
+/// The size of the file header.
+const HEADER: u64 = 12;
+
+/// The size of a chunk header.
+const CHUNK_HEADER: u64 = 8;
+
+/// The smallest capacity reserved for the JSON chunk.
+const MIN_JSON_CAPACITY: usize = 4096;
+
+/// Rounds `n` up to a multiple of four.
+fn align(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+/// Appends geometry to a binary glTF file in place, for capture tools
+/// recording meshes or point clouds over time.
+///
+/// New data is written to the end of the `BIN` chunk, which is kept as the
+/// last chunk of the file, so previously appended data is never rewritten.
+/// The JSON chunk is padded with spare capacity, such that [`flush`] only
+/// overwrites the JSON chunk and the chunk headers. Once the JSON outgrows
+/// its capacity, the capacity is doubled and the `BIN` chunk is moved,
+/// keeping the cost of appending amortized.
+///
+/// The file is only valid binary glTF after [`flush`] or [`finish`];
+/// appended data is not described by the JSON chunk before.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::json::mesh::Mode;
+/// use gltf::GlbAppender;
+///
+/// let file = std::io::Cursor::new(Vec::new());
+/// let mut appender = GlbAppender::create(file, gltf::json::Root::default(), Vec::new())?;
+/// for frame in 0..3 {
+///     let points = [[frame as f32, 0.0, 0.0], [frame as f32, 1.0, 0.0]];
+///     appender.append_mesh(&points, None, Mode::Points)?;
+///     appender.flush()?;
+/// }
+/// let glb = appender.finish()?.into_inner();
+///
+/// let gltf = gltf::Gltf::from_slice(&glb)?;
+/// assert_eq!(gltf.meshes().count(), 3);
+/// assert_eq!(gltf.default_scene().unwrap().nodes().count(), 3);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+///
+/// [`flush`]: #method.flush
+/// [`finish`]: #method.finish
+#[derive(Debug)]
+pub struct GlbAppender<S> {
+    /// The binary glTF file.
+    stream: S,
+
+    /// The JSON of the file, including unflushed changes.
+    root: json::Root,
+
+    /// The length of the JSON chunk in bytes, including padding.
+    json_capacity: usize,
+
+    /// The length of the `BIN` chunk in bytes, or `None` if the file has no
+    /// `BIN` chunk yet.
+    bin_length: Option<usize>,
+
+    /// The number of times the `BIN` chunk was moved.
+    relocations: usize,
+}
+
+impl<S: Read + Write + Seek> GlbAppender<S> {
+    /// Writes `root` with the data `bin` of its first buffer as a new binary
+    /// glTF file to `stream`, and returns an appender for it.
+    ///
+    /// `stream` is expected to be empty, for example a newly created file.
+    pub fn create(stream: S, root: json::Root, mut bin: Vec<u8>) -> Result<Self> {
+        let json = json::serialize::to_vec(&root).map_err(Error::Deserialize)?;
+        let mut appender = Self {
+            stream,
+            root,
+            json_capacity: align(json.len().max(MIN_JSON_CAPACITY)),
+            bin_length: None,
+            relocations: 0,
+        };
+        if !bin.is_empty() {
+            bin.resize(align(bin.len()), 0);
+            appender.ensure_buffer();
+            appender.write_bin(&bin)?;
+        }
+        appender.flush()?;
+        Ok(appender)
+    }
+
+    /// Opens the existing binary glTF file `stream` for appending.
+    ///
+    /// The file must consist of the JSON chunk, optionally followed by the
+    /// `BIN` chunk. Files with further chunks after the `BIN` chunk are
+    /// rejected with an `Error::Binary`.
+    pub fn open(mut stream: S) -> Result<Self> {
+        let length = stream.seek(SeekFrom::End(0))?;
+        stream.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; 4];
+        stream.read_exact(&mut magic)?;
+        if &magic != b"glTF" {
+            return Err(Error::Binary(binary::Error::Magic(magic)));
+        }
+        let version = stream.read_u32::<LittleEndian>()?;
+        if version != 2 {
+            return Err(Error::Binary(binary::Error::Version(version)));
+        }
+        let glb_length = stream.read_u32::<LittleEndian>()?;
+        if glb_length as u64 != length {
+            return Err(Error::Binary(binary::Error::Length {
+                length: glb_length,
+                length_read: length as usize,
+            }));
+        }
+
+        let json_capacity = stream.read_u32::<LittleEndian>()? as usize;
+        let mut ty = [0; 4];
+        stream.read_exact(&mut ty)?;
+        if &ty != b"JSON" {
+            return Err(Error::Binary(binary::Error::UnknownChunkType(ty)));
+        }
+        let mut json = vec![0; json_capacity];
+        stream.read_exact(&mut json)?;
+        let root: json::Root = json::deserialize::from_slice(&json)?;
+
+        let bin_start = HEADER + CHUNK_HEADER + json_capacity as u64;
+        let bin_length = if bin_start < length {
+            let bin_length = stream.read_u32::<LittleEndian>()?;
+            stream.read_exact(&mut ty)?;
+            if &ty != b"BIN\0" {
+                return Err(Error::Binary(binary::Error::UnknownChunkType(ty)));
+            }
+            let available = length - bin_start - CHUNK_HEADER;
+            if bin_length as u64 != available {
+                // Either truncated or followed by further chunks.
+                return Err(Error::Binary(binary::Error::ChunkLength {
+                    ty: binary::ChunkType::Bin,
+                    length: bin_length,
+                    length_read: available as usize,
+                }));
+            }
+            Some(bin_length as usize)
+        } else {
+            None
+        };
+        Ok(Self {
+            stream,
+            root,
+            json_capacity,
+            bin_length,
+            relocations: 0,
+        })
+    }
+
+    /// Returns the JSON of the file, including unflushed changes.
+    pub fn root(&self) -> &json::Root {
+        &self.root
+    }
+
+    /// Returns the JSON of the file for modification, for example to
+    /// reference data written by [`append`] from new meshes or animations.
+    ///
+    /// The first buffer describes the `BIN` chunk and must not be modified.
+    ///
+    /// [`append`]: #method.append
+    pub fn root_mut(&mut self) -> &mut json::Root {
+        &mut self.root
+    }
+
+    /// Returns the number of times the `BIN` chunk was moved because the JSON
+    /// outgrew the capacity of its chunk.
+    pub fn relocations(&self) -> usize {
+        self.relocations
+    }
+
+    /// Appends the data written by `f` to the `BIN` chunk, and returns the
+    /// result of `f`.
+    ///
+    /// The accessors and buffer views created by `f` refer to the first
+    /// buffer of the document, which is created if there are no buffers
+    /// yet. The JSON is updated in memory only, see [`flush`].
+    ///
+    /// Returns an `Error::Validation` if the first buffer refers to an
+    /// external file instead of the `BIN` chunk.
+    ///
+    /// [`flush`]: #method.flush
+    pub fn append<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Writer) -> T,
+    {
+        if self
+            .root
+            .buffers
+            .first()
+            .map_or(false, |buffer| buffer.uri.is_some())
+        {
+            let path = json::Path::new().field("buffers").index(0).field("uri");
+            return Err(Error::Validation(vec![(path, Invalid)]));
+        }
+        let first_view = self.root.buffer_views.len();
+        let mut writer = Writer::new(&mut self.root);
+        let buffer = writer.buffer();
+        let value = f(&mut writer);
+        let data = writer.finish();
+
+        // Rebase the views of the temporary buffer onto the `BIN` chunk.
+        self.root.buffers.pop();
+        let offset = self.bin_length.unwrap_or(0);
+        for view in &mut self.root.buffer_views[first_view..] {
+            if view.buffer == buffer {
+                view.buffer = json::Index::new(0);
+                view.byte_offset = Some(offset as u32 + view.byte_offset.unwrap_or(0));
+            }
+        }
+        self.ensure_buffer();
+        self.write_bin(&data)?;
+        Ok(value)
+    }
+
+    /// Appends a mesh drawing `positions` with optional `indices` in `mode`,
+    /// for example a captured point cloud with `Mode::Points`, and a node
+    /// instancing it in the default scene.
+    ///
+    /// The default scene is created if the document has no scenes. Returns
+    /// the index of the new node, or an `Error::Validation` with a `Missing`
+    /// error at the empty attribute or indices of the new primitive if
+    /// `positions` or `indices` is empty.
+    pub fn append_mesh(
+        &mut self,
+        positions: &[[f32; 3]],
+        indices: Option<&[u32]>,
+        mode: json::mesh::Mode,
+    ) -> Result<json::Index<json::Node>> {
+        let primitive = json::Path::new()
+            .field("meshes")
+            .index(self.root.meshes.len())
+            .field("primitives")
+            .index(0);
+        if positions.is_empty() {
+            let path = primitive.field("attributes").key("POSITION");
+            return Err(Error::Validation(vec![(path, Missing)]));
+        }
+        if indices.map_or(false, <[u32]>::is_empty) {
+            let path = primitive.field("indices");
+            return Err(Error::Validation(vec![(path, Missing)]));
+        }
+        let (positions, indices) = self.append(|writer| {
+            let positions = writer.push(positions, Some(Target::ArrayBuffer));
+            let indices =
+                indices.and_then(|indices| writer.push(indices, Some(Target::ElementArrayBuffer)));
+            (positions, indices)
+        })?;
+        let positions = positions.expect("positions are not empty");
+        let root = &mut self.root;
+        let mut attributes = BTreeMap::new();
+        attributes.insert(Valid(json::mesh::Semantic::Positions), positions);
+        let mesh = json::Index::new(root.meshes.len() as u32);
+        root.meshes.push(json::Mesh {
+            extensions: None,
+            extras: Default::default(),
+            #[cfg(feature = "names")]
+            name: None,
+            primitives: vec![json::mesh::Primitive {
+                attributes,
+                extensions: None,
+                extras: Default::default(),
+                indices,
+                material: None,
+                mode: Valid(mode),
+                targets: None,
+            }],
+            weights: None,
+        });
+        let node = json::Index::new(root.nodes.len() as u32);
+        root.nodes.push(json::Node {
+            camera: None,
+            children: None,
+            extensions: None,
+            extras: Default::default(),
+            matrix: None,
+            mesh: Some(mesh),
+            #[cfg(feature = "names")]
+            name: None,
+            rotation: None,
+            scale: None,
+            translation: None,
+            skin: None,
+            weights: None,
+        });
+        if root.scenes.is_empty() {
+            root.scenes.push(json::Scene {
+                extensions: None,
+                extras: Default::default(),
+                #[cfg(feature = "names")]
+                name: None,
+                nodes: Vec::new(),
+            });
+        }
+        let scene = root.scene.map_or(0, |scene| scene.value());
+        root.scene = Some(json::Index::new(scene as u32));
+        if let Some(scene) = root.scenes.get_mut(scene) {
+            scene.nodes.push(node);
+        }
+        Ok(node)
+    }
+
+    /// Writes the JSON and the chunk headers, making the file valid binary
+    /// glTF including everything appended so far.
+    pub fn flush(&mut self) -> Result<()> {
+        let json = json::serialize::to_vec(&self.root).map_err(Error::Deserialize)?;
+        if json.len() > self.json_capacity {
+            let capacity = (2 * self.json_capacity).max(MIN_JSON_CAPACITY);
+            self.relocate(align(json.len().max(capacity)))?;
+        }
+        let bin_start = HEADER + CHUNK_HEADER + self.json_capacity as u64;
+        let length = match self.bin_length {
+            Some(bin_length) => bin_start + CHUNK_HEADER + bin_length as u64,
+            None => bin_start,
+        };
+        self.stream.seek(SeekFrom::Start(0))?;
+        self.stream.write_all(b"glTF")?;
+        self.stream.write_u32::<LittleEndian>(2)?;
+        self.stream.write_u32::<LittleEndian>(length as u32)?;
+        self.stream
+            .write_u32::<LittleEndian>(self.json_capacity as u32)?;
+        self.stream.write_all(b"JSON")?;
+        self.stream.write_all(&json)?;
+        self.stream
+            .write_all(&vec![b' '; self.json_capacity - json.len()])?;
+        if let Some(bin_length) = self.bin_length {
+            self.stream.write_u32::<LittleEndian>(bin_length as u32)?;
+            self.stream.write_all(b"BIN\0")?;
+        }
+        self.stream.flush()?;
+        Ok(())
+    }
+
+    /// Flushes the file and returns the stream.
+    pub fn finish(mut self) -> Result<S> {
+        self.flush()?;
+        Ok(self.stream)
+    }
+
+    /// Creates the first buffer describing the `BIN` chunk if there are no
+    /// buffers yet.
+    fn ensure_buffer(&mut self) {
+        if self.root.buffers.is_empty() {
+            self.root.buffers.push(json::Buffer {
+                byte_length: 0,
+                #[cfg(feature = "names")]
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+    }
+
+    /// Appends `data`, whose length is a multiple of four, to the `BIN`
+    /// chunk and updates the length of the first buffer.
+    fn write_bin(&mut self, data: &[u8]) -> Result<()> {
+        let bin_length = self.bin_length.unwrap_or(0);
+        let data_start = HEADER + 2 * CHUNK_HEADER + self.json_capacity as u64;
+        self.stream
+            .seek(SeekFrom::Start(data_start + bin_length as u64))?;
+        self.stream.write_all(data)?;
+        self.bin_length = Some(bin_length + data.len());
+        self.root.buffers[0].byte_length = (bin_length + data.len()) as u32;
+        Ok(())
+    }
+
+    /// Moves the `BIN` chunk such that the JSON chunk has `json_capacity`
+    /// bytes.
+    fn relocate(&mut self, json_capacity: usize) -> io::Result<()> {
+        if let Some(bin_length) = self.bin_length {
+            let data_start = HEADER + 2 * CHUNK_HEADER;
+            let mut bin = vec![0; bin_length];
+            self.stream
+                .seek(SeekFrom::Start(data_start + self.json_capacity as u64))?;
+            self.stream.read_exact(&mut bin)?;
+            self.stream
+                .seek(SeekFrom::Start(data_start + json_capacity as u64))?;
+            self.stream.write_all(&bin)?;
+        }
+        self.json_capacity = json_capacity;
+        self.relocations += 1;
+        Ok(())
+    }
+}
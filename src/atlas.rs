@@ -0,0 +1,161 @@
+use crate::geometry::BufferData;
+use crate::prelude::*;
+use crate::streaming::{self, TextureUsage};
+use crate::texture::WrappingMode;
+use crate::{Buffer, Document};
+
+/// Tolerance for texture co-ordinates outside `[0, 1]` due to rounding.
+const UV_EPSILON: f32 = 1.0e-5;
+
+/// A material texture which relies on repeated wrapping.
+///
+/// Created by [`Document::wrap_dependencies`].
+///
+/// [`Document::wrap_dependencies`]: ../struct.Document.html#method.wrap_dependencies
+#[derive(Clone, Debug, PartialEq)]
+pub struct WrapDependency {
+    /// The index of the material.
+    pub material: usize,
+
+    /// The material slot the texture is used in.
+    pub usage: TextureUsage,
+
+    /// The index of the texture.
+    pub texture: usize,
+
+    /// The texture co-ordinate set sampling the texture.
+    pub tex_coord: u32,
+
+    /// The horizontal wrapping mode of the texture sampler.
+    pub wrap_s: WrappingMode,
+
+    /// The vertical wrapping mode of the texture sampler.
+    pub wrap_t: WrappingMode,
+
+    /// The minimum texture co-ordinates of the primitives using the material.
+    pub uv_min: [f32; 2],
+
+    /// The maximum texture co-ordinates of the primitives using the material.
+    pub uv_max: [f32; 2],
+}
+
+impl WrapDependency {
+    /// Returns `true` if the texture co-ordinates leave `[0, 1]` horizontally
+    /// while the sampler repeats horizontally.
+    pub fn wraps_s(&self) -> bool {
+        wraps(self.wrap_s, self.uv_min[0], self.uv_max[0])
+    }
+
+    /// Returns `true` if the texture co-ordinates leave `[0, 1]` vertically
+    /// while the sampler repeats vertically.
+    pub fn wraps_t(&self) -> bool {
+        wraps(self.wrap_t, self.uv_min[1], self.uv_max[1])
+    }
+}
+
+/// Returns `true` if `mode` repeats and `min..=max` leaves `[0, 1]`.
+fn wraps(mode: WrappingMode, min: f32, max: f32) -> bool {
+    mode != WrappingMode::ClampToEdge && (min < -UV_EPSILON || max > 1.0 + UV_EPSILON)
+}
+
+/// The texture co-ordinate bounds of a material and set.
+struct UvBounds {
+    material: usize,
+    set: u32,
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+impl Document {
+    /// Reports the material textures which rely on `REPEAT` or
+    /// `MIRRORED_REPEAT` wrapping, i.e. whose sampler repeats along an axis on
+    /// which the texture co-ordinates of the primitives using the material
+    /// leave `[0, 1]`.
+    ///
+    /// Such textures cannot be packed into an atlas, or have their texture
+    /// co-ordinates rewritten with `KHR_texture_transform`, without changing
+    /// their appearance. Texture co-ordinates are taken as stored, before any
+    /// texture transform is applied. Textures whose co-ordinates cannot be
+    /// read are not reported.
+    ///
+    /// Returns the dependencies ordered by material.
+    pub fn wrap_dependencies<'a, 's, F>(&'a self, get_buffer_data: F) -> Vec<WrapDependency>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let buffer_data = BufferData::new(self, get_buffer_data);
+        let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+        let mut bounds: Vec<UvBounds> = Vec::new();
+        for primitive in self.meshes().flat_map(|mesh| mesh.primitives()) {
+            let material = primitive.material();
+            let index = match material.index() {
+                Some(index) => index,
+                None => continue,
+            };
+            let reader = primitive.reader(get_buffer_data);
+            let mut sets: Vec<u32> = streaming::slots(&material)
+                .into_iter()
+                .map(|(_, _, set)| set)
+                .collect();
+            sets.sort_unstable();
+            sets.dedup();
+            for set in sets {
+                let mut tex_coords = match reader.read_tex_coords(set) {
+                    Some(tex_coords) => tex_coords.into_f32(),
+                    None => continue,
+                };
+                let first = match tex_coords.next() {
+                    Some(uv) => uv,
+                    None => continue,
+                };
+                let position = bounds
+                    .iter()
+                    .position(|b| b.material == index && b.set == set)
+                    .unwrap_or_else(|| {
+                        bounds.push(UvBounds {
+                            material: index,
+                            set,
+                            min: first,
+                            max: first,
+                        });
+                        bounds.len() - 1
+                    });
+                let entry = &mut bounds[position];
+                for [u, v] in core::iter::once(first).chain(tex_coords) {
+                    entry.min = [entry.min[0].min(u), entry.min[1].min(v)];
+                    entry.max = [entry.max[0].max(u), entry.max[1].max(v)];
+                }
+            }
+        }
+
+        let mut dependencies = Vec::new();
+        for material in self.materials() {
+            let index = material.index().unwrap();
+            for (usage, texture, set) in streaming::slots(&material) {
+                let uv = match bounds.iter().find(|b| b.material == index && b.set == set) {
+                    Some(uv) => uv,
+                    None => continue,
+                };
+                let sampler = match self.textures().nth(texture) {
+                    Some(texture) => texture.sampler(),
+                    None => continue,
+                };
+                let dependency = WrapDependency {
+                    material: index,
+                    usage,
+                    texture,
+                    tex_coord: set,
+                    wrap_s: sampler.wrap_s(),
+                    wrap_t: sampler.wrap_t(),
+                    uv_min: uv.min,
+                    uv_max: uv.max,
+                };
+                if dependency.wraps_s() || dependency.wraps_t() {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+        dependencies
+    }
+}
@@ -1,11 +1,18 @@
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::borrow::Cow;
-use std::{fmt, io, mem};
+use crate::prelude::*;
+use alloc::borrow::Cow;
+use byteorder::{ByteOrder, LittleEndian};
+#[cfg(feature = "std")]
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use core::{fmt, mem};
+#[cfg(feature = "std")]
+use std::io;
 
 /// Represents a Glb loader error.
 #[derive(Debug)]
 pub enum Error {
     /// Io error occured.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     Io(::std::io::Error),
     /// Unsupported version.
     Version(u32),
@@ -75,7 +82,60 @@ struct ChunkHeader {
     ty: ChunkType,
 }
 
+/// Options for tolerant parsing of binary glTF with
+/// [`Glb::from_slice_with_options`].
+///
+/// By default parsing is as strict as [`Glb::from_slice`].
+///
+/// [`Glb::from_slice`]: struct.Glb.html#method.from_slice
+/// [`Glb::from_slice_with_options`]: struct.Glb.html#method.from_slice_with_options
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// Whether container versions other than `2` are accepted.
+    unknown_versions: bool,
+
+    /// Whether chunks of unknown type are accepted.
+    unknown_chunks: bool,
+}
+
+/// A deviation from the GLB 2.0 container format that was tolerated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Warning {
+    /// The header declares a container version other than `2`.
+    Version(u32),
+    /// A chunk of unknown or unexpected type was skipped.
+    UnknownChunk {
+        /// The chunk type.
+        ty: [u8; 4],
+        /// The chunk length.
+        length: u32,
+    },
+}
+
+/// A raw chunk of binary glTF.
+#[derive(Clone, Debug)]
+pub struct Chunk<'a> {
+    /// The chunk type, e.g. `b"JSON"`.
+    pub ty: [u8; 4],
+    /// The chunk data.
+    pub data: Cow<'a, [u8]>,
+}
+
+/// Binary glTF parsed by [`Glb::from_slice_with_options`].
+///
+/// [`Glb::from_slice_with_options`]: struct.Glb.html#method.from_slice_with_options
+#[derive(Clone, Debug)]
+pub struct Parsed<'a> {
+    /// The binary glTF contents.
+    pub glb: Glb<'a>,
+    /// The chunks which were skipped, in file order.
+    pub chunks: Vec<Chunk<'a>>,
+    /// The tolerated deviations from the container format, in file order.
+    pub warnings: Vec<Warning>,
+}
+
 impl Header {
+    #[cfg(feature = "std")]
     fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, Error> {
         use self::Error::Io;
         let mut magic = [0; 4];
@@ -94,17 +154,30 @@ impl Header {
         }
     }
 
+    fn from_slice(data: &mut &[u8]) -> Result<Self, Error> {
+        let mut magic = [0; 4];
+        magic.copy_from_slice(take(data, 4)?);
+        if &magic == b"glTF" {
+            Ok(Self {
+                magic,
+                version: LittleEndian::read_u32(take(data, 4)?),
+                length: LittleEndian::read_u32(take(data, 4)?),
+            })
+        } else {
+            Err(Error::Magic(magic))
+        }
+    }
+
     fn size_of() -> usize {
         12
     }
 }
 
 impl ChunkHeader {
-    fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, Error> {
-        use self::Error::Io;
-        let length = reader.read_u32::<LittleEndian>().map_err(Io)?;
+    fn from_slice(data: &mut &[u8]) -> Result<Self, Error> {
+        let length = LittleEndian::read_u32(take(data, 4)?);
         let mut ty = [0; 4];
-        reader.read_exact(&mut ty).map_err(Io)?;
+        ty.copy_from_slice(take(data, 4)?);
         let ty = match &ty {
             b"JSON" => Ok(ChunkType::Json),
             b"BIN\0" => Ok(ChunkType::Bin),
@@ -114,12 +187,25 @@ impl ChunkHeader {
     }
 }
 
+/// Splits the first `n` bytes off `data`.
+fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], Error> {
+    if data.len() < n {
+        return Err(Error::Length {
+            length: n as u32,
+            length_read: data.len(),
+        });
+    }
+    let (head, tail) = data.split_at(n);
+    *data = tail;
+    Ok(head)
+}
+
 fn align_to_multiple_of_four(n: &mut usize) {
     *n = (*n + 3) & !3;
 }
 
 fn split_binary_gltf(mut data: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
-    let (json, mut data) = ChunkHeader::from_reader(&mut data)
+    let (json, mut data) = ChunkHeader::from_slice(&mut data)
         .and_then(|json_h| {
             if let ChunkType::Json = json_h.ty {
                 Ok(json_h)
@@ -143,7 +229,7 @@ fn split_binary_gltf(mut data: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
         .map(|json_h| data.split_at(json_h.length as usize))?;
 
     let bin = if !data.is_empty() {
-        ChunkHeader::from_reader(&mut data)
+        ChunkHeader::from_slice(&mut data)
             .and_then(|bin_h| {
                 if let ChunkType::Bin = bin_h.ty {
                     Ok(bin_h)
@@ -172,8 +258,108 @@ fn split_binary_gltf(mut data: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
     Ok((json, bin))
 }
 
+/// GLB header, JSON chunk, and optional BIN chunk.
+pub(crate) type Chunks<'a> = (Header, &'a [u8], Option<&'a [u8]>);
+
+/// Splits a complete GLB into its header, JSON chunk, and optional BIN chunk,
+/// borrowing both chunks from `data`.
+pub(crate) fn split_slice(mut data: &[u8]) -> Result<Chunks<'_>, crate::Error> {
+    let header = Header::from_slice(&mut data)
+        .and_then(|header| {
+            let contents_length = header.length as usize - Header::size_of();
+            if contents_length <= data.len() {
+                Ok(header)
+            } else {
+                Err(Error::Length {
+                    length: contents_length as u32,
+                    length_read: data.len(),
+                })
+            }
+        })
+        .map_err(crate::Error::Binary)?;
+    match header.version {
+        2 => split_binary_gltf(data)
+            .map(|(json, bin)| (header, json, bin))
+            .map_err(crate::Error::Binary),
+        x => Err(crate::Error::Binary(Error::Version(x))),
+    }
+}
+
+impl Options {
+    /// Constructs strict `Options`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether container versions other than `2` are accepted.
+    ///
+    /// Version 1 containers (`KHR_binary_glTF`) are split into their JSON
+    /// content and binary body, while later versions are assumed to keep the
+    /// chunk layout of version 2. Defaults to `false`.
+    pub fn with_unknown_versions(mut self, unknown_versions: bool) -> Self {
+        self.unknown_versions = unknown_versions;
+        self
+    }
+
+    /// Sets whether chunks of unknown type are accepted.
+    ///
+    /// When `true`, every chunk following the JSON chunk is inspected and all
+    /// but the first `BIN` chunk are skipped, keeping their raw data. When
+    /// `false`, the second chunk must be a `BIN` chunk and any further chunks
+    /// are ignored. Defaults to `false`.
+    pub fn with_unknown_chunks(mut self, unknown_chunks: bool) -> Self {
+        self.unknown_chunks = unknown_chunks;
+        self
+    }
+}
+
+/// Splits the chunks of a version 2 layout, skipping unexpected chunks.
+fn split_chunks<'a>(
+    mut data: &'a [u8],
+    chunks: &mut Vec<Chunk<'a>>,
+) -> Result<(&'a [u8], Option<&'a [u8]>), Error> {
+    let mut json = None;
+    let mut bin = None;
+    while !data.is_empty() {
+        let length = LittleEndian::read_u32(take(&mut data, 4)?);
+        let mut ty = [0; 4];
+        ty.copy_from_slice(take(&mut data, 4)?);
+        let chunk = take(&mut data, length as usize)?;
+        match &ty {
+            b"JSON" if json.is_none() => json = Some(chunk),
+            b"BIN\0" if json.is_some() && bin.is_none() => bin = Some(chunk),
+            b"BIN\0" if json.is_none() => return Err(Error::ChunkType(ChunkType::Bin)),
+            _ if json.is_none() => return Err(Error::UnknownChunkType(ty)),
+            _ => chunks.push(Chunk {
+                ty,
+                data: chunk.into(),
+            }),
+        }
+    }
+    match json {
+        Some(json) => Ok((json, bin)),
+        None => Err(Error::Length {
+            length: mem::size_of::<ChunkHeader>() as u32,
+            length_read: 0,
+        }),
+    }
+}
+
+/// Splits the JSON content and binary body of a version 1 layout.
+fn split_version_1(mut data: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
+    let content_length = LittleEndian::read_u32(take(&mut data, 4)?);
+    let content_format = LittleEndian::read_u32(take(&mut data, 4)?);
+    if content_format != 0 {
+        return Err(Error::Version(1));
+    }
+    let json = take(&mut data, content_length as usize)?;
+    Ok((json, Some(data).filter(|body| !body.is_empty())))
+}
+
 impl<'a> Glb<'a> {
     /// Writes binary glTF to a writer.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn to_writer<W>(&self, mut writer: W) -> Result<(), crate::Error>
     where
         W: io::Write,
@@ -237,7 +423,26 @@ impl<'a> Glb<'a> {
         }
 
         let mut vec = Vec::with_capacity(length);
-        self.to_writer(&mut vec as &mut dyn io::Write)?;
+        vec.extend_from_slice(b"glTF");
+        vec.extend_from_slice(&2u32.to_le_bytes());
+        vec.extend_from_slice(&(length as u32).to_le_bytes());
+
+        let mut json_length = self.json.len();
+        align_to_multiple_of_four(&mut json_length);
+        vec.extend_from_slice(&(json_length as u32).to_le_bytes());
+        vec.extend_from_slice(b"JSON");
+        vec.extend_from_slice(&self.json);
+        vec.resize(vec.len() + json_length - self.json.len(), 0x20);
+
+        if let Some(bin) = self.bin.as_ref() {
+            let mut bin_length = bin.len();
+            align_to_multiple_of_four(&mut bin_length);
+            vec.extend_from_slice(&(bin_length as u32).to_le_bytes());
+            vec.extend_from_slice(b"BIN\0");
+            vec.extend_from_slice(bin);
+            vec.resize(vec.len() + bin_length - bin.len(), 0);
+        }
+
         Ok(vec)
     }
 
@@ -246,30 +451,82 @@ impl<'a> Glb<'a> {
     /// * Mandatory GLB header.
     /// * Mandatory JSON chunk.
     /// * Optional BIN chunk.
-    pub fn from_slice(mut data: &'a [u8]) -> Result<Self, crate::Error> {
-        let header = Header::from_reader(&mut data)
-            .and_then(|header| {
-                let contents_length = header.length as usize - Header::size_of();
-                if contents_length <= data.len() {
-                    Ok(header)
-                } else {
-                    Err(Error::Length {
-                        length: contents_length as u32,
-                        length_read: data.len(),
-                    })
-                }
-            })
-            .map_err(crate::Error::Binary)?;
-        match header.version {
-            2 => split_binary_gltf(data)
-                .map(|(json, bin)| Glb {
-                    header,
-                    json: json.into(),
-                    bin: bin.map(Into::into),
-                })
-                .map_err(crate::Error::Binary),
-            x => Err(crate::Error::Binary(Error::Version(x))),
+    pub fn from_slice(data: &'a [u8]) -> Result<Self, crate::Error> {
+        let (header, json, bin) = split_slice(data)?;
+        Ok(Glb {
+            header,
+            json: json.into(),
+            bin: bin.map(Into::into),
+        })
+    }
+
+    /// Splits loaded GLB into its chunks like [`from_slice`], optionally
+    /// tolerating unknown container versions and chunk types.
+    ///
+    /// Tolerated deviations are reported as [`Warning`]s instead of errors,
+    /// and the raw data of skipped chunks is kept, so that readers keep
+    /// working with future revisions of the container format.
+    ///
+    /// ```
+    /// use gltf::binary::{Glb, Options, Warning};
+    ///
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let data = std::fs::read("examples/Box.glb")?;
+    /// let options = Options::new()
+    ///     .with_unknown_versions(true)
+    ///     .with_unknown_chunks(true);
+    /// let parsed = Glb::from_slice_with_options(&data, options)?;
+    /// for warning in &parsed.warnings {
+    ///     if let Warning::Version(version) = warning {
+    ///         eprintln!("parsing GLB version {} as version 2", version);
+    ///     }
+    /// }
+    /// # assert!(parsed.warnings.is_empty());
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    ///
+    /// [`from_slice`]: #method.from_slice
+    /// [`Warning`]: enum.Warning.html
+    pub fn from_slice_with_options(
+        mut data: &'a [u8],
+        options: Options,
+    ) -> Result<Parsed<'a>, crate::Error> {
+        let header = Header::from_slice(&mut data).map_err(crate::Error::Binary)?;
+        let contents_length = (header.length as usize).saturating_sub(Header::size_of());
+        let data = take(&mut data, contents_length).map_err(crate::Error::Binary)?;
+
+        let mut chunks = Vec::new();
+        let mut warnings = Vec::new();
+        if header.version != 2 {
+            if !options.unknown_versions {
+                return Err(crate::Error::Binary(Error::Version(header.version)));
+            }
+            warnings.push(Warning::Version(header.version));
         }
+        let (json, bin) = match header.version {
+            1 => split_version_1(data),
+            _ if options.unknown_chunks => split_chunks(data, &mut chunks),
+            // Only the first two chunks are inspected, like `from_slice`.
+            _ => split_binary_gltf(data),
+        }
+        .map_err(crate::Error::Binary)?;
+        warnings.extend(chunks.iter().map(|chunk| Warning::UnknownChunk {
+            ty: chunk.ty,
+            length: chunk.data.len() as u32,
+        }));
+        Ok(Parsed {
+            glb: Glb {
+                header,
+                json: json.into(),
+                bin: bin.map(Into::into),
+            },
+            chunks,
+            warnings,
+        })
     }
 
     /// Reads binary glTF from a generic stream of data.
@@ -278,6 +535,8 @@ impl<'a> Glb<'a> {
     ///
     /// Reading terminates early if the stream does not contain valid binary
     /// glTF.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, crate::Error> {
         let header = Header::from_reader(&mut reader).map_err(crate::Error::Binary)?;
         match header.version {
@@ -307,6 +566,7 @@ impl fmt::Display for Error {
             f,
             "{}",
             match *self {
+                #[cfg(feature = "std")]
                 Error::Io(ref e) => return e.fmt(f),
                 Error::Version(_) => "unsupported version",
                 Error::Magic(_) => "not glTF magic",
@@ -325,4 +585,5 @@ impl fmt::Display for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl ::std::error::Error for Error {}
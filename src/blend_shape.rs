@@ -0,0 +1,258 @@
+use crate::prelude::*;
+#[cfg(feature = "extras")]
+use crate::Document;
+
+/// A blend shape naming scheme.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Scheme {
+    /// The 52 face tracking blend shapes of Apple ARKit.
+    ArKit,
+
+    /// The 15 visemes of the Oculus Lipsync SDK.
+    Oculus,
+}
+
+macro_rules! blend_shapes {
+    ($($variant:ident => $name:literal, $scheme:ident;)*) => {
+        /// A canonical blend shape of a common naming scheme.
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub enum BlendShape {
+            $(
+                #[doc = concat!("`", $name, "`.")]
+                $variant,
+            )*
+        }
+
+        impl BlendShape {
+            /// All blend shapes, ordered by scheme.
+            pub const ALL: &'static [BlendShape] = &[$(BlendShape::$variant,)*];
+
+            /// Returns the canonical name of the blend shape in its scheme.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(BlendShape::$variant => $name,)*
+                }
+            }
+
+            /// Returns the naming scheme the blend shape belongs to.
+            pub fn scheme(&self) -> Scheme {
+                match self {
+                    $(BlendShape::$variant => Scheme::$scheme,)*
+                }
+            }
+        }
+    };
+}
+
+blend_shapes! {
+    EyeBlinkLeft => "eyeBlinkLeft", ArKit;
+    EyeLookDownLeft => "eyeLookDownLeft", ArKit;
+    EyeLookInLeft => "eyeLookInLeft", ArKit;
+    EyeLookOutLeft => "eyeLookOutLeft", ArKit;
+    EyeLookUpLeft => "eyeLookUpLeft", ArKit;
+    EyeSquintLeft => "eyeSquintLeft", ArKit;
+    EyeWideLeft => "eyeWideLeft", ArKit;
+    EyeBlinkRight => "eyeBlinkRight", ArKit;
+    EyeLookDownRight => "eyeLookDownRight", ArKit;
+    EyeLookInRight => "eyeLookInRight", ArKit;
+    EyeLookOutRight => "eyeLookOutRight", ArKit;
+    EyeLookUpRight => "eyeLookUpRight", ArKit;
+    EyeSquintRight => "eyeSquintRight", ArKit;
+    EyeWideRight => "eyeWideRight", ArKit;
+    JawForward => "jawForward", ArKit;
+    JawLeft => "jawLeft", ArKit;
+    JawRight => "jawRight", ArKit;
+    JawOpen => "jawOpen", ArKit;
+    MouthClose => "mouthClose", ArKit;
+    MouthFunnel => "mouthFunnel", ArKit;
+    MouthPucker => "mouthPucker", ArKit;
+    MouthLeft => "mouthLeft", ArKit;
+    MouthRight => "mouthRight", ArKit;
+    MouthSmileLeft => "mouthSmileLeft", ArKit;
+    MouthSmileRight => "mouthSmileRight", ArKit;
+    MouthFrownLeft => "mouthFrownLeft", ArKit;
+    MouthFrownRight => "mouthFrownRight", ArKit;
+    MouthDimpleLeft => "mouthDimpleLeft", ArKit;
+    MouthDimpleRight => "mouthDimpleRight", ArKit;
+    MouthStretchLeft => "mouthStretchLeft", ArKit;
+    MouthStretchRight => "mouthStretchRight", ArKit;
+    MouthRollLower => "mouthRollLower", ArKit;
+    MouthRollUpper => "mouthRollUpper", ArKit;
+    MouthShrugLower => "mouthShrugLower", ArKit;
+    MouthShrugUpper => "mouthShrugUpper", ArKit;
+    MouthPressLeft => "mouthPressLeft", ArKit;
+    MouthPressRight => "mouthPressRight", ArKit;
+    MouthLowerDownLeft => "mouthLowerDownLeft", ArKit;
+    MouthLowerDownRight => "mouthLowerDownRight", ArKit;
+    MouthUpperUpLeft => "mouthUpperUpLeft", ArKit;
+    MouthUpperUpRight => "mouthUpperUpRight", ArKit;
+    BrowDownLeft => "browDownLeft", ArKit;
+    BrowDownRight => "browDownRight", ArKit;
+    BrowInnerUp => "browInnerUp", ArKit;
+    BrowOuterUpLeft => "browOuterUpLeft", ArKit;
+    BrowOuterUpRight => "browOuterUpRight", ArKit;
+    CheekPuff => "cheekPuff", ArKit;
+    CheekSquintLeft => "cheekSquintLeft", ArKit;
+    CheekSquintRight => "cheekSquintRight", ArKit;
+    NoseSneerLeft => "noseSneerLeft", ArKit;
+    NoseSneerRight => "noseSneerRight", ArKit;
+    TongueOut => "tongueOut", ArKit;
+    VisemeSil => "viseme_sil", Oculus;
+    VisemePp => "viseme_PP", Oculus;
+    VisemeFf => "viseme_FF", Oculus;
+    VisemeTh => "viseme_TH", Oculus;
+    VisemeDd => "viseme_DD", Oculus;
+    VisemeKk => "viseme_kk", Oculus;
+    VisemeCh => "viseme_CH", Oculus;
+    VisemeSs => "viseme_SS", Oculus;
+    VisemeNn => "viseme_nn", Oculus;
+    VisemeRr => "viseme_RR", Oculus;
+    VisemeAa => "viseme_aa", Oculus;
+    VisemeE => "viseme_E", Oculus;
+    VisemeIh => "viseme_I", Oculus;
+    VisemeOh => "viseme_O", Oculus;
+    VisemeOu => "viseme_U", Oculus;
+}
+
+/// Returns `name` in lowercase without separators, with `_L` and `_R` side
+/// suffixes spelled out.
+fn normalize(name: &str) -> String {
+    let mut normalized: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let suffix = name.len().checked_sub(2).and_then(|i| name.get(i..));
+    match suffix {
+        Some("_L") | Some("_l") | Some(".L") | Some(".l") => {
+            normalized.pop();
+            normalized.push_str("left");
+        }
+        Some("_R") | Some("_r") | Some(".R") | Some(".r") => {
+            normalized.pop();
+            normalized.push_str("right");
+        }
+        _ => {}
+    }
+    normalized
+}
+
+impl BlendShape {
+    /// Returns the blend shapes of `scheme`.
+    pub fn of_scheme(scheme: Scheme) -> impl Iterator<Item = BlendShape> {
+        BlendShape::ALL
+            .iter()
+            .copied()
+            .filter(move |shape| shape.scheme() == scheme)
+    }
+
+    /// Maps a blend shape name to its canonical blend shape.
+    ///
+    /// Names are compared ignoring case and separators, so that variants such
+    /// as `EyeBlink_L`, `eye_blink_left`, and `blendShape1.eyeBlinkLeft` are
+    /// recognized. Visemes are recognized with and without their `viseme_`
+    /// prefix.
+    ///
+    /// ```
+    /// use gltf::blend_shape::BlendShape;
+    ///
+    /// assert_eq!(BlendShape::from_name("EyeBlink_L"), Some(BlendShape::EyeBlinkLeft));
+    /// assert_eq!(BlendShape::from_name("viseme_aa"), Some(BlendShape::VisemeAa));
+    /// assert_eq!(BlendShape::from_name("PP"), Some(BlendShape::VisemePp));
+    /// assert_eq!(BlendShape::from_name("Wink"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<BlendShape> {
+        // Maya prefixes target names with the name of the blend shape node.
+        let name = name.rsplit(['.', '|']).next().unwrap_or(name);
+        let normalized = normalize(name);
+        BlendShape::ALL.iter().copied().find(|shape| {
+            let canonical = normalize(shape.name());
+            canonical == normalized
+                || (shape.scheme() == Scheme::Oculus && canonical[6..] == normalized)
+        })
+    }
+}
+
+/// How the morph target names of a mesh map to canonical blend shapes.
+///
+/// Created by [`Document::resolve_blend_shapes`].
+///
+/// [`Document::resolve_blend_shapes`]: ../struct.Document.html#method.resolve_blend_shapes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resolution {
+    /// The index of the mesh.
+    pub mesh: usize,
+
+    /// The morph target indices mapped to their blend shapes, in target order.
+    pub resolved: Vec<(usize, BlendShape)>,
+
+    /// The morph target indices and names which could not be mapped.
+    pub unresolved: Vec<(usize, String)>,
+
+    /// The blend shapes of the schemes used by the mesh without a morph
+    /// target.
+    pub missing: Vec<BlendShape>,
+}
+
+impl Resolution {
+    /// Resolves the morph target `names` of the mesh with index `mesh`.
+    pub fn new<S: AsRef<str>>(mesh: usize, names: &[S]) -> Self {
+        let mut resolution = Resolution {
+            mesh,
+            ..Default::default()
+        };
+        for (index, name) in names.iter().enumerate() {
+            match BlendShape::from_name(name.as_ref()) {
+                Some(shape) => resolution.resolved.push((index, shape)),
+                None => resolution
+                    .unresolved
+                    .push((index, name.as_ref().to_string())),
+            }
+        }
+        for scheme in [Scheme::ArKit, Scheme::Oculus] {
+            if resolution.schemes().any(|used| used == scheme) {
+                let missing: Vec<_> = BlendShape::of_scheme(scheme)
+                    .filter(|&shape| resolution.target(shape).is_none())
+                    .collect();
+                resolution.missing.extend(missing);
+            }
+        }
+        resolution
+    }
+
+    /// Returns the index of the first morph target mapped to `shape`.
+    pub fn target(&self, shape: BlendShape) -> Option<usize> {
+        self.resolved
+            .iter()
+            .find(|(_, resolved)| *resolved == shape)
+            .map(|(index, _)| *index)
+    }
+
+    /// Returns the schemes of the resolved blend shapes.
+    pub fn schemes(&self) -> impl Iterator<Item = Scheme> + '_ {
+        [Scheme::ArKit, Scheme::Oculus]
+            .into_iter()
+            .filter(move |&scheme| {
+                self.resolved
+                    .iter()
+                    .any(|(_, shape)| shape.scheme() == scheme)
+            })
+    }
+}
+
+#[cfg(feature = "extras")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+impl Document {
+    /// Maps the morph target names of every mesh to canonical blend shapes.
+    ///
+    /// Meshes without [`target_names`] are skipped. The result lists, per
+    /// mesh, the morph targets of each blend shape as well as the names which
+    /// could not be mapped and the blend shapes missing from the schemes used.
+    ///
+    /// [`target_names`]: mesh/struct.Mesh.html#method.target_names
+    pub fn resolve_blend_shapes(&self) -> Vec<Resolution> {
+        self.meshes()
+            .filter_map(|mesh| Some(Resolution::new(mesh.index(), &mesh.target_names()?)))
+            .collect()
+    }
+}
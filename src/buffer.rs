@@ -1,7 +1,15 @@
-#[cfg(feature = "import")]
-use std::ops;
+#[cfg(feature = "import_embedded")]
+use alloc::sync::Arc;
+#[cfg(feature = "import_embedded")]
+use alloc::vec::Vec;
+#[cfg(feature = "import_embedded")]
+use core::{fmt, ops};
 
 use crate::Document;
+#[cfg(feature = "extensions")]
+use alloc::string::String;
+#[cfg(feature = "extensions")]
+use serde_json::{Map, Value};
 
 pub use json::buffer::Target;
 
@@ -46,18 +54,95 @@ pub enum Source<'a> {
     Uri(&'a str),
 }
 
+/// Byte storage backing imported buffer data.
+///
+/// Fetchers may return any type convertible into `Bytes`, which allows buffer
+/// data to be backed by storage other than the heap, for example a memory map.
+#[cfg(feature = "import_embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
+#[derive(Clone)]
+pub enum Bytes {
+    /// Heap allocated bytes.
+    Owned(Vec<u8>),
+
+    /// Shared, externally managed bytes.
+    Shared(Arc<dyn ops::Deref<Target = [u8]> + Send + Sync>),
+}
+
 /// Buffer data belonging to an imported glTF asset.
-#[cfg(feature = "import")]
-#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+#[cfg(feature = "import_embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
 #[derive(Clone, Debug)]
-pub struct Data(pub Vec<u8>);
+pub struct Data(pub Bytes);
+
+#[cfg(feature = "import_embedded")]
+impl Bytes {
+    /// Wraps externally managed storage, for example a memory map.
+    pub fn from_storage<T>(storage: T) -> Self
+    where
+        T: ops::Deref<Target = [u8]> + Send + Sync + 'static,
+    {
+        Bytes::Shared(Arc::new(storage))
+    }
+
+    /// Returns the bytes as a `Vec`, copying shared storage.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            Bytes::Owned(vec) => vec,
+            Bytes::Shared(storage) => storage.to_vec(),
+        }
+    }
+
+    /// Pads the bytes with zeros to a multiple of four.
+    ///
+    /// Shared storage is only copied if padding is required.
+    pub(crate) fn pad_to_multiple_of_four(self) -> Self {
+        if self.len() % 4 == 0 {
+            return self;
+        }
+        let mut vec = self.into_vec();
+        while vec.len() % 4 != 0 {
+            vec.push(0);
+        }
+        Bytes::Owned(vec)
+    }
+}
+
+#[cfg(feature = "import_embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
+impl ops::Deref for Bytes {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Bytes::Owned(vec) => vec.as_slice(),
+            Bytes::Shared(storage) => storage,
+        }
+    }
+}
+
+#[cfg(feature = "import_embedded")]
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bytes::Owned(vec) => f.debug_tuple("Owned").field(&vec.len()).finish(),
+            Bytes::Shared(storage) => f.debug_tuple("Shared").field(&storage.len()).finish(),
+        }
+    }
+}
+
+#[cfg(feature = "import_embedded")]
+impl From<Vec<u8>> for Bytes {
+    fn from(vec: Vec<u8>) -> Self {
+        Bytes::Owned(vec)
+    }
+}
 
-#[cfg(feature = "import")]
-#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+#[cfg(feature = "import_embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
 impl ops::Deref for Data {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
-        self.0.as_slice()
+        &self.0
     }
 }
 
@@ -0,0 +1,397 @@
+use crate::accessor::Writer;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::mesh::BoundingBox;
+use crate::prelude::*;
+use core::f32::consts::PI;
+
+use json::animation::{Channel, Interpolation, Property, Sampler, Target};
+use json::camera::{Perspective, Type};
+use json::validation::Checked::Valid;
+
+/// Options for [`turntable`] and [`orbit`].
+///
+/// [`turntable`]: fn.turntable.html
+/// [`orbit`]: fn.orbit.html
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The duration of one revolution in seconds.
+    duration: f32,
+
+    /// The vertical field of view in radians.
+    yfov: f32,
+
+    /// The aspect ratio of the camera, if fixed.
+    aspect_ratio: Option<f32>,
+
+    /// The angle of the camera above the horizon in radians.
+    elevation: f32,
+
+    /// The amplitude of the elevation change of orbits in radians.
+    sweep: f32,
+
+    /// The factor applied to the distance at which the bounds fill the view.
+    margin: f32,
+
+    /// The number of keyframes of orbits.
+    keyframes: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            duration: 8.0,
+            yfov: 0.8,
+            aspect_ratio: None,
+            elevation: 0.3,
+            sweep: 0.2,
+            margin: 1.1,
+            keyframes: 64,
+        }
+    }
+}
+
+impl Options {
+    /// Constructs the default `Options`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the duration of one revolution in seconds. Defaults to 8.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the vertical field of view of the camera in radians. Defaults
+    /// to 0.8.
+    pub fn with_yfov(mut self, yfov: f32) -> Self {
+        self.yfov = yfov;
+        self
+    }
+
+    /// Sets the aspect ratio of the camera, for example `16.0 / 9.0` for the
+    /// frames of a preview video. Narrow aspect ratios move the camera back
+    /// so that the bounds fit horizontally. Defaults to `None`, leaving the
+    /// aspect ratio to the viewport.
+    pub fn with_aspect_ratio(mut self, aspect_ratio: Option<f32>) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// Sets the angle of the camera above the horizon in radians. Defaults
+    /// to 0.3.
+    pub fn with_elevation(mut self, elevation: f32) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// Sets how far orbits swing above and below the elevation in radians,
+    /// once per revolution. Defaults to 0.2.
+    pub fn with_sweep(mut self, sweep: f32) -> Self {
+        self.sweep = sweep;
+        self
+    }
+
+    /// Sets the factor applied to the distance at which the bounding sphere
+    /// of the bounds just fills the view. Defaults to 1.1.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the number of keyframes of orbits, which are linearly
+    /// interpolated. Defaults to 64.
+    pub fn with_keyframes(mut self, keyframes: usize) -> Self {
+        self.keyframes = keyframes.max(3);
+        self
+    }
+}
+
+/// The objects added by [`turntable`] or [`orbit`].
+///
+/// [`turntable`]: fn.turntable.html
+/// [`orbit`]: fn.orbit.html
+#[derive(Clone, Copy, Debug)]
+pub struct Rig {
+    /// The perspective camera.
+    pub camera: json::Index<json::Camera>,
+
+    /// The node the camera is attached to.
+    pub node: json::Index<json::Node>,
+
+    /// The animation moving the camera.
+    pub animation: json::Index<json::Animation>,
+}
+
+/// The center of the bounding sphere of `bounds`, the distance of the camera
+/// from it, and the camera looking at it.
+struct Framing {
+    center: [f32; 3],
+    distance: f32,
+    camera: json::Camera,
+}
+
+impl Framing {
+    fn new(bounds: &BoundingBox, options: &Options) -> Self {
+        let center = [0, 1, 2].map(|i| (bounds.min[i] + bounds.max[i]) / 2.0);
+        let diagonal: f32 = (0..3)
+            .map(|i| (bounds.max[i] - bounds.min[i]).powi(2))
+            .sum();
+        // Keep empty bounds visible.
+        let radius = (diagonal.sqrt() / 2.0).max(1e-3);
+
+        // The sine of the smaller half field of view.
+        let (sin, cos) = (options.yfov / 2.0).sin_cos();
+        let sin = match options.aspect_ratio {
+            Some(aspect_ratio) if aspect_ratio < 1.0 => {
+                let tan = sin / cos * aspect_ratio;
+                tan / (1.0 + tan * tan).sqrt()
+            }
+            _ => sin,
+        };
+        let distance = radius / sin * options.margin;
+        let camera = json::Camera {
+            #[cfg(feature = "names")]
+            name: None,
+            orthographic: None,
+            perspective: Some(Perspective {
+                aspect_ratio: options.aspect_ratio,
+                yfov: options.yfov,
+                zfar: Some((distance + radius) * 2.0),
+                znear: ((distance - radius) / 2.0).max(distance * 1e-3),
+                extensions: None,
+                extras: Default::default(),
+            }),
+            type_: Valid(Type::Perspective),
+            extensions: None,
+            extras: Default::default(),
+        };
+        Self {
+            center,
+            distance,
+            camera,
+        }
+    }
+}
+
+/// Returns the rotation turning a camera by `yaw` around the Y axis and
+/// tilting it down by `pitch`.
+fn look_rotation(yaw: f32, pitch: f32) -> [f32; 4] {
+    let (sy, cy) = (yaw / 2.0).sin_cos();
+    let (sx, cx) = (-pitch / 2.0).sin_cos();
+    [cy * sx, sy * cx, -sy * sx, cy * cx]
+}
+
+/// Returns a node named `name` with the given transform.
+fn node(name: &str, translation: [f32; 3], rotation: [f32; 4]) -> json::Node {
+    #[cfg(not(feature = "names"))]
+    let _ = name;
+    json::Node {
+        camera: None,
+        children: None,
+        extensions: None,
+        extras: Default::default(),
+        matrix: None,
+        mesh: None,
+        #[cfg(feature = "names")]
+        name: Some(name.to_owned()),
+        rotation: Some(json::scene::UnitQuaternion(rotation)),
+        scale: None,
+        translation: Some(translation),
+        skin: None,
+        weights: None,
+    }
+}
+
+/// Appends `node`, returning its index.
+fn push_node(root: &mut json::Root, node: json::Node) -> json::Index<json::Node> {
+    root.nodes.push(node);
+    json::Index::new(root.nodes.len() as u32 - 1)
+}
+
+/// Appends a camera for `node` and an animation named `name` of `top`, and
+/// attaches `top` to `scene`.
+fn finish(
+    writer: &mut Writer,
+    name: &str,
+    scene: json::Index<json::Scene>,
+    top: json::Index<json::Node>,
+    node: json::Index<json::Node>,
+    camera: json::Camera,
+    samplers: Vec<(
+        Property,
+        json::Index<json::Accessor>,
+        json::Index<json::Accessor>,
+    )>,
+) -> Rig {
+    #[cfg(not(feature = "names"))]
+    let _ = name;
+    let root = writer.root_mut();
+    root.cameras.push(camera);
+    let camera = json::Index::new(root.cameras.len() as u32 - 1);
+    root.nodes[node.value()].camera = Some(camera);
+    root.scenes[scene.value()].nodes.push(top);
+
+    let mut animation = json::Animation {
+        extensions: None,
+        extras: Default::default(),
+        channels: Vec::new(),
+        #[cfg(feature = "names")]
+        name: Some(name.to_owned()),
+        samplers: Vec::new(),
+    };
+    for (property, input, output) in samplers {
+        animation.channels.push(Channel {
+            sampler: json::Index::new(animation.samplers.len() as u32),
+            target: Target {
+                extensions: None,
+                extras: Default::default(),
+                node: top,
+                path: Valid(property),
+            },
+            extensions: None,
+            extras: Default::default(),
+        });
+        animation.samplers.push(Sampler {
+            extensions: None,
+            extras: Default::default(),
+            input,
+            interpolation: Valid(Interpolation::Linear),
+            output,
+        });
+    }
+    root.animations.push(animation);
+    Rig {
+        camera,
+        node,
+        animation: json::Index::new(root.animations.len() as u32 - 1),
+    }
+}
+
+/// Adds a camera circling `bounds` at a constant elevation to `scene`, for
+/// product shots.
+///
+/// The camera is attached to a new pivot node at the center of the bounds,
+/// whose rotation around the Y axis is animated, completing one
+/// counter-clockwise revolution as seen from above. The keyframes are written
+/// by `writer`. `bounds` is typically computed with
+/// [`Scene::compute_bounding_box`] before the document is modified. Returns
+/// `None` without modifying the document if `scene` does not exist.
+///
+/// ```
+/// use gltf::camera_path::{self, Options};
+/// use gltf::mesh::BoundingBox;
+///
+/// let mut root = gltf::json::Root::default();
+/// root.scenes = gltf::json::deserialize::from_str(r#"[{ "nodes": [] }]"#).unwrap();
+/// let bounds = BoundingBox { min: [-1.0; 3], max: [1.0; 3] };
+/// let mut writer = gltf::accessor::Writer::new(&mut root);
+/// let options = Options::new().with_duration(4.0);
+/// let rig = camera_path::turntable(&mut writer, gltf::json::Index::new(0), &bounds, &options)
+///     .unwrap();
+/// let bin = writer.finish();
+/// assert_eq!(root.nodes[rig.node.value()].camera, Some(rig.camera));
+/// assert_eq!(root.nodes.len(), 2);
+/// assert_eq!(root.scenes[0].nodes.len(), 1);
+/// # assert!(!bin.is_empty());
+/// ```
+///
+/// [`Scene::compute_bounding_box`]: ../scene/struct.Scene.html#method.compute_bounding_box
+pub fn turntable(
+    writer: &mut Writer,
+    scene: json::Index<json::Scene>,
+    bounds: &BoundingBox,
+    options: &Options,
+) -> Option<Rig> {
+    writer.root_mut().scenes.get(scene.value())?;
+    let framing = Framing::new(bounds, options);
+
+    // Quarter turns are short enough for interpolation to keep the angular
+    // speed constant.
+    let times: Vec<f32> = (0..5).map(|i| options.duration * i as f32 / 4.0).collect();
+    let rotations: Vec<[f32; 4]> = (0..5)
+        .map(|i| look_rotation(PI / 2.0 * i as f32, 0.0))
+        .collect();
+    let input = writer.push(&times, None)?;
+    let output = writer.push(&rotations, None)?;
+
+    let (sin, cos) = options.elevation.sin_cos();
+    let root = writer.root_mut();
+    let mut pivot = node("Turntable", framing.center, [0.0, 0.0, 0.0, 1.0]);
+    let camera = node(
+        "Turntable Camera",
+        [0.0, framing.distance * sin, framing.distance * cos],
+        look_rotation(0.0, options.elevation),
+    );
+    let camera = push_node(root, camera);
+    pivot.children = Some(vec![camera]);
+    let pivot = push_node(root, pivot);
+    Some(finish(
+        writer,
+        "Turntable",
+        scene,
+        pivot,
+        camera,
+        framing.camera,
+        vec![(Property::Rotation, input, output)],
+    ))
+}
+
+/// Adds a camera orbiting `bounds` to `scene`, for preview videos.
+///
+/// The translation and rotation of a new camera node are keyframed, with
+/// the camera completing one counter-clockwise revolution as seen from above
+/// while swinging above and below the elevation once, see
+/// [`Options::with_sweep`]. The keyframes are written by `writer`. Returns
+/// `None` without modifying the document if `scene` does not exist.
+///
+/// [`Options::with_sweep`]: struct.Options.html#method.with_sweep
+pub fn orbit(
+    writer: &mut Writer,
+    scene: json::Index<json::Scene>,
+    bounds: &BoundingBox,
+    options: &Options,
+) -> Option<Rig> {
+    writer.root_mut().scenes.get(scene.value())?;
+    let framing = Framing::new(bounds, options);
+
+    let count = options.keyframes;
+    let mut times = Vec::with_capacity(count + 1);
+    let mut translations = Vec::with_capacity(count + 1);
+    let mut rotations = Vec::with_capacity(count + 1);
+    for i in 0..=count {
+        let t = i as f32 / count as f32;
+        let yaw = 2.0 * PI * t;
+        let elevation = options.elevation + options.sweep * yaw.sin();
+        let (sin_yaw, cos_yaw) = yaw.sin_cos();
+        let (sin, cos) = elevation.sin_cos();
+        let [x, y, z] = framing.center;
+        let distance = framing.distance;
+        times.push(options.duration * t);
+        translations.push([
+            x + distance * cos * sin_yaw,
+            y + distance * sin,
+            z + distance * cos * cos_yaw,
+        ]);
+        rotations.push(look_rotation(yaw, elevation));
+    }
+    let input = writer.push(&times, None)?;
+    let translation = writer.push(&translations, None)?;
+    let rotation = writer.push(&rotations, None)?;
+
+    let camera = node("Orbit Camera", translations[0], rotations[0]);
+    let camera = push_node(writer.root_mut(), camera);
+    Some(finish(
+        writer,
+        "Orbit",
+        scene,
+        camera,
+        camera,
+        framing.camera,
+        vec![
+            (Property::Translation, input, translation),
+            (Property::Rotation, input, rotation),
+        ],
+    ))
+}
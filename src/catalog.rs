@@ -0,0 +1,400 @@
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use json::Value;
+use serde_json::json;
+
+use crate::mesh::{BoundingBox, Mode, Semantic};
+use crate::{buffer, image, Document, Error, FileKind, Gltf, Primitive, Result};
+
+/// Options for [`summarize_with_options`].
+///
+/// [`summarize_with_options`]: fn.summarize_with_options.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// Whether a thumbnail image is read.
+    thumbnail: bool,
+}
+
+impl Options {
+    /// Constructs the default `Options`, which produce the same summary as
+    /// [`summarize`](fn.summarize.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a thumbnail image is read. Defaults to `false`.
+    ///
+    /// The thumbnail is the base color texture of the first material, or the
+    /// first image if there is no such texture. Images stored in a buffer
+    /// view require reading the buffers of the asset.
+    pub fn with_thumbnail(mut self, thumbnail: bool) -> Self {
+        self.thumbnail = thumbnail;
+        self
+    }
+}
+
+/// Object and geometry counts of an asset.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    /// The number of scenes.
+    pub scenes: usize,
+    /// The number of nodes.
+    pub nodes: usize,
+    /// The number of meshes.
+    pub meshes: usize,
+    /// The number of primitives of all meshes.
+    pub primitives: usize,
+    /// The number of materials.
+    pub materials: usize,
+    /// The number of textures.
+    pub textures: usize,
+    /// The number of images.
+    pub images: usize,
+    /// The number of animations.
+    pub animations: usize,
+    /// The number of skins.
+    pub skins: usize,
+    /// The number of cameras.
+    pub cameras: usize,
+    /// The number of vertices of all primitives.
+    pub vertices: usize,
+    /// The number of triangles of all primitives, counting each mesh once.
+    pub triangles: usize,
+}
+
+/// A compact summary of an asset for building searchable asset databases.
+///
+/// Summaries are computed from the glTF JSON alone unless a thumbnail is
+/// requested, so that thousands of files can be indexed quickly.
+#[derive(Clone)]
+pub struct Summary {
+    /// The path of the summarized file.
+    pub path: PathBuf,
+    /// The size of the summarized file in bytes.
+    pub file_size: u64,
+    /// The container format of the file.
+    pub container: FileKind,
+    /// The tool that generated the asset.
+    pub generator: Option<String>,
+    /// The copyright message of the asset.
+    pub copyright: Option<String>,
+    /// Object and geometry counts.
+    pub counts: Counts,
+    /// The world-space bounds of the default scene, computed from the
+    /// accessor `min` and `max` properties.
+    ///
+    /// If there is no default scene the first scene is used. `None` if no
+    /// positions declare their bounds.
+    pub bounds: Option<BoundingBox>,
+    /// The extensions used by the asset.
+    pub extensions_used: Vec<String>,
+    /// The extensions required to load the asset.
+    pub extensions_required: Vec<String>,
+    /// The URIs of external buffers and images.
+    pub dependencies: Vec<String>,
+    /// The thumbnail image, if requested and available.
+    pub thumbnail: Option<image::Encoded>,
+}
+
+impl fmt::Debug for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Summary")
+            .field("path", &self.path)
+            .field("file_size", &self.file_size)
+            .field("container", &self.container)
+            .field("generator", &self.generator)
+            .field("copyright", &self.copyright)
+            .field("counts", &self.counts)
+            .field("bounds", &self.bounds)
+            .field("extensions_used", &self.extensions_used)
+            .field("extensions_required", &self.extensions_required)
+            .field("dependencies", &self.dependencies)
+            .field(
+                "thumbnail",
+                &self.thumbnail.as_ref().map(|image| image.bytes.len()),
+            )
+            .finish()
+    }
+}
+
+impl Summary {
+    /// Returns the summary as a JSON object, omitting the thumbnail data.
+    ///
+    /// The container is `"glb"` or `"gltf"`, and the thumbnail is described
+    /// by its MIME type and size in bytes.
+    pub fn to_json(&self) -> Value {
+        let counts = &self.counts;
+        let bounds = self
+            .bounds
+            .as_ref()
+            .map(|bounds| json!({ "min": bounds.min, "max": bounds.max }));
+        let thumbnail = self.thumbnail.as_ref().map(|image| {
+            json!({
+                "mimeType": image.encoding.mime_type(),
+                "size": image.bytes.len(),
+            })
+        });
+        json!({
+            "path": self.path.to_string_lossy(),
+            "fileSize": self.file_size,
+            "container": if self.container.is_glb() { "glb" } else { "gltf" },
+            "generator": self.generator,
+            "copyright": self.copyright,
+            "counts": {
+                "scenes": counts.scenes,
+                "nodes": counts.nodes,
+                "meshes": counts.meshes,
+                "primitives": counts.primitives,
+                "materials": counts.materials,
+                "textures": counts.textures,
+                "images": counts.images,
+                "animations": counts.animations,
+                "skins": counts.skins,
+                "cameras": counts.cameras,
+                "vertices": counts.vertices,
+                "triangles": counts.triangles,
+            },
+            "bounds": bounds,
+            "extensionsUsed": self.extensions_used,
+            "extensionsRequired": self.extensions_required,
+            "dependencies": self.dependencies,
+            "thumbnail": thumbnail,
+        })
+    }
+}
+
+/// Returns the bounds declared by the `POSITION` accessor of `primitive`.
+fn declared_bounds(primitive: &Primitive<'_>) -> Option<BoundingBox> {
+    let positions = primitive.get(&Semantic::Positions)?;
+    let min = json::deserialize::from_value(positions.min()?).ok()?;
+    let max = json::deserialize::from_value(positions.max()?).ok()?;
+    Some(BoundingBox { min, max })
+}
+
+/// Returns the number of triangles drawn by `primitive`.
+fn triangle_count(primitive: &Primitive<'_>) -> usize {
+    let count = match primitive.indices() {
+        Some(indices) => indices.count(),
+        None => primitive
+            .get(&Semantic::Positions)
+            .map_or(0, |positions| positions.count()),
+    };
+    match primitive.mode() {
+        Mode::Triangles => count / 3,
+        Mode::TriangleStrip | Mode::TriangleFan => count.saturating_sub(2),
+        _ => 0,
+    }
+}
+
+/// Computes the counts of `document`.
+fn counts(document: &Document) -> Counts {
+    let mut counts = Counts {
+        scenes: document.scenes().len(),
+        nodes: document.nodes().len(),
+        meshes: document.meshes().len(),
+        materials: document.materials().len(),
+        textures: document.textures().len(),
+        images: document.images().len(),
+        animations: document.animations().len(),
+        skins: document.skins().len(),
+        cameras: document.cameras().len(),
+        ..Counts::default()
+    };
+    for primitive in document.meshes().flat_map(|mesh| mesh.primitives()) {
+        counts.primitives += 1;
+        counts.vertices += primitive
+            .get(&Semantic::Positions)
+            .map_or(0, |positions| positions.count());
+        counts.triangles += triangle_count(&primitive);
+    }
+    counts
+}
+
+/// Computes the declared world-space bounds of the default scene.
+fn bounds(document: &Document) -> Option<BoundingBox> {
+    let scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next())?;
+    scene
+        .traverse()
+        .filter_map(|(node, transform)| {
+            node.mesh()?
+                .primitives()
+                .filter_map(|primitive| declared_bounds(&primitive))
+                .reduce(|a, b| a.union(&b))
+                .map(|bounds| bounds.transform(&transform))
+        })
+        .reduce(|a, b| a.union(&b))
+}
+
+/// Returns the URIs of the external buffers and images of `document`.
+fn dependencies(document: &Document) -> Vec<String> {
+    let buffers = document
+        .buffers()
+        .filter_map(|buffer| match buffer.source() {
+            buffer::Source::Uri(uri) => Some(uri),
+            buffer::Source::Bin => None,
+        });
+    let images = document.images().filter_map(|image| match image.source() {
+        image::Source::Uri { uri, .. } => Some(uri),
+        image::Source::View { .. } => None,
+    });
+    buffers
+        .chain(images)
+        .filter(|uri| !uri.starts_with("data:"))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Reads the thumbnail image of `gltf`.
+fn thumbnail(gltf: Gltf, base: &Path) -> Result<Option<image::Encoded>> {
+    let Gltf { document, blob, .. } = gltf;
+    let image = document
+        .materials()
+        .next()
+        .and_then(|material| material.pbr_metallic_roughness().base_color_texture())
+        .map(|info| info.texture().source())
+        .or_else(|| document.images().next());
+    let image = match image {
+        Some(image) => image,
+        None => return Ok(None),
+    };
+    // Only the buffer behind the image is read, the others are left empty.
+    let mut buffer_data = Vec::new();
+    if let image::Source::View { view, .. } = image.source() {
+        let buffer = view.buffer();
+        let mut blob = blob;
+        let data = buffer::Data::from_source_and_blob(
+            buffer.source(),
+            &mut blob,
+            Some(base),
+            crate::filesystem_fetcher,
+        )?;
+        if data.len() < buffer.length() {
+            return Err(Error::BufferLength {
+                buffer: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
+        }
+        buffer_data.resize_with(buffer.index(), || buffer::Data(Vec::new().into()));
+        buffer_data.push(data);
+    }
+    image::Encoded::from_source(
+        image.source(),
+        &buffer_data,
+        Some(base),
+        crate::filesystem_fetcher,
+    )
+    .map(Some)
+}
+
+/// Summarizes the glTF or binary glTF file at `path`.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let summary = gltf::catalog::summarize("examples/Box.gltf")?;
+/// assert_eq!(summary.counts.triangles, 12);
+/// assert_eq!(summary.dependencies, ["Box0.bin"]);
+/// println!("{}", summary.to_json());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+pub fn summarize<P: AsRef<Path>>(path: P) -> Result<Summary> {
+    summarize_with_options(path, Options::new())
+}
+
+/// Summarizes the glTF or binary glTF file at `path` like [`summarize`],
+/// with additional options.
+///
+/// [`summarize`]: fn.summarize.html
+pub fn summarize_with_options<P: AsRef<Path>>(path: P, options: Options) -> Result<Summary> {
+    let path = path.as_ref();
+    let slice = fs::read(path)?;
+    let gltf = Gltf::from_slice(&slice)?;
+    let document = &gltf.document;
+    let asset = &document.0.asset;
+    let mut summary = Summary {
+        path: path.to_path_buf(),
+        file_size: slice.len() as u64,
+        container: gltf.container,
+        generator: asset.generator.clone(),
+        copyright: asset.copyright.clone(),
+        counts: counts(document),
+        bounds: bounds(document),
+        extensions_used: document.extensions_used().map(str::to_owned).collect(),
+        extensions_required: document.extensions_required().map(str::to_owned).collect(),
+        dependencies: dependencies(document),
+        thumbnail: None,
+    };
+    if options.thumbnail {
+        let base = path.parent().unwrap_or_else(|| Path::new("./"));
+        summary.thumbnail = thumbnail(gltf, base)?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog;
+    use crate::fixture::TempDir;
+    use std::fs;
+
+    #[test]
+    fn summarize() {
+        let summary = catalog::summarize("examples/Box.glb").unwrap();
+        assert!(summary.container.is_glb());
+        assert_eq!(summary.counts.meshes, 1);
+        assert_eq!(summary.counts.vertices, 24);
+        assert_eq!(summary.counts.triangles, 12);
+        assert!(summary.dependencies.is_empty());
+        let bounds = summary.bounds.as_ref().unwrap();
+        assert_eq!(bounds.min, [-0.5; 3]);
+        assert_eq!(bounds.max, [0.5; 3]);
+        assert_eq!(summary.to_json()["counts"]["triangles"], 12);
+
+        let root = TempDir::new("catalog");
+        fs::copy("tests/orange_64x32.jpg", root.join("orange.jpg")).unwrap();
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "images": [{ "uri": "orange.jpg" }],
+            "textures": [{ "source": 0 }],
+            "materials": [{ "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } }]
+        }"#;
+        fs::write(root.join("Material.gltf"), json).unwrap();
+
+        let path = root.join("Material.gltf");
+        let summary = catalog::summarize(&path).unwrap();
+        assert_eq!(summary.dependencies, ["orange.jpg"]);
+        assert!(summary.bounds.is_none());
+        assert!(summary.thumbnail.is_none());
+        let options = catalog::Options::new().with_thumbnail(true);
+        let summary = catalog::summarize_with_options(&path, options).unwrap();
+        let thumbnail = summary.thumbnail.as_ref().unwrap();
+        assert_eq!(thumbnail.bytes, fs::read("tests/orange_64x32.jpg").unwrap());
+        assert_eq!(summary.to_json()["thumbnail"]["mimeType"], "image/jpeg");
+
+        // Only the buffer holding the thumbnail is read.
+        let jpg = fs::read("tests/orange_64x32.jpg").unwrap();
+        fs::write(root.join("images.bin"), &jpg).unwrap();
+        let json = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "buffers": [
+                    {{ "byteLength": 4, "uri": "missing.bin" }},
+                    {{ "byteLength": {0}, "uri": "images.bin" }}
+                ],
+                "bufferViews": [{{ "buffer": 1, "byteLength": {0} }}],
+                "images": [{{ "bufferView": 0, "mimeType": "image/jpeg" }}]
+            }}"#,
+            jpg.len()
+        );
+        fs::write(root.join("View.gltf"), json).unwrap();
+        let summary = catalog::summarize_with_options(root.join("View.gltf"), options).unwrap();
+        assert_eq!(summary.thumbnail.unwrap().bytes, jpg);
+    }
+}
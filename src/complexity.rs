@@ -0,0 +1,328 @@
+use crate::prelude::*;
+use crate::{Document, Error, Result};
+use json::validation::Error::Invalid;
+
+use json::Value;
+use serde_json::json;
+
+/// Structural metrics of a document, see [`Document::complexity`].
+///
+/// [`Document::complexity`]: ../struct.Document.html#method.complexity
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Complexity {
+    /// The number of nodes.
+    pub nodes: usize,
+
+    /// The number of nodes on the longest path from a root node to a leaf
+    /// node, or zero without nodes.
+    pub depth: usize,
+
+    /// The largest number of children of a node.
+    pub max_children: usize,
+
+    /// The number of meshes.
+    pub meshes: usize,
+
+    /// The number of primitives of all meshes.
+    pub primitives: usize,
+
+    /// The largest number of primitives of a mesh.
+    pub max_primitives: usize,
+
+    /// The largest number of morph targets of a primitive.
+    pub max_morph_targets: usize,
+
+    /// The number of animations.
+    pub animations: usize,
+
+    /// The number of channels of all animations.
+    pub channels: usize,
+
+    /// The largest number of channels of an animation.
+    pub max_channels: usize,
+
+    /// The number of skins.
+    pub skins: usize,
+
+    /// The largest number of joints of a skin.
+    pub max_joints: usize,
+}
+
+impl Complexity {
+    /// Returns the metrics as a JSON object with camel case keys, for
+    /// example `{ "nodes": 12, "depth": 4, "maxChildren": 3, ... }`.
+    pub fn to_value(&self) -> Value {
+        json!({
+            "nodes": self.nodes,
+            "depth": self.depth,
+            "maxChildren": self.max_children,
+            "meshes": self.meshes,
+            "primitives": self.primitives,
+            "maxPrimitives": self.max_primitives,
+            "maxMorphTargets": self.max_morph_targets,
+            "animations": self.animations,
+            "channels": self.channels,
+            "maxChannels": self.max_channels,
+            "skins": self.skins,
+            "maxJoints": self.max_joints,
+        })
+    }
+}
+
+/// Thresholds for [`Document::check_complexity`], for example the limits of
+/// an embedded viewer.
+///
+/// Every limit is unset by default.
+///
+/// [`Document::check_complexity`]: ../struct.Document.html#method.check_complexity
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Limits {
+    /// The maximum number of nodes.
+    max_nodes: Option<usize>,
+
+    /// The maximum hierarchy depth.
+    max_depth: Option<usize>,
+
+    /// The maximum number of children of a node.
+    max_children: Option<usize>,
+
+    /// The maximum number of primitives of a mesh.
+    max_primitives: Option<usize>,
+
+    /// The maximum number of morph targets of a primitive.
+    max_morph_targets: Option<usize>,
+
+    /// The maximum number of channels of an animation.
+    max_channels: Option<usize>,
+
+    /// The maximum number of joints of a skin.
+    max_joints: Option<usize>,
+}
+
+impl Limits {
+    /// Constructs `Limits` without any limit set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of nodes.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Sets the maximum number of nodes on a path from a root node to a leaf
+    /// node.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the maximum number of children of a node.
+    pub fn with_max_children(mut self, max_children: usize) -> Self {
+        self.max_children = Some(max_children);
+        self
+    }
+
+    /// Sets the maximum number of primitives of a mesh.
+    pub fn with_max_primitives(mut self, max_primitives: usize) -> Self {
+        self.max_primitives = Some(max_primitives);
+        self
+    }
+
+    /// Sets the maximum number of morph targets of a primitive.
+    pub fn with_max_morph_targets(mut self, max_morph_targets: usize) -> Self {
+        self.max_morph_targets = Some(max_morph_targets);
+        self
+    }
+
+    /// Sets the maximum number of channels of an animation.
+    pub fn with_max_channels(mut self, max_channels: usize) -> Self {
+        self.max_channels = Some(max_channels);
+        self
+    }
+
+    /// Sets the maximum number of joints of a skin.
+    pub fn with_max_joints(mut self, max_joints: usize) -> Self {
+        self.max_joints = Some(max_joints);
+        self
+    }
+}
+
+/// Returns `true` if `value` exceeds `limit`.
+fn exceeds(value: usize, limit: Option<usize>) -> bool {
+    limit.map_or(false, |limit| value > limit)
+}
+
+/// Returns the depth of every node of `root`, where root nodes have depth
+/// one.
+///
+/// Nodes which are only reachable through a cycle have depth zero. Nodes
+/// with several parents have the depth of any of the paths to them.
+fn depths(root: &json::Root) -> Vec<usize> {
+    let nodes = &root.nodes;
+    let mut is_child = vec![false; nodes.len()];
+    for node in nodes {
+        for child in node.children.iter().flatten() {
+            if let Some(is_child) = is_child.get_mut(child.value()) {
+                *is_child = true;
+            }
+        }
+    }
+    let mut depths = vec![0; nodes.len()];
+    let mut stack: Vec<(usize, usize)> = (0..nodes.len())
+        .filter(|&index| !is_child[index])
+        .map(|index| (index, 1))
+        .collect();
+    while let Some((index, depth)) = stack.pop() {
+        // Paths longer than the number of nodes run through a cycle.
+        if depth > nodes.len() {
+            continue;
+        }
+        depths[index] = depth;
+        for child in nodes[index].children.iter().flatten() {
+            if child.value() < nodes.len() {
+                stack.push((child.value(), depth + 1));
+            }
+        }
+    }
+    depths
+}
+
+impl Document {
+    /// Returns structural metrics of the document, such as the depth of the
+    /// node hierarchy and the number of animation channels.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let (document, _, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+    /// let complexity = document.complexity();
+    /// assert_eq!(complexity.depth, 2);
+    /// assert_eq!(complexity.max_children, 1);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn complexity(&self) -> Complexity {
+        let root = &self.0;
+        let children = |node: &json::Node| node.children.as_ref().map_or(0, Vec::len);
+        let targets =
+            |primitive: &json::mesh::Primitive| primitive.targets.as_ref().map_or(0, Vec::len);
+        Complexity {
+            nodes: root.nodes.len(),
+            depth: depths(root).into_iter().max().unwrap_or(0),
+            max_children: root.nodes.iter().map(children).max().unwrap_or(0),
+            meshes: root.meshes.len(),
+            primitives: root.meshes.iter().map(|mesh| mesh.primitives.len()).sum(),
+            max_primitives: root
+                .meshes
+                .iter()
+                .map(|mesh| mesh.primitives.len())
+                .max()
+                .unwrap_or(0),
+            max_morph_targets: root
+                .meshes
+                .iter()
+                .flat_map(|mesh| mesh.primitives.iter().map(targets))
+                .max()
+                .unwrap_or(0),
+            animations: root.animations.len(),
+            channels: root.animations.iter().map(|a| a.channels.len()).sum(),
+            max_channels: root
+                .animations
+                .iter()
+                .map(|animation| animation.channels.len())
+                .max()
+                .unwrap_or(0),
+            skins: root.skins.len(),
+            max_joints: root
+                .skins
+                .iter()
+                .map(|skin| skin.joints.len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Checks the structure of the document against `limits`, for
+    /// constrained runtimes such as embedded viewers.
+    ///
+    /// Returns an `Error::Validation` with an `Invalid` error for each object
+    /// exceeding a limit, at the path of the offending property, for example
+    /// `nodes[3].children` for too many children or `nodes[7]` for a node
+    /// nested too deeply.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// use gltf::complexity::Limits;
+    ///
+    /// let (document, _, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+    /// assert!(document.check_complexity(&Limits::new().with_max_depth(2)).is_ok());
+    /// assert!(document.check_complexity(&Limits::new().with_max_depth(1)).is_err());
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn check_complexity(&self, limits: &Limits) -> Result<()> {
+        let root = &self.0;
+        let mut errors = Vec::new();
+        let path = || json::Path::new();
+        if exceeds(root.nodes.len(), limits.max_nodes) {
+            errors.push((path().field("nodes"), Invalid));
+        }
+        for (index, depth) in depths(root).into_iter().enumerate() {
+            // Only the deepest node of each path is reported.
+            let is_leaf = root.nodes[index]
+                .children
+                .as_ref()
+                .map_or(true, Vec::is_empty);
+            if is_leaf && exceeds(depth, limits.max_depth) {
+                errors.push((path().field("nodes").index(index), Invalid));
+            }
+        }
+        for (index, node) in root.nodes.iter().enumerate() {
+            let children = node.children.as_ref().map_or(0, Vec::len);
+            if exceeds(children, limits.max_children) {
+                let path = path().field("nodes").index(index).field("children");
+                errors.push((path, Invalid));
+            }
+        }
+        for (index, mesh) in root.meshes.iter().enumerate() {
+            let mesh_path = || path().field("meshes").index(index);
+            if exceeds(mesh.primitives.len(), limits.max_primitives) {
+                errors.push((mesh_path().field("primitives"), Invalid));
+            }
+            for (primitive, json) in mesh.primitives.iter().enumerate() {
+                let targets = json.targets.as_ref().map_or(0, Vec::len);
+                if exceeds(targets, limits.max_morph_targets) {
+                    let path = mesh_path()
+                        .field("primitives")
+                        .index(primitive)
+                        .field("targets");
+                    errors.push((path, Invalid));
+                }
+            }
+        }
+        for (index, animation) in root.animations.iter().enumerate() {
+            if exceeds(animation.channels.len(), limits.max_channels) {
+                let path = path().field("animations").index(index).field("channels");
+                errors.push((path, Invalid));
+            }
+        }
+        for (index, skin) in root.skins.iter().enumerate() {
+            if exceeds(skin.joints.len(), limits.max_joints) {
+                let path = path().field("skins").index(index).field("joints");
+                errors.push((path, Invalid));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+}
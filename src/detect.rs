@@ -0,0 +1,58 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+/// The container format of a glTF file, as determined by [`detect`].
+///
+/// [`detect`]: fn.detect.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FileKind {
+    /// Binary glTF with the container version of the GLB header.
+    Glb {
+        /// The container version, which is `2` for glTF 2.0.
+        version: u32,
+    },
+
+    /// Standard glTF, i.e. a JSON document.
+    GltfJson,
+
+    /// Neither binary glTF nor JSON.
+    Unknown,
+}
+
+impl FileKind {
+    /// Returns `true` for binary glTF of any container version.
+    pub fn is_glb(&self) -> bool {
+        matches!(self, FileKind::Glb { .. })
+    }
+}
+
+/// Detects the container format of glTF file contents.
+///
+/// Binary glTF is recognized by its magic number, while JSON is recognized by
+/// an opening brace after optional whitespace and byte order mark. Only the
+/// start of `bytes` is inspected, so the contents are not validated.
+///
+/// ```
+/// use gltf::FileKind;
+///
+/// assert_eq!(gltf::detect(b"glTF\x02\0\0\0"), FileKind::Glb { version: 2 });
+/// assert_eq!(gltf::detect(b"\n{\"asset\": {}}"), FileKind::GltfJson);
+/// assert_eq!(gltf::detect(b"\x89PNG"), FileKind::Unknown);
+/// ```
+pub fn detect(bytes: &[u8]) -> FileKind {
+    if bytes.starts_with(b"glTF") {
+        return match bytes.get(4..8) {
+            Some(version) => FileKind::Glb {
+                version: LittleEndian::read_u32(version),
+            },
+            None => FileKind::Unknown,
+        };
+    }
+    let json = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match json
+        .iter()
+        .find(|b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+    {
+        Some(b'{') => FileKind::GltfJson,
+        _ => FileKind::Unknown,
+    }
+}
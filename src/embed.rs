@@ -0,0 +1,40 @@
+/// Embeds a binary glTF file at compile time and defines a function that
+/// parses it on first access.
+///
+/// The generated function returns a `&'static GltfSlice<'static>` whose BIN
+/// chunk borrows directly from the embedded bytes, so no buffer data is copied.
+/// The file is parsed and validated only once, the first time the function is
+/// called. As with `include_bytes!`, the path is relative to the current file.
+///
+/// This is intended for small tools and tests that want a built-in fallback
+/// asset.
+///
+/// # Panics
+///
+/// The generated function panics if the embedded file is not valid glTF.
+///
+/// # Example
+///
+/// ```
+/// gltf::include_glb! {
+///     /// Returns the built-in fallback asset.
+///     fn fallback_asset = "../examples/Box.glb";
+/// }
+///
+/// let gltf = fallback_asset();
+/// assert_eq!(gltf.meshes().count(), 1);
+/// assert!(gltf.blob.is_some());
+/// ```
+#[macro_export]
+macro_rules! include_glb {
+    ($(#[$attr:meta])* $vis:vis fn $name:ident = $path:literal;) => {
+        $(#[$attr])*
+        $vis fn $name() -> &'static $crate::GltfSlice<'static> {
+            static GLTF: $crate::__Lazy<$crate::GltfSlice<'static>> = $crate::__Lazy::new(|| {
+                $crate::GltfSlice::from_slice(include_bytes!($path))
+                    .expect(concat!("invalid embedded glTF: ", $path))
+            });
+            &GLTF
+        }
+    };
+}
@@ -0,0 +1,283 @@
+use crate::prelude::*;
+use crate::{binary, Result};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use core::fmt;
+
+/// Workarounds for quirks of web viewers.
+#[cfg(feature = "utils")]
+mod viewer;
+
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub use self::viewer::Viewer;
+
+/// A hook run by an [`Exporter`] right before the glTF JSON is serialized.
+///
+/// Hooks may modify the JSON freely, for example to inject custom extensions
+/// or to stamp pipeline metadata, and may append data to the binary chunk
+/// through the [`BufferLayout`].
+///
+/// Hooks are implemented for closures taking the same arguments as
+/// [`before_serialize`](#tymethod.before_serialize).
+///
+/// [`Exporter`]: struct.Exporter.html
+/// [`BufferLayout`]: struct.BufferLayout.html
+pub trait ExportHook {
+    /// Called right before `root` is serialized.
+    fn before_serialize(&mut self, root: &mut json::Root, layout: &mut BufferLayout);
+}
+
+impl<F> ExportHook for F
+where
+    F: FnMut(&mut json::Root, &mut BufferLayout),
+{
+    fn before_serialize(&mut self, root: &mut json::Root, layout: &mut BufferLayout) {
+        self(root, layout)
+    }
+}
+
+/// The binary data of an export, as seen by an [`ExportHook`].
+///
+/// [`ExportHook`]: trait.ExportHook.html
+#[derive(Clone, Debug, Default)]
+pub struct BufferLayout {
+    /// The `BIN` chunk of binary glTF, stored as the first buffer of the
+    /// document, or `None` for standard glTF.
+    pub bin: Option<Vec<u8>>,
+}
+
+impl BufferLayout {
+    /// Appends `data` to the `BIN` chunk as a new buffer view, updating the
+    /// length of the first buffer, and returns the index of the view.
+    ///
+    /// If the first buffer does not exist yet, it is created. Returns `None`
+    /// when exporting standard glTF.
+    pub fn push_view(
+        &mut self,
+        root: &mut json::Root,
+        data: &[u8],
+    ) -> Option<json::Index<json::buffer::View>> {
+        let bin = self.bin.as_mut()?;
+        if root.buffers.is_empty() {
+            root.buffers.push(json::Buffer {
+                byte_length: 0,
+                #[cfg(feature = "names")]
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+        while bin.len() % 4 != 0 {
+            bin.push(0);
+        }
+        let offset = bin.len();
+        bin.extend_from_slice(data);
+        root.buffers[0].byte_length = bin.len() as u32;
+
+        let view = json::Index::new(root.buffer_views.len() as u32);
+        root.buffer_views.push(json::buffer::View {
+            buffer: json::Index::new(0),
+            byte_length: data.len() as u32,
+            byte_offset: Some(offset as u32),
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            target: None,
+            extensions: None,
+            extras: Default::default(),
+        });
+        Some(view)
+    }
+}
+
+/// Serializes glTF, running [`ExportHook`]s right before writing.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::export::{BufferLayout, Exporter};
+///
+/// let root = gltf::json::Root::default();
+/// let glb = Exporter::new()
+///     .with_hook(|root: &mut gltf::json::Root, _: &mut BufferLayout| {
+///         root.asset.generator = Some("my pipeline".to_owned());
+///     })
+///     .to_glb(root, Vec::new())?;
+/// let gltf = gltf::Gltf::from_slice(&glb)?;
+/// let root = gltf.document.into_json();
+/// assert_eq!(root.asset.generator.as_deref(), Some("my pipeline"));
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+///
+/// [`ExportHook`]: trait.ExportHook.html
+#[derive(Default)]
+pub struct Exporter<'a> {
+    /// The hooks, in order of registration.
+    hooks: Vec<Box<dyn ExportHook + 'a>>,
+
+    /// The alignment of the buffer views of the `BIN` chunk, if any.
+    alignment: Option<usize>,
+
+    /// The viewer whose quirks are worked around, if any.
+    #[cfg(feature = "utils")]
+    viewer: Option<Viewer>,
+}
+
+impl<'a> fmt::Debug for Exporter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut f = f.debug_struct("Exporter");
+        f.field("hooks", &self.hooks.len())
+            .field("alignment", &self.alignment);
+        #[cfg(feature = "utils")]
+        f.field("viewer", &self.viewer);
+        f.finish()
+    }
+}
+
+impl<'a> Exporter<'a> {
+    /// Constructs an `Exporter` without hooks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a hook, which is run after all previously added hooks.
+    pub fn with_hook<H: ExportHook + 'a>(mut self, hook: H) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Aligns the buffer views of binary glTF for direct memory mapping and
+    /// upload.
+    ///
+    /// The views of the `BIN` chunk are repacked with vertex data first,
+    /// followed by index data and all other data, each starting at a multiple
+    /// of `alignment` bytes. The JSON chunk is padded so that the `BIN` chunk
+    /// data starts at a multiple of `alignment` bytes within the file, too.
+    /// Data not referenced by any view is dropped. This trades file size for
+    /// load speed and has no effect on standard glTF.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alignment` is not a power of two of at least 4.
+    pub fn with_alignment(mut self, alignment: usize) -> Self {
+        assert!(
+            alignment.is_power_of_two() && alignment >= 4,
+            "alignment must be a power of two of at least 4"
+        );
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Works around known quirks of `viewer`, so that the exported asset
+    /// displays as intended.
+    ///
+    /// * Texture coordinate sets beyond those read by the viewer are removed,
+    ///   and textures using them fall back to the first set.
+    /// * 8-bit indices are widened to 16 bits, since the WebGPU renderers of
+    ///   the viewers cannot draw them.
+    /// * Skin weights are renormalized to sum to one, since the viewers do not
+    ///   normalize them. Vertices without weights are bound to their first
+    ///   joint.
+    ///
+    /// The workarounds are applied after the hooks. Indices and weights are
+    /// only rewritten when exporting binary glTF, and only if their data is
+    /// stored in the `BIN` chunk. The rewritten data is appended to the
+    /// chunk, leaving the original data unreferenced.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn with_viewer(mut self, viewer: Viewer) -> Self {
+        self.viewer = Some(viewer);
+        self
+    }
+
+    /// Runs the hooks and serializes `root` as standard glTF JSON.
+    pub fn to_gltf(&mut self, mut root: json::Root) -> Result<Vec<u8>> {
+        self.run_hooks(&mut root, None);
+        json::serialize::to_vec_pretty(&root).map_err(crate::Error::Deserialize)
+    }
+
+    /// Runs the hooks and serializes `root` with `bin` as binary glTF.
+    ///
+    /// `bin` holds the data of the first buffer of `root`. The `BIN` chunk is
+    /// omitted if it is empty.
+    pub fn to_glb(&mut self, mut root: json::Root, bin: Vec<u8>) -> Result<Vec<u8>> {
+        let mut bin = self
+            .run_hooks(&mut root, Some(bin))
+            .filter(|bin| !bin.is_empty());
+        if let (Some(alignment), Some(data)) = (self.alignment, bin.as_mut()) {
+            *data = align_views(&mut root, data, alignment);
+        }
+        let mut json = json::serialize::to_vec(&root).map_err(crate::Error::Deserialize)?;
+        if let Some(alignment) = self.alignment {
+            // The file header and both chunk headers precede the BIN data.
+            while (28 + json.len()) % alignment != 0 {
+                json.push(b' ');
+            }
+        }
+        binary::Glb {
+            header: binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                // Computed when writing.
+                length: 0,
+            },
+            json: Cow::Owned(json),
+            bin: bin.map(Cow::Owned),
+        }
+        .to_vec()
+    }
+
+    fn run_hooks(&mut self, root: &mut json::Root, bin: Option<Vec<u8>>) -> Option<Vec<u8>> {
+        let mut layout = BufferLayout { bin };
+        for hook in self.hooks.iter_mut() {
+            hook.before_serialize(root, &mut layout);
+        }
+        #[cfg(feature = "utils")]
+        if let Some(viewer) = self.viewer {
+            viewer.apply(root, &mut layout);
+        }
+        layout.bin
+    }
+}
+
+/// Returns the order in which buffer views with `target` are packed.
+fn pack_order(target: Option<json::validation::Checked<json::buffer::Target>>) -> u8 {
+    use json::buffer::Target;
+    use json::validation::Checked::Valid;
+    match target {
+        Some(Valid(Target::ArrayBuffer)) => 0,
+        Some(Valid(Target::ElementArrayBuffer)) => 1,
+        _ => 2,
+    }
+}
+
+/// Repacks the views of the first buffer with every view starting at a
+/// multiple of `alignment`, and returns the new buffer data.
+fn align_views(root: &mut json::Root, bin: &[u8], alignment: usize) -> Vec<u8> {
+    let mut views: Vec<usize> = (0..root.buffer_views.len())
+        .filter(|&index| root.buffer_views[index].buffer.value() == 0)
+        .collect();
+    // The sort is stable, so views of the same kind keep their order.
+    views.sort_by_key(|&index| pack_order(root.buffer_views[index].target));
+
+    let mut data = Vec::with_capacity(bin.len());
+    for index in views {
+        let view = &mut root.buffer_views[index];
+        let start = view.byte_offset.unwrap_or(0) as usize;
+        let length = view.byte_length as usize;
+        let offset = (data.len() + alignment - 1) & !(alignment - 1);
+        data.resize(offset, 0);
+        data.extend_from_slice(bin.get(start..start + length).unwrap_or(&[]));
+        data.resize(offset + length, 0);
+        view.byte_offset = Some(offset as u32);
+    }
+    if let Some(buffer) = root.buffers.get_mut(0) {
+        buffer.byte_length = data.len() as u32;
+    }
+    data
+}
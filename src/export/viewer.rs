@@ -0,0 +1,219 @@
+use super::BufferLayout;
+use crate::mesh::util::ReadIndices;
+use crate::prelude::*;
+use crate::{Buffer, Document};
+use alloc::collections::{BTreeMap, BTreeSet};
+use json::accessor::{ComponentType, GenericComponentType};
+use json::buffer::Target;
+use json::mesh::Semantic;
+use json::validation::Checked::Valid;
+
+/// A web viewer whose quirks an [`Exporter`] can work around.
+///
+/// See [`Exporter::with_viewer`].
+///
+/// [`Exporter`]: struct.Exporter.html
+/// [`Exporter::with_viewer`]: struct.Exporter.html#method.with_viewer
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Viewer {
+    /// three.js, which reads up to four texture coordinate sets.
+    ThreeJs,
+
+    /// Babylon.js, which reads up to six texture coordinate sets.
+    Babylon,
+}
+
+impl Viewer {
+    /// Returns the number of texture coordinate sets the viewer reads.
+    fn texcoord_sets(self) -> u32 {
+        match self {
+            Viewer::ThreeJs => 4,
+            Viewer::Babylon => 6,
+        }
+    }
+
+    /// Applies the workarounds for the viewer to `root` and the `BIN` chunk
+    /// of `layout`, if any.
+    pub(crate) fn apply(self, root: &mut json::Root, layout: &mut BufferLayout) {
+        limit_texcoords(root, self.texcoord_sets());
+        if layout.bin.is_some() {
+            widen_indices(root, layout);
+            normalize_weights(root, layout);
+        }
+    }
+}
+
+/// Removes the texture coordinate sets from `sets` onwards, and makes the
+/// textures using them use the first set.
+fn limit_texcoords(root: &mut json::Root, sets: u32) {
+    for primitive in root.meshes.iter_mut().flat_map(|mesh| &mut mesh.primitives) {
+        primitive.attributes.retain(|semantic, _| match semantic {
+            Valid(Semantic::TexCoords(set)) => *set < sets,
+            _ => true,
+        });
+    }
+    for material in &mut root.materials {
+        let pbr = &mut material.pbr_metallic_roughness;
+        let tex_coords = [
+            pbr.base_color_texture
+                .as_mut()
+                .map(|info| &mut info.tex_coord),
+            pbr.metallic_roughness_texture
+                .as_mut()
+                .map(|info| &mut info.tex_coord),
+            material
+                .normal_texture
+                .as_mut()
+                .map(|info| &mut info.tex_coord),
+            material
+                .occlusion_texture
+                .as_mut()
+                .map(|info| &mut info.tex_coord),
+            material
+                .emissive_texture
+                .as_mut()
+                .map(|info| &mut info.tex_coord),
+        ];
+        for tex_coord in tex_coords.into_iter().flatten() {
+            if *tex_coord >= sets {
+                *tex_coord = 0;
+            }
+        }
+    }
+}
+
+/// Appends `data` to the `BIN` chunk as a new buffer view with `target`.
+fn push_view(
+    root: &mut json::Root,
+    layout: &mut BufferLayout,
+    data: &[u8],
+    target: Target,
+) -> Option<json::Index<json::buffer::View>> {
+    let view = layout.push_view(root, data)?;
+    root.buffer_views[view.value()].target = Some(Valid(target));
+    Some(view)
+}
+
+/// Widens the 8-bit index accessors stored in the `BIN` chunk to 16 bits.
+fn widen_indices(root: &mut json::Root, layout: &mut BufferLayout) {
+    let widened: Vec<(usize, Vec<u8>)> = {
+        let document = Document::from_json_without_validation(root.clone());
+        let bin = layout.bin.as_deref();
+        let get_buffer_data = |buffer: Buffer| if buffer.index() == 0 { bin } else { None };
+        let mut seen = BTreeSet::new();
+        document
+            .meshes()
+            .flat_map(|mesh| mesh.primitives())
+            .filter_map(|primitive| {
+                let accessor = primitive.indices()?.index();
+                match primitive.reader(get_buffer_data).read_indices()? {
+                    ReadIndices::U8(iter) if seen.insert(accessor) => {
+                        let data = iter.flat_map(|index| u16::from(index).to_le_bytes());
+                        Some((accessor, data.collect()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    };
+    for (accessor, data) in widened {
+        if let Some(view) = push_view(root, layout, &data, Target::ElementArrayBuffer) {
+            let accessor = &mut root.accessors[accessor];
+            accessor.buffer_view = Some(view);
+            accessor.byte_offset = None;
+            accessor.component_type = Valid(GenericComponentType(ComponentType::U16));
+            accessor.sparse = None;
+        }
+    }
+}
+
+/// The skin weights of each vertex, by set.
+type WeightSets = Vec<Vec<[f32; 4]>>;
+
+/// Renormalizes the skin weights stored in the `BIN` chunk which do not sum
+/// to one, storing them as floats.
+fn normalize_weights(root: &mut json::Root, layout: &mut BufferLayout) {
+    // The renormalized weight sets of each combination of weight accessors,
+    // and the primitives using them.
+    let mut weights: BTreeMap<Vec<usize>, (WeightSets, Vec<[usize; 2]>)> = BTreeMap::new();
+    {
+        let document = Document::from_json_without_validation(root.clone());
+        let bin = layout.bin.as_deref();
+        let get_buffer_data = |buffer: Buffer| if buffer.index() == 0 { bin } else { None };
+        for primitive in document.meshes().flat_map(|mesh| mesh.primitives()) {
+            let accessors: Vec<usize> = (0..)
+                .map_while(|set| primitive.get(&Semantic::Weights(set)))
+                .map(|accessor| accessor.index())
+                .collect();
+            let location = [primitive.mesh.index(), primitive.index()];
+            if let Some((_, primitives)) = weights.get_mut(&accessors) {
+                primitives.push(location);
+                continue;
+            }
+            let reader = primitive.reader(get_buffer_data);
+            let sets: Option<WeightSets> = (0..accessors.len() as u32)
+                .map(|set| Some(reader.read_weights(set)?.into_f32().collect()))
+                .collect();
+            let mut sets = match sets {
+                Some(sets) if !sets.is_empty() => sets,
+                _ => continue,
+            };
+            let count = sets[0].len();
+            if sets.iter().any(|set| set.len() != count) {
+                continue;
+            }
+            let sums: Vec<f32> = (0..count)
+                .map(|vertex| sets.iter().flat_map(|set| set[vertex]).sum())
+                .collect();
+            if sums.iter().all(|sum| (sum - 1.0).abs() <= 2e-3) {
+                continue;
+            }
+            for (vertex, &sum) in sums.iter().enumerate() {
+                for set in sets.iter_mut() {
+                    let vertex = &mut set[vertex];
+                    *vertex = if sum > 0.0 {
+                        vertex.map(|weight| weight / sum)
+                    } else {
+                        [0.0; 4]
+                    };
+                }
+                if sum <= 0.0 {
+                    sets[0][vertex][0] = 1.0;
+                }
+            }
+            weights.insert(accessors, (sets, vec![location]));
+        }
+    }
+
+    for (accessors, (sets, primitives)) in weights {
+        for (set, (original, weights)) in accessors.into_iter().zip(sets).enumerate() {
+            let data: Vec<u8> = weights
+                .iter()
+                .flatten()
+                .flat_map(|weight| weight.to_le_bytes())
+                .collect();
+            let view = match push_view(root, layout, &data, Target::ArrayBuffer) {
+                Some(view) => view,
+                None => return,
+            };
+            let mut accessor = root.accessors[original].clone();
+            accessor.buffer_view = Some(view);
+            accessor.byte_offset = None;
+            accessor.component_type = Valid(GenericComponentType(ComponentType::F32));
+            accessor.normalized = false;
+            accessor.sparse = None;
+            accessor.min = None;
+            accessor.max = None;
+            let accessor = {
+                root.accessors.push(accessor);
+                json::Index::new(root.accessors.len() as u32 - 1)
+            };
+            for &[mesh, primitive] in &primitives {
+                let semantic = Valid(Semantic::Weights(set as u32));
+                root.meshes[mesh].primitives[primitive]
+                    .attributes
+                    .insert(semantic, accessor);
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Document, Image};
+use gltf_json::Extras;
+
+#[cfg(feature = "import")]
+use crate::image;
+
+/// An environment light defined by prefiltered specular cubemaps and
+/// spherical harmonic irradiance coefficients.
+#[derive(Clone, Debug)]
+pub struct ImageBasedLight<'a> {
+    /// The parent `Document` struct.
+    document: &'a Document,
+
+    /// The corresponding JSON index.
+    index: usize,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::scene::ext_lights_image_based::ImageBasedLight,
+}
+
+impl<'a> ImageBasedLight<'a> {
+    /// Constructs an `ImageBasedLight`.
+    pub(crate) fn new(
+        document: &'a Document,
+        index: usize,
+        json: &'a json::extensions::scene::ext_lights_image_based::ImageBasedLight,
+    ) -> Self {
+        Self {
+            document,
+            index,
+            json,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Optional user-defined name for this object.
+    #[cfg(feature = "names")]
+    pub fn name(&self) -> Option<&'a str> {
+        self.json.name.as_deref()
+    }
+
+    /// Optional application specific data.
+    pub fn extras(&self) -> &'a Extras {
+        &self.json.extras
+    }
+
+    /// Brightness multiplier of the environment.
+    pub fn intensity(&self) -> f32 {
+        self.json.intensity
+    }
+
+    /// XYZW unit quaternion rotating the environment.
+    pub fn rotation(&self) -> [f32; 4] {
+        self.json.rotation
+    }
+
+    /// The spherical harmonic coefficients for irradiance up to l=2, as nine
+    /// linear RGB values.
+    pub fn irradiance_coefficients(&self) -> [[f32; 3]; 9] {
+        self.json.irradiance_coefficients
+    }
+
+    /// The width and height in pixels of the largest specular mip level.
+    ///
+    /// Every following mip level halves the size.
+    pub fn specular_image_size(&self) -> u32 {
+        self.json.specular_image_size
+    }
+
+    /// Returns an `Iterator` that visits the specular mip levels, largest
+    /// first.
+    ///
+    /// Every mip level is a cubemap whose faces are in the order +X, -X, +Y,
+    /// -Y, +Z, -Z.
+    pub fn specular_images(&self) -> SpecularImages<'a> {
+        SpecularImages {
+            document: self.document,
+            iter: self.json.specular_images.iter(),
+        }
+    }
+
+    /// Returns the decoded specular cubemap faces of every mip level, largest
+    /// first, given the image data returned by [`import`].
+    ///
+    /// Returns `None` if an image is missing from `images`.
+    ///
+    /// [`import`]: ../fn.import.html
+    #[cfg(feature = "import")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    pub fn specular_data<'d>(
+        &self,
+        images: &'d [image::Data],
+    ) -> Option<Vec<[&'d image::Data; 6]>> {
+        self.json
+            .specular_images
+            .iter()
+            .map(|faces| {
+                let mut data = [None; 6];
+                for (data, face) in data.iter_mut().zip(faces) {
+                    *data = Some(images.get(face.value())?);
+                }
+                Some(data.map(Option::unwrap))
+            })
+            .collect()
+    }
+}
+
+/// An `Iterator` that visits the specular mip levels of an image based light.
+#[derive(Clone, Debug)]
+pub struct SpecularImages<'a> {
+    /// The parent `Document` struct.
+    document: &'a Document,
+
+    /// The internal mip level iterator.
+    iter: core::slice::Iter<'a, [json::Index<json::Image>; 6]>,
+}
+
+impl<'a> ExactSizeIterator for SpecularImages<'a> {}
+impl<'a> Iterator for SpecularImages<'a> {
+    type Item = [Image<'a>; 6];
+    fn next(&mut self) -> Option<Self::Item> {
+        let document = self.document;
+        self.iter
+            .next()
+            .map(|faces| faces.map(|face| document.images().nth(face.value()).unwrap()))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
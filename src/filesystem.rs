@@ -0,0 +1,265 @@
+use std::path::{Component, Path, PathBuf};
+use std::{fs, io};
+
+use crate::buffer;
+use crate::import::{Fetcher, ResourceRequest};
+use crate::{Error, Result};
+
+/// Returns an `Error::Io` of the given kind.
+fn io_error(kind: io::ErrorKind, message: String) -> Error {
+    Error::Io(io::Error::new(kind, message))
+}
+
+/// Returns `true` if `path` itself is a symbolic link.
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// Returns the entry of `directory` whose name matches `name` ignoring case.
+fn find_ignoring_case(directory: &Path, name: &str) -> Option<PathBuf> {
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+    let name = name.to_lowercase();
+    fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.file_name().to_string_lossy().to_lowercase() == name)
+        .map(|entry| entry.path())
+}
+
+/// Configurable fetcher for filesystem references.
+///
+/// By default this resolves references exactly like [`filesystem_fetcher`].
+/// Additional options help with assets whose references do not match the
+/// file system they are loaded from, for example after being moved between
+/// operating systems with different case sensitivity.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let fetcher = gltf::FilesystemFetcher::new()
+///     .with_case_insensitive_fallback(true)
+///     .with_search_path("examples");
+/// let (document, buffers, images) =
+///     gltf::import_with_progress("examples/Box.gltf", &fetcher, |_| {
+///         std::ops::ControlFlow::Continue(())
+///     })?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`filesystem_fetcher`]: fn.filesystem_fetcher.html
+#[derive(Clone, Debug)]
+pub struct FilesystemFetcher {
+    /// Whether the base directory is canonicalized before resolving.
+    canonicalize: bool,
+
+    /// Whether symbolic links in referenced paths are followed.
+    follow_symlinks: bool,
+
+    /// Whether to fall back to matching path components ignoring case.
+    case_insensitive_fallback: bool,
+
+    /// Directories searched for relative references not found next to the
+    /// referencing file.
+    search_paths: Vec<PathBuf>,
+}
+
+impl Default for FilesystemFetcher {
+    fn default() -> Self {
+        Self {
+            canonicalize: false,
+            follow_symlinks: true,
+            case_insensitive_fallback: false,
+            search_paths: Vec::new(),
+        }
+    }
+}
+
+impl FilesystemFetcher {
+    /// Constructs a `FilesystemFetcher` with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the base directory is canonicalized before references
+    /// are resolved from it.
+    ///
+    /// Canonicalizing resolves symbolic links and `..` components of the base
+    /// directory, so that references resolve relative to the real location
+    /// of a symlinked glTF file. Defaults to `false`.
+    pub fn with_canonicalization(mut self, canonicalize: bool) -> Self {
+        self.canonicalize = canonicalize;
+        self
+    }
+
+    /// Sets whether symbolic links in the referenced path are followed.
+    ///
+    /// When `false`, references passing through a symbolic link fail with an
+    /// `Error::Io` of kind `PermissionDenied`. Only the components of the
+    /// reference itself are checked, not those of the base directory.
+    /// Defaults to `true`.
+    pub fn with_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Sets whether path components which do not exist are matched against
+    /// the directory entries ignoring case. Defaults to `false`.
+    pub fn with_case_insensitive_fallback(mut self, case_insensitive_fallback: bool) -> Self {
+        self.case_insensitive_fallback = case_insensitive_fallback;
+        self
+    }
+
+    /// Adds a directory to search for relative references which are not
+    /// found next to the referencing file.
+    ///
+    /// Search paths are tried in the order they were added.
+    pub fn with_search_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    /// Returns the path of the file referenced by `path` relative to `base`.
+    ///
+    /// Returns an `Error::Io` of kind `NotFound` if the file does not exist at
+    /// any of the candidate locations.
+    pub fn resolve(&self, base: Option<&Path>, path: &str) -> Result<PathBuf> {
+        let reference = Path::new(path);
+        let base = match base {
+            Some(base) if self.canonicalize => {
+                Some(base.canonicalize().unwrap_or_else(|_| base.to_path_buf()))
+            }
+            Some(base) => Some(base.to_path_buf()),
+            None => None,
+        };
+        let mut roots = vec![base.unwrap_or_default()];
+        if reference.is_relative() {
+            roots.extend(self.search_paths.iter().cloned());
+        }
+        for root in &roots {
+            if let Some(resolved) = self.find(root, reference)? {
+                return Ok(resolved);
+            }
+        }
+        Err(io_error(
+            io::ErrorKind::NotFound,
+            format!("{} not found", path),
+        ))
+    }
+
+    /// Reads a file with the signature of [`filesystem_fetcher`], such that
+    /// `|base, path| fetcher.read(base, path)` can be passed to the `import`
+    /// functions.
+    ///
+    /// [`filesystem_fetcher`]: fn.filesystem_fetcher.html
+    pub fn read(&self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        fs::read(self.resolve(base, path)?).map_err(Error::Io)
+    }
+
+    /// Walks `reference` from `root` one component at a time.
+    fn find(&self, root: &Path, reference: &Path) -> Result<Option<PathBuf>> {
+        let mut current = root.to_path_buf();
+        for component in reference.components() {
+            match component {
+                Component::Normal(name) => {
+                    let next = current.join(name);
+                    current = if fs::symlink_metadata(&next).is_ok() {
+                        next
+                    } else if self.case_insensitive_fallback {
+                        match find_ignoring_case(&current, &name.to_string_lossy()) {
+                            Some(next) => next,
+                            None => return Ok(None),
+                        }
+                    } else {
+                        return Ok(None);
+                    };
+                    if !self.follow_symlinks && is_symlink(&current) {
+                        return Err(io_error(
+                            io::ErrorKind::PermissionDenied,
+                            format!("{} is a symbolic link", current.display()),
+                        ));
+                    }
+                }
+                component => current.push(component),
+            }
+        }
+        Ok(if current.is_file() {
+            Some(current)
+        } else {
+            None
+        })
+    }
+}
+
+impl Fetcher for &FilesystemFetcher {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        self.read(request.base, request.path).map(Into::into)
+    }
+}
+
+impl Fetcher for FilesystemFetcher {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        (&*self).fetch(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::TempDir;
+    use std::fs;
+
+    #[test]
+    fn options() {
+        let root = TempDir::new("fetcher");
+        fs::create_dir_all(root.join("model")).unwrap();
+        fs::create_dir_all(root.join("shared")).unwrap();
+        fs::copy("examples/Box.gltf", root.join("model/Box.gltf")).unwrap();
+        fs::copy("examples/Box0.bin", root.join("model/BOX0.BIN")).unwrap();
+        fs::write(root.join("shared/extra.bin"), b"extra").unwrap();
+        let model = root.join("model");
+
+        assert!(crate::FilesystemFetcher::new()
+            .resolve(Some(&model), "Box0.bin")
+            .is_err());
+        let fetcher = crate::FilesystemFetcher::new()
+            .with_case_insensitive_fallback(true)
+            .with_search_path(root.join("shared"));
+        assert_eq!(
+            fetcher.resolve(Some(&model), "Box0.bin").unwrap(),
+            model.join("BOX0.BIN")
+        );
+        assert_eq!(fetcher.read(Some(&model), "extra.bin").unwrap(), b"extra");
+        let (_, buffers, _) = crate::import_with_progress(model.join("Box.gltf"), &fetcher, |_| {
+            std::ops::ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(buffers[0].len(), 648);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("shared/extra.bin"), model.join("link.bin"))
+                .unwrap();
+            assert!(fetcher.read(Some(&model), "link.bin").is_ok());
+            let fetcher = fetcher.with_symlinks(false);
+            match fetcher.read(Some(&model), "link.bin") {
+                Err(crate::Error::Io(error)) => {
+                    assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied)
+                }
+                _ => panic!("expected the symbolic link to be rejected"),
+            }
+            assert!(fetcher
+                .with_canonicalization(true)
+                .read(Some(&model.join("..").join("model")), "BOX0.BIN")
+                .is_ok());
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs, ops, process};
+
+/// A temporary directory, removed with its contents when dropped.
+///
+/// The directory is named after the process, so that concurrent test runs
+/// do not share their files, and emptied when created, so that a run which
+/// failed to clean up does not affect the next one.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates an empty temporary directory named `gltf-{name}-{pid}`.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("gltf-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl ops::Deref for TempDir {
+    type Target = Path;
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,214 @@
+use core::f64::consts::{FRAC_PI_2, PI};
+
+/// The `f32` methods used by the crate that are provided by `std` only.
+///
+/// Intermediate results are computed in double precision, which is ample for
+/// `f32` results.
+pub(crate) trait Float {
+    /// Computes the absolute value.
+    fn abs(self) -> Self;
+
+    /// Returns `1.0` for positive numbers and `-1.0` for negative numbers.
+    fn signum(self) -> Self;
+
+    /// Computes the square root.
+    fn sqrt(self) -> Self;
+
+    /// Returns the largest integer less than or equal to a number.
+    fn floor(self) -> Self;
+
+    /// Returns the smallest integer greater than or equal to a number.
+    fn ceil(self) -> Self;
+
+    /// Computes the sine of an angle in radians.
+    fn sin(self) -> Self;
+
+    /// Computes the cosine of an angle in radians.
+    fn cos(self) -> Self;
+
+    /// Computes the sine and cosine of an angle in radians.
+    fn sin_cos(self) -> (Self, Self)
+    where
+        Self: Sized;
+
+    /// Computes the arccosine in radians.
+    fn acos(self) -> Self;
+
+    /// Raises a number to an integer power.
+    fn powi(self, n: i32) -> Self;
+}
+
+fn sqrt(x: f64) -> f64 {
+    if x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x.is_nan() || x.is_infinite() {
+        return x;
+    }
+    // Halving the exponent gives an initial estimate within a factor of two,
+    // which Newton's method refines quadratically.
+    let mut y = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    for _ in 0..6 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+/// Returns the sine and cosine of `x` in `[-PI / 4, PI / 4]`.
+fn sin_cos_reduced(x: f64) -> (f64, f64) {
+    let x2 = x * x;
+    let (mut sin, mut cos) = (0.0, 0.0);
+    let (mut sin_term, mut cos_term) = (x, 1.0);
+    for n in 1..10 {
+        sin += sin_term;
+        cos += cos_term;
+        let n = n as f64;
+        sin_term *= -x2 / ((2.0 * n) * (2.0 * n + 1.0));
+        cos_term *= -x2 / ((2.0 * n - 1.0) * (2.0 * n));
+    }
+    (sin, cos)
+}
+
+fn sin_cos(x: f64) -> (f64, f64) {
+    if x.is_nan() || x.is_infinite() {
+        return (f64::NAN, f64::NAN);
+    }
+    let quadrant = x / FRAC_PI_2;
+    let quadrant = if quadrant < 0.0 {
+        quadrant - 0.5
+    } else {
+        quadrant + 0.5
+    } as i64;
+    let (sin, cos) = sin_cos_reduced(x - quadrant as f64 * FRAC_PI_2);
+    match quadrant & 3 {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+/// Computes the arctangent of a non-negative number.
+fn atan(x: f64) -> f64 {
+    if x > 1.0 {
+        return FRAC_PI_2 - atan(1.0 / x);
+    }
+    // Halve the angle twice, so that the series converges quickly.
+    let mut t = x;
+    for _ in 0..2 {
+        t /= 1.0 + sqrt(1.0 + t * t);
+    }
+    let t2 = t * t;
+    let mut sum = 0.0;
+    let mut term = t;
+    for n in 0..12 {
+        sum += term / (2 * n + 1) as f64;
+        term *= -t2;
+    }
+    4.0 * sum
+}
+
+impl Float for f32 {
+    fn abs(self) -> Self {
+        f32::from_bits(self.to_bits() & 0x7fff_ffff)
+    }
+
+    fn signum(self) -> Self {
+        if self.is_nan() {
+            f32::NAN
+        } else if self.is_sign_negative() {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        sqrt(self as f64) as f32
+    }
+
+    fn floor(self) -> Self {
+        // Numbers of this magnitude have no fractional part.
+        if self.is_nan() || self.abs() >= 8_388_608.0 {
+            return self;
+        }
+        let truncated = self as i32 as f32;
+        if truncated > self {
+            truncated - 1.0
+        } else {
+            truncated
+        }
+    }
+
+    fn ceil(self) -> Self {
+        -Float::floor(-self)
+    }
+
+    fn sin(self) -> Self {
+        sin_cos(self as f64).0 as f32
+    }
+
+    fn cos(self) -> Self {
+        sin_cos(self as f64).1 as f32
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = sin_cos(self as f64);
+        (sin as f32, cos as f32)
+    }
+
+    fn acos(self) -> Self {
+        let x = self as f64;
+        if x.is_nan() || !(-1.0..=1.0).contains(&x) {
+            f32::NAN
+        } else if x == -1.0 {
+            PI as f32
+        } else {
+            (2.0 * atan(sqrt((1.0 - x) / (1.0 + x)))) as f32
+        }
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let mut base = self as f64;
+        let mut exponent = n.unsigned_abs();
+        let mut result = 1.0;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        if n < 0 {
+            (1.0 / result) as f32
+        } else {
+            result as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Float;
+
+    #[test]
+    fn matches_std() {
+        for i in -200..=200 {
+            let x = i as f32 * 0.05;
+            assert_relative_eq!(Float::sin(x), x.sin(), epsilon = 1.0e-6);
+            assert_relative_eq!(Float::cos(x), x.cos(), epsilon = 1.0e-6);
+            assert_relative_eq!(Float::sqrt(x.abs()), x.abs().sqrt(), epsilon = 1.0e-6);
+            assert_relative_eq!(Float::powi(x, 3), x.powi(3), epsilon = 1.0e-4);
+            assert_eq!(Float::abs(x), x.abs());
+            assert_eq!(Float::floor(x), x.floor());
+            assert_eq!(Float::ceil(x), x.ceil());
+            if x != 0.0 {
+                assert_eq!(Float::signum(x), x.signum());
+            }
+            let y = i as f32 / 200.0;
+            assert_relative_eq!(Float::acos(y), y.acos(), epsilon = 1.0e-6);
+        }
+        assert!(Float::acos(1.5f32).is_nan());
+        assert!(Float::sqrt(-1.0f32).is_nan());
+    }
+}
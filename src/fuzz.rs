@@ -0,0 +1,122 @@
+use crate::prelude::*;
+use crate::{binary, Document, Result};
+
+/// Parses `data` as binary glTF and, if it is valid, reads everything the
+/// document refers to.
+///
+/// This is intended as the body of a fuzz target. Malformed input results
+/// in an error; any panic is a bug in this crate.
+///
+/// ```
+/// let _ = gltf::fuzz::parse_glb(b"glTF\x02\x00\x00\x00\x0c\x00\x00\x00");
+/// ```
+pub fn parse_glb(data: &[u8]) -> Result<()> {
+    let glb = binary::Glb::from_slice(data)?;
+    let document = Document::from_json(crate::deserialize_root(&glb.json)?)?;
+    exercise(&document, glb.bin.as_deref());
+    Ok(())
+}
+
+/// Parses `data` as glTF JSON and, if it is valid, reads everything the
+/// document refers to.
+///
+/// Buffers are not loaded, so only the JSON is exercised. Malformed input
+/// results in an error; any panic is a bug in this crate.
+///
+/// ```
+/// let _ = gltf::fuzz::parse_json(br#"{"asset":{"version":"2.0"}}"#);
+/// ```
+pub fn parse_json(data: &[u8]) -> Result<()> {
+    let document = Document::from_json(crate::deserialize_root(data)?)?;
+    exercise(&document, None);
+    Ok(())
+}
+
+/// Validates the structured input `root` with `bin` as the data of its
+/// `BIN` chunk and, if it is valid, reads everything the document refers to.
+///
+/// `root` is typically generated using its implementation of
+/// `arbitrary::Arbitrary`, which reaches deeper into the crate than raw bytes
+/// since most inputs are well-formed JSON. Any panic is a bug in this crate.
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+pub fn load_json(root: json::Root, bin: &[u8]) -> Result<()> {
+    let document = Document::from_json(root)?;
+    exercise(&document, Some(bin));
+    Ok(())
+}
+
+/// Walks the scene graph of the validated `document` and, with the `utils`
+/// feature, reads every accessor, mesh, skin and animation.
+fn exercise(document: &Document, bin: Option<&[u8]>) {
+    for scene in document.scenes() {
+        let mut stack: Vec<_> = scene.nodes().collect();
+        while let Some(node) = stack.pop() {
+            let _ = node.transform().decomposed();
+            stack.extend(node.children());
+        }
+    }
+    #[cfg(feature = "utils")]
+    read(document, bin);
+    #[cfg(not(feature = "utils"))]
+    let _ = bin;
+}
+
+/// Reads the data of every accessor, mesh, skin and animation in `document`.
+#[cfg(feature = "utils")]
+fn read(document: &Document, bin: Option<&[u8]>) {
+    use crate::animation::util::ReadOutputs;
+    use crate::buffer;
+
+    let get_buffer_data = |buffer: buffer::Buffer| match buffer.source() {
+        buffer::Source::Bin => bin,
+        buffer::Source::Uri(_) => None,
+    };
+
+    for accessor in document.accessors() {
+        // Without a buffer view, the dense data is as large as the count
+        // declared in the JSON, which the input does not bound.
+        if accessor.view().is_some() {
+            let _ = accessor.dense_data(get_buffer_data);
+        }
+    }
+    for mesh in document.meshes() {
+        for primitive in mesh.primitives() {
+            let reader = primitive.reader(get_buffer_data);
+            reader.read_positions().map(Iterator::count);
+            reader.read_normals().map(Iterator::count);
+            reader.read_tangents().map(Iterator::count);
+            reader
+                .read_indices()
+                .map(|indices| indices.into_u32().count());
+            for set in 0..2 {
+                reader.read_tex_coords(set).map(|t| t.into_f32().count());
+                reader.read_colors(set).map(|c| c.into_rgba_f32().count());
+                reader.read_joints(set).map(|j| j.into_u16().count());
+                reader.read_weights(set).map(|w| w.into_f32().count());
+            }
+            for (positions, normals, tangents) in reader.read_morph_targets() {
+                positions.map(Iterator::count);
+                normals.map(Iterator::count);
+                tangents.map(Iterator::count);
+            }
+        }
+    }
+    for skin in document.skins() {
+        let reader = skin.reader(get_buffer_data);
+        reader.read_inverse_bind_matrices().map(Iterator::count);
+    }
+    for animation in document.animations() {
+        for channel in animation.channels() {
+            let reader = channel.reader(get_buffer_data);
+            reader.read_inputs().map(Iterator::count);
+            match reader.read_outputs() {
+                Some(ReadOutputs::Translations(outputs)) => outputs.count(),
+                Some(ReadOutputs::Rotations(outputs)) => outputs.into_f32().count(),
+                Some(ReadOutputs::Scales(outputs)) => outputs.count(),
+                Some(ReadOutputs::MorphTargetWeights(outputs)) => outputs.into_f32().count(),
+                None => 0,
+            };
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::prelude::*;
+use crate::{Buffer, Document};
+use alloc::collections::BTreeMap;
+
+/// The data of every buffer in a document, looked up once.
+///
+/// Readers borrow the primitives they read, which are shorter lived than
+/// the document, so the caller's lookup cannot be passed on directly.
+pub struct BufferData<'s>(Vec<Option<&'s [u8]>>);
+
+impl<'s> BufferData<'s> {
+    /// Looks up the data of every buffer in `document`.
+    pub fn new<'a, F>(document: &'a Document, get_buffer_data: F) -> Self
+    where
+        F: Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        BufferData(document.buffers().map(get_buffer_data).collect())
+    }
+
+    /// Returns the data of `buffer`, if it was available.
+    pub fn get(&self, buffer: Buffer<'_>) -> Option<&'s [u8]> {
+        self.0.get(buffer.index()).copied()?
+    }
+}
+
+/// Transforms a point by a column-major matrix.
+pub fn transform_point(m: &[[f32; 4]; 4], [x, y, z]: [f32; 3]) -> [f32; 3] {
+    let mut out = [0.0; 3];
+    for (i, out) in out.iter_mut().enumerate() {
+        *out = m[0][i] * x + m[1][i] * y + m[2][i] * z + m[3][i];
+    }
+    out
+}
+
+/// Returns `a - b`.
+pub fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Returns the dot product of `a` and `b`.
+pub fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns the cross product of `a` and `b`.
+pub fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Returns the area of a triangle in 3D.
+pub fn triangle_area(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> f32 {
+    let n = cross(sub(b, a), sub(c, a));
+    0.5 * dot(n, n).sqrt()
+}
+
+/// Returns a key identifying vertices at the same position.
+pub fn position_key(position: [f32; 3]) -> [u32; 3] {
+    // Adding zero turns negative zero into positive zero.
+    position.map(|x| (x + 0.0).to_bits())
+}
+
+/// The identity transform.
+pub const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Returns the surface area of `triangles`, and their enclosed volume if they
+/// form a closed surface.
+///
+/// The surface is closed if every edge is shared by as many triangles in
+/// one direction as in the other. Vertices are matched by position, since
+/// vertices are commonly split along seams, and degenerate triangles are
+/// ignored.
+pub fn measure(triangles: &[[[f32; 3]; 3]]) -> (f32, Option<f32>) {
+    let mut edges = BTreeMap::new();
+    let mut area = 0.0;
+    let mut volume = 0.0;
+    for &[a, b, c] in triangles {
+        let [ka, kb, kc] = [a, b, c].map(position_key);
+        if ka == kb || kb == kc || kc == ka {
+            continue;
+        }
+        area += triangle_area(a, b, c);
+        volume += dot(a, cross(b, c)) / 6.0;
+        for (from, to) in [(ka, kb), (kb, kc), (kc, ka)] {
+            let (edge, direction) = if from < to {
+                ((from, to), 1)
+            } else {
+                ((to, from), -1)
+            };
+            *edges.entry(edge).or_insert(0i32) += direction;
+        }
+    }
+    let closed = !edges.is_empty() && edges.values().all(|&count| count == 0);
+    (area, if closed { Some(volume.abs()) } else { None })
+}
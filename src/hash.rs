@@ -0,0 +1,50 @@
+/// FNV-1a offset basis.
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime.
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hasher.
+///
+/// Unlike `std::collections::hash_map::DefaultHasher`, the output of this
+/// hasher is guaranteed to be stable across platforms, runs, and compiler
+/// versions, which makes it suitable for persistent caches.
+#[derive(Clone, Debug)]
+pub(crate) struct Hasher {
+    state: u64,
+}
+
+// Some helpers are unused with certain feature combinations.
+#[allow(dead_code)]
+impl Hasher {
+    /// Constructs a `Hasher` in its initial state.
+    pub fn new() -> Self {
+        Self {
+            state: OFFSET_BASIS,
+        }
+    }
+
+    /// Feeds raw bytes into the hasher.
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(PRIME);
+        }
+    }
+
+    /// Feeds a `u32` in little endian byte order into the hasher.
+    pub fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Feeds a length prefixed string into the hasher.
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u32(value.len() as u32);
+        self.write(value.as_bytes());
+    }
+
+    /// Returns the hash of the data written so far.
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
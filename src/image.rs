@@ -1,6 +1,10 @@
+#[cfg(any(feature = "import", feature = "utils"))]
+use crate::hash::Hasher;
 #[allow(unused)]
 use crate::{buffer, Document, Error, Result};
 
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 #[cfg(feature = "import")]
 #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
 use image_crate::DynamicImage;
@@ -43,6 +47,27 @@ pub enum Format {
     R32G32B32A32FLOAT,
 }
 
+/// Encoding of image data prior to decoding.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Encoding {
+    /// PNG (`image/png`).
+    Png,
+
+    /// JPEG (`image/jpeg`).
+    Jpeg,
+
+    /// WebP (`image/webp`), as used by the `EXT_texture_webp` extension.
+    WebP,
+
+    /// BMP, only detected with the `guess_mime_type` feature.
+    Bmp,
+
+    /// TIFF, only detected with the `guess_mime_type` feature.
+    Tiff,
+}
+
 /// Describes an image data source.
 #[derive(Clone, Debug)]
 pub enum Source<'a> {
@@ -118,6 +143,42 @@ impl<'a> Image<'a> {
         self.json.name.as_deref()
     }
 
+    /// Returns a stable hash of the encoded image data.
+    ///
+    /// For images stored in a buffer view, the hash covers the MIME type and
+    /// the encoded bytes. For images referring to a URI, only the URI and MIME
+    /// type are hashed; hash the imported [`Data`] to detect changes to
+    /// external files.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn content_hash<'s, F>(&self, get_buffer_data: F) -> u64
+    where
+        F: Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut hasher = Hasher::new();
+        match self.source() {
+            Source::View { view, mime_type } => {
+                hasher.write(&[0]);
+                hasher.write_str(mime_type);
+                let start = view.offset();
+                let end = start + view.length();
+                match get_buffer_data(view.buffer()).and_then(|slice| slice.get(start..end)) {
+                    Some(bytes) => {
+                        hasher.write(&[1]);
+                        hasher.write(bytes);
+                    }
+                    None => hasher.write(&[0]),
+                }
+            }
+            Source::Uri { uri, mime_type } => {
+                hasher.write(&[1]);
+                hasher.write_str(uri);
+                hasher.write_str(mime_type.unwrap_or_default());
+            }
+        }
+        hasher.finish()
+    }
+
     /// Returns the image data source.
     pub fn source(&self) -> Source<'a> {
         if let Some(index) = self.json.buffer_view.as_ref() {
@@ -153,6 +214,23 @@ impl<'a> Image<'a> {
     }
 }
 
+/// Encoded image data belonging to an imported glTF asset.
+///
+/// This is an alternative to [`Data`] for applications which decode images
+/// themselves, for example on the GPU.
+///
+/// [`Data`]: struct.Data.html
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+#[derive(Clone, Debug)]
+pub struct Encoded {
+    /// The encoded image data.
+    pub bytes: Vec<u8>,
+
+    /// The encoding of the image data.
+    pub encoding: Encoding,
+}
+
 #[cfg(feature = "import")]
 impl Data {
     /// Note: We don't implement `From<DynamicImage>` since we don't want
@@ -181,4 +259,54 @@ impl Data {
             pixels,
         })
     }
+
+    /// Returns a stable hash of the decoded pixel data, format, and dimensions.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Hasher::new();
+        hasher.write_u32(self.format as u32);
+        hasher.write_u32(self.width);
+        hasher.write_u32(self.height);
+        hasher.write(&self.pixels);
+        hasher.finish()
+    }
+
+    /// Converts the pixel data back to an image of the `image` crate, or
+    /// returns `None` for floating point formats.
+    #[cfg(feature = "utils")]
+    pub(crate) fn to_dynamic_image(&self) -> Option<DynamicImage> {
+        use image_crate::ImageBuffer;
+        let (width, height) = (self.width, self.height);
+        let bytes = || self.pixels.clone();
+        let words = || {
+            self.pixels
+                .chunks_exact(2)
+                .map(|word| u16::from_ne_bytes([word[0], word[1]]))
+                .collect::<Vec<u16>>()
+        };
+        Some(match self.format {
+            Format::R8 => DynamicImage::ImageLuma8(ImageBuffer::from_raw(width, height, bytes())?),
+            Format::R8G8 => {
+                DynamicImage::ImageLumaA8(ImageBuffer::from_raw(width, height, bytes())?)
+            }
+            Format::R8G8B8 => {
+                DynamicImage::ImageRgb8(ImageBuffer::from_raw(width, height, bytes())?)
+            }
+            Format::R8G8B8A8 => {
+                DynamicImage::ImageRgba8(ImageBuffer::from_raw(width, height, bytes())?)
+            }
+            Format::R16 => {
+                DynamicImage::ImageLuma16(ImageBuffer::from_raw(width, height, words())?)
+            }
+            Format::R16G16 => {
+                DynamicImage::ImageLumaA16(ImageBuffer::from_raw(width, height, words())?)
+            }
+            Format::R16G16B16 => {
+                DynamicImage::ImageRgb16(ImageBuffer::from_raw(width, height, words())?)
+            }
+            Format::R16G16B16A16 => {
+                DynamicImage::ImageRgba16(ImageBuffer::from_raw(width, height, words())?)
+            }
+            Format::R32G32B32FLOAT | Format::R32G32B32A32FLOAT => return None,
+        })
+    }
 }
@@ -1,14 +1,14 @@
 use crate::buffer;
 use crate::image;
 use std::borrow::Cow;
+use std::ops::ControlFlow;
 use std::{fs, io};
 
 use crate::{Document, Error, Gltf, Result};
-use image_crate::ImageFormat::{Jpeg, Png};
 use std::path::Path;
 
 /// Return type of `import`.
-type Import = (Document, Vec<buffer::Data>, Vec<image::Data>);
+pub(crate) type Import = (Document, Vec<buffer::Data>, Vec<image::Data>);
 
 /// Represents the set of URI schemes the importer supports.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -51,23 +51,211 @@ impl<'a> Scheme<'a> {
         }
     }
 
-    fn read<F>(base: Option<&Path>, uri: &str, mut fetcher: F) -> Result<Vec<u8>> 
-        where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
-    {
+    fn read<F: Fetcher>(
+        base: Option<&Path>,
+        uri: &str,
+        resource: Resource<'_>,
+        fetcher: &mut F,
+    ) -> Result<buffer::Bytes> {
+        let request = |base, path| ResourceRequest {
+            kind: resource.kind,
+            index: resource.index,
+            uri,
+            base,
+            path,
+            expected_length: resource.expected_length,
+            mime_type: resource.mime_type,
+        };
         match Scheme::parse(uri) {
             // The path may be unused in the Scheme::Data case
             // Example: "uri" : "data:application/octet-stream;base64,wsVHPgA...."
-            Scheme::Data(_, base64) => base64::decode(base64).map_err(Error::Base64),
-            Scheme::File(path) => fetcher(None, path),
-            Scheme::Relative(path) if base.is_some() => fetcher(base, &path),
+            Scheme::Data(_, base64) => base64::decode(base64)
+                .map(Into::into)
+                .map_err(Error::Base64),
+            Scheme::File(path) => fetcher.fetch(&request(None, path)),
+            Scheme::Relative(path) if base.is_some() => fetcher.fetch(&request(base, &path)),
             Scheme::Unsupported => Err(Error::UnsupportedScheme),
             _ => Err(Error::ExternalReferenceInSliceImport),
         }
     }
 }
 
+/// The kind of resource requested from a [`Fetcher`].
+///
+/// [`Fetcher`]: trait.Fetcher.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ResourceKind {
+    /// Buffer data.
+    Buffer,
+
+    /// Encoded image data.
+    Image,
+}
+
+/// Describes an external resource requested from a [`Fetcher`].
+///
+/// [`Fetcher`]: trait.Fetcher.html
+#[derive(Clone, Copy, Debug)]
+pub struct ResourceRequest<'a> {
+    /// The kind of resource requested.
+    pub kind: ResourceKind,
+
+    /// The index of the buffer or image in the document.
+    ///
+    /// This is `None` for resources fetched through the `from_source`
+    /// functions, which have no knowledge of the document.
+    pub index: Option<usize>,
+
+    /// The URI as written in the document.
+    pub uri: &'a str,
+
+    /// The directory relative references are resolved from, if any.
+    pub base: Option<&'a Path>,
+
+    /// The percent-decoded path to fetch, relative to `base` if provided.
+    pub path: &'a str,
+
+    /// The minimum number of bytes the resource is expected to contain.
+    ///
+    /// This is the buffer `byteLength` for buffers and `None` for images.
+    pub expected_length: Option<usize>,
+
+    /// The MIME type declared in the document, if any.
+    pub mime_type: Option<&'a str>,
+}
+
+/// Loads the data of external resources referenced by a glTF document.
+///
+/// Fetchers are passed to [`import_with_progress`] and
+/// [`import_slice_with_progress`]. Fetcher functions such as
+/// [`filesystem_fetcher`] and closures of the form
+/// `FnMut(Option<&Path>, &str) -> Result<B>`, where `B` converts into
+/// [`buffer::Bytes`], implement this trait by receiving the `base` and `path`
+/// of the request only. Closures need explicitly typed arguments for this.
+/// Implement the trait directly to make use of the rest of the
+/// [`ResourceRequest`].
+///
+/// Returning an error, for example [`Error::Cancelled`], aborts the import.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::{Fetcher, ResourceRequest};
+/// use std::ops::ControlFlow;
+///
+/// struct Verbose;
+///
+/// impl Fetcher for Verbose {
+///     fn fetch(&mut self, request: &ResourceRequest<'_>) -> gltf::Result<gltf::buffer::Bytes> {
+///         println!("fetching {:?} {:?}: {}", request.kind, request.index, request.uri);
+///         gltf::filesystem_fetcher(request.base, request.path).map(Into::into)
+///     }
+/// }
+///
+/// let (document, buffers, images) =
+///     gltf::import_with_progress("examples/Box.gltf", Verbose, |_| ControlFlow::Continue(()))?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`import_with_progress`]: fn.import_with_progress.html
+/// [`import_slice_with_progress`]: fn.import_slice_with_progress.html
+/// [`filesystem_fetcher`]: fn.filesystem_fetcher.html
+/// [`buffer::Bytes`]: buffer/enum.Bytes.html
+/// [`ResourceRequest`]: struct.ResourceRequest.html
+/// [`Error::Cancelled`]: enum.Error.html#variant.Cancelled
+pub trait Fetcher {
+    /// Returns the data of the requested resource.
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes>;
+}
+
+impl<F, B> Fetcher for F
+where
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
+{
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        self(request.base, request.path).map(Into::into)
+    }
+}
+
+/// The progress of an import, reported after every buffer and image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    /// The kind of resource just loaded.
+    pub kind: ResourceKind,
+
+    /// The index of the buffer or image just loaded.
+    pub index: usize,
+
+    /// The number of resources loaded so far.
+    pub completed: usize,
+
+    /// The total number of buffers and images in the document.
+    pub total: usize,
+}
+
+/// Describes the resource being read, apart from its location.
+#[derive(Clone, Copy)]
+struct Resource<'a> {
+    kind: ResourceKind,
+    index: Option<usize>,
+    expected_length: Option<usize>,
+    mime_type: Option<&'a str>,
+}
+
+impl<'a> Resource<'a> {
+    fn buffer(buffer: Option<&crate::Buffer<'_>>) -> Self {
+        Resource {
+            kind: ResourceKind::Buffer,
+            index: buffer.map(crate::Buffer::index),
+            expected_length: buffer.map(crate::Buffer::length),
+            mime_type: None,
+        }
+    }
+
+    fn image(index: Option<usize>, mime_type: Option<&'a str>) -> Self {
+        Resource {
+            kind: ResourceKind::Image,
+            index,
+            expected_length: None,
+            mime_type,
+        }
+    }
+}
+
+/// Forwards progress to the user callback and counts loaded resources.
+struct Reporter<'a> {
+    callback: &'a mut dyn FnMut(&Progress) -> ControlFlow<()>,
+    completed: usize,
+    total: usize,
+}
+
+impl<'a> Reporter<'a> {
+    fn report(&mut self, kind: ResourceKind, index: usize) -> Result<()> {
+        self.completed += 1;
+        let progress = Progress {
+            kind,
+            index,
+            completed: self.completed,
+            total: self.total,
+        };
+        match (self.callback)(&progress) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(Error::Cancelled),
+        }
+    }
+}
+
 /// Fetcher function for filesystem references.
 /// This can be used as the `fetcher` argument to the `import` functions.
+///
+/// See [`FilesystemFetcher`] for a configurable alternative.
+///
+/// [`FilesystemFetcher`]: struct.FilesystemFetcher.html
 pub fn filesystem_fetcher(base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
     let path = match base {
         Some(base) => base.join(path),
@@ -76,6 +264,42 @@ pub fn filesystem_fetcher(base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
     read_to_end(path)
 }
 
+/// Fetcher function for filesystem references which memory maps files
+/// instead of reading them into memory.
+/// This can be used as the `fetcher` argument to the `import` functions.
+///
+/// Buffer data is backed by the memory map without being copied, unless its
+/// length is not a multiple of four. Image data is always copied for decoding.
+///
+/// # Safety
+///
+/// The mapped files must not be modified or truncated by this or any other
+/// process while the imported buffer data is alive.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, buffers, images) = gltf::import("examples/Box.gltf", |base, path| unsafe {
+///     gltf::mmap_fetcher(base, path)
+/// })?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub unsafe fn mmap_fetcher(base: Option<&Path>, path: &str) -> Result<buffer::Bytes> {
+    let path = match base {
+        Some(base) => base.join(path),
+        None => Path::new(path).to_path_buf(),
+    };
+    let file = fs::File::open(path).map_err(Error::Io)?;
+    let map = memmap2::Mmap::map(&file).map_err(Error::Io)?;
+    Ok(buffer::Bytes::from_storage(map))
+}
+
 /// Fetcher function that should never be called.
 /// Intended for use in slice import without external references.
 pub fn empty_fetcher(_base: Option<&Path>, _path: &str) -> Result<Vec<u8>> {
@@ -102,8 +326,14 @@ impl buffer::Data {
     /// Construct a buffer data object by reading the given source.
     /// If `base` is provided, then external filesystem references will
     /// be resolved from this directory.
-    pub fn from_source<F>(source: buffer::Source<'_>, base: Option<&Path>, fetcher: F) -> Result<Self>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+    pub fn from_source<F, B>(
+        source: buffer::Source<'_>,
+        base: Option<&Path>,
+        fetcher: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(Option<&Path>, &str) -> Result<B>,
+        B: Into<buffer::Bytes>,
     {
         Self::from_source_and_blob(source, &mut None, base, fetcher)
     }
@@ -113,22 +343,50 @@ impl buffer::Data {
     /// be resolved from this directory.
     /// `blob` represents the `BIN` section of a binary glTF file,
     /// and it will be taken to fill the buffer if the `source` refers to it.
-    pub fn from_source_and_blob<F>(
+    pub fn from_source_and_blob<F, B>(
         source: buffer::Source<'_>,
         blob: &mut Option<Vec<u8>>,
         base: Option<&Path>,
-        fetcher: F
+        mut fetcher: F,
     ) -> Result<Self>
-        where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+    where
+        F: FnMut(Option<&Path>, &str) -> Result<B>,
+        B: Into<buffer::Bytes>,
     {
-        let mut data = match source {
-            buffer::Source::Uri(uri) => Scheme::read(base, uri, fetcher),
-            buffer::Source::Bin => blob.take().ok_or(Error::MissingBlob),
+        Self::read(source, blob, base, Resource::buffer(None), &mut fetcher)
+    }
+
+    fn read<F: Fetcher>(
+        source: buffer::Source<'_>,
+        blob: &mut Option<Vec<u8>>,
+        base: Option<&Path>,
+        resource: Resource<'_>,
+        fetcher: &mut F,
+    ) -> Result<Self> {
+        let data = match source {
+            buffer::Source::Uri(uri) => Scheme::read(base, uri, resource, fetcher),
+            buffer::Source::Bin => blob.take().map(Into::into).ok_or(Error::MissingBlob),
         }?;
-        while data.len() % 4 != 0 {
-            data.push(0);
+        Ok(buffer::Data(data.pad_to_multiple_of_four()))
+    }
+
+    /// Reads the data of `buffer`, checking its length.
+    fn import<F: Fetcher>(
+        buffer: &crate::Buffer<'_>,
+        blob: &mut Option<Vec<u8>>,
+        base: Option<&Path>,
+        fetcher: &mut F,
+    ) -> Result<Self> {
+        let resource = Resource::buffer(Some(buffer));
+        let data = Self::read(buffer.source(), blob, base, resource, fetcher)?;
+        if data.len() < buffer.length() {
+            return Err(Error::BufferLength {
+                buffer: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
         }
-        Ok(buffer::Data(data))
+        Ok(data)
     }
 }
 
@@ -138,105 +396,218 @@ impl buffer::Data {
 ///
 /// This function is intended for advanced users who wish to forego loading image data.
 /// A typical user should call [`import`] instead.
-pub fn import_buffers<F>(
+pub fn import_buffers<F, B>(
     document: &Document,
-    mut blob: Option<Vec<u8>>,
+    blob: Option<Vec<u8>>,
     base: Option<&Path>,
-    mut fetcher: F
+    mut fetcher: F,
 ) -> Result<Vec<buffer::Data>>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+where
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
 {
+    let mut reporter = Reporter {
+        callback: &mut |_| ControlFlow::Continue(()),
+        completed: 0,
+        total: 0,
+    };
+    import_buffers_impl(document, blob, base, &mut fetcher, &mut reporter)
+}
+
+fn import_buffers_impl<F: Fetcher>(
+    document: &Document,
+    mut blob: Option<Vec<u8>>,
+    base: Option<&Path>,
+    fetcher: &mut F,
+    reporter: &mut Reporter<'_>,
+) -> Result<Vec<buffer::Data>> {
     let mut buffers = Vec::new();
     for buffer in document.buffers() {
-        let data = buffer::Data::from_source_and_blob(buffer.source(), &mut blob, base, &mut fetcher)?;
-        if data.len() < buffer.length() {
-            return Err(Error::BufferLength {
-                buffer: buffer.index(),
-                expected: buffer.length(),
-                actual: data.len(),
-            });
-        }
-        buffers.push(data);
+        buffers.push(buffer::Data::import(&buffer, &mut blob, base, fetcher)?);
+        reporter.report(ResourceKind::Buffer, buffer.index())?;
     }
     Ok(buffers)
 }
 
-impl image::Data {
-    /// Construct an image data object by reading the given source.
+impl image::Encoding {
+    /// Returns the MIME type of the encoding.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            image::Encoding::Png => "image/png",
+            image::Encoding::Jpeg => "image/jpeg",
+            image::Encoding::WebP => "image/webp",
+            image::Encoding::Bmp => "image/bmp",
+            image::Encoding::Tiff => "image/tiff",
+        }
+    }
+
+    /// Returns the encoding corresponding to a glTF image MIME type.
+    pub(crate) fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type {
+            "image/png" => Some(image::Encoding::Png),
+            "image/jpeg" => Some(image::Encoding::Jpeg),
+            "image/webp" => Some(image::Encoding::WebP),
+            _ => None,
+        }
+    }
+
+    /// Returns the encoding corresponding to the file extension of a URI.
+    pub(crate) fn from_extension(uri: &str) -> Option<Self> {
+        match uri.rsplit('.').next() {
+            Some("png") => Some(image::Encoding::Png),
+            Some("jpg") | Some("jpeg") => Some(image::Encoding::Jpeg),
+            Some("webp") => Some(image::Encoding::WebP),
+            _ => None,
+        }
+    }
+
+    /// Guesses the encoding from the leading bytes of the image data.
+    #[cfg(feature = "guess_mime_type")]
+    fn guess(encoded_image: &[u8]) -> Option<Self> {
+        match image_crate::guess_format(encoded_image) {
+            Ok(image_crate::ImageFormat::Png) => Some(image::Encoding::Png),
+            Ok(image_crate::ImageFormat::Jpeg) => Some(image::Encoding::Jpeg),
+            Ok(image_crate::ImageFormat::WebP) => Some(image::Encoding::WebP),
+            Ok(image_crate::ImageFormat::Bmp) => Some(image::Encoding::Bmp),
+            Ok(image_crate::ImageFormat::Tiff) => Some(image::Encoding::Tiff),
+            _ => None,
+        }
+    }
+
+    #[cfg(not(feature = "guess_mime_type"))]
+    fn guess(_encoded_image: &[u8]) -> Option<Self> {
+        None
+    }
+
+    fn image_format(self) -> image_crate::ImageFormat {
+        match self {
+            image::Encoding::Png => image_crate::ImageFormat::Png,
+            image::Encoding::Jpeg => image_crate::ImageFormat::Jpeg,
+            image::Encoding::WebP => image_crate::ImageFormat::WebP,
+            image::Encoding::Bmp => image_crate::ImageFormat::Bmp,
+            image::Encoding::Tiff => image_crate::ImageFormat::Tiff,
+        }
+    }
+}
+
+impl image::Encoded {
+    /// Construct an encoded image data object by reading the given source,
+    /// without decoding it.
     /// If `base` is provided, then external filesystem references will
     /// be resolved from this directory.
-    pub fn from_source<F>(
+    pub fn from_source<F, B>(
         source: image::Source<'_>,
         buffer_data: &[buffer::Data],
         base: Option<&Path>,
-        fetcher: F
-    ) -> Result<Self> 
-        where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+        mut fetcher: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(Option<&Path>, &str) -> Result<B>,
+        B: Into<buffer::Bytes>,
     {
-        #[cfg(feature = "guess_mime_type")]
-        let guess_format = |encoded_image: &[u8]| match image_crate::guess_format(encoded_image) {
-            Ok(image_crate::ImageFormat::Png) => Some(Png),
-            Ok(image_crate::ImageFormat::Jpeg) => Some(Jpeg),
-            _ => None,
-        };
-        #[cfg(not(feature = "guess_mime_type"))]
-        let guess_format = |_encoded_image: &[u8]| None;
-        let decoded_image = match source {
+        Self::read(source, buffer_data, base, None, &mut fetcher)
+    }
+
+    fn read<F: Fetcher>(
+        source: image::Source<'_>,
+        buffer_data: &[buffer::Data],
+        base: Option<&Path>,
+        index: Option<usize>,
+        fetcher: &mut F,
+    ) -> Result<Self> {
+        let (bytes, encoding) = match source {
             image::Source::Uri { uri, mime_type } => match Scheme::parse(uri) {
-                Scheme::Data(Some(annoying_case), base64) => {
+                Scheme::Data(annoying_case, base64) => {
                     let encoded_image = base64::decode(base64).map_err(Error::Base64)?;
-                    let encoded_format = match annoying_case {
-                        "image/png" => Png,
-                        "image/jpeg" => Jpeg,
-                        _ => match guess_format(&encoded_image) {
-                            Some(format) => format,
-                            None => return Err(Error::UnsupportedImageEncoding),
-                        },
-                    };
-
-                    image_crate::load_from_memory_with_format(&encoded_image, encoded_format)?
+                    let encoding = annoying_case.and_then(image::Encoding::from_mime_type);
+                    (encoded_image, encoding)
                 }
                 Scheme::Unsupported => return Err(Error::UnsupportedScheme),
                 _ => {
-                    let encoded_image = Scheme::read(base, uri, fetcher)?;
-                    let encoded_format = match mime_type {
-                        Some("image/png") => Png,
-                        Some("image/jpeg") => Jpeg,
-                        Some(_) => match guess_format(&encoded_image) {
-                            Some(format) => format,
-                            None => return Err(Error::UnsupportedImageEncoding),
-                        },
-                        None => match uri.rsplit('.').next() {
-                            Some("png") => Png,
-                            Some("jpg") | Some("jpeg") => Jpeg,
-                            _ => match guess_format(&encoded_image) {
-                                Some(format) => format,
-                                None => return Err(Error::UnsupportedImageEncoding),
-                            },
-                        },
+                    let resource = Resource::image(index, mime_type);
+                    let encoded_image = Scheme::read(base, uri, resource, fetcher)?.into_vec();
+                    let encoding = match mime_type {
+                        Some(mime_type) => image::Encoding::from_mime_type(mime_type),
+                        None => image::Encoding::from_extension(uri),
                     };
-                    image_crate::load_from_memory_with_format(&encoded_image, encoded_format)?
+                    (encoded_image, encoding)
                 }
             },
             image::Source::View { view, mime_type } => {
                 let parent_buffer_data = &buffer_data[view.buffer().index()].0;
                 let begin = view.offset();
                 let end = begin + view.length();
-                let encoded_image = &parent_buffer_data[begin..end];
-                let encoded_format = match mime_type {
-                    "image/png" => Png,
-                    "image/jpeg" => Jpeg,
-                    _ => match guess_format(encoded_image) {
-                        Some(format) => format,
-                        None => return Err(Error::UnsupportedImageEncoding),
-                    },
-                };
-                image_crate::load_from_memory_with_format(encoded_image, encoded_format)?
+                let encoded_image = parent_buffer_data[begin..end].to_vec();
+                (encoded_image, image::Encoding::from_mime_type(mime_type))
             }
         };
+        let encoding = match encoding.or_else(|| image::Encoding::guess(&bytes)) {
+            Some(encoding) => encoding,
+            None => return Err(Error::UnsupportedImageEncoding),
+        };
+        Ok(image::Encoded { bytes, encoding })
+    }
 
+    /// Decodes the image data.
+    pub fn decode(&self) -> Result<image::Data> {
+        let decoded_image =
+            image_crate::load_from_memory_with_format(&self.bytes, self.encoding.image_format())?;
         image::Data::new(decoded_image)
     }
+
+    /// Decodes the image data, downsampling it so that neither dimension
+    /// exceeds `max_dimension` pixels.
+    ///
+    /// JPEG images are scaled by 1/2, 1/4, or 1/8 while decoding, so the full
+    /// resolution image is never held in memory. Other encodings are decoded
+    /// at full resolution first. Any remaining excess is removed by
+    /// resampling, preserving the aspect ratio.
+    pub fn decode_with_max_dimension(&self, max_dimension: u32) -> Result<image::Data> {
+        use image_crate::{imageops::FilterType, DynamicImage, GenericImageView, ImageDecoder};
+        let max_dimension = max_dimension.max(1);
+        let decoded_image = match self.encoding {
+            image::Encoding::Jpeg => {
+                let mut decoder = image_crate::codecs::jpeg::JpegDecoder::new(&self.bytes[..])?;
+                let (width, height) = decoder.dimensions();
+                let largest = width.max(height);
+                if largest > max_dimension {
+                    // The decoder picks the smallest scale which is at least
+                    // the requested size.
+                    let fit = |n: u32| (n as u64 * max_dimension as u64 / largest as u64).max(1);
+                    decoder.scale(fit(width) as u16, fit(height) as u16)?;
+                }
+                DynamicImage::from_decoder(decoder)?
+            }
+            encoding => {
+                image_crate::load_from_memory_with_format(&self.bytes, encoding.image_format())?
+            }
+        };
+        let (width, height) = decoded_image.dimensions();
+        if width.max(height) > max_dimension {
+            let resized = decoded_image.resize(max_dimension, max_dimension, FilterType::Triangle);
+            image::Data::new(resized)
+        } else {
+            image::Data::new(decoded_image)
+        }
+    }
+}
+
+impl image::Data {
+    /// Construct an image data object by reading the given source.
+    /// If `base` is provided, then external filesystem references will
+    /// be resolved from this directory.
+    pub fn from_source<F, B>(
+        source: image::Source<'_>,
+        buffer_data: &[buffer::Data],
+        base: Option<&Path>,
+        fetcher: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(Option<&Path>, &str) -> Result<B>,
+        B: Into<buffer::Bytes>,
+    {
+        image::Encoded::from_source(source, buffer_data, base, fetcher)?.decode()
+    }
 }
 
 /// Import image data referenced by a glTF document.
@@ -245,37 +616,253 @@ impl image::Data {
 ///
 /// This function is intended for advanced users who wish to forego loading buffer data.
 /// A typical user should call [`import`] instead.
-pub fn import_images<F>(
+pub fn import_images<F, B>(
     document: &Document,
     buffer_data: &[buffer::Data],
     base: Option<&Path>,
-    mut fetcher: F
+    mut fetcher: F,
 ) -> Result<Vec<image::Data>>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+where
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
+{
+    let mut reporter = Reporter {
+        callback: &mut |_| ControlFlow::Continue(()),
+        completed: 0,
+        total: 0,
+    };
+    import_images_impl(
+        document,
+        buffer_data,
+        base,
+        None,
+        &mut fetcher,
+        &mut reporter,
+    )
+}
+
+/// Import image data referenced by a glTF document like [`import_images`],
+/// downsampling every image so that neither dimension exceeds
+/// `max_dimension` pixels.
+///
+/// This bounds the memory used by decoded images, for example when only
+/// thumbnails are needed. See [`image::Encoded::decode_with_max_dimension`].
+///
+/// [`image::Encoded::decode_with_max_dimension`]: image/struct.Encoded.html#method.decode_with_max_dimension
+pub fn import_images_with_max_dimension<F, B>(
+    document: &Document,
+    buffer_data: &[buffer::Data],
+    base: Option<&Path>,
+    max_dimension: u32,
+    mut fetcher: F,
+) -> Result<Vec<image::Data>>
+where
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
+{
+    let mut reporter = Reporter {
+        callback: &mut |_| ControlFlow::Continue(()),
+        completed: 0,
+        total: 0,
+    };
+    import_images_impl(
+        document,
+        buffer_data,
+        base,
+        Some(max_dimension),
+        &mut fetcher,
+        &mut reporter,
+    )
+}
+
+fn import_images_impl<F: Fetcher>(
+    document: &Document,
+    buffer_data: &[buffer::Data],
+    base: Option<&Path>,
+    max_dimension: Option<u32>,
+    fetcher: &mut F,
+    reporter: &mut Reporter<'_>,
+) -> Result<Vec<image::Data>> {
+    let mut images = Vec::new();
+    for image in document.images() {
+        let index = Some(image.index());
+        let encoded = image::Encoded::read(image.source(), buffer_data, base, index, fetcher)?;
+        images.push(match max_dimension {
+            Some(max_dimension) => encoded.decode_with_max_dimension(max_dimension)?,
+            None => encoded.decode()?,
+        });
+        reporter.report(ResourceKind::Image, image.index())?;
+    }
+    Ok(images)
+}
+
+/// Import image data referenced by a glTF document without decoding it.
+///
+/// This is an alternative to [`import_images`] for applications which decode
+/// images themselves, for example on the GPU.
+pub fn import_encoded_images<F, B>(
+    document: &Document,
+    buffer_data: &[buffer::Data],
+    base: Option<&Path>,
+    mut fetcher: F,
+) -> Result<Vec<image::Encoded>>
+where
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
 {
     let mut images = Vec::new();
     for image in document.images() {
-        images.push(image::Data::from_source(image.source(), buffer_data, base, &mut fetcher)?);
+        let index = Some(image.index());
+        images.push(image::Encoded::read(
+            image.source(),
+            buffer_data,
+            base,
+            index,
+            &mut fetcher,
+        )?);
     }
     Ok(images)
 }
 
-fn import_impl<F>(Gltf { document, blob }: Gltf, base: Option<&Path>, mut fetcher: F) -> Result<Import>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+/// Refetch and redecode the buffers and images of an existing import whose
+/// URIs changed, leaving all other data untouched.
+///
+/// Images stored in a buffer view are redecoded when their parent buffer
+/// changed. The buffer embedded in a binary glTF cannot be reimported and is
+/// never considered changed. `base` is the directory external references are
+/// resolved from, i.e. the parent directory of the imported file.
+///
+/// Returns the indices of the reimported buffers and images. If an error
+/// occurs, `import` is left unchanged. Returns an `Error::Validation` of kind
+/// `Missing` if `import` holds fewer buffers or images than its document.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// # use std::path::Path;
+/// let mut import = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+/// let (buffers, images) = gltf::reimport_changed(
+///     &mut import,
+///     &["Box0.bin"],
+///     Some(Path::new("examples")),
+///     gltf::filesystem_fetcher,
+/// )?;
+/// assert_eq!(buffers, vec![0]);
+/// assert!(images.is_empty());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+pub fn reimport_changed<S, F, B>(
+    import: &mut Import,
+    changed_uris: &[S],
+    base: Option<&Path>,
+    mut fetcher: F,
+) -> Result<(Vec<usize>, Vec<usize>)>
+where
+    S: AsRef<str>,
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
 {
-    let buffer_data = import_buffers(&document, blob, base, &mut fetcher)?;
-    let image_data = import_images(&document, &buffer_data, base, fetcher)?;
+    let (document, buffer_data, image_data) = import;
+    let missing = |field, index| {
+        let path = json::Path::new().field(field).index(index);
+        Error::Validation(vec![(path, json::validation::Error::Missing)])
+    };
+    if buffer_data.len() < document.buffers().len() {
+        return Err(missing("buffers", buffer_data.len()));
+    }
+    if image_data.len() < document.images().len() {
+        return Err(missing("images", image_data.len()));
+    }
+    let is_changed = |uri: &str| changed_uris.iter().any(|changed| changed.as_ref() == uri);
+
+    let mut buffers = Vec::new();
+    for buffer in document.buffers() {
+        if let buffer::Source::Uri(uri) = buffer.source() {
+            if is_changed(uri) {
+                let data = buffer::Data::import(&buffer, &mut None, base, &mut fetcher)?;
+                buffers.push((buffer.index(), data));
+            }
+        }
+    }
+
+    let swap_buffers = |buffer_data: &mut Vec<buffer::Data>,
+                        buffers: &mut Vec<(usize, buffer::Data)>| {
+        for (index, data) in buffers.iter_mut() {
+            std::mem::swap(&mut buffer_data[*index], data);
+        }
+    };
+    // Image views are decoded from the new buffer data.
+    swap_buffers(buffer_data, &mut buffers);
+
+    let mut images = Vec::new();
+    for image in document.images() {
+        let changed = match image.source() {
+            image::Source::Uri { uri, .. } => is_changed(uri),
+            image::Source::View { view, .. } => buffers
+                .iter()
+                .any(|(index, _)| *index == view.buffer().index()),
+        };
+        if changed {
+            let index = Some(image.index());
+            let result =
+                image::Encoded::read(image.source(), buffer_data, base, index, &mut fetcher)
+                    .and_then(|encoded| encoded.decode());
+            match result {
+                Ok(data) => images.push((image.index(), data)),
+                Err(error) => {
+                    // Restore the previous buffer data.
+                    swap_buffers(buffer_data, &mut buffers);
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    for (index, data) in images.iter_mut() {
+        std::mem::swap(&mut image_data[*index], data);
+    }
+    Ok((
+        buffers.into_iter().map(|(index, _)| index).collect(),
+        images.into_iter().map(|(index, _)| index).collect(),
+    ))
+}
+
+fn import_impl<F: Fetcher>(
+    Gltf { document, blob, .. }: Gltf,
+    base: Option<&Path>,
+    mut fetcher: F,
+    progress: &mut dyn FnMut(&Progress) -> ControlFlow<()>,
+) -> Result<Import> {
+    let mut reporter = Reporter {
+        callback: progress,
+        completed: 0,
+        total: document.buffers().len() + document.images().len(),
+    };
+    let buffer_data = import_buffers_impl(&document, blob, base, &mut fetcher, &mut reporter)?;
+    let image_data = import_images_impl(
+        &document,
+        &buffer_data,
+        base,
+        None,
+        &mut fetcher,
+        &mut reporter,
+    )?;
     let import = (document, buffer_data, image_data);
     Ok(import)
 }
 
-fn import_path<F>(path: &Path, fetcher: F) -> Result<Import>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
-{
+pub(crate) fn import_path<F: Fetcher>(
+    path: &Path,
+    fetcher: F,
+    progress: &mut dyn FnMut(&Progress) -> ControlFlow<()>,
+) -> Result<Import> {
     let base = path.parent().unwrap_or_else(|| Path::new("./"));
     let file = fs::File::open(path).map_err(Error::Io)?;
     let reader = io::BufReader::new(file);
-    import_impl(Gltf::from_reader(reader)?, Some(base), fetcher)
+    import_impl(Gltf::from_reader(reader)?, Some(base), fetcher, progress)
 }
 
 /// Import glTF 2.0 from the file system.
@@ -305,18 +892,55 @@ fn import_path<F>(path: &Path, fetcher: F) -> Result<Import>
 ///
 /// [`Gltf`]: struct.Gltf.html
 /// [`Glb`]: struct.Glb.html
-pub fn import<P, F>(path: P, fetcher: F) -> Result<Import>
+pub fn import<P, F, B>(path: P, fetcher: F) -> Result<Import>
 where
     P: AsRef<Path>,
-    F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
 {
-    import_path(path.as_ref(), fetcher)
+    import_path(path.as_ref(), fetcher, &mut |_| ControlFlow::Continue(()))
 }
 
-fn import_slice_impl<F>(slice: &[u8], base: Option<&Path>, fetcher: F) -> Result<Import>
-    where F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+/// Import glTF 2.0 from the file system like [`import`], reporting progress
+/// after every buffer and image.
+///
+/// Returning `ControlFlow::Break` from `progress` aborts the import with
+/// [`Error::Cancelled`].
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use std::ops::ControlFlow;
+///
+/// let (document, buffers, images) =
+///     gltf::import_with_progress("examples/Box.gltf", gltf::filesystem_fetcher, |progress| {
+///         println!("loaded {} of {}", progress.completed, progress.total);
+///         ControlFlow::Continue(())
+///     })?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`Error::Cancelled`]: enum.Error.html#variant.Cancelled
+pub fn import_with_progress<P, F, C>(path: P, fetcher: F, mut progress: C) -> Result<Import>
+where
+    P: AsRef<Path>,
+    F: Fetcher,
+    C: FnMut(&Progress) -> ControlFlow<()>,
 {
-    import_impl(Gltf::from_slice(slice)?, base, fetcher)
+    import_path(path.as_ref(), fetcher, &mut progress)
+}
+
+pub(crate) fn import_slice_impl<F: Fetcher>(
+    slice: &[u8],
+    base: Option<&Path>,
+    fetcher: F,
+    progress: &mut dyn FnMut(&Progress) -> ControlFlow<()>,
+) -> Result<Import> {
+    import_impl(Gltf::from_slice(slice)?, base, fetcher, progress)
 }
 
 /// Import glTF 2.0 from a slice.
@@ -345,10 +969,124 @@ fn import_slice_impl<F>(slice: &[u8], base: Option<&Path>, fetcher: F) -> Result
 /// #     run().expect("test failure");
 /// # }
 /// ```
-pub fn import_slice<S, F>(slice: S, base: Option<&Path>, fetcher: F) -> Result<Import>
+pub fn import_slice<S, F, B>(slice: S, base: Option<&Path>, fetcher: F) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
+{
+    import_slice_impl(slice.as_ref(), base, fetcher, &mut |_| {
+        ControlFlow::Continue(())
+    })
+}
+
+/// Import glTF 2.0 from a stream, which need not be seekable.
+///
+/// The stream is read to the end and may contain either standard or binary
+/// glTF, which is detected from its contents. External references are
+/// resolved from `base` like in [`import_slice`]. This allows reading assets
+/// piped through standard input:
+///
+/// ```no_run
+/// # fn run() -> Result<(), gltf::Error> {
+/// let stdin = std::io::stdin();
+/// let (document, buffers, images) =
+///     gltf::import_reader(stdin.lock(), None, gltf::empty_fetcher)?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// See [`import_tar`] for streams carrying an asset together with its
+/// resources.
+///
+/// [`import_tar`]: fn.import_tar.html
+pub fn import_reader<R, F, B>(mut reader: R, base: Option<&Path>, fetcher: F) -> Result<Import>
+where
+    R: io::Read,
+    F: FnMut(Option<&Path>, &str) -> Result<B>,
+    B: Into<buffer::Bytes>,
+{
+    let mut slice = Vec::new();
+    reader.read_to_end(&mut slice).map_err(Error::Io)?;
+    import_slice_impl(&slice, base, fetcher, &mut |_| ControlFlow::Continue(()))
+}
+
+/// Import glTF 2.0 from a slice like [`import_slice`], reporting progress
+/// after every buffer and image.
+///
+/// Returning `ControlFlow::Break` from `progress` aborts the import with
+/// [`Error::Cancelled`].
+///
+/// [`Error::Cancelled`]: enum.Error.html#variant.Cancelled
+pub fn import_slice_with_progress<S, F, C>(
+    slice: S,
+    base: Option<&Path>,
+    fetcher: F,
+    mut progress: C,
+) -> Result<Import>
 where
     S: AsRef<[u8]>,
-    F: FnMut(Option<&Path>, &str) -> Result<Vec<u8>>
+    F: Fetcher,
+    C: FnMut(&Progress) -> ControlFlow<()>,
 {
-    import_slice_impl(slice.as_ref(), base, fetcher)
+    import_slice_impl(slice.as_ref(), base, fetcher, &mut progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::TempDir;
+    use std::fs;
+
+    #[test]
+    fn reimport_changed() {
+        let root = TempDir::new("reimport");
+        let jpg = fs::read("tests/orange_64x32.jpg").unwrap();
+        fs::write(root.join("a.bin"), [1u8; 4]).unwrap();
+        fs::write(root.join("b.bin"), [2u8; 4]).unwrap();
+        fs::write(root.join("one.jpg"), &jpg).unwrap();
+        fs::write(root.join("two.jpg"), &jpg).unwrap();
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [
+                { "byteLength": 4, "uri": "a.bin" },
+                { "byteLength": 4, "uri": "b.bin" }
+            ],
+            "images": [{ "uri": "one.jpg" }, { "uri": "two.jpg" }]
+        }"#;
+        fs::write(root.join("scene.gltf"), json).unwrap();
+        let mut import = crate::import(root.join("scene.gltf"), crate::filesystem_fetcher).unwrap();
+        let before = import.clone();
+
+        fs::write(root.join("b.bin"), [3u8; 4]).unwrap();
+        let mut fetched = Vec::new();
+        let fetcher = |base: Option<&std::path::Path>, uri: &str| {
+            fetched.push(uri.to_owned());
+            crate::filesystem_fetcher(base, uri)
+        };
+        let (buffers, images) =
+            crate::reimport_changed(&mut import, &["b.bin", "two.jpg"], Some(&root), fetcher)
+                .unwrap();
+        assert_eq!((buffers, images), (vec![1], vec![1]));
+        assert_eq!(fetched, ["b.bin", "two.jpg"]);
+
+        let (_, buffer_data, image_data) = &import;
+        assert_eq!(*buffer_data[0], *before.1[0]);
+        assert_eq!(*buffer_data[1], [3u8; 4]);
+        assert_eq!(image_data[0].pixels, before.2[0].pixels);
+        assert_eq!(image_data[1].pixels, before.2[1].pixels);
+
+        // Data that does not cover the document is an error, not a panic.
+        import.1.truncate(1);
+        let result = crate::reimport_changed(
+            &mut import,
+            &["b.bin"],
+            Some(&root),
+            crate::filesystem_fetcher,
+        );
+        assert!(matches!(result, Err(crate::Error::Validation(_))));
+    }
 }
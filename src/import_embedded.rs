@@ -0,0 +1,64 @@
+use crate::prelude::*;
+use crate::{buffer, Document, Error, Gltf, Result};
+
+/// Return type of `import_embedded`.
+type Import = (Document, Vec<buffer::Data>);
+
+/// Reads buffer data from the `BIN` chunk or from a base64 data URI.
+fn read(source: buffer::Source<'_>, blob: &mut Option<Vec<u8>>) -> Result<Vec<u8>> {
+    match source {
+        buffer::Source::Uri(uri) => match uri.strip_prefix("data:") {
+            Some(rest) => {
+                let data = rest.split_once(";base64,").map_or(rest, |(_, data)| data);
+                base64::decode(data).map_err(Error::Base64)
+            }
+            None if uri.contains(':') && !uri.starts_with("file:") => Err(Error::UnsupportedScheme),
+            None => Err(Error::ExternalReferenceInSliceImport),
+        },
+        buffer::Source::Bin => blob.take().ok_or(Error::MissingBlob),
+    }
+}
+
+/// Import glTF 2.0 from a slice without access to a file system.
+///
+/// Buffer data is taken from the `BIN` chunk of binary glTF and from base64
+/// data URIs. Any other reference results in
+/// `Error::ExternalReferenceInSliceImport`. Images are not decoded, since
+/// decoding requires the `image` crate and thus the `import` feature.
+///
+/// Unlike [`import_slice`], this function is available without `std`.
+///
+/// [`import_slice`]: fn.import_slice.html
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, buffers) = gltf::import_embedded(include_bytes!("../examples/Box.glb"))?;
+/// assert_eq!(buffers.len(), document.buffers().count());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+pub fn import_embedded<S>(slice: S) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+{
+    let Gltf {
+        document, mut blob, ..
+    } = Gltf::from_slice(slice.as_ref())?;
+    let mut buffers = Vec::new();
+    for buffer in document.buffers() {
+        let data = buffer::Bytes::from(read(buffer.source(), &mut blob)?);
+        let data = buffer::Data(data.pad_to_multiple_of_four());
+        if data.len() < buffer.length() {
+            return Err(Error::BufferLength {
+                buffer: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
+        }
+        buffers.push(data);
+    }
+    Ok((document, buffers))
+}
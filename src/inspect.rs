@@ -0,0 +1,415 @@
+use crate::prelude::*;
+use crate::scene::Transform;
+use crate::{texture, Document, Node};
+use alloc::collections::BTreeSet;
+use core::fmt::{self, Write};
+
+/// Returns the name of a glTF object, or `None` without the `names` feature.
+macro_rules! name {
+    ($object:expr) => {{
+        #[cfg(feature = "names")]
+        let name = $object.name();
+        #[cfg(not(feature = "names"))]
+        let name: Option<&str> = {
+            let _ = &$object;
+            None
+        };
+        name
+    }};
+}
+
+/// Formats an object as `kind index "name"`.
+fn label(kind: &str, index: usize, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{} {} {:?}", kind, index, name),
+        None => format!("{} {}", kind, index),
+    }
+}
+
+/// Describes the transform, mesh, skin and camera of `node`.
+fn annotations(node: &Node) -> Vec<String> {
+    let mut annotations = Vec::new();
+    match node.transform() {
+        Transform::Matrix { matrix } => {
+            let identity = [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ];
+            if matrix != identity {
+                annotations.push(format!("matrix {:?}", matrix));
+            }
+        }
+        Transform::Decomposed {
+            translation,
+            rotation,
+            scale,
+        } => {
+            if translation != [0.0; 3] {
+                annotations.push(format!("translation {:?}", translation));
+            }
+            if rotation != [0.0, 0.0, 0.0, 1.0] {
+                annotations.push(format!("rotation {:?}", rotation));
+            }
+            if scale != [1.0; 3] {
+                annotations.push(format!("scale {:?}", scale));
+            }
+        }
+    }
+    if let Some(mesh) = node.mesh() {
+        let materials: BTreeSet<String> = mesh
+            .primitives()
+            .map(|primitive| match primitive.material().index() {
+                Some(index) => index.to_string(),
+                None => "default".to_string(),
+            })
+            .collect();
+        let materials: Vec<String> = materials.into_iter().collect();
+        annotations.push(format!(
+            "{} ({} primitives, materials {})",
+            label("mesh", mesh.index(), name!(mesh)),
+            mesh.primitives().len(),
+            materials.join(", ")
+        ));
+    }
+    if let Some(skin) = node.skin() {
+        annotations.push(format!(
+            "{} ({} joints)",
+            label("skin", skin.index(), name!(skin)),
+            skin.joints().len()
+        ));
+    }
+    if let Some(camera) = node.camera() {
+        let projection = match camera.projection() {
+            crate::camera::Projection::Perspective(_) => "perspective",
+            crate::camera::Projection::Orthographic(_) => "orthographic",
+        };
+        annotations.push(format!(
+            "{} ({})",
+            label("camera", camera.index(), name!(camera)),
+            projection
+        ));
+    }
+    annotations
+}
+
+/// Writes `node` and its descendants as a tree branch.
+fn write_node(
+    out: &mut String,
+    node: Node,
+    prefix: &str,
+    last: bool,
+    visited: &mut BTreeSet<usize>,
+) -> fmt::Result {
+    let (branch, indent) = if last {
+        ("└── ", "    ")
+    } else {
+        ("├── ", "│   ")
+    };
+    write!(
+        out,
+        "{}{}{}",
+        prefix,
+        branch,
+        label("Node", node.index(), name!(node))
+    )?;
+    if !visited.insert(node.index()) {
+        // Only possible in invalid documents.
+        return writeln!(out, " (cycle)");
+    }
+    for annotation in annotations(&node) {
+        write!(out, " [{}]", annotation)?;
+    }
+    writeln!(out)?;
+    let prefix = format!("{}{}", prefix, indent);
+    let count = node.children().len();
+    for (i, child) in node.children().enumerate() {
+        write_node(out, child, &prefix, i + 1 == count, visited)?;
+    }
+    visited.remove(&node.index());
+    Ok(())
+}
+
+/// Escapes `text` for a quoted Graphviz string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The vertices and edges of a Graphviz graph.
+#[derive(Default)]
+struct Graph {
+    /// The declared vertices.
+    vertices: Vec<String>,
+
+    /// The edges as `from`, `to` and label, without duplicates.
+    edges: BTreeSet<(String, String, &'static str)>,
+}
+
+impl Graph {
+    /// Declares the vertex `id` with the label of an object and `shape`.
+    fn vertex(&mut self, id: String, kind: &str, index: usize, name: Option<&str>, shape: &str) {
+        let label = match name {
+            Some(name) => format!("{} {}\\n{}", kind, index, escape(name)),
+            None => format!("{} {}", kind, index),
+        };
+        self.vertices
+            .push(format!("{} [label=\"{}\", shape={}];", id, label, shape));
+    }
+
+    /// Adds an edge from `from` to `to`.
+    fn edge(&mut self, from: String, to: String, label: &'static str) {
+        self.edges.insert((from, to, label));
+    }
+}
+
+impl Document {
+    /// Returns a human-readable outline of the scene graph, for debugging.
+    ///
+    /// Every scene is listed with its node hierarchy. Nodes are annotated
+    /// with their non-identity transform, mesh, skin and camera. Nodes which
+    /// belong to no scene are listed last.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let gltf = gltf::Gltf::from_slice(br#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "scenes": [{ "nodes": [0] }],
+    ///     "nodes": [{ "children": [1] }, { "translation": [0.0, 1.0, 0.0] }]
+    /// }"#)?;
+    /// assert_eq!(
+    ///     gltf.print_tree(),
+    ///     "Scene 0\n└── Node 0\n    └── Node 1 [translation [0.0, 1.0, 0.0]]\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn print_tree(&self) -> String {
+        let mut out = String::new();
+        // Writing to a `String` cannot fail.
+        self.write_tree(&mut out).unwrap();
+        out
+    }
+
+    fn write_tree(&self, out: &mut String) -> fmt::Result {
+        let mut visited = BTreeSet::new();
+        let mut reachable = BTreeSet::new();
+        let mut stack: Vec<Node> = self.scenes().flat_map(|scene| scene.nodes()).collect();
+        while let Some(node) = stack.pop() {
+            if reachable.insert(node.index()) {
+                stack.extend(node.children());
+            }
+        }
+        for scene in self.scenes() {
+            writeln!(out, "{}", label("Scene", scene.index(), name!(scene)))?;
+            let count = scene.nodes().len();
+            for (i, node) in scene.nodes().enumerate() {
+                write_node(out, node, "", i + 1 == count, &mut visited)?;
+            }
+        }
+
+        let children: BTreeSet<usize> = self
+            .nodes()
+            .flat_map(|node| node.children())
+            .map(|node| node.index())
+            .collect();
+        let detached: Vec<Node> = self
+            .nodes()
+            .filter(|node| !reachable.contains(&node.index()))
+            .filter(|node| !children.contains(&node.index()))
+            .collect();
+        if !detached.is_empty() {
+            writeln!(out, "Nodes in no scene")?;
+            let count = detached.len();
+            for (i, node) in detached.into_iter().enumerate() {
+                write_node(out, node, "", i + 1 == count, &mut visited)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a [Graphviz] graph of the references between the objects of
+    /// the document, for debugging.
+    ///
+    /// The graph contains the scenes, nodes, meshes, materials, textures,
+    /// images, samplers, skins, cameras, animations, accessors, buffer views
+    /// and buffers, with an edge from every object to each object it
+    /// references. Render it with, for example, `dot -Tsvg`.
+    ///
+    /// [Graphviz]: https://graphviz.org/
+    pub fn to_dot(&self) -> String {
+        let mut graph = Graph::default();
+        let id = |kind: &str, index: usize| format!("{}{}", kind, index);
+
+        for scene in self.scenes() {
+            let from = id("scene", scene.index());
+            graph.vertex(from.clone(), "Scene", scene.index(), name!(scene), "house");
+            for node in scene.nodes() {
+                graph.edge(from.clone(), id("node", node.index()), "");
+            }
+        }
+        for node in self.nodes() {
+            let from = id("node", node.index());
+            graph.vertex(from.clone(), "Node", node.index(), name!(node), "ellipse");
+            for child in node.children() {
+                graph.edge(from.clone(), id("node", child.index()), "");
+            }
+            if let Some(mesh) = node.mesh() {
+                graph.edge(from.clone(), id("mesh", mesh.index()), "");
+            }
+            if let Some(skin) = node.skin() {
+                graph.edge(from.clone(), id("skin", skin.index()), "");
+            }
+            if let Some(camera) = node.camera() {
+                graph.edge(from.clone(), id("camera", camera.index()), "");
+            }
+        }
+        for mesh in self.meshes() {
+            let from = id("mesh", mesh.index());
+            graph.vertex(from.clone(), "Mesh", mesh.index(), name!(mesh), "box");
+            for primitive in mesh.primitives() {
+                if let Some(index) = primitive.material().index() {
+                    graph.edge(from.clone(), id("material", index), "");
+                }
+                for (_, accessor) in primitive.attributes() {
+                    graph.edge(from.clone(), id("accessor", accessor.index()), "");
+                }
+                if let Some(accessor) = primitive.indices() {
+                    graph.edge(from.clone(), id("accessor", accessor.index()), "indices");
+                }
+            }
+        }
+        for material in self.materials() {
+            let index = match material.index() {
+                Some(index) => index,
+                None => continue,
+            };
+            let from = id("material", index);
+            graph.vertex(from.clone(), "Material", index, name!(material), "box");
+            let pbr = material.pbr_metallic_roughness();
+            let textures: [(Option<texture::Texture>, &'static str); 5] = [
+                (pbr.base_color_texture().map(|t| t.texture()), "baseColor"),
+                (
+                    pbr.metallic_roughness_texture().map(|t| t.texture()),
+                    "metallicRoughness",
+                ),
+                (material.normal_texture().map(|t| t.texture()), "normal"),
+                (
+                    material.occlusion_texture().map(|t| t.texture()),
+                    "occlusion",
+                ),
+                (material.emissive_texture().map(|t| t.texture()), "emissive"),
+            ];
+            for (texture, slot) in textures {
+                if let Some(texture) = texture {
+                    graph.edge(from.clone(), id("texture", texture.index()), slot);
+                }
+            }
+        }
+        for texture in self.textures() {
+            let from = id("texture", texture.index());
+            graph.vertex(
+                from.clone(),
+                "Texture",
+                texture.index(),
+                name!(texture),
+                "box",
+            );
+            graph.edge(from.clone(), id("image", texture.source().index()), "");
+            if let Some(index) = texture.sampler().index() {
+                graph.edge(from.clone(), id("sampler", index), "");
+            }
+        }
+        for image in self.images() {
+            let from = id("image", image.index());
+            graph.vertex(from.clone(), "Image", image.index(), name!(image), "box");
+            if let crate::image::Source::View { view, .. } = image.source() {
+                graph.edge(from, id("view", view.index()), "");
+            }
+        }
+        for sampler in self.samplers() {
+            if let Some(index) = sampler.index() {
+                graph.vertex(
+                    id("sampler", index),
+                    "Sampler",
+                    index,
+                    name!(sampler),
+                    "box",
+                );
+            }
+        }
+        for skin in self.skins() {
+            let from = id("skin", skin.index());
+            graph.vertex(from.clone(), "Skin", skin.index(), name!(skin), "box");
+            for joint in skin.joints() {
+                graph.edge(from.clone(), id("node", joint.index()), "joint");
+            }
+            if let Some(skeleton) = skin.skeleton() {
+                graph.edge(from.clone(), id("node", skeleton.index()), "skeleton");
+            }
+            if let Some(accessor) = skin.inverse_bind_matrices() {
+                graph.edge(from.clone(), id("accessor", accessor.index()), "");
+            }
+        }
+        for camera in self.cameras() {
+            let index = camera.index();
+            graph.vertex(id("camera", index), "Camera", index, name!(camera), "box");
+        }
+        for animation in self.animations() {
+            let from = id("animation", animation.index());
+            let index = animation.index();
+            graph.vertex(from.clone(), "Animation", index, name!(animation), "box");
+            for channel in animation.channels() {
+                let node = channel.target().node();
+                graph.edge(from.clone(), id("node", node.index()), "target");
+                let sampler = channel.sampler();
+                graph.edge(from.clone(), id("accessor", sampler.input().index()), "");
+                graph.edge(from.clone(), id("accessor", sampler.output().index()), "");
+            }
+        }
+        for accessor in self.accessors() {
+            let from = id("accessor", accessor.index());
+            let index = accessor.index();
+            graph.vertex(from.clone(), "Accessor", index, name!(accessor), "box");
+            if let Some(view) = accessor.view() {
+                graph.edge(from.clone(), id("view", view.index()), "");
+            }
+            if let Some(sparse) = accessor.sparse() {
+                graph.edge(
+                    from.clone(),
+                    id("view", sparse.indices().view().index()),
+                    "",
+                );
+                graph.edge(from.clone(), id("view", sparse.values().view().index()), "");
+            }
+        }
+        for view in self.views() {
+            let from = id("view", view.index());
+            graph.vertex(from.clone(), "View", view.index(), name!(view), "box");
+            graph.edge(from, id("buffer", view.buffer().index()), "");
+        }
+        for buffer in self.buffers() {
+            let index = buffer.index();
+            graph.vertex(id("buffer", index), "Buffer", index, name!(buffer), "box");
+        }
+
+        let mut out = String::from("digraph gltf {\n");
+        for vertex in &graph.vertices {
+            out.push_str("    ");
+            out.push_str(vertex);
+            out.push('\n');
+        }
+        for (from, to, label) in &graph.edges {
+            if label.is_empty() {
+                out.push_str(&format!("    {} -> {};\n", from, to));
+            } else {
+                out.push_str(&format!("    {} -> {} [label=\"{}\"];\n", from, to, label));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
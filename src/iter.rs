@@ -1,4 +1,5 @@
-use std::{iter, slice};
+use crate::prelude::*;
+use core::{iter, slice};
 
 use crate::accessor::Accessor;
 use crate::animation::Animation;
@@ -80,6 +81,19 @@ pub struct Images<'a> {
     pub(crate) document: &'a Document,
 }
 
+/// An `Iterator` that visits every image based light in a glTF asset.
+#[cfg(feature = "EXT_lights_image_based")]
+#[derive(Clone, Debug)]
+pub struct ImageBasedLights<'a> {
+    /// Internal image based light iterator.
+    pub(crate) iter: iter::Enumerate<
+        slice::Iter<'a, json::extensions::scene::ext_lights_image_based::ImageBasedLight>,
+    >,
+
+    /// The internal root glTF object.
+    pub(crate) document: &'a Document,
+}
+
 /// An `Iterator` that visits every light in a glTF asset.
 #[cfg(feature = "KHR_lights_punctual")]
 #[derive(Clone, Debug)]
@@ -188,10 +202,10 @@ impl<'a> Iterator for Accessors<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Accessor::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -215,10 +229,10 @@ impl<'a> Iterator for Animations<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Animation::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -242,10 +256,10 @@ impl<'a> Iterator for Buffers<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Buffer::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -309,10 +323,10 @@ impl<'a> Iterator for Views<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| View::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -336,10 +350,10 @@ impl<'a> Iterator for Cameras<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Camera::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -363,10 +377,10 @@ impl<'a> Iterator for Images<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Image::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -376,6 +390,38 @@ impl<'a> Iterator for Images<'a> {
     }
 }
 
+#[cfg(feature = "EXT_lights_image_based")]
+impl<'a> ExactSizeIterator for ImageBasedLights<'a> {}
+
+#[cfg(feature = "EXT_lights_image_based")]
+impl<'a> Iterator for ImageBasedLights<'a> {
+    type Item = crate::ext_lights_image_based::ImageBasedLight<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let document = self.document;
+        self.iter.next().map(|(index, json)| {
+            crate::ext_lights_image_based::ImageBasedLight::new(document, index, json)
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        let document = self.document;
+        self.iter.next_back().map(|(index, json)| {
+            crate::ext_lights_image_based::ImageBasedLight::new(document, index, json)
+        })
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let document = self.document;
+        self.iter.nth(n).map(|(index, json)| {
+            crate::ext_lights_image_based::ImageBasedLight::new(document, index, json)
+        })
+    }
+}
+
 #[cfg(feature = "KHR_lights_punctual")]
 impl<'a> ExactSizeIterator for Lights<'a> {}
 
@@ -393,10 +439,10 @@ impl<'a> Iterator for Lights<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| crate::khr_lights_punctual::Light::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -423,10 +469,10 @@ impl<'a> Iterator for Variants<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| crate::khr_materials_variants::Variant::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -450,10 +496,10 @@ impl<'a> Iterator for Materials<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Material::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -477,10 +523,10 @@ impl<'a> Iterator for Meshes<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Mesh::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -504,10 +550,10 @@ impl<'a> Iterator for Nodes<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Node::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -531,10 +577,10 @@ impl<'a> Iterator for Samplers<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Sampler::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -558,10 +604,10 @@ impl<'a> Iterator for Scenes<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Scene::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -585,10 +631,10 @@ impl<'a> Iterator for Skins<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Skin::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -612,10 +658,10 @@ impl<'a> Iterator for Textures<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let document = self.document;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Texture::new(document, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
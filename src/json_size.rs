@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use crate::Document;
+use core::cmp::Reverse;
+
+use json::Value;
+use serde_json::{json, Map};
+
+/// The share of one top-level property in the size of the glTF JSON.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PropertySize {
+    /// The name of the property, for example `"accessors"`.
+    pub property: String,
+
+    /// The number of objects if the property is an array, otherwise 1.
+    pub count: usize,
+
+    /// The serialized size of the property, including its key.
+    pub bytes: usize,
+
+    /// The serialized size of the `name` members of the objects.
+    pub names: usize,
+
+    /// The serialized size of the `extras` members of the objects.
+    pub extras: usize,
+
+    /// The serialized size of the `extensions` members of the objects.
+    pub extensions: usize,
+
+    /// The index and serialized size of the largest object, if the property
+    /// is a non-empty array.
+    pub largest: Option<(usize, usize)>,
+}
+
+impl PropertySize {
+    /// Returns the property size as a JSON object.
+    pub fn to_value(&self) -> Value {
+        json!({
+            "property": self.property,
+            "count": self.count,
+            "bytes": self.bytes,
+            "names": self.names,
+            "extras": self.extras,
+            "extensions": self.extensions,
+            "largest": self.largest.map(|(index, bytes)| json!({
+                "index": index,
+                "bytes": bytes,
+            })),
+        })
+    }
+}
+
+/// The size of the glTF JSON, broken down by top-level property.
+///
+/// Created by [`Document::json_size`].
+///
+/// [`Document::json_size`]: ../struct.Document.html#method.json_size
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct JsonSize {
+    /// The size of the compact JSON.
+    pub total: usize,
+
+    /// The top-level properties, largest first.
+    pub properties: Vec<PropertySize>,
+}
+
+impl JsonSize {
+    /// Returns the breakdown as a JSON object of the form:
+    ///
+    /// ```json
+    /// {
+    ///   "total": 1234,
+    ///   "properties": [
+    ///     {
+    ///       "property": "accessors",
+    ///       "count": 12,
+    ///       "bytes": 987,
+    ///       "names": 120,
+    ///       "extras": 0,
+    ///       "extensions": 0,
+    ///       "largest": { "index": 3, "bytes": 98 }
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    pub fn to_value(&self) -> Value {
+        let properties: Vec<Value> = self.properties.iter().map(|p| p.to_value()).collect();
+        json!({
+            "total": self.total,
+            "properties": properties,
+        })
+    }
+}
+
+/// Returns the size of `value` serialized as compact JSON.
+fn serialized_len(value: &Value) -> usize {
+    // Serializing a `Value` cannot fail.
+    json::serialize::to_vec(value).unwrap().len()
+}
+
+/// Returns the size of the object member `key: value`.
+fn member_len(key: &str, value: &Value) -> usize {
+    serialized_len(&Value::from(key)) + 1 + serialized_len(value)
+}
+
+/// Measures the top-level property `key` of the JSON.
+fn measure(key: &str, value: &Value) -> PropertySize {
+    let mut size = PropertySize {
+        property: key.to_owned(),
+        count: 1,
+        bytes: member_len(key, value),
+        ..PropertySize::default()
+    };
+    let objects = match value {
+        Value::Array(objects) => {
+            size.count = objects.len();
+            objects.as_slice()
+        }
+        _ => return size,
+    };
+    for (index, object) in objects.iter().enumerate() {
+        let bytes = serialized_len(object);
+        if size.largest.map_or(true, |(_, largest)| bytes > largest) {
+            size.largest = Some((index, bytes));
+        }
+        let object: &Map<String, Value> = match object.as_object() {
+            Some(object) => object,
+            None => continue,
+        };
+        let member = |key: &str| object.get(key).map_or(0, |value| member_len(key, value));
+        size.names += member("name");
+        size.extras += member("extras");
+        size.extensions += member("extensions");
+    }
+    size
+}
+
+impl Document {
+    /// Breaks the size of the glTF JSON down by top-level property, for
+    /// finding what dominates metadata-heavy assets.
+    ///
+    /// Sizes are measured on the compact serialization of the document, as
+    /// written into binary glTF, and exclude separating commas. Names and
+    /// extras are only counted if the `names` and `extras` features are
+    /// enabled, since they are discarded otherwise.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let gltf = gltf::Gltf::from_slice(br#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "nodes": [{ "name": "A long and descriptive node name" }, {}]
+    /// }"#)?;
+    /// let size = gltf.json_size();
+    /// let nodes = &size.properties[0];
+    /// assert_eq!(nodes.property, "nodes");
+    /// assert_eq!(nodes.count, 2);
+    /// assert_eq!(nodes.largest.map(|(index, _)| index), Some(0));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn json_size(&self) -> JsonSize {
+        // Serializing the root cannot fail.
+        let root = json::serialize::to_value(&self.0).unwrap();
+        let mut properties: Vec<PropertySize> = match &root {
+            Value::Object(members) => members
+                .iter()
+                .map(|(key, value)| measure(key, value))
+                .collect(),
+            _ => Vec::new(),
+        };
+        // The sort is stable, so properties of equal size keep their order.
+        properties.sort_by_key(|property| Reverse(property.bytes));
+        JsonSize {
+            total: serialized_len(&root),
+            properties,
+        }
+    }
+}
@@ -1,7 +1,62 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
 use crate::Document;
+use core::f32::consts::PI;
 use gltf_json::Extras;
 
+/// The luminous efficacy in lumens per watt used to convert between
+/// photometric and radiometric units.
+///
+/// This is the maximum efficacy of monochromatic light at 555 nm, as used by
+/// common content creation tools.
+pub const LUMENS_PER_WATT: f32 = 683.0;
+
+/// Converts the luminous intensity of a point or spot light in candela to
+/// its radiant power in watts, assuming uniform emission over the sphere.
+pub fn candela_to_watts(candela: f32) -> f32 {
+    candela * 4.0 * PI / LUMENS_PER_WATT
+}
+
+/// Converts the radiant power of a point or spot light in watts to its
+/// luminous intensity in candela, assuming uniform emission over the sphere.
+pub fn watts_to_candela(watts: f32) -> f32 {
+    watts * LUMENS_PER_WATT / (4.0 * PI)
+}
+
+/// Converts the illuminance of a directional light in lux to its irradiance
+/// in watts per square metre.
+pub fn lux_to_watts_per_square_metre(lux: f32) -> f32 {
+    lux / LUMENS_PER_WATT
+}
+
+/// Converts the irradiance of a directional light in watts per square metre
+/// to its illuminance in lux.
+pub fn watts_per_square_metre_to_lux(watts_per_square_metre: f32) -> f32 {
+    watts_per_square_metre * LUMENS_PER_WATT
+}
+
+/// Returns the attenuation of a point or spot light at `distance` from the
+/// light as recommended by `KHR_lights_punctual`.
+///
+/// This is the inverse square law, smoothly windowed to reach zero at
+/// `range` when provided.
+///
+/// ```
+/// use gltf::khr_lights_punctual::range_attenuation;
+///
+/// assert_eq!(range_attenuation(2.0, None), 0.25);
+/// assert_eq!(range_attenuation(2.0, Some(2.0)), 0.0);
+/// ```
+pub fn range_attenuation(distance: f32, range: Option<f32>) -> f32 {
+    let window = match range {
+        Some(range) if range > 0.0 => (1.0 - (distance / range).powi(4)).clamp(0.0, 1.0),
+        _ => 1.0,
+    };
+    window / (distance * distance)
+}
+
 /// A light in the scene.
+#[derive(Clone, Debug)]
 pub struct Light<'a> {
     /// The parent `Document` struct.
     #[allow(dead_code)]
@@ -61,24 +116,84 @@ impl<'a> Light<'a> {
         self.json.range
     }
 
+    /// Returns the intensity in watts for point and spot lights and in watts
+    /// per square metre for directional lights.
+    ///
+    /// See [`candela_to_watts`] and [`lux_to_watts_per_square_metre`].
+    ///
+    /// [`candela_to_watts`]: fn.candela_to_watts.html
+    /// [`lux_to_watts_per_square_metre`]: fn.lux_to_watts_per_square_metre.html
+    pub fn watts(&self) -> f32 {
+        match self.kind() {
+            Kind::Directional => lux_to_watts_per_square_metre(self.intensity()),
+            Kind::Point | Kind::Spot { .. } => candela_to_watts(self.intensity()),
+        }
+    }
+
+    /// Returns the intensity as a unitless multiplier, for engines that do not
+    /// use photometric units.
+    ///
+    /// The intensity of point and spot lights is divided by `4π`, while the
+    /// illuminance of directional lights is returned unchanged.
+    pub fn unitless_intensity(&self) -> f32 {
+        match self.kind() {
+            Kind::Directional => self.intensity(),
+            Kind::Point | Kind::Spot { .. } => self.intensity() / (4.0 * PI),
+        }
+    }
+
+    /// Returns the attenuation of the light at `distance` from its position.
+    ///
+    /// Directional lights are never attenuated. See [`range_attenuation`].
+    ///
+    /// [`range_attenuation`]: fn.range_attenuation.html
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        match self.kind() {
+            Kind::Directional => 1.0,
+            Kind::Point | Kind::Spot { .. } => range_attenuation(distance, self.range()),
+        }
+    }
+
+    /// Returns the distance beyond which the attenuated intensity of a point
+    /// or spot light falls below `threshold`, for example to size light
+    /// culling volumes.
+    ///
+    /// The result never exceeds the range of the light. Directional lights
+    /// have an infinite cutoff distance.
+    pub fn cutoff_distance(&self, threshold: f32) -> f32 {
+        if let Kind::Directional = self.kind() {
+            return f32::INFINITY;
+        }
+        let distance = (self.intensity() / threshold).sqrt();
+        match self.range() {
+            Some(range) if range > 0.0 => distance.min(range),
+            _ => distance,
+        }
+    }
+
     /// Specifies the light subcategory.
     pub fn kind(&self) -> Kind {
         use json::extensions::scene::khr_lights_punctual::Type;
         match self.json.type_.unwrap() {
             Type::Directional => Kind::Directional,
             Type::Point => Kind::Point,
-            Type::Spot => {
-                let args = self.json.spot.as_ref().unwrap();
-                Kind::Spot {
+            Type::Spot => match self.json.spot.as_ref() {
+                Some(args) => Kind::Spot {
                     inner_cone_angle: args.inner_cone_angle,
                     outer_cone_angle: args.outer_cone_angle,
-                }
-            }
+                },
+                // The default cone angles as defined by the extension.
+                None => Kind::Spot {
+                    inner_cone_angle: 0.0,
+                    outer_cone_angle: core::f32::consts::FRAC_PI_4,
+                },
+            },
         }
     }
 }
 
 /// Light subcategory.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Kind {
     /// Directional lights are light sources that act as though they are infinitely far away
     /// and emit light in the direction of the local -z axis. This light type inherits the
@@ -1,13 +1,13 @@
 use crate::{Document, Material};
 
 /// A variant.
+#[derive(Clone, Debug)]
 pub struct Variant<'a> {
     /// The parent `Document` struct.
     #[allow(dead_code)]
     document: &'a Document,
 
     /// The corresponding JSON index.
-    #[allow(dead_code)]
     index: usize,
 
     /// The corresponding JSON struct.
@@ -28,6 +28,11 @@ impl<'a> Variant<'a> {
         }
     }
 
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// Name of the variant.
     pub fn name(&self) -> &'a str {
         &self.json.name
@@ -35,6 +40,7 @@ impl<'a> Variant<'a> {
 }
 
 /// A mapping.
+#[derive(Clone, Debug)]
 pub struct Mapping<'a> {
     /// The parent `Document` struct.
     document: &'a Document,
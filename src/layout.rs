@@ -0,0 +1,388 @@
+use crate::prelude::*;
+use crate::reindex::VIEW_EXTENSIONS;
+use crate::Document;
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+
+/// A consequence of the layout of a buffer view for uploading it to the GPU.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Implication {
+    /// The view holds both index and vertex data.
+    ///
+    /// WebGL cannot bind one buffer to both targets, so loaders must copy the
+    /// view into separate GPU buffers. See [`split_shared_views`].
+    ///
+    /// [`split_shared_views`]: fn.split_shared_views.html
+    MixedTargets,
+
+    /// The declared target contradicts how the accessors of the view are
+    /// used, so loaders relying on the target bind the data incorrectly.
+    TargetMismatch,
+
+    /// The view holds only vertex or only index data but declares no target,
+    /// so loaders must infer the target from the accessors.
+    MissingTarget,
+
+    /// The view can be merged with an adjacent compatible view, saving a GPU
+    /// buffer and an upload. See [`merge_views`].
+    ///
+    /// [`merge_views`]: fn.merge_views.html
+    Fragmented,
+}
+
+/// How a buffer view is used, and what that implies for GPU uploads.
+///
+/// Created by [`Document::view_layout`].
+///
+/// [`Document::view_layout`]: ../struct.Document.html#method.view_layout
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ViewReport {
+    /// The index of the buffer view.
+    pub view: usize,
+
+    /// The accessors of the view used as vertex attributes or morph targets.
+    pub vertex_accessors: Vec<usize>,
+
+    /// The accessors of the view used as primitive indices.
+    pub index_accessors: Vec<usize>,
+
+    /// The accessors of the view used otherwise, including accessors with
+    /// sparse data in the view.
+    pub other_accessors: Vec<usize>,
+
+    /// The images stored in the view.
+    pub images: Vec<usize>,
+
+    /// The upload implications of the view, empty for well-formed views.
+    pub implications: Vec<Implication>,
+}
+
+/// Collects the accessors and images of every buffer view of `root`.
+fn reports(root: &json::Root) -> Vec<ViewReport> {
+    let mut vertex = vec![false; root.accessors.len()];
+    let mut index = vec![false; root.accessors.len()];
+    for primitive in root.meshes.iter().flat_map(|mesh| &mesh.primitives) {
+        let targets = primitive
+            .targets
+            .iter()
+            .flatten()
+            .flat_map(|target| [target.positions, target.normals, target.tangents]);
+        for accessor in primitive
+            .attributes
+            .values()
+            .copied()
+            .chain(targets.flatten())
+        {
+            if let Some(vertex) = vertex.get_mut(accessor.value()) {
+                *vertex = true;
+            }
+        }
+        if let Some(index) = primitive.indices.and_then(|i| index.get_mut(i.value())) {
+            *index = true;
+        }
+    }
+
+    let mut reports: Vec<ViewReport> = (0..root.buffer_views.len())
+        .map(|view| ViewReport {
+            view,
+            ..ViewReport::default()
+        })
+        .collect();
+    for (i, accessor) in root.accessors.iter().enumerate() {
+        if let Some(report) = accessor
+            .buffer_view
+            .and_then(|v| reports.get_mut(v.value()))
+        {
+            if vertex[i] {
+                report.vertex_accessors.push(i);
+            }
+            if index[i] {
+                report.index_accessors.push(i);
+            }
+            if !vertex[i] && !index[i] {
+                report.other_accessors.push(i);
+            }
+        }
+        let sparse = accessor
+            .sparse
+            .iter()
+            .flat_map(|sparse| [sparse.indices.buffer_view, sparse.values.buffer_view]);
+        for view in sparse {
+            if let Some(report) = reports.get_mut(view.value()) {
+                if !report.other_accessors.contains(&i) {
+                    report.other_accessors.push(i);
+                }
+            }
+        }
+    }
+    for (i, image) in root.images.iter().enumerate() {
+        if let Some(report) = image.buffer_view.and_then(|v| reports.get_mut(v.value())) {
+            report.images.push(i);
+        }
+    }
+    reports
+}
+
+/// Returns the size of the elements of `accessor`.
+fn element_size(accessor: &json::Accessor) -> usize {
+    match (accessor.component_type, accessor.type_) {
+        (Valid(component_type), Valid(type_)) => component_type.0.size() * type_.multiplicity(),
+        _ => 0,
+    }
+}
+
+/// Returns the groups of adjacent buffer views of `root` which can be merged
+/// into their first view.
+///
+/// Views are compatible if they hold accessors only, are used in the same
+/// way, share their buffer, target and stride, and are separated by padding
+/// at most. Vertex data must have an explicit stride, since vertex attribute
+/// accessors sharing a view require one.
+fn merge_groups(root: &json::Root, reports: &[ViewReport]) -> Vec<Vec<usize>> {
+    #[derive(PartialEq)]
+    enum Kind {
+        Vertex,
+        Index,
+        Other,
+    }
+    let kind = |report: &ViewReport| {
+        let view = &root.buffer_views[report.view];
+        let kinds = [
+            !report.vertex_accessors.is_empty(),
+            !report.index_accessors.is_empty(),
+            !report.other_accessors.is_empty(),
+        ];
+        match kinds {
+            _ if !report.images.is_empty() => None,
+            [true, false, false] if view.byte_stride.is_some() => Some(Kind::Vertex),
+            [false, true, false] => Some(Kind::Index),
+            [false, false, true] if view.byte_stride.is_none() => Some(Kind::Other),
+            _ => None,
+        }
+    };
+
+    let mut order: Vec<usize> = (0..root.buffer_views.len())
+        .filter(|&view| kind(&reports[view]).is_some())
+        .collect();
+    order.sort_by_key(|&view| {
+        let view = &root.buffer_views[view];
+        (view.buffer.value(), view.byte_offset.unwrap_or(0))
+    });
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut end = 0;
+    for view in order {
+        let json = &root.buffer_views[view];
+        let offset = json.byte_offset.unwrap_or(0) as usize;
+        let compatible = groups.last().map_or(false, |group| {
+            let first = &root.buffer_views[group[0]];
+            let first_offset = first.byte_offset.unwrap_or(0) as usize;
+            let aligned = reports[view]
+                .vertex_accessors
+                .iter()
+                .chain(&reports[view].index_accessors)
+                .chain(&reports[view].other_accessors)
+                .all(|&accessor| {
+                    let accessor = &root.accessors[accessor];
+                    let component = match accessor.component_type {
+                        Valid(component_type) => component_type.0.size(),
+                        _ => 1,
+                    };
+                    (offset - first_offset) % component == 0
+                });
+            // Vertex attributes must be aligned to four bytes.
+            let vertex = kind(&reports[view]) == Some(Kind::Vertex);
+            let aligned = aligned && (!vertex || (offset - first_offset) % 4 == 0);
+            first.buffer == json.buffer
+                && first.target == json.target
+                && first.byte_stride == json.byte_stride
+                && kind(&reports[group[0]]) == kind(&reports[view])
+                && offset >= end
+                && offset - end < 4
+                && aligned
+        });
+        if compatible {
+            groups.last_mut().unwrap().push(view);
+        } else {
+            groups.push(vec![view]);
+        }
+        end = if compatible {
+            end.max(offset + json.byte_length as usize)
+        } else {
+            offset + json.byte_length as usize
+        };
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+impl Document {
+    /// Reports how every buffer view is used, and what its layout implies
+    /// for uploading it to the GPU.
+    ///
+    /// Returns one report per buffer view, in order.
+    pub fn view_layout(&self) -> Vec<ViewReport> {
+        let root = &self.0;
+        let mut reports = reports(root);
+        for group in merge_groups(root, &reports) {
+            for view in group {
+                reports[view].implications.push(Implication::Fragmented);
+            }
+        }
+        for report in &mut reports {
+            let vertex = !report.vertex_accessors.is_empty();
+            let index = !report.index_accessors.is_empty();
+            let target = root.buffer_views[report.view].target;
+            if vertex && index {
+                report.implications.insert(0, Implication::MixedTargets);
+            }
+            let expected = match (vertex, index) {
+                (true, false) => Some(Target::ArrayBuffer),
+                (false, true) => Some(Target::ElementArrayBuffer),
+                _ => None,
+            };
+            match (target, expected) {
+                (Some(Valid(target)), Some(expected)) if target != expected => {
+                    report.implications.push(Implication::TargetMismatch)
+                }
+                (Some(Valid(_)), None) if vertex && index => {
+                    report.implications.push(Implication::TargetMismatch)
+                }
+                (None, Some(_)) => report.implications.push(Implication::MissingTarget),
+                _ => {}
+            }
+        }
+        reports
+    }
+}
+
+/// Splits the index data out of buffer views holding both index and vertex
+/// data, and returns the number of views split.
+///
+/// The index accessors of such a view are moved to a new view covering just
+/// their byte range, with the `ELEMENT_ARRAY_BUFFER` target. No data is
+/// copied, since the new view refers to the same bytes. The original view
+/// retains the remaining accessors, and its target is set to `ARRAY_BUFFER`
+/// if it holds vertex data only.
+pub fn split_shared_views(root: &mut json::Root) -> usize {
+    let mut split = 0;
+    for report in reports(root) {
+        if report.vertex_accessors.is_empty() || report.index_accessors.is_empty() {
+            continue;
+        }
+        let range = |accessor: &json::Accessor| {
+            let start = accessor.byte_offset.unwrap_or(0) as usize;
+            (
+                start,
+                start + accessor.count as usize * element_size(accessor),
+            )
+        };
+        let (start, end) = report
+            .index_accessors
+            .iter()
+            .map(|&accessor| range(&root.accessors[accessor]))
+            .fold((usize::MAX, 0), |(start, end), (s, e)| {
+                (start.min(s), end.max(e))
+            });
+        let view = &mut root.buffer_views[report.view];
+        view.target = if report.other_accessors.is_empty() {
+            Some(Valid(Target::ArrayBuffer))
+        } else {
+            None
+        };
+        let mut index_view = view.clone();
+        index_view.byte_offset = Some(view.byte_offset.unwrap_or(0) + start as u32);
+        index_view.byte_length = (end - start) as u32;
+        index_view.byte_stride = None;
+        index_view.target = Some(Valid(Target::ElementArrayBuffer));
+        root.buffer_views.push(index_view);
+        let index_view = json::Index::new(root.buffer_views.len() as u32 - 1);
+        for &accessor in &report.index_accessors {
+            let accessor = &mut root.accessors[accessor];
+            accessor.buffer_view = Some(index_view);
+            accessor.byte_offset = Some(accessor.byte_offset.unwrap_or(0) - start as u32);
+        }
+        split += 1;
+    }
+    split
+}
+
+/// Merges adjacent buffer views which are used in the same way, and returns
+/// the number of views removed.
+///
+/// These are the views reported as [`Implication::Fragmented`]. Each group
+/// of views is replaced by its first view, extended to cover the others,
+/// and their accessors are rebased onto it. Views holding images and views
+/// not referenced by any accessor are never merged. Nothing is merged if the
+/// asset uses an extension that references buffer views, such as
+/// `KHR_draco_mesh_compression`, since the views are renumbered.
+///
+/// [`Implication::Fragmented`]: enum.Implication.html#variant.Fragmented
+pub fn merge_views(root: &mut json::Root) -> usize {
+    if root
+        .extensions_used
+        .iter()
+        .any(|extension| VIEW_EXTENSIONS.contains(&extension.as_str()))
+    {
+        return 0;
+    }
+    let reports = reports(root);
+    let groups = merge_groups(root, &reports);
+
+    // The view and byte offset each original view is moved to.
+    let mut moves: Vec<(usize, u32)> = (0..root.buffer_views.len()).map(|v| (v, 0)).collect();
+    let mut removed = vec![false; root.buffer_views.len()];
+    for group in &groups {
+        let first = group[0];
+        let first_offset = root.buffer_views[first].byte_offset.unwrap_or(0);
+        let mut end = first_offset + root.buffer_views[first].byte_length;
+        for &view in &group[1..] {
+            let json = &root.buffer_views[view];
+            let offset = json.byte_offset.unwrap_or(0);
+            end = end.max(offset + json.byte_length);
+            moves[view] = (first, offset - first_offset);
+            removed[view] = true;
+        }
+        root.buffer_views[first].byte_length = end - first_offset;
+    }
+
+    // The new indices of the retained views.
+    let mut indices = vec![0; root.buffer_views.len()];
+    let mut next = 0;
+    for (index, removed) in indices.iter_mut().zip(&removed) {
+        *index = next;
+        if !removed {
+            next += 1;
+        }
+    }
+    let relocate = |view: &mut json::Index<json::buffer::View>| -> u32 {
+        let (target, offset) = moves
+            .get(view.value())
+            .copied()
+            .unwrap_or((view.value(), 0));
+        *view = json::Index::new(indices.get(target).copied().unwrap_or(target as u32));
+        offset
+    };
+    for accessor in &mut root.accessors {
+        if let Some(view) = accessor.buffer_view.as_mut() {
+            let offset = relocate(view);
+            if offset != 0 {
+                accessor.byte_offset = Some(accessor.byte_offset.unwrap_or(0) + offset);
+            }
+        }
+        if let Some(sparse) = accessor.sparse.as_mut() {
+            sparse.indices.byte_offset += relocate(&mut sparse.indices.buffer_view);
+            sparse.values.byte_offset += relocate(&mut sparse.values.buffer_view);
+        }
+    }
+    for view in root
+        .images
+        .iter_mut()
+        .filter_map(|image| image.buffer_view.as_mut())
+    {
+        relocate(view);
+    }
+    let mut removed = removed.into_iter();
+    root.buffer_views.retain(|_| !removed.next().unwrap());
+    groups.iter().map(|group| group.len() - 1).sum()
+}
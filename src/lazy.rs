@@ -0,0 +1,102 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNINITIALIZED: u8 = 0;
+const INITIALIZING: u8 = 1;
+const INITIALIZED: u8 = 2;
+
+/// A value that is initialized on first access.
+///
+/// This is a minimal replacement for `lazy_static` that works without `std`.
+/// Threads that access the value while it is being initialized spin until the
+/// initialization completes.
+///
+/// `core::cell::OnceCell` is not `Sync` and requires Rust 1.70, so the value
+/// is guarded by `state` instead, which upholds these invariants:
+///
+/// * `value` is written at most once, by the only thread that moved `state`
+///   from `UNINITIALIZED` to `INITIALIZING`.
+/// * `state` becomes `INITIALIZED` with `Release` ordering after the write,
+///   and is never changed afterwards.
+/// * `value` is only read after observing `INITIALIZED` with `Acquire`
+///   ordering, which makes the write visible to the reading thread.
+pub struct Lazy<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+    init: fn() -> T,
+}
+
+// SAFETY: by the invariants of `Lazy`, the value is never written while it is
+// shared, so sharing `&Lazy<T>` only shares `&T`, which requires `T: Sync`.
+// The value is created on whichever thread accesses it first and dropped on
+// the thread owning the `Lazy`, which requires `T: Send`.
+unsafe impl<T: Send + Sync> Sync for Lazy<T> {}
+
+/// Resets the state if the initializer panics, so the next access retries.
+struct Reset<'a>(&'a AtomicU8);
+
+impl<'a> Drop for Reset<'a> {
+    fn drop(&mut self) {
+        self.0.store(UNINITIALIZED, Ordering::Release);
+    }
+}
+
+impl<T> Lazy<T> {
+    /// Creates a value that is initialized by calling `init` on first access.
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            state: AtomicU8::new(UNINITIALIZED),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+            init,
+        }
+    }
+
+    fn get(&self) -> &T {
+        loop {
+            match self.state.compare_exchange_weak(
+                UNINITIALIZED,
+                INITIALIZING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let reset = Reset(&self.state);
+                    let value = (self.init)();
+                    // SAFETY: this thread moved `state` to `INITIALIZING`,
+                    // so no other thread reads or writes the value until
+                    // `state` is `INITIALIZED`. If `init` panics, `reset`
+                    // restores `UNINITIALIZED` without the value written.
+                    unsafe { (*self.value.get()).as_mut_ptr().write(value) };
+                    core::mem::forget(reset);
+                    self.state.store(INITIALIZED, Ordering::Release);
+                }
+                Err(INITIALIZED) => break,
+                Err(_) => core::hint::spin_loop(),
+            }
+        }
+        // SAFETY: the loop only exits after observing `INITIALIZED` with
+        // `Acquire` ordering, so the value has been written, the write is
+        // visible to this thread, and the value is never written again.
+        unsafe { &*(*self.value.get()).as_ptr() }
+    }
+}
+
+impl<T> Deref for Lazy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T> Drop for Lazy<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == INITIALIZED {
+            // SAFETY: `state` is `INITIALIZED`, so the value has been written,
+            // and `&mut self` guarantees that no references to it remain.
+            unsafe { self.value.get_mut().as_mut_ptr().drop_in_place() };
+        }
+    }
+}
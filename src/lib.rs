@@ -1,6 +1,7 @@
 #![deny(missing_docs)]
 #![allow(unknown_lints)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! glTF 2.0 loader
 //!
@@ -79,13 +80,13 @@
 //! [`Node`]: struct.Node.html
 //! [`Scene`]: struct.Scene.html
 
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
 #[cfg(test)]
 #[macro_use]
 extern crate approx;
 #[cfg(feature = "import")]
 extern crate image as image_crate;
-#[macro_use]
-extern crate lazy_static;
 
 /// Contains (de)serializable data structures that match the glTF JSON text.
 pub extern crate gltf_json as json;
@@ -96,15 +97,74 @@ pub mod accessor;
 /// Animations, their channels, targets, and samplers.
 pub mod animation;
 
+/// In-place appending to binary glTF files.
+#[cfg(all(feature = "std", feature = "utils"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "utils"))))]
+pub mod append;
+
+/// Texture atlas safety analysis.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod atlas;
+
 /// Primitives for working with binary glTF.
 pub mod binary;
 
+/// Blend shape naming schemes for facial animation.
+pub mod blend_shape;
+
 /// Buffers and buffer views.
 pub mod buffer;
 
 /// Cameras and their projections.
 pub mod camera;
 
+/// Turntable and orbit camera animations.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod camera_path;
+
+/// Summaries of assets for catalog indexing.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+pub mod catalog;
+
+/// Structural complexity metrics and limits.
+pub mod complexity;
+
+/// File format detection.
+mod detect;
+
+/// Compile-time asset embedding.
+mod embed;
+
+/// Serialization of glTF with pre-serialize hooks.
+pub mod export;
+
+/// Configurable filesystem fetcher.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+mod filesystem;
+
+/// Fixtures shared by the unit tests.
+#[cfg(all(test, feature = "std"))]
+mod fixture;
+
+/// Floating point functions for `no_std` builds.
+#[cfg(any(test, not(feature = "std")))]
+#[allow(dead_code)]
+mod float;
+
+/// Entry points for fuzzing the parsers.
+pub mod fuzz;
+
+/// For internal use.
+#[cfg(feature = "utils")]
+mod geometry;
+
+/// For internal use.
+mod hash;
+
 /// Images that may be used by textures.
 pub mod image;
 
@@ -113,9 +173,27 @@ pub mod image;
 #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
 mod import;
 
+/// The reference importer for assets without external references.
+#[cfg(feature = "import_embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
+mod import_embedded;
+
+/// Support for the `EXT_lights_image_based` extension.
+#[cfg(feature = "EXT_lights_image_based")]
+#[cfg_attr(docsrs, doc(cfg(feature = "EXT_lights_image_based")))]
+pub mod ext_lights_image_based;
+
+/// Scene graph outlines and Graphviz graphs for debugging.
+mod inspect;
+
 /// Iterators for walking the glTF node hierarchy.
 pub mod iter;
 
+/// Size breakdown of the glTF JSON.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod json_size;
+
 /// Support for the `KHR_lights_punctual` extension.
 #[cfg(feature = "KHR_lights_punctual")]
 #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
@@ -126,33 +204,135 @@ pub mod khr_lights_punctual;
 #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
 pub mod khr_materials_variants;
 
+/// Buffer view layout analysis and rewriting.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod layout;
+
+/// For internal use.
+mod lazy;
+
 /// Material properties of primitives.
 pub mod material;
 
+/// Asset dependency manifests.
+mod manifest;
+
 /// For internal use.
 mod math;
 
+/// Scripted and error-injecting fetchers for testing imports.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+pub mod mock;
+
+/// In-memory virtual file system fetcher.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+mod memory;
+
 /// Meshes and their primitives.
 pub mod mesh;
 
+/// Quantization analysis of vertex attributes.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod quantize;
+
+/// Hot-reload support.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+mod reload;
+
+/// Renaming of objects with unique names.
+#[cfg(feature = "names")]
+#[cfg_attr(docsrs, doc(cfg(feature = "names")))]
+pub mod rename;
+
+/// Compaction of primitive vertex ranges.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod reindex;
+
+/// Repair of mesh geometry.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod repair;
+
 /// The glTF node heirarchy.
 pub mod scene;
 
+/// Thread-safe shared handles to imported data.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+pub mod shared;
+
+/// Renderable visualization of skeletons.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod skeleton;
+
 /// Mesh skinning primitives.
 pub mod skin;
 
+/// Normalized digests of imports for golden tests.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+pub mod snapshot;
+
+/// Texture streaming priorities.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod streaming;
+
+/// Removal of names, extras and unused extension declarations.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod strip;
+
 /// Textures and their samplers.
 pub mod texture;
 
+/// Per-slot texture resolution budgets.
+#[cfg(all(feature = "import", feature = "utils"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "import", feature = "utils"))))]
+pub mod texture_budget;
+
+/// Mesh topology analysis.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod topology;
+
+/// Conversion of glTF 1.0 assets to glTF 2.0.
+#[cfg(feature = "upgrade")]
+#[cfg_attr(docsrs, doc(cfg(feature = "upgrade")))]
+pub mod upgrade;
+
+/// Generation of platform variants of assets.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod variants;
+
+/// Voxelization of scene geometry.
+#[cfg(feature = "voxelize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "voxelize")))]
+pub mod voxel;
+
 #[cfg(feature = "extensions")]
 use json::Value;
 #[cfg(feature = "extensions")]
 use serde_json::Map;
 
+#[doc(hidden)]
+pub use self::lazy::Lazy as __Lazy;
+
 #[doc(inline)]
 pub use self::accessor::Accessor;
 #[doc(inline)]
 pub use self::animation::Animation;
+#[cfg(all(feature = "std", feature = "utils"))]
+#[doc(inline)]
+pub use self::append::GlbAppender;
 #[doc(inline)]
 pub use self::binary::Glb;
 #[doc(inline)]
@@ -160,29 +340,67 @@ pub use self::buffer::Buffer;
 #[doc(inline)]
 pub use self::camera::Camera;
 #[doc(inline)]
+pub use self::detect::{detect, FileKind};
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::filesystem::FilesystemFetcher;
+#[doc(inline)]
 pub use self::image::Image;
 #[cfg(feature = "import")]
 #[doc(inline)]
+pub use self::import::empty_fetcher;
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::filesystem_fetcher;
+#[cfg(feature = "import")]
+#[doc(inline)]
 pub use self::import::import;
 #[cfg(feature = "import")]
 #[doc(inline)]
 pub use self::import::import_buffers;
 #[cfg(feature = "import")]
 #[doc(inline)]
+pub use self::import::import_encoded_images;
+#[cfg(feature = "import")]
+#[doc(inline)]
 pub use self::import::import_images;
 #[cfg(feature = "import")]
 #[doc(inline)]
+pub use self::import::import_images_with_max_dimension;
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::import_reader;
+#[cfg(feature = "import")]
+#[doc(inline)]
 pub use self::import::import_slice;
 #[cfg(feature = "import")]
 #[doc(inline)]
-pub use self::import::filesystem_fetcher;
+pub use self::import::import_slice_with_progress;
 #[cfg(feature = "import")]
 #[doc(inline)]
-pub use self::import::empty_fetcher;
+pub use self::import::import_with_progress;
+#[cfg(feature = "mmap")]
+#[doc(inline)]
+pub use self::import::mmap_fetcher;
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::reimport_changed;
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::{Fetcher, Progress, ResourceKind, ResourceRequest};
+#[cfg(feature = "import_embedded")]
+#[doc(inline)]
+pub use self::import_embedded::import_embedded;
 #[doc(inline)]
 pub use self::material::Material;
+#[cfg(feature = "import")]
 #[doc(inline)]
-pub use self::mesh::{Attribute, Mesh, Primitive, Semantic};
+pub use self::memory::{import_tar, MemoryFetcher};
+#[doc(inline)]
+pub use self::mesh::{Attribute, AttributeFlags, Mesh, Primitive, Semantic};
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::reload::ReloadTracker;
 #[doc(inline)]
 pub use self::scene::{Node, Scene};
 #[doc(inline)]
@@ -190,9 +408,21 @@ pub use self::skin::Skin;
 #[doc(inline)]
 pub use self::texture::Texture;
 
+use crate::prelude::*;
+use core::{ops, result};
+#[cfg(feature = "std")]
 use std::path::Path;
-use std::{fs, io, ops, result};
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+/// The subset of the standard prelude provided by `alloc`.
+mod prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
 
+#[cfg(feature = "utils")]
 pub(crate) trait Normalize<T> {
     fn normalize(self) -> T;
 }
@@ -204,16 +434,21 @@ pub type Result<T> = result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
     /// Base 64 decoding error.
-    #[cfg(feature = "import")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    #[cfg(feature = "import_embedded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
     Base64(base64::DecodeError),
 
     /// GLB parsing error.
     Binary(binary::Error),
 
-    /// Buffer length does not match expected length.
+    /// The import was cancelled.
     #[cfg(feature = "import")]
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    Cancelled,
+
+    /// Buffer length does not match expected length.
+    #[cfg(feature = "import_embedded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
     BufferLength {
         /// The index of the offending buffer.
         buffer: usize,
@@ -229,6 +464,8 @@ pub enum Error {
     Deserialize(json::Error),
 
     /// Standard I/O error.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     Io(std::io::Error),
 
     /// Image decoding error.
@@ -237,13 +474,13 @@ pub enum Error {
     Image(image_crate::ImageError),
 
     /// The `BIN` chunk of binary glTF is referenced but does not exist.
-    #[cfg(feature = "import")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    #[cfg(feature = "import_embedded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
     MissingBlob,
 
     /// An external file is referenced in a slice only import without path
-    #[cfg(feature = "import")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    #[cfg(feature = "import_embedded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
     ExternalReferenceInSliceImport,
 
     /// Unsupported image encoding.
@@ -257,12 +494,23 @@ pub enum Error {
     UnsupportedImageFormat(image_crate::DynamicImage),
 
     /// Unsupported URI scheme.
-    #[cfg(feature = "import")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    #[cfg(feature = "import_embedded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import_embedded")))]
     UnsupportedScheme,
 
+    /// The asset is not glTF 2.0.
+    UnsupportedVersion {
+        /// The declared version, or `None` for legacy assets without one.
+        found: Option<String>,
+    },
+
     /// glTF validation error.
     Validation(Vec<(json::Path, json::validation::Error)>),
+
+    /// The JSON violates the glTF JSON schema.
+    #[cfg(feature = "schema-validate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "schema-validate")))]
+    Schema(Vec<json::schema::Violation>),
 }
 
 /// glTF JSON wrapper plus binary payload.
@@ -273,6 +521,26 @@ pub struct Gltf {
 
     /// The glTF binary payload in the case of binary glTF.
     pub blob: Option<Vec<u8>>,
+
+    /// The container format the glTF was loaded from.
+    pub container: FileKind,
+}
+
+/// glTF JSON wrapper plus binary payload borrowed from the input slice.
+///
+/// Unlike [`Gltf`], the BIN chunk of binary glTF is not copied.
+///
+/// [`Gltf`]: struct.Gltf.html
+#[derive(Clone, Debug)]
+pub struct GltfSlice<'a> {
+    /// The glTF JSON wrapper.
+    pub document: Document,
+
+    /// The glTF binary payload in the case of binary glTF.
+    pub blob: Option<&'a [u8]>,
+
+    /// The container format the glTF was loaded from.
+    pub container: FileKind,
 }
 
 /// glTF JSON wrapper.
@@ -281,6 +549,8 @@ pub struct Document(json::Root);
 
 impl Gltf {
     /// Convenience function that loads glTF from the file system.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn open<P>(path: P) -> Result<Self>
     where
         P: AsRef<Path>,
@@ -292,6 +562,8 @@ impl Gltf {
     }
 
     /// Loads glTF from a reader without performing validation checks.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn from_reader_without_validation<R>(mut reader: R) -> Result<Self>
     where
         R: io::Read + io::Seek,
@@ -299,21 +571,46 @@ impl Gltf {
         let mut magic = [0u8; 4];
         reader.read_exact(&mut magic)?;
         reader.seek(io::SeekFrom::Current(-4))?;
-        let (json, blob): (json::Root, Option<Vec<u8>>);
+        let (json, blob, container): (json::Root, Option<Vec<u8>>, FileKind);
         if magic.starts_with(b"glTF") {
             let mut glb = binary::Glb::from_reader(reader)?;
             // TODO: use `json::from_reader` instead of `json::from_slice`
-            json = json::deserialize::from_slice(&glb.json)?;
+            json = deserialize_root(&glb.json)?;
             blob = glb.bin.take().map(|x| x.into_owned());
+            container = FileKind::Glb {
+                version: glb.header.version,
+            };
         } else {
-            json = json::deserialize::from_reader(reader)?;
+            let start = reader.stream_position()?;
+            json = match json::deserialize::from_reader::<_, json::Root>(&mut reader) {
+                Ok(root) if is_version_2(&root.asset.version) => root,
+                Ok(root) => {
+                    return Err(Error::UnsupportedVersion {
+                        found: Some(root.asset.version),
+                    })
+                }
+                Err(error) => {
+                    // Only rejected JSON is buffered, to inspect its version.
+                    let mut data = Vec::new();
+                    reader.seek(io::SeekFrom::Start(start))?;
+                    reader.read_to_end(&mut data)?;
+                    return Err(unsupported_version(&data).unwrap_or(Error::Deserialize(error)));
+                }
+            };
             blob = None;
+            container = FileKind::GltfJson;
         };
         let document = Document::from_json_without_validation(json);
-        Ok(Gltf { document, blob })
+        Ok(Gltf {
+            document,
+            blob,
+            container,
+        })
     }
 
     /// Loads glTF from a reader.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn from_reader<R>(reader: R) -> Result<Self>
     where
         R: io::Read + io::Seek,
@@ -326,17 +623,25 @@ impl Gltf {
     /// Loads glTF from a slice of bytes without performing validation
     /// checks.
     pub fn from_slice_without_validation(slice: &[u8]) -> Result<Self> {
-        let (json, blob): (json::Root, Option<Vec<u8>>);
+        let (json, blob, container): (json::Root, Option<Vec<u8>>, FileKind);
         if slice.starts_with(b"glTF") {
             let mut glb = binary::Glb::from_slice(slice)?;
-            json = json::deserialize::from_slice(&glb.json)?;
+            json = deserialize_root(&glb.json)?;
             blob = glb.bin.take().map(|x| x.into_owned());
+            container = FileKind::Glb {
+                version: glb.header.version,
+            };
         } else {
-            json = json::deserialize::from_slice(slice)?;
+            json = deserialize_root(slice)?;
             blob = None;
+            container = FileKind::GltfJson;
         };
         let document = Document::from_json_without_validation(json);
-        Ok(Gltf { document, blob })
+        Ok(Gltf {
+            document,
+            blob,
+            container,
+        })
     }
 
     /// Loads glTF from a slice of bytes.
@@ -345,6 +650,67 @@ impl Gltf {
         gltf.document.validate()?;
         Ok(gltf)
     }
+
+    /// Loads glTF from a slice of bytes, validating the raw JSON against the
+    /// glTF JSON schema before performing the usual validation checks.
+    ///
+    /// Schema violations are reported with `Error::Schema`. This catches
+    /// problems that are otherwise silently accepted, such as out of range
+    /// material factors, at the cost of parsing the JSON twice.
+    #[cfg(feature = "schema-validate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "schema-validate")))]
+    pub fn from_slice_with_schema_validation(slice: &[u8]) -> Result<Self> {
+        let json = if slice.starts_with(b"glTF") {
+            binary::split_slice(slice)?.1
+        } else {
+            slice
+        };
+        let value = json::deserialize::from_slice(json)?;
+        let violations = json::schema::validate(&value);
+        if !violations.is_empty() {
+            return Err(unsupported_version(json).unwrap_or(Error::Schema(violations)));
+        }
+        Self::from_slice(slice)
+    }
+}
+
+/// Returns an `Error::UnsupportedVersion` if `slice` holds the JSON of an
+/// older or newer glTF version.
+///
+/// Legacy documents without a declared version are recognized by their use
+/// of dictionaries instead of arrays for top-level objects.
+fn unsupported_version(slice: &[u8]) -> Option<Error> {
+    let value: json::Value = json::deserialize::from_slice(slice).ok()?;
+    let found = match value.get("asset").and_then(|asset| asset.get("version")) {
+        Some(json::Value::String(version)) if !is_version_2(version) => Some(version.clone()),
+        Some(_) => return None,
+        None => {
+            let uses_dictionaries = ["meshes", "nodes", "accessors", "buffers"]
+                .iter()
+                .any(|key| value.get(key).map_or(false, json::Value::is_object));
+            if !uses_dictionaries {
+                return None;
+            }
+            None
+        }
+    };
+    Some(Error::UnsupportedVersion { found })
+}
+
+fn is_version_2(version: &str) -> bool {
+    version.split('.').next() == Some("2")
+}
+
+/// Deserializes glTF 2.0 JSON, reporting other glTF versions with
+/// `Error::UnsupportedVersion` instead of a deserialization error.
+fn deserialize_root(slice: &[u8]) -> Result<json::Root> {
+    match json::deserialize::from_slice::<json::Root>(slice) {
+        Ok(root) if is_version_2(&root.asset.version) => Ok(root),
+        Ok(root) => Err(Error::UnsupportedVersion {
+            found: Some(root.asset.version),
+        }),
+        Err(error) => Err(unsupported_version(slice).unwrap_or(Error::Deserialize(error))),
+    }
 }
 
 impl ops::Deref for Gltf {
@@ -360,6 +726,61 @@ impl ops::DerefMut for Gltf {
     }
 }
 
+impl<'a> GltfSlice<'a> {
+    /// Loads glTF from a slice of bytes without performing validation
+    /// checks.
+    pub fn from_slice_without_validation(slice: &'a [u8]) -> Result<Self> {
+        let (json, blob, container): (json::Root, Option<&'a [u8]>, FileKind);
+        if slice.starts_with(b"glTF") {
+            let (header, json_chunk, bin_chunk) = binary::split_slice(slice)?;
+            json = deserialize_root(json_chunk)?;
+            blob = bin_chunk;
+            container = FileKind::Glb {
+                version: header.version,
+            };
+        } else {
+            json = deserialize_root(slice)?;
+            blob = None;
+            container = FileKind::GltfJson;
+        };
+        let document = Document::from_json_without_validation(json);
+        Ok(GltfSlice {
+            document,
+            blob,
+            container,
+        })
+    }
+
+    /// Loads glTF from a slice of bytes.
+    pub fn from_slice(slice: &'a [u8]) -> Result<Self> {
+        let gltf = Self::from_slice_without_validation(slice)?;
+        gltf.document.validate()?;
+        Ok(gltf)
+    }
+
+    /// Copies the binary payload, releasing the borrow of the input slice.
+    pub fn into_owned(self) -> Gltf {
+        Gltf {
+            document: self.document,
+            blob: self.blob.map(<[u8]>::to_vec),
+            container: self.container,
+        }
+    }
+}
+
+impl ops::Deref for GltfSlice<'_> {
+    type Target = Document;
+    fn deref(&self) -> &Self::Target {
+        &self.document
+    }
+}
+
+impl ops::DerefMut for GltfSlice<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.document
+    }
+}
+
 impl Document {
     /// Loads glTF from pre-deserialized JSON.
     pub fn from_json(json: json::Root) -> Result<Self> {
@@ -374,6 +795,41 @@ impl Document {
         Document(json)
     }
 
+    /// Parses only the metadata of glTF JSON or binary glTF, for indexing
+    /// large numbers of assets.
+    ///
+    /// Asset information, the names of scenes, nodes, and meshes, and the
+    /// extension declarations are deserialized, while all other properties
+    /// are skipped. The result is not validated.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let slice = std::fs::read("examples/Box.glb")?;
+    /// let metadata = gltf::Document::from_json_metadata_only(&slice)?;
+    /// assert_eq!(metadata.asset.version, "2.0");
+    /// assert_eq!(metadata.meshes.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn from_json_metadata_only(slice: &[u8]) -> Result<json::Metadata> {
+        let json = if slice.starts_with(b"glTF") {
+            binary::split_slice(slice)?.1
+        } else {
+            slice
+        };
+        let metadata: json::Metadata = json::deserialize::from_slice(json)?;
+        if is_version_2(&metadata.asset.version) {
+            Ok(metadata)
+        } else {
+            Err(Error::UnsupportedVersion {
+                found: Some(metadata.asset.version),
+            })
+        }
+    }
+
     /// Unwraps the glTF document.
     pub fn into_json(self) -> json::Root {
         self.0
@@ -387,6 +843,7 @@ impl Document {
             .validate(&self.0, json::Path::new, &mut |path, error| {
                 errors.push((path(), error))
             });
+        scene::validate_hierarchy(&self.0, &mut |path, error| errors.push((path(), error)));
         if errors.is_empty() {
             Ok(())
         } else {
@@ -395,7 +852,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the accessors of the glTF asset.
-    pub fn accessors(&self) -> iter::Accessors {
+    pub fn accessors(&self) -> iter::Accessors<'_> {
         iter::Accessors {
             iter: self.0.accessors.iter().enumerate(),
             document: self,
@@ -403,7 +860,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the animations of the glTF asset.
-    pub fn animations(&self) -> iter::Animations {
+    pub fn animations(&self) -> iter::Animations<'_> {
         iter::Animations {
             iter: self.0.animations.iter().enumerate(),
             document: self,
@@ -411,7 +868,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the pre-loaded buffers of the glTF asset.
-    pub fn buffers(&self) -> iter::Buffers {
+    pub fn buffers(&self) -> iter::Buffers<'_> {
         iter::Buffers {
             iter: self.0.buffers.iter().enumerate(),
             document: self,
@@ -419,7 +876,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the cameras of the glTF asset.
-    pub fn cameras(&self) -> iter::Cameras {
+    pub fn cameras(&self) -> iter::Cameras<'_> {
         iter::Cameras {
             iter: self.0.cameras.iter().enumerate(),
             document: self,
@@ -427,7 +884,7 @@ impl Document {
     }
 
     /// Returns the default scene, if provided.
-    pub fn default_scene(&self) -> Option<Scene> {
+    pub fn default_scene(&self) -> Option<Scene<'_>> {
         self.0
             .scene
             .as_ref()
@@ -435,17 +892,17 @@ impl Document {
     }
 
     /// Returns the extensions referenced in this .document file.
-    pub fn extensions_used(&self) -> iter::ExtensionsUsed {
+    pub fn extensions_used(&self) -> iter::ExtensionsUsed<'_> {
         iter::ExtensionsUsed(self.0.extensions_used.iter())
     }
 
     /// Returns the extensions required to load and render this asset.
-    pub fn extensions_required(&self) -> iter::ExtensionsRequired {
+    pub fn extensions_required(&self) -> iter::ExtensionsRequired<'_> {
         iter::ExtensionsRequired(self.0.extensions_required.iter())
     }
 
     /// Returns an `Iterator` that visits the pre-loaded images of the glTF asset.
-    pub fn images(&self) -> iter::Images {
+    pub fn images(&self) -> iter::Images<'_> {
         iter::Images {
             iter: self.0.images.iter().enumerate(),
             document: self,
@@ -468,11 +925,32 @@ impl Document {
         root.others.get(ext_name)
     }
 
+    /// Returns an `Iterator` that visits the image based lights of the glTF asset as defined
+    /// by the `EXT_lights_image_based` extension.
+    #[cfg(feature = "EXT_lights_image_based")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "EXT_lights_image_based")))]
+    pub fn image_based_lights(&self) -> Option<iter::ImageBasedLights<'_>> {
+        let iter = self
+            .0
+            .extensions
+            .as_ref()?
+            .ext_lights_image_based
+            .as_ref()?
+            .lights
+            .iter()
+            .enumerate();
+
+        Some(iter::ImageBasedLights {
+            iter,
+            document: self,
+        })
+    }
+
     /// Returns an `Iterator` that visits the lights of the glTF asset as defined by the
     /// `KHR_lights_punctual` extension.
     #[cfg(feature = "KHR_lights_punctual")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
-    pub fn lights(&self) -> Option<iter::Lights> {
+    pub fn lights(&self) -> Option<iter::Lights<'_>> {
         let iter = self
             .0
             .extensions
@@ -493,7 +971,7 @@ impl Document {
     /// `KHR_materials_variants` extension.
     #[cfg(feature = "KHR_materials_variants")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
-    pub fn variants(&self) -> Option<iter::Variants> {
+    pub fn variants(&self) -> Option<iter::Variants<'_>> {
         let iter = self
             .0
             .extensions
@@ -511,7 +989,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the materials of the glTF asset.
-    pub fn materials(&self) -> iter::Materials {
+    pub fn materials(&self) -> iter::Materials<'_> {
         iter::Materials {
             iter: self.0.materials.iter().enumerate(),
             document: self,
@@ -519,7 +997,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the meshes of the glTF asset.
-    pub fn meshes(&self) -> iter::Meshes {
+    pub fn meshes(&self) -> iter::Meshes<'_> {
         iter::Meshes {
             iter: self.0.meshes.iter().enumerate(),
             document: self,
@@ -527,7 +1005,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the nodes of the glTF asset.
-    pub fn nodes(&self) -> iter::Nodes {
+    pub fn nodes(&self) -> iter::Nodes<'_> {
         iter::Nodes {
             iter: self.0.nodes.iter().enumerate(),
             document: self,
@@ -535,7 +1013,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the samplers of the glTF asset.
-    pub fn samplers(&self) -> iter::Samplers {
+    pub fn samplers(&self) -> iter::Samplers<'_> {
         iter::Samplers {
             iter: self.0.samplers.iter().enumerate(),
             document: self,
@@ -543,7 +1021,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the scenes of the glTF asset.
-    pub fn scenes(&self) -> iter::Scenes {
+    pub fn scenes(&self) -> iter::Scenes<'_> {
         iter::Scenes {
             iter: self.0.scenes.iter().enumerate(),
             document: self,
@@ -551,7 +1029,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the skins of the glTF asset.
-    pub fn skins(&self) -> iter::Skins {
+    pub fn skins(&self) -> iter::Skins<'_> {
         iter::Skins {
             iter: self.0.skins.iter().enumerate(),
             document: self,
@@ -559,7 +1037,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the textures of the glTF asset.
-    pub fn textures(&self) -> iter::Textures {
+    pub fn textures(&self) -> iter::Textures<'_> {
         iter::Textures {
             iter: self.0.textures.iter().enumerate(),
             document: self,
@@ -568,7 +1046,7 @@ impl Document {
 
     /// Returns an `Iterator` that visits the pre-loaded buffer views of the glTF
     /// asset.
-    pub fn views(&self) -> iter::Views {
+    pub fn views(&self) -> iter::Views<'_> {
         iter::Views {
             iter: self.0.buffer_views.iter().enumerate(),
             document: self,
@@ -576,13 +1054,15 @@ impl Document {
     }
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
-            #[cfg(feature = "import")]
+            #[cfg(feature = "import_embedded")]
             Error::Base64(ref e) => e.fmt(f),
             Error::Binary(ref e) => e.fmt(f),
             #[cfg(feature = "import")]
+            Error::Cancelled => write!(f, "import cancelled"),
+            #[cfg(feature = "import_embedded")]
             Error::BufferLength {
                 buffer,
                 expected,
@@ -595,12 +1075,13 @@ impl std::fmt::Display for Error {
                 )
             }
             Error::Deserialize(ref e) => e.fmt(f),
+            #[cfg(feature = "std")]
             Error::Io(ref e) => e.fmt(f),
             #[cfg(feature = "import")]
             Error::Image(ref e) => e.fmt(f),
-            #[cfg(feature = "import")]
+            #[cfg(feature = "import_embedded")]
             Error::MissingBlob => write!(f, "missing binary portion of binary glTF"),
-            #[cfg(feature = "import")]
+            #[cfg(feature = "import_embedded")]
             Error::ExternalReferenceInSliceImport => {
                 write!(f, "external reference in slice only import")
             }
@@ -610,8 +1091,14 @@ impl std::fmt::Display for Error {
             Error::UnsupportedImageFormat(image) => {
                 write!(f, "unsupported image format: {:?}", image.color())
             }
-            #[cfg(feature = "import")]
+            #[cfg(feature = "import_embedded")]
             Error::UnsupportedScheme => write!(f, "unsupported URI scheme"),
+            Error::UnsupportedVersion { found: Some(found) } => {
+                write!(f, "unsupported glTF version {}, expected 2.0", found)
+            }
+            Error::UnsupportedVersion { found: None } => {
+                write!(f, "unsupported legacy glTF version, expected 2.0")
+            }
             Error::Validation(ref xs) => {
                 write!(f, "invalid glTF:")?;
                 for (ref path, ref error) in xs {
@@ -619,10 +1106,19 @@ impl std::fmt::Display for Error {
                 }
                 Ok(())
             }
+            #[cfg(feature = "schema-validate")]
+            Error::Schema(ref violations) => {
+                write!(f, "glTF schema violation:")?;
+                for violation in violations {
+                    write!(f, " {};", violation)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 impl From<binary::Error> for Error {
@@ -631,6 +1127,7 @@ impl From<binary::Error> for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err)
@@ -656,156 +1153,182 @@ impl From<Vec<(json::Path, json::validation::Error)>> for Error {
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i8> for i8 {
     fn normalize(self) -> i8 {
         self
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u8> for i8 {
     fn normalize(self) -> u8 {
         self.max(0) as u8 * 2
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i16> for i8 {
     fn normalize(self) -> i16 {
         self as i16 * 0x100
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u16> for i8 {
     fn normalize(self) -> u16 {
         self.max(0) as u16 * 0x200
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<f32> for i8 {
     fn normalize(self) -> f32 {
         (self as f32 * 127.0_f32.recip()).max(-1.0)
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i8> for u8 {
     fn normalize(self) -> i8 {
         (self / 2) as i8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u8> for u8 {
     fn normalize(self) -> u8 {
         self
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i16> for u8 {
     fn normalize(self) -> i16 {
         self as i16 * 0x80
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u16> for u8 {
     fn normalize(self) -> u16 {
         self as u16 * 0x100
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<f32> for u8 {
     fn normalize(self) -> f32 {
         self as f32 * 255.0_f32.recip()
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i8> for i16 {
     fn normalize(self) -> i8 {
         (self / 0x100) as i8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u8> for i16 {
     fn normalize(self) -> u8 {
         (self.max(0) / 0x80) as u8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i16> for i16 {
     fn normalize(self) -> i16 {
         self
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u16> for i16 {
     fn normalize(self) -> u16 {
         self.max(0) as u16 * 2
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<f32> for i16 {
     fn normalize(self) -> f32 {
         (self as f32 * 32767.0_f32.recip()).max(-1.0)
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i8> for u16 {
     fn normalize(self) -> i8 {
         (self / 0x200) as i8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u8> for u16 {
     fn normalize(self) -> u8 {
         (self / 0x100) as u8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i16> for u16 {
     fn normalize(self) -> i16 {
         (self / 2) as i16
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u16> for u16 {
     fn normalize(self) -> u16 {
         self
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<f32> for u16 {
     fn normalize(self) -> f32 {
         self as f32 * 65535.0_f32.recip()
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i8> for f32 {
     fn normalize(self) -> i8 {
         (self * 127.0) as i8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u8> for f32 {
     fn normalize(self) -> u8 {
         (self.max(0.0) * 255.0) as u8
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<i16> for f32 {
     fn normalize(self) -> i16 {
         (self * 32767.0) as i16
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<u16> for f32 {
     fn normalize(self) -> u16 {
         (self.max(0.0) * 65535.0) as u16
     }
 }
 
+#[cfg(feature = "utils")]
 impl Normalize<f32> for f32 {
     fn normalize(self) -> f32 {
         self
     }
 }
 
+#[cfg(feature = "utils")]
 impl<U, T> Normalize<[T; 2]> for [U; 2]
 where
     U: Normalize<T> + Copy,
@@ -815,6 +1338,7 @@ where
     }
 }
 
+#[cfg(feature = "utils")]
 impl<U, T> Normalize<[T; 3]> for [U; 3]
 where
     U: Normalize<T> + Copy,
@@ -828,6 +1352,7 @@ where
     }
 }
 
+#[cfg(feature = "utils")]
 impl<U, T> Normalize<[T; 4]> for [U; 4]
 where
     U: Normalize<T> + Copy,
@@ -0,0 +1,182 @@
+use crate::hash::Hasher;
+use crate::prelude::*;
+use crate::{buffer, image, Document};
+
+use json::Value;
+use serde_json::{json, Map};
+
+/// Formats a content hash for inclusion in a manifest.
+fn format_hash(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+/// Returns `true` if `uri` refers to data outside of the glTF document.
+fn is_external(uri: &str) -> bool {
+    !uri.starts_with("data:")
+}
+
+impl Document {
+    /// Returns a machine-readable manifest of the document's external
+    /// dependencies and extension requirements.
+    ///
+    /// The manifest is a JSON object of the form:
+    ///
+    /// ```json
+    /// {
+    ///   "fingerprint": "<hash of the glTF JSON>",
+    ///   "extensionsUsed": ["..."],
+    ///   "extensionsRequired": ["..."],
+    ///   "dependencies": [
+    ///     { "kind": "buffer", "index": 0, "uri": "buffer0.bin", "byteLength": 648 },
+    ///     { "kind": "image", "index": 0, "uri": "texture.png", "mimeType": "image/png" }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Only resources referenced by a URI which is not a data URI are listed.
+    /// Buffer sizes are those declared in the JSON; see [`manifest_with_data`]
+    /// for a manifest including the sizes and hashes of imported data.
+    ///
+    /// [`manifest_with_data`]: #method.manifest_with_data
+    pub fn manifest(&self) -> Value {
+        self.manifest_impl(|_, _| {}, |_, _| {})
+    }
+
+    /// Like [`manifest`], but additionally records the actual size and content
+    /// hash of every external dependency as `size` and `hash` properties.
+    ///
+    /// `buffers` and `images` are expected to be the data returned by
+    /// [`import_buffers`](fn.import_buffers.html) and
+    /// [`import_encoded_images`](fn.import_encoded_images.html) for this
+    /// document. Sizes and hashes are computed from the source bytes, so they
+    /// match the files on disk: the first `byteLength` bytes of each buffer,
+    /// without the padding added on import, and the encoded image data.
+    ///
+    /// [`manifest`]: #method.manifest
+    #[cfg(feature = "import")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    pub fn manifest_with_data(&self, buffers: &[buffer::Data], images: &[image::Encoded]) -> Value {
+        self.manifest_impl(
+            |buffer, entry| {
+                if let Some(data) = buffers.get(buffer.index()) {
+                    let bytes = &data[..buffer.length().min(data.len())];
+                    let mut hasher = Hasher::new();
+                    hasher.write(bytes);
+                    entry.insert("size".to_owned(), bytes.len().into());
+                    entry.insert("hash".to_owned(), format_hash(hasher.finish()).into());
+                }
+            },
+            |image, entry| {
+                if let Some(data) = images.get(image.index()) {
+                    let mut hasher = Hasher::new();
+                    hasher.write(&data.bytes);
+                    entry.insert("size".to_owned(), data.bytes.len().into());
+                    entry.insert("hash".to_owned(), format_hash(hasher.finish()).into());
+                }
+            },
+        )
+    }
+
+    fn manifest_impl<B, I>(&self, mut buffer_data: B, mut image_data: I) -> Value
+    where
+        B: FnMut(&buffer::Buffer<'_>, &mut Map<String, Value>),
+        I: FnMut(&image::Image<'_>, &mut Map<String, Value>),
+    {
+        let mut dependencies = Vec::new();
+        for buffer in self.buffers() {
+            if let buffer::Source::Uri(uri) = buffer.source() {
+                if is_external(uri) {
+                    let mut entry = Map::new();
+                    entry.insert("kind".to_owned(), "buffer".into());
+                    entry.insert("index".to_owned(), buffer.index().into());
+                    entry.insert("uri".to_owned(), uri.into());
+                    entry.insert("byteLength".to_owned(), buffer.length().into());
+                    buffer_data(&buffer, &mut entry);
+                    dependencies.push(Value::Object(entry));
+                }
+            }
+        }
+        for image in self.images() {
+            if let image::Source::Uri { uri, mime_type } = image.source() {
+                if is_external(uri) {
+                    let mut entry = Map::new();
+                    entry.insert("kind".to_owned(), "image".into());
+                    entry.insert("index".to_owned(), image.index().into());
+                    entry.insert("uri".to_owned(), uri.into());
+                    if let Some(mime_type) = mime_type {
+                        entry.insert("mimeType".to_owned(), mime_type.into());
+                    }
+                    image_data(&image, &mut entry);
+                    dependencies.push(Value::Object(entry));
+                }
+            }
+        }
+
+        let mut hasher = Hasher::new();
+        // Serializing a document cannot fail.
+        hasher.write(&json::serialize::to_vec(&self.0).unwrap());
+
+        json!({
+            "fingerprint": format_hash(hasher.finish()),
+            "extensionsUsed": self.extensions_used().collect::<Vec<_>>(),
+            "extensionsRequired": self.extensions_required().collect::<Vec<_>>(),
+            "dependencies": dependencies,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "import"))]
+mod tests {
+    use crate::fixture::TempDir;
+    use std::fs;
+
+    #[test]
+    fn with_data() {
+        let root = TempDir::new("manifest");
+        fs::write(root.join("data.bin"), b"abcdef").unwrap();
+        fs::copy("tests/orange_64x32.jpg", root.join("orange.jpg")).unwrap();
+        let path = root.join("model.gltf");
+        fs::write(
+            &path,
+            br#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "uri": "data.bin", "byteLength": 6 }],
+                "images": [{ "uri": "orange.jpg" }]
+            }"#,
+        )
+        .unwrap();
+        let manifest = |path: &std::path::Path| {
+            let (document, buffers, _) = crate::import(path, crate::filesystem_fetcher).unwrap();
+            let images = crate::import_encoded_images(
+                &document,
+                &buffers,
+                Some(&root),
+                crate::filesystem_fetcher,
+            )
+            .unwrap();
+            document.manifest_with_data(&buffers, &images)
+        };
+
+        // Sizes and hashes describe the files on disk, not the padded buffer or
+        // the decoded pixels. The hashes are the FNV-1a hashes of the files.
+        let before = manifest(&path);
+        let dependencies = before["dependencies"].as_array().unwrap();
+        assert_eq!(dependencies[0]["size"], 6);
+        assert_eq!(dependencies[0]["hash"], "d80bda3fbe244a0a");
+        let jpeg = fs::metadata(root.join("orange.jpg")).unwrap().len();
+        assert_eq!(dependencies[1]["size"], jpeg);
+        assert_eq!(dependencies[1]["hash"], "42782a3fc2f5232d");
+
+        // Trailing bytes after the JPEG end marker do not alter the pixels.
+        let mut bytes = fs::read(root.join("orange.jpg")).unwrap();
+        bytes.push(0);
+        fs::write(root.join("orange.jpg"), bytes).unwrap();
+        let after = manifest(&path);
+        assert_eq!(after["dependencies"][0], before["dependencies"][0]);
+        assert_eq!(after["dependencies"][1]["size"], jpeg + 1);
+        assert_ne!(
+            after["dependencies"][1]["hash"],
+            before["dependencies"][1]["hash"]
+        );
+    }
+}
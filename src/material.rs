@@ -1,12 +1,14 @@
+use crate::hash::Hasher;
+use crate::lazy::Lazy;
 use crate::{texture, Document};
 
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 pub use json::material::AlphaMode;
 #[cfg(feature = "extensions")]
 use serde_json::{Map, Value};
 
-lazy_static! {
-    static ref DEFAULT_MATERIAL: json::material::Material = Default::default();
-}
+static DEFAULT_MATERIAL: Lazy<json::material::Material> = Lazy::new(Default::default);
 
 /// The material appearance of a primitive.
 #[derive(Clone, Debug)]
@@ -40,10 +42,23 @@ impl<'a> Material<'a> {
         Self {
             document,
             index: None,
-            json: &DEFAULT_MATERIAL,
+            json: &*DEFAULT_MATERIAL,
         }
     }
 
+    /// Returns a stable hash of the material definition.
+    ///
+    /// The hash is computed from the serialized material JSON, so textures
+    /// are identified by index rather than by content. Use
+    /// [`Image::content_hash`](../image/struct.Image.html#method.content_hash)
+    /// to track changes to the referenced images.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Hasher::new();
+        // Serializing a material cannot fail.
+        hasher.write(&json::serialize::to_vec(self.json).unwrap());
+        hasher.finish()
+    }
+
     /// Returns the internal JSON index if this `Material` was explicity defined.
     ///
     /// This function returns `None` if the `Material` is the default material.
@@ -180,6 +195,18 @@ impl<'a> Material<'a> {
             .map(|x| Specular::new(self.document, x))
     }
 
+    /// Parameter values that define a thin-film iridescence effect.
+    #[cfg(feature = "KHR_materials_iridescence")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_iridescence")))]
+    pub fn iridescence(&self) -> Option<Iridescence<'a>> {
+        self.json
+            .extensions
+            .as_ref()?
+            .iridescence
+            .as_ref()
+            .map(|x| Iridescence::new(self.document, x))
+    }
+
     /// A tangent space normal map.
     ///
     /// The texture contains RGB components in linear space. Each texel represents
@@ -249,12 +276,146 @@ impl<'a> Material<'a> {
             .map_or(false, |extensions| extensions.unlit.is_some())
     }
 
+    /// Returns the factors of the core metallic-roughness model combined with
+    /// the `KHR_materials_ior` and `KHR_materials_specular` extensions.
+    ///
+    /// Extensions which are absent, or whose crate feature is disabled,
+    /// contribute their default values. Textures are not taken into account;
+    /// multiply the sampled texels into the returned factors to resolve the
+    /// reflectance per texel.
+    pub fn specular_parameters(&self) -> SpecularParameters {
+        let pbr = self.pbr_metallic_roughness();
+        let [r, g, b, _] = pbr.base_color_factor();
+        #[allow(unused_mut)]
+        let mut parameters = SpecularParameters {
+            base_color: [r, g, b],
+            metallic: pbr.metallic_factor(),
+            ..Default::default()
+        };
+        #[cfg(feature = "KHR_materials_ior")]
+        if let Some(ior) = self.ior() {
+            parameters.ior = ior;
+        }
+        #[cfg(feature = "KHR_materials_specular")]
+        if let Some(specular) = self.specular() {
+            parameters.specular = specular.specular_factor();
+            parameters.specular_color = specular.specular_color_factor();
+        }
+        parameters
+    }
+
+    /// Resolves the effective Fresnel reflectance of the material from its
+    /// factors.
+    ///
+    /// Materials using `KHR_materials_pbrSpecularGlossiness`, when the feature
+    /// is enabled, take their `F0` directly from the specular factor.
+    /// Otherwise the result of [`specular_parameters`] is resolved, so the
+    /// `KHR_materials_ior` and `KHR_materials_specular` extensions only
+    /// affect the dielectric component of the metallic-roughness model.
+    ///
+    /// [`specular_parameters`]: #method.specular_parameters
+    pub fn reflectance(&self) -> Reflectance {
+        #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
+        if let Some(pbr) = self.pbr_specular_glossiness() {
+            return Reflectance {
+                f0: pbr.specular_factor(),
+                f90: 1.0,
+            };
+        }
+        self.specular_parameters().resolve()
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
 }
 
+/// The Fresnel reflectance of a surface at normal and grazing incidence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reflectance {
+    /// The linear RGB reflectance at normal incidence.
+    pub f0: [f32; 3],
+
+    /// The reflectance at grazing incidence.
+    pub f90: f32,
+}
+
+/// The inputs determining the specular reflectance of the metallic-roughness
+/// material model.
+///
+/// The default values are those of a material without the `KHR_materials_ior`
+/// and `KHR_materials_specular` extensions, surrounded by air.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpecularParameters {
+    /// The linear RGB base color.
+    pub base_color: [f32; 3],
+
+    /// The metalness.
+    pub metallic: f32,
+
+    /// The index of refraction of the material.
+    pub ior: f32,
+
+    /// The strength of the specular reflection.
+    pub specular: f32,
+
+    /// The linear RGB color of the specular reflection at normal incidence.
+    pub specular_color: [f32; 3],
+
+    /// The index of refraction of the surrounding medium.
+    pub outside_ior: f32,
+}
+
+impl Default for SpecularParameters {
+    fn default() -> Self {
+        Self {
+            base_color: [1.0; 3],
+            metallic: 1.0,
+            ior: 1.5,
+            specular: 1.0,
+            specular_color: [1.0; 3],
+            outside_ior: 1.0,
+        }
+    }
+}
+
+impl SpecularParameters {
+    /// Resolves the reflectance as specified by `KHR_materials_specular`.
+    ///
+    /// The dielectric `F0` is derived from the relative index of refraction,
+    /// tinted by the specular color and clamped to one, then scaled by the
+    /// specular strength, which also scales the dielectric `F90`. Both are
+    /// finally blended towards the base color and one respectively by the
+    /// metalness.
+    ///
+    /// ```
+    /// use gltf::material::SpecularParameters;
+    ///
+    /// let dielectric = SpecularParameters {
+    ///     metallic: 0.0,
+    ///     ..Default::default()
+    /// };
+    /// let reflectance = dielectric.resolve();
+    /// assert!((reflectance.f0[0] - 0.04).abs() < 1e-6);
+    /// assert_eq!(reflectance.f90, 1.0);
+    /// ```
+    pub fn resolve(&self) -> Reflectance {
+        let ratio = (self.ior - self.outside_ior) / (self.ior + self.outside_ior);
+        let f0 = ratio * ratio;
+        let mix = |dielectric: f32, metal: f32| dielectric + (metal - dielectric) * self.metallic;
+        let mut result = [0.0; 3];
+        for (i, component) in result.iter_mut().enumerate() {
+            let dielectric = (f0 * self.specular_color[i]).min(1.0) * self.specular;
+            *component = mix(dielectric, self.base_color[i]);
+        }
+        Reflectance {
+            f0: result,
+            f90: mix(self.specular, 1.0),
+        }
+    }
+}
+
 /// A set of parameter values that are used to define the metallic-roughness
 /// material model from Physically-Based Rendering (PBR) methodology.
 pub struct PbrMetallicRoughness<'a> {
@@ -503,6 +664,78 @@ impl<'a> Specular<'a> {
     }
 }
 
+/// Parameter values that define a thin-film iridescence effect.
+#[cfg(feature = "KHR_materials_iridescence")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_iridescence")))]
+pub struct Iridescence<'a> {
+    /// The parent `Document` struct.
+    document: &'a Document,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::material::Iridescence,
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_iridescence")))]
+impl<'a> Iridescence<'a> {
+    /// Constructs `Iridescence`.
+    pub(crate) fn new(
+        document: &'a Document,
+        json: &'a json::extensions::material::Iridescence,
+    ) -> Self {
+        Self { document, json }
+    }
+
+    /// The iridescence intensity factor. Defaults to 0.0.
+    pub fn iridescence_factor(&self) -> f32 {
+        self.json.iridescence_factor.0
+    }
+
+    /// The iridescence intensity texture, stored in the red (`R`) channel.
+    /// This will be multiplied by `iridescence_factor`.
+    pub fn iridescence_texture(&self) -> Option<texture::Info<'a>> {
+        self.json.iridescence_texture.as_ref().map(|json| {
+            let texture = self.document.textures().nth(json.index.value()).unwrap();
+            texture::Info::new(texture, json)
+        })
+    }
+
+    /// The index of refraction of the dielectric thin-film layer. Defaults to 1.3.
+    pub fn iridescence_ior(&self) -> f32 {
+        self.json.iridescence_ior.0
+    }
+
+    /// The minimum thickness of the thin-film layer given in nanometers.
+    /// Defaults to 100.0.
+    pub fn iridescence_thickness_minimum(&self) -> f32 {
+        self.json.iridescence_thickness_minimum.0
+    }
+
+    /// The maximum thickness of the thin-film layer given in nanometers.
+    /// Defaults to 400.0.
+    pub fn iridescence_thickness_maximum(&self) -> f32 {
+        self.json.iridescence_thickness_maximum.0
+    }
+
+    /// The thickness texture of the thin-film layer, stored in the green (`G`)
+    /// channel. The thickness is interpolated between the minimum and maximum
+    /// thickness.
+    pub fn iridescence_thickness_texture(&self) -> Option<texture::Info<'a>> {
+        self.json
+            .iridescence_thickness_texture
+            .as_ref()
+            .map(|json| {
+                let texture = self.document.textures().nth(json.index.value()).unwrap();
+                texture::Info::new(texture, json)
+            })
+    }
+
+    /// Optional application specific data.
+    pub fn extras(&self) -> &'a json::Extras {
+        &self.json.extras
+    }
+}
+
 /// A set of parameter values that are used to define the specular-glossiness
 /// material model from Physically-Based Rendering (PBR) methodology.
 #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
@@ -14,7 +14,9 @@
 //
 // Modified for the gltf crate by the gltf library developers.
 
-use std::ops;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use core::ops;
 
 #[cfg(test)]
 mod test {
@@ -163,7 +165,6 @@ impl Vector4 {
         [self.x, self.y, self.z, self.w]
     }
 
-    #[cfg(test)]
     pub fn from_array([x, y, z, w]: [f32; 4]) -> Self {
         Self { x, y, z, w }
     }
@@ -250,7 +251,6 @@ impl Matrix4 {
         }
     }
 
-    #[cfg(test)]
     pub fn from_array([x, y, z, w]: [[f32; 4]; 4]) -> Matrix4 {
         Matrix4 {
             x: Vector4::from_array(x),
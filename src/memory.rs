@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::ops::ControlFlow;
+use std::path::{Component, Path};
+use std::{fmt, io, str};
+
+use crate::buffer;
+use crate::import::{self, Fetcher, Import, ResourceRequest};
+use crate::{Error, Result};
+
+/// Returns the lexically normalized form of `path` with `/` separators.
+///
+/// `.` components are dropped and `..` components remove the preceding
+/// component, while absolute paths keep a leading `/`.
+pub(crate) fn normalize(path: &Path) -> String {
+    let mut absolute = false;
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => absolute = true,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part.to_string_lossy()),
+        }
+    }
+    let path = parts.join("/");
+    if absolute {
+        format!("/{}", path)
+    } else {
+        path
+    }
+}
+
+/// Size of a tar header and the alignment of tar file data.
+const TAR_BLOCK: usize = 512;
+
+/// Returns an `Error::Io` for a malformed tar archive.
+fn invalid_tar(message: &str) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid tar archive: {}", message),
+    ))
+}
+
+/// Returns the NUL terminated string at the start of `field`.
+fn tar_string(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Parses an octal or base-256 encoded tar number.
+fn tar_number(field: &[u8]) -> Result<u64> {
+    if field.first().map_or(false, |&b| b & 0x80 != 0) {
+        let mut value = u64::from(field[0] & 0x7f);
+        for &b in &field[1..] {
+            value = value
+                .checked_mul(256)
+                .and_then(|value| value.checked_add(u64::from(b)))
+                .ok_or_else(|| invalid_tar("number out of range"))?;
+        }
+        return Ok(value);
+    }
+    let digits = str::from_utf8(field).map_err(|_| invalid_tar("malformed number"))?;
+    let digits = digits.trim_matches(|c: char| c == '\0' || c == ' ');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(digits, 8).map_err(|_| invalid_tar("malformed number"))
+}
+
+/// Returns the `path` record of pax extended header data.
+fn pax_path(data: &[u8]) -> Option<String> {
+    let mut rest = data;
+    while !rest.is_empty() {
+        let space = rest.iter().position(|&b| b == b' ')?;
+        let length: usize = str::from_utf8(&rest[..space]).ok()?.parse().ok()?;
+        let record = rest.get(space + 1..length)?;
+        if let Some(value) = record.strip_prefix(b"path=") {
+            let value = value.strip_suffix(b"\n").unwrap_or(value);
+            return Some(String::from_utf8_lossy(value).into_owned());
+        }
+        rest = &rest[length..];
+    }
+    None
+}
+
+/// Reads the regular files of a tar archive in archive order, passing the
+/// path and contents of each to `visit`.
+fn read_tar<R: Read, F: FnMut(String, Vec<u8>)>(mut reader: R, mut visit: F) -> Result<()> {
+    let mut long_name = None;
+    let mut header = [0u8; TAR_BLOCK];
+    loop {
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            // Archives may end without the terminating zero blocks.
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(Error::Io(error)),
+        }
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        let checksum = tar_number(&header[148..156])?;
+        let sum: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if (148..156).contains(&i) {
+                    32
+                } else {
+                    u64::from(b)
+                }
+            })
+            .sum();
+        if sum != checksum {
+            return Err(invalid_tar("checksum mismatch"));
+        }
+
+        let size = tar_number(&header[124..136])? as usize;
+        let mut data = Vec::new();
+        (&mut reader)
+            .take(size as u64)
+            .read_to_end(&mut data)
+            .map_err(Error::Io)?;
+        if data.len() != size {
+            return Err(invalid_tar("unexpected end of file"));
+        }
+        let padding = (TAR_BLOCK - size % TAR_BLOCK) % TAR_BLOCK;
+        io::copy(&mut (&mut reader).take(padding as u64), &mut io::sink()).map_err(Error::Io)?;
+
+        match header[156] {
+            b'L' => long_name = Some(tar_string(&data)),
+            b'x' => long_name = pax_path(&data).or(long_name),
+            b'0' | b'\0' | b'7' => {
+                let name = match long_name.take() {
+                    Some(name) => name,
+                    // POSIX ustar stores a prefix, which GNU tar uses for other fields.
+                    None if &header[257..263] == b"ustar\0" && header[345] != 0 => {
+                        format!(
+                            "{}/{}",
+                            tar_string(&header[345..500]),
+                            tar_string(&header[..100])
+                        )
+                    }
+                    None => tar_string(&header[..100]),
+                };
+                visit(name, data);
+            }
+            _ => long_name = None,
+        }
+    }
+    Ok(())
+}
+
+/// An in-memory virtual file system for importing multi-file assets without
+/// touching the disk, for example in unit tests or sandboxed tools.
+///
+/// Files are resolved like [`filesystem_fetcher`] resolves them, i.e. the
+/// referenced path is joined to the directory of the referencing file. Paths
+/// are compared after normalizing `.` and `..` components and separators.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let mut files = std::collections::HashMap::new();
+/// files.insert("models/Box.gltf".to_owned(), std::fs::read("examples/Box.gltf")?);
+/// files.insert("models/Box0.bin".to_owned(), std::fs::read("examples/Box0.bin")?);
+///
+/// let fetcher = gltf::MemoryFetcher::new(files);
+/// let (document, buffers, images) = fetcher.import("models/Box.gltf")?;
+/// # let _ = (document, images);
+/// assert_eq!(buffers[0].len(), 648);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`filesystem_fetcher`]: fn.filesystem_fetcher.html
+#[derive(Clone, Default)]
+pub struct MemoryFetcher {
+    /// Maps normalized paths to file contents.
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl fmt::Debug for MemoryFetcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut paths: Vec<_> = self.files.keys().collect();
+        paths.sort();
+        f.debug_struct("MemoryFetcher")
+            .field("files", &paths)
+            .finish()
+    }
+}
+
+impl MemoryFetcher {
+    /// Constructs a `MemoryFetcher` from a map of paths to file contents.
+    pub fn new(files: HashMap<String, Vec<u8>>) -> Self {
+        files.into_iter().collect()
+    }
+
+    /// Adds a file, replacing any previous file at the same path.
+    pub fn insert<P: AsRef<Path>>(&mut self, path: P, data: Vec<u8>) {
+        self.files.insert(normalize(path.as_ref()), data);
+    }
+
+    /// Returns the contents of the file at `path`, if any.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&[u8]> {
+        self.files.get(&normalize(path.as_ref())).map(Vec::as_slice)
+    }
+
+    /// Reads a file with the signature of [`filesystem_fetcher`], such that
+    /// `|base, path| fetcher.read(base, path)` can be passed to the `import`
+    /// functions.
+    ///
+    /// Returns an `Error::Io` of kind `NotFound` if the file does not exist.
+    ///
+    /// [`filesystem_fetcher`]: fn.filesystem_fetcher.html
+    pub fn read(&self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        let resolved = match base {
+            Some(base) => base.join(path),
+            None => Path::new(path).to_path_buf(),
+        };
+        self.get(&resolved).map(<[u8]>::to_vec).ok_or_else(|| {
+            let message = format!("{} not found in memory", resolved.display());
+            Error::Io(io::Error::new(io::ErrorKind::NotFound, message))
+        })
+    }
+
+    /// Constructs a `MemoryFetcher` holding the regular files of a tar
+    /// archive, which is read from a stream that need not be seekable.
+    ///
+    /// Both ustar and pax archives as well as GNU long names are supported.
+    /// Directories, links, and other special entries are skipped. Returns an
+    /// `Error::Io` of kind `InvalidData` if the archive is malformed.
+    pub fn from_tar<R: Read>(reader: R) -> Result<Self> {
+        let mut fetcher = MemoryFetcher::default();
+        read_tar(reader, |name, data| fetcher.insert(name, data))?;
+        Ok(fetcher)
+    }
+
+    /// Imports glTF 2.0 from the file at `path`, resolving all external
+    /// references from memory.
+    pub fn import<P: AsRef<Path>>(&self, path: P) -> Result<Import> {
+        let path = path.as_ref();
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let slice = self.read(None, &path.to_string_lossy())?;
+        import::import_slice_impl(&slice, Some(base), self, &mut |_| ControlFlow::Continue(()))
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Vec<u8>)> for MemoryFetcher {
+    fn from_iter<I: IntoIterator<Item = (S, Vec<u8>)>>(iter: I) -> Self {
+        let mut fetcher = MemoryFetcher::default();
+        for (path, data) in iter {
+            fetcher.insert(path.into(), data);
+        }
+        fetcher
+    }
+}
+
+impl Fetcher for &MemoryFetcher {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        self.read(request.base, request.path).map(Into::into)
+    }
+}
+
+impl Fetcher for MemoryFetcher {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        (&*self).fetch(request)
+    }
+}
+
+/// Import glTF 2.0 from a tar archive holding an asset and its resources.
+///
+/// The archive is read from a stream that need not be seekable, such as
+/// standard input. The first `.gltf` or `.glb` file of the archive is
+/// imported, and its external references are resolved from the other files of
+/// the archive. Returns an `Error::Io` of kind `NotFound` if the archive does
+/// not contain a glTF file. See [`MemoryFetcher::from_tar`].
+///
+/// [`MemoryFetcher::from_tar`]: struct.MemoryFetcher.html#method.from_tar
+pub fn import_tar<R: Read>(reader: R) -> Result<Import> {
+    let mut fetcher = MemoryFetcher::default();
+    let mut root = None;
+    read_tar(reader, |name, data| {
+        let extension = Path::new(&name).extension().and_then(|ext| ext.to_str());
+        let is_gltf = matches!(extension, Some(ext) if ext.eq_ignore_ascii_case("gltf") || ext.eq_ignore_ascii_case("glb"));
+        if root.is_none() && is_gltf {
+            root = Some(name.clone());
+        }
+        fetcher.insert(name, data);
+    })?;
+    match root {
+        Some(root) => fetcher.import(root),
+        None => Err(Error::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no glTF file in tar archive",
+        ))),
+    }
+}
@@ -1,4 +1,5 @@
-use std::{collections, iter, slice};
+use alloc::collections;
+use core::{iter, slice};
 
 use super::{Attribute, Mesh, MorphTarget, Primitive};
 use crate::Document;
@@ -71,10 +72,10 @@ impl<'a> Iterator for Primitives<'a> {
     fn count(self) -> usize {
         self.iter.count()
     }
-    fn last(self) -> Option<Self::Item> {
+    fn last(mut self) -> Option<Self::Item> {
         let mesh = self.mesh;
         self.iter
-            .last()
+            .next_back()
             .map(|(index, json)| Primitive::new(mesh, index, json))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
@@ -56,15 +56,27 @@ pub mod iter;
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
+#[cfg(all(not(feature = "std"), feature = "utils"))]
+use crate::float::Float;
+#[cfg(any(feature = "utils", feature = "extras"))]
+use crate::prelude::*;
 use crate::{Accessor, Buffer, Document, Material};
 
 #[cfg(feature = "utils")]
 use crate::accessor;
+#[cfg(feature = "utils")]
+use crate::geometry;
+#[cfg(feature = "utils")]
+use crate::hash::Hasher;
 
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 pub use json::mesh::{Mode, Semantic};
 use json::validation::Checked;
 #[cfg(feature = "extensions")]
-use serde_json::{Map, Value};
+use serde_json::Map;
+#[cfg(any(feature = "extensions", feature = "extras"))]
+use serde_json::Value;
 
 /// Vertex attribute data.
 pub type Attribute<'a> = (Semantic, Accessor<'a>);
@@ -82,6 +94,134 @@ pub struct Bounds<T> {
     pub max: T,
 }
 
+impl BoundingBox {
+    /// Returns the smallest bounding box containing all of `points`, or `None`
+    /// if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = [f32; 3]>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Bounds {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            bounds.extend(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the bounding box to contain `point`.
+    pub fn extend(&mut self, point: [f32; 3]) {
+        for (i, value) in point.iter().enumerate() {
+            self.min[i] = self.min[i].min(*value);
+            self.max[i] = self.max[i].max(*value);
+        }
+    }
+
+    /// Returns the smallest bounding box containing both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut bounds = self.clone();
+        bounds.extend(other.min);
+        bounds.extend(other.max);
+        bounds
+    }
+
+    /// Returns the eight corners of the bounding box.
+    pub fn corners(&self) -> [[f32; 3]; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            [a[0], a[1], a[2]],
+            [b[0], a[1], a[2]],
+            [a[0], b[1], a[2]],
+            [b[0], b[1], a[2]],
+            [a[0], a[1], b[2]],
+            [b[0], a[1], b[2]],
+            [a[0], b[1], b[2]],
+            [b[0], b[1], b[2]],
+        ]
+    }
+
+    /// Returns the axis-aligned bounding box of this bounding box after
+    /// applying the 4x4 column-major affine `matrix`.
+    pub fn transform(&self, matrix: &[[f32; 4]; 4]) -> Self {
+        let m = matrix;
+        Self::from_points(self.corners().iter().map(|p| {
+            [
+                m[0][0] * p[0] + m[1][0] * p[1] + m[2][0] * p[2] + m[3][0],
+                m[0][1] * p[0] + m[1][1] * p[1] + m[2][1] * p[2] + m[3][1],
+                m[0][2] * p[0] + m[1][2] * p[1] + m[2][2] * p[2] + m[3][2],
+            ]
+        }))
+        .unwrap()
+    }
+}
+
+bitflags::bitflags! {
+    /// The set of standard vertex attributes present on a primitive.
+    ///
+    /// Attribute sets beyond those listed here and custom attributes have no
+    /// corresponding flag.
+    #[derive(Default)]
+    pub struct AttributeFlags: u32 {
+        /// `POSITION`.
+        const POSITION = 1 << 0;
+        /// `NORMAL`.
+        const NORMAL = 1 << 1;
+        /// `TANGENT`.
+        const TANGENT = 1 << 2;
+        /// `TEXCOORD_0`.
+        const TEXCOORD_0 = 1 << 3;
+        /// `TEXCOORD_1`.
+        const TEXCOORD_1 = 1 << 4;
+        /// `TEXCOORD_2`.
+        const TEXCOORD_2 = 1 << 5;
+        /// `TEXCOORD_3`.
+        const TEXCOORD_3 = 1 << 6;
+        /// `COLOR_0`.
+        const COLOR_0 = 1 << 7;
+        /// `COLOR_1`.
+        const COLOR_1 = 1 << 8;
+        /// `JOINTS_0`.
+        const JOINTS_0 = 1 << 9;
+        /// `JOINTS_1`.
+        const JOINTS_1 = 1 << 10;
+        /// `WEIGHTS_0`.
+        const WEIGHTS_0 = 1 << 11;
+        /// `WEIGHTS_1`.
+        const WEIGHTS_1 = 1 << 12;
+    }
+}
+
+impl AttributeFlags {
+    /// Returns the flag corresponding to `semantic`, if any.
+    pub fn from_semantic(semantic: &Semantic) -> Option<Self> {
+        let (flags, set) = match *semantic {
+            Semantic::Positions => return Some(Self::POSITION),
+            Semantic::Normals => return Some(Self::NORMAL),
+            Semantic::Tangents => return Some(Self::TANGENT),
+            Semantic::TexCoords(set) => (
+                [
+                    Self::TEXCOORD_0,
+                    Self::TEXCOORD_1,
+                    Self::TEXCOORD_2,
+                    Self::TEXCOORD_3,
+                ]
+                .as_ref(),
+                set,
+            ),
+            Semantic::Colors(set) => ([Self::COLOR_0, Self::COLOR_1].as_ref(), set),
+            Semantic::Joints(set) => ([Self::JOINTS_0, Self::JOINTS_1].as_ref(), set),
+            Semantic::Weights(set) => ([Self::WEIGHTS_0, Self::WEIGHTS_1].as_ref(), set),
+            #[cfg(feature = "extras")]
+            Semantic::Extras(_) => return None,
+        };
+        flags.get(set as usize).copied()
+    }
+}
+
 /// A set of primitives to be rendered.
 #[derive(Clone, Debug)]
 pub struct Mesh<'a> {
@@ -112,7 +252,7 @@ pub struct MorphTarget<'a> {
 #[derive(Clone, Debug)]
 pub struct Primitive<'a> {
     /// The parent `Mesh` struct.
-    mesh: Mesh<'a>,
+    pub(crate) mesh: Mesh<'a>,
 
     /// The corresponding JSON index.
     index: usize,
@@ -188,6 +328,40 @@ impl<'a> Mesh<'a> {
     pub fn weights(&self) -> Option<&'a [f32]> {
         self.json.weights.as_deref()
     }
+
+    /// Returns the names of the morph targets of the mesh.
+    ///
+    /// glTF does not define morph target names, so this follows the common
+    /// convention of a `targetNames` array of strings in the mesh extras.
+    /// Returns `None` if there is no such array.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn target_names(&self) -> Option<Vec<String>> {
+        let extras: Value = serde_json::from_str(self.json.extras.as_ref()?.get()).ok()?;
+        extras
+            .get("targetNames")?
+            .as_array()?
+            .iter()
+            .map(|name| name.as_str().map(String::from))
+            .collect()
+    }
+
+    /// Computes the bounding box of all primitives from their vertex positions.
+    ///
+    /// Returns `None` if no primitive has readable positions. See
+    /// [`Primitive::compute_bounding_box`].
+    ///
+    /// [`Primitive::compute_bounding_box`]: struct.Primitive.html#method.compute_bounding_box
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn compute_bounding_box<'s, F>(&self, get_buffer_data: F) -> Option<BoundingBox>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.primitives()
+            .filter_map(|primitive| primitive.compute_bounding_box(get_buffer_data.clone()))
+            .reduce(|a, b| a.union(&b))
+    }
 }
 
 impl<'a> Primitive<'a> {
@@ -215,6 +389,137 @@ impl<'a> Primitive<'a> {
         Bounds { min, max }
     }
 
+    /// Computes the bounds of the `POSITION` vertex attribute by reading the
+    /// vertex data.
+    ///
+    /// Unlike [`bounding_box`], this does not rely on the accessor `min` and
+    /// `max` properties, which are frequently missing or inaccurate. Morph
+    /// targets and skinning are not taken into account. Returns `None` if the
+    /// primitive has no readable positions.
+    ///
+    /// [`bounding_box`]: #method.bounding_box
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn compute_bounding_box<'s, F>(&self, get_buffer_data: F) -> Option<BoundingBox>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let positions = self.get(&Semantic::Positions)?;
+        BoundingBox::from_points(accessor::Iter::new(positions, get_buffer_data)?)
+    }
+
+    /// Computes the surface area of the primitive from its vertex positions.
+    ///
+    /// Returns `None` if the primitive has no readable positions or does not
+    /// consist of triangles. See [`surface_area_transformed`] for the area
+    /// in the space of a node or scene.
+    ///
+    /// [`surface_area_transformed`]: #method.surface_area_transformed
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn surface_area<'s, F>(&'a self, get_buffer_data: F) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.surface_area_transformed(get_buffer_data, &geometry::IDENTITY)
+    }
+
+    /// Computes the surface area of the primitive with its vertex positions
+    /// transformed by the column-major matrix `transform`, such as a world
+    /// transform returned by [`Scene::traverse`].
+    ///
+    /// [`Scene::traverse`]: ../scene/struct.Scene.html#method.traverse
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn surface_area_transformed<'s, F>(
+        &'a self,
+        get_buffer_data: F,
+        transform: &[[f32; 4]; 4],
+    ) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let triangles = self.transformed_triangles(get_buffer_data, transform)?;
+        Some(geometry::measure(&triangles).0)
+    }
+
+    /// Computes the volume enclosed by the primitive from its vertex
+    /// positions.
+    ///
+    /// Returns `None` if the primitive is not closed, as reported by
+    /// [`is_closed`], since open meshes enclose no well-defined volume.
+    ///
+    /// [`is_closed`]: #method.is_closed
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn volume<'s, F>(&'a self, get_buffer_data: F) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.volume_transformed(get_buffer_data, &geometry::IDENTITY)
+    }
+
+    /// Computes the volume enclosed by the primitive with its vertex
+    /// positions transformed by the column-major matrix `transform`.
+    ///
+    /// Returns `None` if the primitive is not closed.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn volume_transformed<'s, F>(
+        &'a self,
+        get_buffer_data: F,
+        transform: &[[f32; 4]; 4],
+    ) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let triangles = self.transformed_triangles(get_buffer_data, transform)?;
+        geometry::measure(&triangles).1
+    }
+
+    /// Returns `true` if the triangles of the primitive form a closed,
+    /// consistently oriented surface.
+    ///
+    /// Vertices are matched by position, so vertices split along seams do
+    /// not open the surface. Returns `false` if the primitive has no readable
+    /// positions or does not consist of triangles.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn is_closed<'s, F>(&'a self, get_buffer_data: F) -> bool
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.volume(get_buffer_data).is_some()
+    }
+
+    /// Reads the triangles of the primitive with vertex positions transformed
+    /// by `transform`, skipping triangles with out of range indices.
+    #[cfg(feature = "utils")]
+    pub(crate) fn transformed_triangles<'s, F>(
+        &'a self,
+        get_buffer_data: F,
+        transform: &[[f32; 4]; 4],
+    ) -> Option<Vec<[[f32; 3]; 3]>>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let reader = self.reader(get_buffer_data);
+        let indices = reader.triangulate()?;
+        let positions = reader
+            .read_positions()?
+            .map(|position| geometry::transform_point(transform, position))
+            .collect::<Vec<_>>();
+        Some(
+            indices
+                .chunks_exact(3)
+                .filter_map(|triangle| {
+                    let vertex = |i: usize| positions.get(triangle[i] as usize).copied();
+                    Some([vertex(0)?, vertex(1)?, vertex(2)?])
+                })
+                .collect(),
+        )
+    }
+
     /// Returns the extension values map
     #[cfg(feature = "extensions")]
     #[cfg_attr(docsrs, doc(cfg(feature = "extensions")))]
@@ -257,6 +562,21 @@ impl<'a> Primitive<'a> {
             .map(|index| self.mesh.document.accessors().nth(index.value()).unwrap())
     }
 
+    /// Returns the set of standard vertex attributes present on this primitive.
+    ///
+    /// This is intended for cheaply selecting a vertex format or building
+    /// pipeline cache keys.
+    pub fn attribute_flags(&self) -> AttributeFlags {
+        self.json
+            .attributes
+            .keys()
+            .filter_map(|semantic| match semantic {
+                Checked::Valid(semantic) => AttributeFlags::from_semantic(semantic),
+                Checked::Invalid => None,
+            })
+            .collect()
+    }
+
     /// Returns an `Iterator` that visits the vertex attributes.
     pub fn attributes(&self) -> iter::Attributes<'a> {
         iter::Attributes {
@@ -280,6 +600,23 @@ impl<'a> Primitive<'a> {
         self.json.mode.unwrap()
     }
 
+    /// Returns the index of the morph target named `name`, as named by
+    /// [`Mesh::target_names`].
+    ///
+    /// Returns `None` if there is no such name or if the primitive has fewer
+    /// morph targets.
+    ///
+    /// [`Mesh::target_names`]: struct.Mesh.html#method.target_names
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn target_index_by_name(&self, name: &str) -> Option<usize> {
+        self.mesh
+            .target_names()?
+            .iter()
+            .position(|target| target == name)
+            .filter(|&index| index < self.morph_targets().len())
+    }
+
     /// Returns an `Iterator` that visits the morph targets of the primitive.
     pub fn morph_targets(&self) -> iter::MorphTargets<'a> {
         if let Some(slice) = self.json.targets.as_ref() {
@@ -305,7 +642,7 @@ impl<'a> Primitive<'a> {
             .as_ref()
             .and_then(|extensions| extensions.khr_materials_variants.as_ref())
             .map(|variants| variants.mappings.iter())
-            .unwrap_or_else(|| (&[]).iter());
+            .unwrap_or_else(|| [].iter());
 
         iter::Mappings {
             document: self.mesh.document,
@@ -313,6 +650,103 @@ impl<'a> Primitive<'a> {
         }
     }
 
+    /// Returns the material used by this primitive for the given variant as
+    /// defined by the `KHR_materials_variants` extension.
+    ///
+    /// Falls back to [`material`](#method.material) if the variant is not
+    /// mapped.
+    #[cfg(feature = "KHR_materials_variants")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
+    pub fn variant_material(&self, variant: usize) -> Material<'a> {
+        self.mappings()
+            .find(|mapping| mapping.variants().iter().any(|&v| v as usize == variant))
+            .map(|mapping| mapping.material())
+            .unwrap_or_else(|| self.material())
+    }
+
+    /// Returns a stable hash of the primitive geometry.
+    ///
+    /// The hash covers the primitive mode, the content of every vertex
+    /// attribute, the indices, and the morph targets. It is stable across runs
+    /// and platforms, so it can be used as a key for caching derived GPU
+    /// resources. The material is not included; see
+    /// [`Material::content_hash`](../material/struct.Material.html#method.content_hash).
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn content_hash<'s, F>(&self, get_buffer_data: F) -> u64
+    where
+        F: Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut hasher = Hasher::new();
+        hasher.write_u32(self.mode().as_gl_enum());
+        for (semantic, accessor) in self.attributes() {
+            hasher.write_str(&semantic.to_string());
+            accessor.hash_into(&mut hasher, &get_buffer_data);
+        }
+        match self.indices() {
+            Some(accessor) => {
+                hasher.write(&[1]);
+                accessor.hash_into(&mut hasher, &get_buffer_data);
+            }
+            None => hasher.write(&[0]),
+        }
+        for target in self.morph_targets() {
+            for accessor in [target.positions(), target.normals(), target.tangents()] {
+                match accessor {
+                    Some(accessor) => {
+                        hasher.write(&[1]);
+                        accessor.hash_into(&mut hasher, &get_buffer_data);
+                    }
+                    None => hasher.write(&[0]),
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Generates vertex tangents for the primitive, ignoring any tangents
+    /// already present.
+    ///
+    /// Each vertex receives the angle-weighted average of the tangents of
+    /// its triangles, computed from the vertex positions, the vertex normals,
+    /// and the texture co-ordinate set used by the normal texture of the
+    /// material, or set 0 if there is none. Area-weighted averages of the
+    /// face normals are used if the primitive has no normals. Returns `None`
+    /// if the primitive has no positions or texture co-ordinates.
+    ///
+    /// This is not MikkTSpace: vertices are never split, so vertices shared
+    /// by triangles with mirrored texture co-ordinates receive a single
+    /// averaged tangent, and normal maps baked against MikkTSpace tangents
+    /// may show seams. Vertices not part of any triangle with non-degenerate
+    /// texture co-ordinates receive an arbitrary tangent perpendicular to the
+    /// normal.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn generate_averaged_tangents<'s, F>(&'a self, get_buffer_data: F) -> Option<Vec<[f32; 4]>>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let reader = self.reader(get_buffer_data);
+        let positions = reader.read_positions()?.collect::<Vec<_>>();
+        let set = self
+            .material()
+            .normal_texture()
+            .map(|texture| texture.tex_coord())
+            .unwrap_or(0);
+        let tex_coords = reader.read_tex_coords(set)?.into_f32().collect::<Vec<_>>();
+        let triangles = util::triangles(self.mode(), &reader.read_indices_u32());
+        let normals = match reader.read_normals() {
+            Some(normals) => normals.collect(),
+            None => util::average_normals(&positions, &triangles),
+        };
+        Some(util::average_tangents(
+            &positions,
+            &normals,
+            &tex_coords,
+            &triangles,
+        ))
+    }
+
     /// Constructs the primitive reader.
     #[cfg(feature = "utils")]
     #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
@@ -374,7 +808,7 @@ where
                         .map(ReadColors::RgbaU16),
                     (F32, Vec4) => accessor::Iter::new(accessor, self.get_buffer_data.clone())
                         .map(ReadColors::RgbaF32),
-                    _ => unreachable!(),
+                    _ => None,
                 },
             )
     }
@@ -393,7 +827,7 @@ where
                     .map(ReadIndices::U16),
                 DataType::U32 => accessor::Iter::new(accessor, self.get_buffer_data.clone())
                     .map(ReadIndices::U32),
-                _ => unreachable!(),
+                _ => None,
             })
     }
 
@@ -410,7 +844,7 @@ where
                 DataType::U16 => {
                     accessor::Iter::new(accessor, self.get_buffer_data.clone()).map(ReadJoints::U16)
                 }
-                _ => unreachable!(),
+                _ => None,
             })
     }
 
@@ -427,10 +861,31 @@ where
                     .map(ReadTexCoords::U16),
                 DataType::F32 => accessor::Iter::new(accessor, self.get_buffer_data.clone())
                     .map(ReadTexCoords::F32),
-                _ => unreachable!(),
+                _ => None,
             })
     }
 
+    /// Visits the texture co-ordinates of the primitive as `f32` with
+    /// `transform` applied.
+    ///
+    /// This is intended for renderers which do not implement the
+    /// `KHR_texture_transform` extension in their shaders. Note that the
+    /// transform may override the texture co-ordinate set, see
+    /// [`TextureTransform::tex_coord`].
+    ///
+    /// [`TextureTransform::tex_coord`]: ../texture/struct.TextureTransform.html#method.tex_coord
+    #[cfg(feature = "KHR_texture_transform")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_texture_transform")))]
+    pub fn read_tex_coords_transformed(
+        &self,
+        set: u32,
+        transform: &crate::texture::TextureTransform<'_>,
+    ) -> Option<util::tex_coords::TransformedIter<'s>> {
+        self.read_tex_coords(set).map(|tex_coords| {
+            util::tex_coords::TransformedIter::new(tex_coords, transform.matrix())
+        })
+    }
+
     /// Visits the joint weights of the primitive.
     pub fn read_weights(&self, set: u32) -> Option<util::ReadWeights<'s>> {
         use self::accessor::DataType;
@@ -445,10 +900,208 @@ where
                     .map(ReadWeights::U16),
                 DataType::F32 => accessor::Iter::new(accessor, self.get_buffer_data.clone())
                     .map(ReadWeights::F32),
-                _ => unreachable!(),
+                _ => None,
             })
     }
 
+    /// Splits the vertices of the primitive into contiguous chunks of
+    /// `chunk_size` vertices, the last of which may be shorter.
+    ///
+    /// Each chunk reads only the vertex attributes in its range, so chunks can
+    /// be processed independently of one another.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunks(&self, chunk_size: usize) -> util::VertexChunks<'a, 's, F> {
+        util::VertexChunks::new(self.clone(), chunk_size, self.vertex_count())
+    }
+
+    /// Splits the vertices of the primitive into contiguous chunks of
+    /// `chunk_size` vertices as a parallel iterator.
+    ///
+    /// This is the parallel counterpart of [`chunks`](#method.chunks), which
+    /// is useful for expensive per-vertex work such as CPU skinning.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rayon::prelude::*;
+    ///
+    /// let (document, buffers, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+    /// let mesh = document.meshes().next().unwrap();
+    /// let primitive = mesh.primitives().next().unwrap();
+    /// let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    /// let translated: Vec<Vec<[f32; 3]>> = reader
+    ///     .par_chunks(8)
+    ///     .map(|chunk| {
+    ///         chunk
+    ///             .read_positions()
+    ///             .unwrap()
+    ///             .map(|[x, y, z]| [x + 1.0, y, z])
+    ///             .collect()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(translated.concat().len(), 24);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_chunks(
+        &self,
+        chunk_size: usize,
+    ) -> rayon::vec::IntoIter<util::VertexChunk<'a, 's, F>>
+    where
+        F: Send,
+    {
+        use rayon::iter::IntoParallelIterator;
+        self.chunks(chunk_size).collect::<Vec<_>>().into_par_iter()
+    }
+
+    /// Returns the number of vertices of the primitive.
+    fn vertex_count(&self) -> usize {
+        self.primitive
+            .get(&Semantic::Positions)
+            .map(|accessor| accessor.count())
+            .unwrap_or(0)
+    }
+
+    /// Reads the vertex draw sequence of a primitive as `u32`, substituting
+    /// the sequence of every vertex in order if the primitive is not indexed.
+    pub fn read_indices_u32(&self) -> Vec<u32> {
+        match self.read_indices() {
+            Some(indices) => indices.into_u32().collect(),
+            None => (0..self.vertex_count() as u32).collect(),
+        }
+    }
+
+    /// Reads the vertex draw sequence of a primitive as a triangle list.
+    ///
+    /// Triangle strips and fans are converted to lists with consistent
+    /// winding. Returns `None` if the primitive does not consist of
+    /// triangles.
+    pub fn triangulate(&self) -> Option<Vec<u32>> {
+        use self::Mode::{TriangleFan, TriangleStrip, Triangles};
+        match self.primitive.mode() {
+            Triangles | TriangleStrip | TriangleFan => {
+                let triangles = util::triangles(self.primitive.mode(), &self.read_indices_u32());
+                Some(triangles.into_iter().flatten().collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Expands per-vertex `values`, such as the positions returned by
+    /// [`read_positions`](#method.read_positions), into a flat stream with one
+    /// element per drawn vertex.
+    ///
+    /// Triangle primitives are expanded along the triangle list returned by
+    /// [`triangulate`](#method.triangulate), other primitives along their draw
+    /// sequence. Triangles, lines, or points referencing vertices beyond the
+    /// end of `values` are skipped.
+    pub fn deindex<T: Clone>(&self, values: &[T]) -> Vec<T> {
+        let (indices, width) = match self.triangulate() {
+            Some(indices) => (indices, 3),
+            None => {
+                let width = match self.primitive.mode() {
+                    Mode::Lines => 2,
+                    _ => 1,
+                };
+                (self.read_indices_u32(), width)
+            }
+        };
+        indices
+            .chunks_exact(width)
+            .filter(|chunk| chunk.iter().all(|&index| (index as usize) < values.len()))
+            .flatten()
+            .map(|&index| values[index as usize].clone())
+            .collect()
+    }
+
+    /// Reads the vertex texture co-ordinates of a primitive as `f32`,
+    /// substituting `[0.0, 0.0]` for every vertex if they are not present.
+    pub fn read_tex_coords_or_default(&self, set: u32) -> Vec<[f32; 2]> {
+        match self.read_tex_coords(set) {
+            Some(tex_coords) => tex_coords.into_f32().collect(),
+            None => vec![[0.0; 2]; self.vertex_count()],
+        }
+    }
+
+    /// Reads the vertex colors of a primitive as RGBA `f32`, substituting
+    /// opaque white for every vertex if they are not present.
+    pub fn read_colors_or_white(&self, set: u32) -> Vec<[f32; 4]> {
+        match self.read_colors(set) {
+            Some(colors) => colors.into_rgba_f32().collect(),
+            None => vec![[1.0; 4]; self.vertex_count()],
+        }
+    }
+
+    /// Reads the vertex normals of a primitive expanded like
+    /// [`deindex`](#method.deindex), computing flat normals from `positions`
+    /// if they are not present.
+    ///
+    /// Returns one normal per drawn vertex, matching
+    /// `self.deindex(&positions)`. The specification asks for flat shading
+    /// when normals are missing, so every triangle corner then receives the
+    /// face normal of its triangle, even where the triangles share vertices.
+    /// Corners of degenerate triangles, and lines and points without
+    /// normals, receive a zero normal.
+    pub fn read_normals_or_flat(&self, positions: &[[f32; 3]]) -> Vec<[f32; 3]> {
+        use self::Mode::{TriangleFan, TriangleStrip, Triangles};
+        if let Some(normals) = self.read_normals() {
+            return self.deindex(&normals.collect::<Vec<_>>());
+        }
+
+        let corners = self.deindex(positions);
+        match self.primitive.mode() {
+            Triangles | TriangleStrip | TriangleFan => corners
+                .chunks_exact(3)
+                .flat_map(|triangle| {
+                    let n = geometry::cross(
+                        geometry::sub(triangle[1], triangle[0]),
+                        geometry::sub(triangle[2], triangle[0]),
+                    );
+                    let length = geometry::dot(n, n).sqrt();
+                    if length > 0.0 {
+                        [[n[0] / length, n[1] / length, n[2] / length]; 3]
+                    } else {
+                        [[0.0; 3]; 3]
+                    }
+                })
+                .collect(),
+            _ => vec![[0.0; 3]; corners.len()],
+        }
+    }
+
+    /// Reads the vertex tangents of a primitive, generating averaged
+    /// tangents from `positions`, `normals`, and `tex_coords` if they are not
+    /// present.
+    ///
+    /// The generated tangents only approximate the MikkTSpace tangents the
+    /// specification asks for; see [`Primitive::generate_averaged_tangents`].
+    ///
+    /// [`Primitive::generate_averaged_tangents`]: struct.Primitive.html#method.generate_averaged_tangents
+    pub fn read_tangents_or_averaged(
+        &self,
+        positions: &[[f32; 3]],
+        normals: &[[f32; 3]],
+        tex_coords: &[[f32; 2]],
+    ) -> Vec<[f32; 4]> {
+        if let Some(tangents) = self.read_tangents() {
+            return tangents.collect();
+        }
+
+        let triangles = util::triangles(self.primitive.mode(), &self.read_indices_u32());
+        util::average_tangents(positions, normals, tex_coords, &triangles)
+    }
+
     /// Visits the morph targets of the primitive.
     pub fn read_morph_targets(&self) -> util::ReadMorphTargets<'a, 's, F> {
         util::ReadMorphTargets {
@@ -0,0 +1,163 @@
+use core::ops::Range;
+
+use crate::mesh;
+use crate::Buffer;
+
+use super::{
+    ReadColors, ReadJoints, ReadNormals, ReadPositions, ReadTangents, ReadTexCoords, ReadWeights,
+};
+
+/// A contiguous range of the vertices of a primitive.
+///
+/// The `read_*` functions behave like those of [`Reader`] but only visit the
+/// vertices in [`range`](#method.range).
+///
+/// [`Reader`]: ../struct.Reader.html
+#[derive(Clone, Debug)]
+pub struct VertexChunk<'a, 's, F>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    reader: mesh::Reader<'a, 's, F>,
+    range: Range<usize>,
+}
+
+/// Iterator over the vertex chunks of a primitive.
+///
+/// Created by [`Reader::chunks`].
+///
+/// [`Reader::chunks`]: ../struct.Reader.html#method.chunks
+#[derive(Clone, Debug)]
+pub struct VertexChunks<'a, 's, F>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    reader: mesh::Reader<'a, 's, F>,
+    chunk_size: usize,
+    remaining: Range<usize>,
+}
+
+impl<'a, 's, F> VertexChunk<'a, 's, F>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    /// Returns the range of vertex indices visited by this chunk.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of vertices in this chunk.
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns `true` if this chunk contains no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Visits the vertex positions of the chunk.
+    pub fn read_positions(&self) -> Option<ReadPositions<'s>> {
+        self.reader.read_positions().map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the vertex normals of the chunk.
+    pub fn read_normals(&self) -> Option<ReadNormals<'s>> {
+        self.reader.read_normals().map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the vertex tangents of the chunk.
+    pub fn read_tangents(&self) -> Option<ReadTangents<'s>> {
+        self.reader.read_tangents().map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the vertex colors of the chunk.
+    pub fn read_colors(&self, set: u32) -> Option<ReadColors<'s>> {
+        self.reader.read_colors(set).map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the vertex texture co-ordinates of the chunk.
+    pub fn read_tex_coords(&self, set: u32) -> Option<ReadTexCoords<'s>> {
+        self.reader.read_tex_coords(set).map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the joint indices of the chunk.
+    pub fn read_joints(&self, set: u32) -> Option<ReadJoints<'s>> {
+        self.reader.read_joints(set).map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+
+    /// Visits the joint weights of the chunk.
+    pub fn read_weights(&self, set: u32) -> Option<ReadWeights<'s>> {
+        self.reader.read_weights(set).map(|mut iter| {
+            iter.restrict(self.range());
+            iter
+        })
+    }
+}
+
+impl<'a, 's, F> VertexChunks<'a, 's, F>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    /// Constructs a `VertexChunks` over the vertices `0..vertex_count`.
+    pub(crate) fn new(
+        reader: mesh::Reader<'a, 's, F>,
+        chunk_size: usize,
+        vertex_count: usize,
+    ) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self {
+            reader,
+            chunk_size,
+            remaining: 0..vertex_count,
+        }
+    }
+}
+
+impl<'a, 's, F> Iterator for VertexChunks<'a, 's, F>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    type Item = VertexChunk<'a, 's, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let start = self.remaining.start;
+        let end = start + self.chunk_size.min(self.remaining.len());
+        self.remaining.start = end;
+        Some(VertexChunk {
+            reader: self.reader.clone(),
+            range: start..end,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.remaining.len() + self.chunk_size - 1) / self.chunk_size;
+        (len, Some(len))
+    }
+}
+
+impl<'a, 's, F> ExactSizeIterator for VertexChunks<'a, 's, F> where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>
+{
+}
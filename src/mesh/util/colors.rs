@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::Normalize;
 
@@ -38,13 +38,13 @@ trait ColorChannel {
 
 impl ColorChannel for u8 {
     fn max_color() -> Self {
-        u8::max_value()
+        u8::MAX
     }
 }
 
 impl ColorChannel for u16 {
     fn max_color() -> Self {
-        u16::max_value()
+        u16::MAX
     }
 }
 
@@ -168,6 +168,32 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            ReadColors::RgbU8(ref mut i) => i.next_back().map(A::cast_rgb_u8),
+            ReadColors::RgbU16(ref mut i) => i.next_back().map(A::cast_rgb_u16),
+            ReadColors::RgbF32(ref mut i) => i.next_back().map(A::cast_rgb_f32),
+            ReadColors::RgbaU8(ref mut i) => i.next_back().map(A::cast_rgba_u8),
+            ReadColors::RgbaU16(ref mut i) => i.next_back().map(A::cast_rgba_u16),
+            ReadColors::RgbaF32(ref mut i) => i.next_back().map(A::cast_rgba_f32),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            ReadColors::RgbU8(ref mut i) => i.nth_back(x).map(A::cast_rgb_u8),
+            ReadColors::RgbU16(ref mut i) => i.nth_back(x).map(A::cast_rgb_u16),
+            ReadColors::RgbF32(ref mut i) => i.nth_back(x).map(A::cast_rgb_f32),
+            ReadColors::RgbaU8(ref mut i) => i.nth_back(x).map(A::cast_rgba_u8),
+            ReadColors::RgbaU16(ref mut i) => i.nth_back(x).map(A::cast_rgba_u16),
+            ReadColors::RgbaF32(ref mut i) => i.nth_back(x).map(A::cast_rgba_f32),
+        }
+    }
+}
+
 impl Cast for RgbU8 {
     type Output = [u8; 3];
 
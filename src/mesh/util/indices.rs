@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use super::ReadIndices;
 
@@ -80,6 +80,26 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            ReadIndices::U8(ref mut i) => i.next_back().map(A::cast_u8),
+            ReadIndices::U16(ref mut i) => i.next_back().map(A::cast_u16),
+            ReadIndices::U32(ref mut i) => i.next_back().map(A::cast_u32),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            ReadIndices::U8(ref mut i) => i.nth_back(x).map(A::cast_u8),
+            ReadIndices::U16(ref mut i) => i.nth_back(x).map(A::cast_u16),
+            ReadIndices::U32(ref mut i) => i.nth_back(x).map(A::cast_u32),
+        }
+    }
+}
+
 impl Cast for U32 {
     type Output = u32;
 
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use super::ReadJoints;
 
@@ -73,6 +73,24 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            ReadJoints::U8(ref mut i) => i.next_back().map(A::cast_u8),
+            ReadJoints::U16(ref mut i) => i.next_back().map(A::cast_u16),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            ReadJoints::U8(ref mut i) => i.nth_back(x).map(A::cast_u8),
+            ReadJoints::U16(ref mut i) => i.nth_back(x).map(A::cast_u16),
+        }
+    }
+}
+
 impl Cast for U16 {
     type Output = [u16; 4];
 
@@ -13,7 +13,18 @@ pub mod tex_coords;
 /// Casting iterator adapters for node weights.
 pub mod weights;
 
+/// Tangent generation.
+mod tangents;
+
+/// Contiguous vertex ranges of a primitive.
+mod chunks;
+
 use crate::mesh;
+use crate::prelude::*;
+
+pub(crate) use self::tangents::{average as average_tangents, average_normals};
+
+pub use self::chunks::{VertexChunk, VertexChunks};
 
 use crate::accessor::Iter;
 use crate::Buffer;
@@ -145,7 +156,46 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.reader.primitive.morph_targets().size_hint()
+        let len = self
+            .reader
+            .primitive
+            .morph_targets()
+            .len()
+            .saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+/// Returns the vertex indices of the triangles of a primitive.
+///
+/// `indices` is the vertex draw sequence. Modes that do not describe
+/// triangles yield no triangles. Strips and fans are converted to lists with
+/// consistent winding.
+pub(crate) fn triangles(mode: mesh::Mode, indices: &[u32]) -> Vec<[u32; 3]> {
+    use mesh::Mode;
+    match mode {
+        Mode::Triangles => indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect(),
+        Mode::TriangleStrip => indices
+            .windows(3)
+            .enumerate()
+            .map(|(i, window)| {
+                if i % 2 == 0 {
+                    [window[0], window[1], window[2]]
+                } else {
+                    [window[1], window[0], window[2]]
+                }
+            })
+            .collect(),
+        Mode::TriangleFan => indices
+            .get(1..)
+            .unwrap_or_default()
+            .windows(2)
+            .map(|window| [window[0], window[1], indices[0]])
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
@@ -185,6 +235,18 @@ impl<'a> ReadColors<'a> {
     pub fn into_rgba_f32(self) -> self::colors::CastingIter<'a, self::colors::RgbaF32> {
         self::colors::CastingIter::new(self)
     }
+
+    /// Drops all items outside of `range`.
+    pub(crate) fn restrict(&mut self, range: core::ops::Range<usize>) {
+        match self {
+            ReadColors::RgbU8(ref mut iter) => iter.restrict(range),
+            ReadColors::RgbU16(ref mut iter) => iter.restrict(range),
+            ReadColors::RgbF32(ref mut iter) => iter.restrict(range),
+            ReadColors::RgbaU8(ref mut iter) => iter.restrict(range),
+            ReadColors::RgbaU16(ref mut iter) => iter.restrict(range),
+            ReadColors::RgbaF32(ref mut iter) => iter.restrict(range),
+        }
+    }
 }
 
 impl<'a> ReadIndices<'a> {
@@ -199,6 +261,14 @@ impl<'a> ReadJoints<'a> {
     pub fn into_u16(self) -> self::joints::CastingIter<'a, self::joints::U16> {
         self::joints::CastingIter::new(self)
     }
+
+    /// Drops all items outside of `range`.
+    pub(crate) fn restrict(&mut self, range: core::ops::Range<usize>) {
+        match self {
+            ReadJoints::U8(ref mut iter) => iter.restrict(range),
+            ReadJoints::U16(ref mut iter) => iter.restrict(range),
+        }
+    }
 }
 
 impl<'a> ReadTexCoords<'a> {
@@ -219,6 +289,15 @@ impl<'a> ReadTexCoords<'a> {
     pub fn into_f32(self) -> self::tex_coords::CastingIter<'a, self::tex_coords::F32> {
         self::tex_coords::CastingIter::new(self)
     }
+
+    /// Drops all items outside of `range`.
+    pub(crate) fn restrict(&mut self, range: core::ops::Range<usize>) {
+        match self {
+            ReadTexCoords::U8(ref mut iter) => iter.restrict(range),
+            ReadTexCoords::U16(ref mut iter) => iter.restrict(range),
+            ReadTexCoords::F32(ref mut iter) => iter.restrict(range),
+        }
+    }
 }
 
 impl<'a> ReadWeights<'a> {
@@ -239,4 +318,13 @@ impl<'a> ReadWeights<'a> {
     pub fn into_f32(self) -> self::weights::CastingIter<'a, self::weights::F32> {
         self::weights::CastingIter::new(self)
     }
+
+    /// Drops all items outside of `range`.
+    pub(crate) fn restrict(&mut self, range: core::ops::Range<usize>) {
+        match self {
+            ReadWeights::U8(ref mut iter) => iter.restrict(range),
+            ReadWeights::U16(ref mut iter) => iter.restrict(range),
+            ReadWeights::F32(ref mut iter) => iter.restrict(range),
+        }
+    }
 }
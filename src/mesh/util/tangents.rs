@@ -0,0 +1,143 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::geometry::{cross, dot, sub};
+use crate::prelude::*;
+
+/// Returns `v` with its component along the unit vector `n` removed, normalized,
+/// or `None` if nothing remains.
+fn orthonormalize(v: [f32; 3], n: [f32; 3]) -> Option<[f32; 3]> {
+    let d = dot(v, n);
+    let v = [v[0] - n[0] * d, v[1] - n[1] * d, v[2] - n[2] * d];
+    let length = dot(v, v).sqrt();
+    if length > 1.0e-12 && length.is_finite() {
+        Some([v[0] / length, v[1] / length, v[2] / length])
+    } else {
+        None
+    }
+}
+
+/// Returns an arbitrary unit vector perpendicular to the unit vector `n`.
+fn perpendicular(n: [f32; 3]) -> [f32; 3] {
+    let axis = if n[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    orthonormalize(axis, n).unwrap_or([1.0, 0.0, 0.0])
+}
+
+/// Returns the area-weighted average of the face normals around each vertex.
+///
+/// Vertices which are not part of any non-degenerate triangle receive a zero
+/// normal.
+pub(crate) fn average_normals(positions: &[[f32; 3]], triangles: &[[u32; 3]]) -> Vec<[f32; 3]> {
+    let mut normals = vec![[0.0f32; 3]; positions.len()];
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(|index| index as usize);
+        if a >= positions.len() || b >= positions.len() || c >= positions.len() {
+            continue;
+        }
+        // The length of the cross product is twice the area of the triangle.
+        let n = cross(
+            sub(positions[b], positions[a]),
+            sub(positions[c], positions[a]),
+        );
+        for vertex in [a, b, c] {
+            for i in 0..3 {
+                normals[vertex][i] += n[i];
+            }
+        }
+    }
+    for n in &mut normals {
+        let length = dot(*n, *n).sqrt();
+        if length > 0.0 {
+            *n = [n[0] / length, n[1] / length, n[2] / length];
+        }
+    }
+    normals
+}
+
+/// Generates per-vertex tangents by averaging the tangents of the triangles
+/// around each vertex.
+///
+/// Every triangle corner contributes its texture space tangent projected onto
+/// the plane of the vertex normal and weighted by the corner angle. The
+/// accumulated tangents are orthonormalized against the vertex normals and the
+/// handedness is taken from the accumulated bitangents. Vertices are never
+/// split, so vertices shared by triangles with mirrored texture co-ordinates
+/// receive a single averaged basis.
+pub(crate) fn average(
+    positions: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    tex_coords: &[[f32; 2]],
+    triangles: &[[u32; 3]],
+) -> Vec<[f32; 4]> {
+    let count = positions.len().min(normals.len()).min(tex_coords.len());
+    let mut tangents = vec![[0.0f32; 3]; positions.len()];
+    let mut bitangents = vec![[0.0f32; 3]; positions.len()];
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(|index| index as usize);
+        if a >= count || b >= count || c >= count {
+            continue;
+        }
+        let e1 = sub(positions[b], positions[a]);
+        let e2 = sub(positions[c], positions[a]);
+        let (s1, t1) = (
+            tex_coords[b][0] - tex_coords[a][0],
+            tex_coords[b][1] - tex_coords[a][1],
+        );
+        let (s2, t2) = (
+            tex_coords[c][0] - tex_coords[a][0],
+            tex_coords[c][1] - tex_coords[a][1],
+        );
+        let area = s1 * t2 - s2 * t1;
+        if area == 0.0 {
+            continue;
+        }
+        // Only the directions matter, so the reciprocal of the area is
+        // replaced by its sign to avoid amplifying tiny texture areas.
+        let sign = area.signum();
+        let tangent = [
+            (e1[0] * t2 - e2[0] * t1) * sign,
+            (e1[1] * t2 - e2[1] * t1) * sign,
+            (e1[2] * t2 - e2[2] * t1) * sign,
+        ];
+        let bitangent = [
+            (e2[0] * s1 - e1[0] * s2) * sign,
+            (e2[1] * s1 - e1[1] * s2) * sign,
+            (e2[2] * s1 - e1[2] * s2) * sign,
+        ];
+
+        for (vertex, previous, next) in [(a, c, b), (b, a, c), (c, b, a)] {
+            let n = normals[vertex];
+            let (tangent, bitangent) =
+                match (orthonormalize(tangent, n), orthonormalize(bitangent, n)) {
+                    (Some(tangent), Some(bitangent)) => (tangent, bitangent),
+                    _ => continue,
+                };
+            let u = orthonormalize(sub(positions[next], positions[vertex]), n);
+            let v = orthonormalize(sub(positions[previous], positions[vertex]), n);
+            let angle = match (u, v) {
+                (Some(u), Some(v)) => dot(u, v).clamp(-1.0, 1.0).acos(),
+                _ => continue,
+            };
+            for i in 0..3 {
+                tangents[vertex][i] += tangent[i] * angle;
+                bitangents[vertex][i] += bitangent[i] * angle;
+            }
+        }
+    }
+
+    (0..positions.len())
+        .map(|vertex| {
+            let n = normals.get(vertex).copied().unwrap_or([0.0, 0.0, 1.0]);
+            let t = orthonormalize(tangents[vertex], n).unwrap_or_else(|| perpendicular(n));
+            let w = if dot(cross(n, t), bitangents[vertex]) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            [t[0], t[1], t[2], w]
+        })
+        .collect()
+}
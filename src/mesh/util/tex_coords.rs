@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::Normalize;
 
@@ -90,6 +90,26 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            ReadTexCoords::U8(ref mut i) => i.next_back().map(A::cast_u8),
+            ReadTexCoords::U16(ref mut i) => i.next_back().map(A::cast_u16),
+            ReadTexCoords::F32(ref mut i) => i.next_back().map(A::cast_f32),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            ReadTexCoords::U8(ref mut i) => i.nth_back(x).map(A::cast_u8),
+            ReadTexCoords::U16(ref mut i) => i.nth_back(x).map(A::cast_u16),
+            ReadTexCoords::F32(ref mut i) => i.nth_back(x).map(A::cast_f32),
+        }
+    }
+}
+
 impl Cast for U8 {
     type Output = [u8; 2];
 
@@ -137,3 +157,51 @@ impl Cast for F32 {
         x.normalize()
     }
 }
+
+/// Texture co-ordinates of type `[f32; 2]` with a `KHR_texture_transform`
+/// applied.
+#[cfg(feature = "KHR_texture_transform")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_texture_transform")))]
+#[derive(Clone, Debug)]
+pub struct TransformedIter<'a> {
+    iter: CastingIter<'a, F32>,
+    matrix: [[f32; 3]; 3],
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> TransformedIter<'a> {
+    pub(crate) fn new(iter: ReadTexCoords<'a>, matrix: [[f32; 3]; 3]) -> Self {
+        Self {
+            iter: iter.into_f32(),
+            matrix,
+        }
+    }
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> ExactSizeIterator for TransformedIter<'a> {}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> Iterator for TransformedIter<'a> {
+    type Item = [f32; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let uv = self.iter.next()?;
+        Some(crate::texture::apply_matrix(&self.matrix, uv))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> DoubleEndedIterator for TransformedIter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let uv = self.iter.next_back()?;
+        Some(crate::texture::apply_matrix(&self.matrix, uv))
+    }
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::Normalize;
 
@@ -90,6 +90,26 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+impl<'a, A: Cast> DoubleEndedIterator for CastingIter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.0 {
+            ReadWeights::U8(ref mut i) => i.next_back().map(A::cast_u8),
+            ReadWeights::U16(ref mut i) => i.next_back().map(A::cast_u16),
+            ReadWeights::F32(ref mut i) => i.next_back().map(A::cast_f32),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, x: usize) -> Option<Self::Item> {
+        match self.0 {
+            ReadWeights::U8(ref mut i) => i.nth_back(x).map(A::cast_u8),
+            ReadWeights::U16(ref mut i) => i.nth_back(x).map(A::cast_u16),
+            ReadWeights::F32(ref mut i) => i.nth_back(x).map(A::cast_f32),
+        }
+    }
+}
+
 impl Cast for U8 {
     type Output = [u8; 4];
 
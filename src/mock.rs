@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use std::{io, thread};
+
+use crate::buffer;
+use crate::import::{Fetcher, ResourceKind, ResourceRequest};
+use crate::memory::normalize;
+use crate::{Error, Result};
+
+/// Returns the request `read` functions forward, which know nothing but the
+/// `base` and `path` of the resource.
+fn path_request<'a>(base: Option<&'a Path>, path: &'a str) -> ResourceRequest<'a> {
+    ResourceRequest {
+        kind: ResourceKind::Buffer,
+        index: None,
+        uri: path,
+        base,
+        path,
+        expected_length: None,
+        mime_type: None,
+    }
+}
+
+/// A request received by a [`MockScheme`].
+///
+/// [`MockScheme`]: struct.MockScheme.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Request {
+    /// The kind of resource requested.
+    pub kind: ResourceKind,
+
+    /// The index of the buffer or image in the document, if known.
+    pub index: Option<usize>,
+
+    /// The URI as written in the document.
+    pub uri: String,
+
+    /// The normalized path of the resource, joined with the base directory.
+    pub path: String,
+}
+
+/// The response of a [`MockScheme`] for one path.
+///
+/// [`MockScheme`]: struct.MockScheme.html
+#[derive(Clone, Debug)]
+enum Response {
+    /// The resource data.
+    Data(Vec<u8>),
+
+    /// An `Error::Io` of the given kind.
+    Error(io::ErrorKind),
+}
+
+/// A fetcher serving scripted responses, for testing import error handling.
+///
+/// Every path is answered with its registered data or error, or with an
+/// `Error::Io` of kind `NotFound` if nothing is registered. Requests are
+/// recorded, so that tests can check which resources were fetched, and in
+/// which order.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::mock::MockScheme;
+///
+/// let mut scheme = MockScheme::new()
+///     .with_file("models/Box0.bin", std::fs::read("examples/Box0.bin")?)
+///     .with_error("models/Texture.png", std::io::ErrorKind::PermissionDenied);
+/// let slice = std::fs::read("examples/Box.gltf")?;
+/// let base = std::path::Path::new("models");
+/// let (_, buffers, _) = gltf::import_slice(&slice, Some(base), |base, path| {
+///     scheme.read(base, path)
+/// })?;
+/// assert_eq!(buffers[0].len(), 648);
+/// assert_eq!(scheme.requests()[0].path, "models/Box0.bin");
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MockScheme {
+    /// Maps normalized paths to responses.
+    responses: HashMap<String, Response>,
+
+    /// The requests received, in order.
+    requests: Vec<Request>,
+}
+
+impl MockScheme {
+    /// Constructs a `MockScheme` without responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves `data` for `path`.
+    pub fn with_file<P: AsRef<Path>>(mut self, path: P, data: Vec<u8>) -> Self {
+        let path = normalize(path.as_ref());
+        self.responses.insert(path, Response::Data(data));
+        self
+    }
+
+    /// Fails requests for `path` with an `Error::Io` of kind `kind`.
+    pub fn with_error<P: AsRef<Path>>(mut self, path: P, kind: io::ErrorKind) -> Self {
+        let path = normalize(path.as_ref());
+        self.responses.insert(path, Response::Error(kind));
+        self
+    }
+
+    /// Returns the requests received so far, in order.
+    pub fn requests(&self) -> &[Request] {
+        &self.requests
+    }
+
+    /// Reads a resource with the signature of [`filesystem_fetcher`], such
+    /// that `|base, path| scheme.read(base, path)` can be passed to the
+    /// `import` functions.
+    ///
+    /// Requests made this way are recorded as `ResourceKind::Buffer`
+    /// requests without an index.
+    ///
+    /// [`filesystem_fetcher`]: ../fn.filesystem_fetcher.html
+    pub fn read(&mut self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        self.fetch(&path_request(base, path))
+            .map(buffer::Bytes::into_vec)
+    }
+}
+
+impl Fetcher for MockScheme {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        let path = match request.base {
+            Some(base) => normalize(&base.join(request.path)),
+            None => normalize(Path::new(request.path)),
+        };
+        self.requests.push(Request {
+            kind: request.kind,
+            index: request.index,
+            uri: request.uri.to_owned(),
+            path: path.clone(),
+        });
+        match self.responses.get(&path) {
+            Some(Response::Data(data)) => Ok(data.clone().into()),
+            Some(Response::Error(kind)) => Err(Error::Io(io::Error::new(
+                *kind,
+                format!("{} failed by mock", path),
+            ))),
+            None => Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} not found in mock", path),
+            ))),
+        }
+    }
+}
+
+impl Fetcher for &mut MockScheme {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        (**self).fetch(request)
+    }
+}
+
+/// A fetcher failing the `n`th request, counting from zero, and forwarding
+/// all others to the wrapped fetcher.
+///
+/// Wrap a [`MockScheme`] by mutable reference to inspect the requests
+/// afterwards.
+///
+/// ```
+/// use gltf::mock::FailNth;
+///
+/// let fetcher = FailNth::new(gltf::filesystem_fetcher, 0, std::io::ErrorKind::Interrupted);
+/// let result = gltf::import_with_progress("examples/Box.gltf", fetcher, |_| {
+///     std::ops::ControlFlow::Continue(())
+/// });
+/// assert!(matches!(result, Err(gltf::Error::Io(_))));
+/// ```
+///
+/// [`MockScheme`]: struct.MockScheme.html
+#[derive(Clone, Debug)]
+pub struct FailNth<F> {
+    /// The wrapped fetcher.
+    inner: F,
+
+    /// The index of the request to fail.
+    n: usize,
+
+    /// The kind of the injected error.
+    kind: io::ErrorKind,
+
+    /// The number of requests received so far.
+    count: usize,
+}
+
+impl<F: Fetcher> FailNth<F> {
+    /// Wraps `inner`, failing its `n`th request with an `Error::Io` of kind
+    /// `kind`.
+    pub fn new(inner: F, n: usize, kind: io::ErrorKind) -> Self {
+        Self {
+            inner,
+            n,
+            kind,
+            count: 0,
+        }
+    }
+
+    /// Returns the number of requests received so far, including the failed
+    /// one.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Reads a resource with the signature of [`filesystem_fetcher`], see
+    /// [`MockScheme::read`].
+    ///
+    /// [`filesystem_fetcher`]: ../fn.filesystem_fetcher.html
+    /// [`MockScheme::read`]: struct.MockScheme.html#method.read
+    pub fn read(&mut self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        self.fetch(&path_request(base, path))
+            .map(buffer::Bytes::into_vec)
+    }
+}
+
+impl<F: Fetcher> Fetcher for FailNth<F> {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        self.count += 1;
+        if self.count - 1 == self.n {
+            let message = format!("request {} failed by mock", self.n);
+            return Err(Error::Io(io::Error::new(self.kind, message)));
+        }
+        self.inner.fetch(request)
+    }
+}
+
+/// A fetcher sleeping before forwarding every request to the wrapped
+/// fetcher, for testing timeouts and progress reporting.
+#[derive(Clone, Debug)]
+pub struct Slow<F> {
+    /// The wrapped fetcher.
+    inner: F,
+
+    /// The delay before every request.
+    delay: Duration,
+}
+
+impl<F: Fetcher> Slow<F> {
+    /// Wraps `inner`, delaying each of its requests by `delay`.
+    pub fn new(inner: F, delay: Duration) -> Self {
+        Self { inner, delay }
+    }
+
+    /// Reads a resource with the signature of [`filesystem_fetcher`], see
+    /// [`MockScheme::read`].
+    ///
+    /// [`filesystem_fetcher`]: ../fn.filesystem_fetcher.html
+    /// [`MockScheme::read`]: struct.MockScheme.html#method.read
+    pub fn read(&mut self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        self.fetch(&path_request(base, path))
+            .map(buffer::Bytes::into_vec)
+    }
+}
+
+impl<F: Fetcher> Fetcher for Slow<F> {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        thread::sleep(self.delay);
+        self.inner.fetch(request)
+    }
+}
+
+/// A fetcher truncating the data returned by the wrapped fetcher, like a
+/// connection closed early or a file truncated on disk.
+///
+/// Buffers truncated below their `byteLength` fail to import with
+/// `Error::BufferLength`, keeping in mind that buffer data is padded to a
+/// multiple of four bytes first. Truncated images typically fail to decode.
+#[derive(Clone, Debug)]
+pub struct ShortRead<F> {
+    /// The wrapped fetcher.
+    inner: F,
+
+    /// The number of bytes removed from the end of every resource.
+    missing: usize,
+}
+
+impl<F: Fetcher> ShortRead<F> {
+    /// Wraps `inner`, removing `missing` bytes from the end of every
+    /// resource it returns.
+    pub fn new(inner: F, missing: usize) -> Self {
+        Self { inner, missing }
+    }
+
+    /// Reads a resource with the signature of [`filesystem_fetcher`], see
+    /// [`MockScheme::read`].
+    ///
+    /// [`filesystem_fetcher`]: ../fn.filesystem_fetcher.html
+    /// [`MockScheme::read`]: struct.MockScheme.html#method.read
+    pub fn read(&mut self, base: Option<&Path>, path: &str) -> Result<Vec<u8>> {
+        self.fetch(&path_request(base, path))
+            .map(buffer::Bytes::into_vec)
+    }
+}
+
+impl<F: Fetcher> Fetcher for ShortRead<F> {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        let mut data = self.inner.fetch(request)?.into_vec();
+        data.truncate(data.len().saturating_sub(self.missing));
+        Ok(data.into())
+    }
+}
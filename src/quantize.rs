@@ -0,0 +1,502 @@
+use crate::accessor::packed::{self, Format};
+use crate::accessor::Writer;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::geometry::{dot, BufferData};
+use crate::prelude::*;
+use crate::{Buffer, Document, Primitive, Semantic};
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+
+/// The formats considered for quantization, in order of preference when
+/// they are stored in the same number of bytes.
+const FORMATS: [Format; 2] = [Format::Snorm16, Format::Snorm8];
+
+/// Options for [`Document::quantization`].
+///
+/// [`Document::quantization`]: ../struct.Document.html#method.quantization
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// The maximum position error, relative to the largest extent of the
+    /// mesh.
+    position_error: f32,
+
+    /// The maximum normal and tangent error in degrees.
+    normal_error: f32,
+
+    /// The maximum texture co-ordinate error.
+    tex_coord_error: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            position_error: 1.0 / 4096.0,
+            normal_error: 1.0,
+            tex_coord_error: 1.0 / 8192.0,
+        }
+    }
+}
+
+impl Options {
+    /// Constructs the default `Options`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum position error, relative to the largest extent of
+    /// the bounds of the mesh. Defaults to `1 / 4096`.
+    pub fn with_position_error(mut self, position_error: f32) -> Self {
+        self.position_error = position_error;
+        self
+    }
+
+    /// Sets the maximum angle in degrees between an original and a quantized
+    /// normal or tangent. Defaults to `1`.
+    pub fn with_normal_error(mut self, normal_error: f32) -> Self {
+        self.normal_error = normal_error;
+        self
+    }
+
+    /// Sets the maximum texture co-ordinate error. Defaults to `1 / 8192`,
+    /// which is half a texel of an 4096 pixel wide texture.
+    pub fn with_tex_coord_error(mut self, tex_coord_error: f32) -> Self {
+        self.tex_coord_error = tex_coord_error;
+        self
+    }
+}
+
+/// The transform restoring quantized positions, i.e. the original position
+/// is `offset + scale * quantized`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dequantization {
+    /// The center of the bounds of the mesh.
+    pub offset: [f32; 3],
+
+    /// Half the largest extent of the bounds of the mesh.
+    pub scale: f32,
+}
+
+/// The quantization recommended for one vertex attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    /// The semantic of the attribute.
+    pub semantic: Semantic,
+
+    /// The index of the accessor of the attribute.
+    pub accessor: usize,
+
+    /// The recommended format, or `None` to keep the attribute as it is.
+    pub format: Option<Format>,
+
+    /// The largest error of the recommended format measured over the data,
+    /// in the units of the corresponding tolerance of [`Options`], or zero
+    /// if the attribute is kept.
+    ///
+    /// [`Options`]: struct.Options.html
+    pub max_error: f32,
+
+    /// The size of the attribute data in bytes.
+    pub bytes: usize,
+
+    /// The size of the attribute data in bytes in the recommended format.
+    pub quantized_bytes: usize,
+}
+
+/// The quantization recommended for the attributes of a primitive.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The index of the mesh.
+    pub mesh: usize,
+
+    /// The index of the primitive within the mesh.
+    pub primitive: usize,
+
+    /// The analyzed attributes, ordered by semantic.
+    pub attributes: Vec<Attribute>,
+
+    /// The transform restoring quantized positions, if the positions are to
+    /// be quantized.
+    ///
+    /// The transform is the same for every primitive of the mesh, since it
+    /// is applied by the nodes instantiating the mesh.
+    pub dequantization: Option<Dequantization>,
+}
+
+impl Report {
+    /// Returns the number of bytes saved by the recommended formats.
+    pub fn savings(&self) -> usize {
+        self.attributes
+            .iter()
+            .map(|attribute| attribute.bytes - attribute.quantized_bytes)
+            .sum()
+    }
+}
+
+/// The data of an attribute considered for quantization.
+enum Values {
+    Positions(Vec<[f32; 3]>),
+    Normals(Vec<[f32; 3]>),
+    Tangents(Vec<[f32; 4]>),
+    TexCoords(Vec<[f32; 2]>),
+}
+
+/// Reads the data of the attribute `semantic` of `primitive`, if it is
+/// considered for quantization.
+fn values(primitive: &Primitive, buffer_data: &BufferData, semantic: &Semantic) -> Option<Values> {
+    let reader = primitive.reader(|buffer| buffer_data.get(buffer));
+    match *semantic {
+        Semantic::Positions => Some(Values::Positions(reader.read_positions()?.collect())),
+        Semantic::Normals => Some(Values::Normals(reader.read_normals()?.collect())),
+        Semantic::Tangents => Some(Values::Tangents(reader.read_tangents()?.collect())),
+        Semantic::TexCoords(set) => Some(Values::TexCoords(
+            reader.read_tex_coords(set)?.into_f32().collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Returns `value` after a round trip through the signed normalized integer
+/// with `max` as its maximum.
+fn round_trip(value: f32, max: i32) -> f32 {
+    (packed::to_snorm(value, max) as f32 / max as f32).max(-1.0)
+}
+
+/// Returns the largest value of the signed normalized `format`.
+fn snorm_max(format: Format) -> i32 {
+    match format {
+        Format::Snorm8 => 127,
+        _ => 32767,
+    }
+}
+
+/// Returns the angle in degrees between `a` and `b` after a round trip of
+/// `b` through the signed normalized integer with `max` as its maximum.
+fn angle_error(a: [f32; 3], max: i32) -> f32 {
+    let length = dot(a, a).sqrt();
+    if length == 0.0 {
+        return 0.0;
+    }
+    let b = a.map(|x| round_trip(x / length, max));
+    let cos = dot(a, b) / (length * dot(b, b).sqrt());
+    cos.clamp(-1.0, 1.0).acos() * (180.0 / core::f32::consts::PI)
+}
+
+/// Returns the largest error of `values` after a round trip through the
+/// signed normalized integer with `max` as its maximum, in the units of the
+/// tolerance in `Options`, or `None` if the values cannot be stored.
+fn max_error(values: &Values, dequantization: Option<Dequantization>, max: i32) -> Option<f32> {
+    let mut error = 0.0f32;
+    match values {
+        Values::Positions(positions) => {
+            let Dequantization { offset, scale } = dequantization?;
+            for position in positions {
+                for i in 0..3 {
+                    let quantized = (position[i] - offset[i]) / scale;
+                    let restored = offset[i] + scale * round_trip(quantized, max);
+                    error = error.max((restored - position[i]).abs() / (2.0 * scale));
+                }
+            }
+        }
+        Values::Normals(normals) => {
+            for &normal in normals {
+                error = error.max(angle_error(normal, max));
+            }
+        }
+        Values::Tangents(tangents) => {
+            for &[x, y, z, _] in tangents {
+                error = error.max(angle_error([x, y, z], max));
+            }
+        }
+        Values::TexCoords(tex_coords) => {
+            for &tex_coord in tex_coords {
+                for value in tex_coord {
+                    if !(-1.0..=1.0).contains(&value) {
+                        return None;
+                    }
+                    error = error.max((round_trip(value, max) - value).abs());
+                }
+            }
+        }
+    }
+    Some(error)
+}
+
+/// Returns the size of elements with `size` bytes as vertex attributes,
+/// which are padded to four bytes.
+fn padded(size: usize) -> usize {
+    (size + 3) & !3
+}
+
+/// Returns the bounds of the positions of the primitives of `mesh`, if they
+/// may be quantized.
+fn dequantization(
+    document: &Document,
+    mesh: &crate::Mesh,
+    buffer_data: &BufferData,
+) -> Option<Dequantization> {
+    // Skinned meshes ignore the transform of their node, and morph targets
+    // would have to be quantized alongside.
+    let skinned = document
+        .nodes()
+        .any(|node| node.skin().is_some() && node.mesh().map(|m| m.index()) == Some(mesh.index()));
+    if skinned {
+        return None;
+    }
+    let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+    for primitive in mesh.primitives() {
+        if primitive.morph_targets().len() > 0 {
+            return None;
+        }
+        let reader = primitive.reader(|buffer| buffer_data.get(buffer));
+        for position in reader.read_positions()? {
+            for i in 0..3 {
+                min[i] = min[i].min(position[i]);
+                max[i] = max[i].max(position[i]);
+            }
+        }
+    }
+    if min[0] > max[0] {
+        return None;
+    }
+    let offset = [0, 1, 2].map(|i| (min[i] + max[i]) / 2.0);
+    let scale = [0, 1, 2]
+        .map(|i| (max[i] - min[i]) / 2.0)
+        .iter()
+        .fold(0.0f32, |a, &b| a.max(b));
+    Some(Dequantization {
+        offset,
+        scale: if scale > 0.0 { scale } else { 1.0 },
+    })
+}
+
+impl Document {
+    /// Recommends signed normalized formats of `KHR_mesh_quantization` for
+    /// the positions, normals, tangents and texture co-ordinates of every
+    /// primitive, measuring the error of each format over the data.
+    ///
+    /// The smallest format within the tolerances of `options` is
+    /// recommended, if it is smaller than the attribute. Positions are
+    /// quantized relative to the bounds of their mesh, and are kept if any
+    /// primitive of the mesh cannot be quantized, if the mesh has morph
+    /// targets, or if it is skinned. Texture co-ordinates outside `[-1, 1]`
+    /// are kept.
+    ///
+    /// Returns the reports ordered by mesh and primitive. The recommended
+    /// formats can be applied with [`apply`].
+    ///
+    /// [`apply`]: quantize/fn.apply.html
+    pub fn quantization<'a, 's, F>(&'a self, get_buffer_data: F, options: &Options) -> Vec<Report>
+    where
+        F: Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let buffer_data = BufferData::new(self, get_buffer_data);
+        let mut reports = Vec::new();
+        for mesh in self.meshes() {
+            let dequantization = dequantization(self, &mesh, &buffer_data);
+            let first = reports.len();
+            for primitive in mesh.primitives() {
+                let mut report = Report {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                    attributes: Vec::new(),
+                    dequantization,
+                };
+                for (semantic, accessor) in primitive.attributes() {
+                    let values = match values(&primitive, &buffer_data, &semantic) {
+                        Some(values) => values,
+                        None => continue,
+                    };
+                    let tolerance = match values {
+                        Values::Positions(_) => options.position_error,
+                        Values::Normals(_) | Values::Tangents(_) => options.normal_error,
+                        Values::TexCoords(_) => options.tex_coord_error,
+                    };
+                    let count = accessor.count();
+                    let bytes = count * padded(accessor.size());
+                    let multiplicity = accessor.dimensions().multiplicity();
+                    let mut attribute = Attribute {
+                        semantic,
+                        accessor: accessor.index(),
+                        format: None,
+                        max_error: 0.0,
+                        bytes,
+                        quantized_bytes: bytes,
+                    };
+                    for &format in &FORMATS {
+                        let size = match format {
+                            Format::Snorm8 => count * padded(multiplicity),
+                            _ => count * padded(2 * multiplicity),
+                        };
+                        if size >= attribute.quantized_bytes {
+                            continue;
+                        }
+                        match max_error(&values, dequantization, snorm_max(format)) {
+                            Some(error) if error <= tolerance => {
+                                attribute.format = Some(format);
+                                attribute.max_error = error;
+                                attribute.quantized_bytes = size;
+                            }
+                            _ => {}
+                        }
+                    }
+                    report.attributes.push(attribute);
+                }
+                reports.push(report);
+            }
+
+            // The positions of every primitive share the node transform.
+            let keep = reports[first..].iter().any(|report| {
+                report
+                    .attributes
+                    .iter()
+                    .any(|a| a.semantic == Semantic::Positions && a.format.is_none())
+            });
+            if keep {
+                for report in &mut reports[first..] {
+                    report.dequantization = None;
+                    for attribute in &mut report.attributes {
+                        if attribute.semantic == Semantic::Positions {
+                            attribute.format = None;
+                            attribute.max_error = 0.0;
+                            attribute.quantized_bytes = attribute.bytes;
+                        }
+                    }
+                }
+            }
+        }
+        reports
+    }
+}
+
+/// Applies the formats recommended by `reports` for the primitives of
+/// `document` to `root`, which is expected to be a copy of the JSON of
+/// `document`, and returns the data of the buffer appended for the
+/// quantized attributes.
+///
+/// The attributes of the primitives are replaced by new accessors, while the
+/// previous accessors are left in place. `KHR_mesh_quantization` is declared
+/// as used and required. Meshes with quantized positions are moved from
+/// their nodes to new child nodes applying the dequantization transform, so
+/// that the children of the nodes are not affected.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::quantize::{self, Options};
+///
+/// let (document, buffers, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+/// let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|data| &data[..]);
+/// let reports = document.quantization(get_buffer_data, &Options::new());
+/// let savings: usize = reports.iter().map(|report| report.savings()).sum();
+/// assert!(savings > 0);
+///
+/// let mut root = document.clone().into_json();
+/// let bin = quantize::apply(&document, get_buffer_data, &reports, &mut root);
+/// assert_eq!(root.buffers.last().unwrap().byte_length as usize, bin.len());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+pub fn apply<'a, 's, F>(
+    document: &'a Document,
+    get_buffer_data: F,
+    reports: &[Report],
+    root: &mut json::Root,
+) -> Vec<u8>
+where
+    F: Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    let buffer_data = BufferData::new(document, get_buffer_data);
+    let mut quantized = Vec::new();
+    let mut meshes = Vec::new();
+    for report in reports {
+        let primitive = match document
+            .meshes()
+            .nth(report.mesh)
+            .and_then(|mesh| mesh.primitives().nth(report.primitive))
+        {
+            Some(primitive) => primitive,
+            None => continue,
+        };
+        for attribute in &report.attributes {
+            let format = match attribute.format {
+                Some(format) => format,
+                None => continue,
+            };
+            let values = match values(&primitive, &buffer_data, &attribute.semantic) {
+                Some(values) => values,
+                None => continue,
+            };
+            if let (Values::Positions(_), Some(dequantization)) = (&values, report.dequantization) {
+                if !meshes.contains(&(report.mesh, dequantization)) {
+                    meshes.push((report.mesh, dequantization));
+                }
+            }
+            quantized.push((report, attribute.semantic.clone(), format, values));
+        }
+    }
+
+    let mut writer = Writer::new(root);
+    let mut accessors = Vec::with_capacity(quantized.len());
+    for (report, semantic, format, values) in quantized {
+        let target = Some(Target::ArrayBuffer);
+        let accessor = match values {
+            Values::Positions(mut positions) => {
+                let Dequantization { offset, scale } = match report.dequantization {
+                    Some(dequantization) => dequantization,
+                    None => continue,
+                };
+                for position in &mut positions {
+                    *position = [0, 1, 2].map(|i| (position[i] - offset[i]) / scale);
+                }
+                writer.push_packed(&positions, format, target)
+            }
+            Values::Normals(normals) => writer.push_packed(&normals, format, target),
+            Values::Tangents(tangents) => writer.push_packed(&tangents, format, target),
+            Values::TexCoords(tex_coords) => writer.push_packed(&tex_coords, format, target),
+        };
+        if let Some(accessor) = accessor {
+            accessors.push((report.mesh, report.primitive, semantic, accessor));
+        }
+    }
+    let bin = writer.finish();
+
+    for (mesh, primitive, semantic, accessor) in accessors {
+        if let Some(primitive) = root
+            .meshes
+            .get_mut(mesh)
+            .and_then(|mesh| mesh.primitives.get_mut(primitive))
+        {
+            primitive.attributes.insert(Valid(semantic), accessor);
+        }
+    }
+    for (mesh, Dequantization { offset, scale }) in meshes {
+        for index in 0..root.nodes.len() {
+            if root.nodes[index].mesh.map(|m| m.value()) != Some(mesh) {
+                continue;
+            }
+            let child = json::Index::new(root.nodes.len() as u32);
+            let node = &mut root.nodes[index];
+            let mesh = node.mesh.take();
+            node.children.get_or_insert_with(Vec::new).push(child);
+            root.nodes.push(json::Node {
+                camera: None,
+                children: None,
+                extensions: None,
+                extras: Default::default(),
+                matrix: None,
+                mesh,
+                #[cfg(feature = "names")]
+                name: None,
+                rotation: None,
+                scale: Some([scale; 3]),
+                translation: Some(offset),
+                skin: None,
+                weights: None,
+            });
+        }
+    }
+    bin
+}
@@ -0,0 +1,510 @@
+use crate::accessor::DataType;
+use crate::geometry::BufferData;
+use crate::prelude::*;
+use crate::{Accessor, Buffer, Document, Primitive};
+use alloc::collections::{BTreeMap, BTreeSet};
+use byteorder::{ByteOrder, LittleEndian};
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+
+/// Extensions which reference buffer views by index, and whose references
+/// would be invalidated by removing buffer views.
+pub(crate) const VIEW_EXTENSIONS: &[&str] =
+    &["KHR_draco_mesh_compression", "EXT_meshopt_compression"];
+
+/// The vertices referenced by the indices of a primitive, and the indices
+/// rebased onto them.
+///
+/// Created by [`Primitive::vertex_remap`].
+///
+/// [`Primitive::vertex_remap`]: ../mesh/struct.Primitive.html#method.vertex_remap
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VertexRemap {
+    /// The referenced vertices in ascending order, i.e. the original index of
+    /// every compacted vertex.
+    pub vertices: Vec<u32>,
+
+    /// The indices of the primitive into `vertices`.
+    pub indices: Vec<u32>,
+}
+
+impl<'a> Primitive<'a> {
+    /// Computes the vertices referenced by the indices of the primitive, and
+    /// the indices rebased onto them.
+    ///
+    /// The relative order of the vertices is preserved. Returns `None` if the
+    /// primitive has no indices, its indices or positions cannot be read, or
+    /// an index is out of range.
+    pub fn vertex_remap<'s, F>(&'a self, get_buffer_data: F) -> Option<VertexRemap>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let count = self
+            .attributes()
+            .map(|(_, accessor)| accessor.count())
+            .min()?;
+        let reader = self.reader(get_buffer_data);
+        let indices: Vec<u32> = reader.read_indices()?.into_u32().collect();
+        let mut rebased = vec![u32::MAX; count];
+        for &index in &indices {
+            *rebased.get_mut(index as usize)? = 0;
+        }
+        let mut vertices = Vec::new();
+        for (vertex, rebased) in rebased.iter_mut().enumerate() {
+            if *rebased == 0 {
+                *rebased = vertices.len() as u32;
+                vertices.push(vertex as u32);
+            }
+        }
+        let indices = indices
+            .iter()
+            .map(|&index| rebased[index as usize])
+            .collect();
+        Some(VertexRemap { vertices, indices })
+    }
+}
+
+/// The location of the elements of a non-sparse accessor.
+struct Layout<'s> {
+    /// The data of the buffer viewed by the accessor.
+    data: &'s [u8],
+
+    /// The offset of the first element in `data`.
+    offset: usize,
+
+    /// The distance between consecutive elements.
+    stride: usize,
+
+    /// The size of an element.
+    size: usize,
+}
+
+impl<'s> Layout<'s> {
+    /// Locates the elements of `accessor`, returning `None` if the accessor is
+    /// sparse, has no buffer view, or exceeds its buffer.
+    fn new<'a, F>(accessor: &Accessor<'a>, get_buffer_data: F) -> Option<Self>
+    where
+        F: Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        if accessor.sparse().is_some() {
+            return None;
+        }
+        let view = accessor.view()?;
+        let data = get_buffer_data(view.buffer())?;
+        let size = accessor.size();
+        let layout = Layout {
+            data,
+            offset: view.offset() + accessor.offset(),
+            stride: view.stride().unwrap_or(size),
+            size,
+        };
+        let end = match accessor.count() {
+            0 => layout.offset,
+            count => layout.offset + (count - 1) * layout.stride + size,
+        };
+        if end <= data.len() && end <= view.offset() + view.length() {
+            Some(layout)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bytes of element `index`, which must be in range.
+    fn get(&self, index: u32) -> &'s [u8] {
+        let start = self.offset + index as usize * self.stride;
+        &self.data[start..start + self.size]
+    }
+}
+
+/// Decodes a little endian component of `data_type`.
+fn decode(data_type: DataType, bytes: &[u8]) -> json::Value {
+    match data_type {
+        DataType::I8 => (bytes[0] as i8).into(),
+        DataType::U8 => bytes[0].into(),
+        DataType::I16 => LittleEndian::read_i16(bytes).into(),
+        DataType::U16 => LittleEndian::read_u16(bytes).into(),
+        DataType::U32 => LittleEndian::read_u32(bytes).into(),
+        DataType::F32 => LittleEndian::read_f32(bytes).into(),
+    }
+}
+
+/// Computes the component-wise bounds of `elements` of `data_type`.
+fn bounds<'s, I>(data_type: DataType, elements: I) -> Option<(json::Value, json::Value)>
+where
+    I: Iterator<Item = &'s [u8]>,
+{
+    let size = data_type.size();
+    let mut min: Vec<json::Value> = Vec::new();
+    let mut max: Vec<json::Value> = Vec::new();
+    let less = |a: &json::Value, b: &json::Value| a.as_f64() < b.as_f64();
+    for element in elements {
+        let components = element
+            .chunks_exact(size)
+            .map(|bytes| decode(data_type, bytes));
+        if min.is_empty() {
+            min = components.collect();
+            max = min.clone();
+            continue;
+        }
+        for (i, component) in components.enumerate() {
+            if less(&component, &min[i]) {
+                min[i] = component.clone();
+            }
+            if less(&max[i], &component) {
+                max[i] = component;
+            }
+        }
+    }
+    if min.is_empty() {
+        None
+    } else {
+        Some((min.into(), max.into()))
+    }
+}
+
+/// Returns the buffer views referenced by the accessors and images of `root`.
+fn referenced_views(root: &json::Root) -> BTreeSet<u32> {
+    let accessors = root.accessors.iter().flat_map(|accessor| {
+        let sparse = accessor
+            .sparse
+            .iter()
+            .flat_map(|sparse| [sparse.indices.buffer_view, sparse.values.buffer_view]);
+        accessor.buffer_view.into_iter().chain(sparse)
+    });
+    let images = root.images.iter().filter_map(|image| image.buffer_view);
+    accessors
+        .chain(images)
+        .map(|view| view.value() as u32)
+        .collect()
+}
+
+/// The state of [`compact`].
+///
+/// [`compact`]: fn.compact.html
+struct Compaction<'c> {
+    /// The original JSON.
+    original: &'c json::Root,
+
+    /// The data of the original buffers.
+    buffer_data: &'c BufferData<'c>,
+
+    /// The rewritten JSON.
+    root: json::Root,
+
+    /// The buffer views written for compacted accessors.
+    views: Vec<json::buffer::View>,
+
+    /// The data of `views`.
+    bin: Vec<u8>,
+
+    /// The distinct vertex sets of the compacted primitives.
+    remaps: Vec<Vec<u32>>,
+
+    /// The compacted accessors by original accessor and vertex set.
+    accessors: BTreeMap<(usize, usize), json::Index<json::Accessor>>,
+
+    /// The original accessors which have been rewritten in place.
+    rewritten: BTreeSet<usize>,
+}
+
+impl<'c> Compaction<'c> {
+    /// Appends `data` as a new buffer view starting at a four byte boundary.
+    ///
+    /// The returned index counts from the number of original buffer views,
+    /// so that it can be told apart from original indices.
+    fn push_view(
+        &mut self,
+        data: &[u8],
+        stride: Option<usize>,
+        target: Target,
+    ) -> json::Index<json::buffer::View> {
+        self.bin.resize((self.bin.len() + 3) & !3, 0);
+        self.views.push(json::buffer::View {
+            buffer: json::Index::new(0),
+            byte_length: data.len() as u32,
+            byte_offset: Some(self.bin.len() as u32),
+            byte_stride: stride.map(|stride| stride as u32),
+            #[cfg(feature = "names")]
+            name: None,
+            target: Some(Valid(target)),
+            extensions: None,
+            extras: Default::default(),
+        });
+        self.bin.extend_from_slice(data);
+        json::Index::new((self.original.buffer_views.len() + self.views.len() - 1) as u32)
+    }
+
+    /// Stores `accessor` as a compacted accessor for vertex set `remap`.
+    ///
+    /// The first compacted version of an accessor replaces the original, so
+    /// that other references to the accessor remain valid.
+    fn store(
+        &mut self,
+        original: usize,
+        remap: usize,
+        accessor: json::Accessor,
+    ) -> json::Index<json::Accessor> {
+        let index = if self.rewritten.insert(original) {
+            self.root.accessors[original] = accessor;
+            json::Index::new(original as u32)
+        } else {
+            self.root.accessors.push(accessor);
+            json::Index::new(self.root.accessors.len() as u32 - 1)
+        };
+        self.accessors.insert((original, remap), index);
+        index
+    }
+
+    /// Returns the index of a compacted copy of the vertex attribute
+    /// `accessor` restricted to the vertices of `remap`.
+    fn attribute(&mut self, accessor: &Accessor, remap: usize) -> json::Index<json::Accessor> {
+        if let Some(&index) = self.accessors.get(&(accessor.index(), remap)) {
+            return index;
+        }
+        let buffer_data = self.buffer_data;
+        let get_buffer_data = |buffer: Buffer| buffer_data.get(buffer);
+        // Validated by the caller.
+        let layout = Layout::new(accessor, get_buffer_data).unwrap();
+        let vertices = &self.remaps[remap];
+
+        // Attribute elements must be aligned to four bytes.
+        let stride = (layout.size + 3) & !3;
+        let mut data = Vec::with_capacity(vertices.len() * stride);
+        for &vertex in vertices {
+            data.extend_from_slice(layout.get(vertex));
+            data.resize(data.len() + stride - layout.size, 0);
+        }
+        let mut json = self.original.accessors[accessor.index()].clone();
+        json.byte_offset = None;
+        json.count = vertices.len() as u32;
+        if json.min.is_some() || json.max.is_some() {
+            let elements = vertices.iter().map(|&vertex| layout.get(vertex));
+            if let Some((min, max)) = bounds(accessor.data_type(), elements) {
+                json.min = Some(min);
+                json.max = Some(max);
+            }
+        }
+        let stride = if stride == layout.size {
+            None
+        } else {
+            Some(stride)
+        };
+        json.buffer_view = Some(self.push_view(&data, stride, Target::ArrayBuffer));
+        self.store(accessor.index(), remap, json)
+    }
+
+    /// Returns the index of an accessor holding the rebased `indices`, with the
+    /// component type of the original index `accessor`.
+    fn indices(
+        &mut self,
+        accessor: &Accessor,
+        remap: usize,
+        indices: &[u32],
+    ) -> json::Index<json::Accessor> {
+        if let Some(&index) = self.accessors.get(&(accessor.index(), remap)) {
+            return index;
+        }
+        let data_type = accessor.data_type();
+        let mut data = vec![0; indices.len() * data_type.size()];
+        match data_type {
+            DataType::U8 => {
+                for (byte, &index) in data.iter_mut().zip(indices) {
+                    *byte = index as u8;
+                }
+            }
+            DataType::U16 => {
+                for (bytes, &index) in data.chunks_exact_mut(2).zip(indices) {
+                    LittleEndian::write_u16(bytes, index as u16);
+                }
+            }
+            _ => LittleEndian::write_u32_into(indices, &mut data),
+        }
+        let view = self.push_view(&data, None, Target::ElementArrayBuffer);
+        let mut json = self.original.accessors[accessor.index()].clone();
+        json.buffer_view = Some(view);
+        json.byte_offset = None;
+        json.sparse = None;
+        if json.min.is_some() || json.max.is_some() {
+            let min = indices.iter().min().copied().unwrap_or(0);
+            let max = indices.iter().max().copied().unwrap_or(0);
+            json.min = Some(vec![min].into());
+            json.max = Some(vec![max].into());
+        }
+        self.store(accessor.index(), remap, json)
+    }
+}
+
+/// Compacts the vertex range of every indexed primitive, removing the
+/// vertices its indices never reference and rebasing the indices.
+///
+/// This shrinks assets whose primitives reference small windows of large
+/// shared vertex buffers. Vertex attributes and morph targets are copied for
+/// the referenced vertices only, and their bounds are recomputed. Buffer views
+/// which are no longer referenced are dropped.
+///
+/// Returns the rewritten JSON with a single buffer, together with the data
+/// of that buffer, ready for [`Exporter::to_glb`]. Primitives with sparse or
+/// unreadable accessors are left as they are, as are primitives which
+/// already reference every vertex. Returns `None` if the data of a retained
+/// buffer view cannot be read, or if the asset uses an extension that
+/// references buffer views, such as `KHR_draco_mesh_compression`.
+///
+/// [`Exporter::to_glb`]: ../export/struct.Exporter.html#method.to_glb
+pub fn compact<'a, 's, F>(
+    document: &'a Document,
+    get_buffer_data: F,
+) -> Option<(json::Root, Vec<u8>)>
+where
+    F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+{
+    if document
+        .extensions_used()
+        .any(|extension| VIEW_EXTENSIONS.contains(&extension))
+    {
+        return None;
+    }
+
+    let buffer_data = BufferData::new(document, get_buffer_data);
+    let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+    let mut compaction = Compaction {
+        original: &document.0,
+        buffer_data: &buffer_data,
+        root: document.0.clone(),
+        views: Vec::new(),
+        bin: Vec::new(),
+        remaps: Vec::new(),
+        accessors: BTreeMap::new(),
+        rewritten: BTreeSet::new(),
+    };
+    for mesh in document.meshes() {
+        for primitive in mesh.primitives() {
+            let remap = match primitive.vertex_remap(get_buffer_data) {
+                Some(remap) => remap,
+                None => continue,
+            };
+            let indices = primitive.indices().unwrap();
+            let targets: Vec<_> = primitive
+                .morph_targets()
+                .flat_map(|target| [target.positions(), target.normals(), target.tangents()])
+                .flatten()
+                .collect();
+            let attributes: Vec<_> = primitive
+                .attributes()
+                .map(|(_, accessor)| accessor)
+                .chain(targets)
+                .collect();
+            let compact = attributes
+                .iter()
+                .all(|accessor| accessor.count() == remap.vertices.len());
+            let readable = attributes
+                .iter()
+                .all(|accessor| Layout::new(accessor, get_buffer_data).is_some());
+            if compact || !readable {
+                continue;
+            }
+
+            let id = match compaction.remaps.iter().position(|v| *v == remap.vertices) {
+                Some(id) => id,
+                None => {
+                    compaction.remaps.push(remap.vertices);
+                    compaction.remaps.len() - 1
+                }
+            };
+            let mut json = document.0.meshes[mesh.index()].primitives[primitive.index()].clone();
+            for (index, (_, accessor)) in json.attributes.values_mut().zip(primitive.attributes()) {
+                *index = compaction.attribute(&accessor, id);
+            }
+            for (json, target) in json
+                .targets
+                .iter_mut()
+                .flatten()
+                .zip(primitive.morph_targets())
+            {
+                let accessors = [
+                    (&mut json.positions, target.positions()),
+                    (&mut json.normals, target.normals()),
+                    (&mut json.tangents, target.tangents()),
+                ];
+                for (index, accessor) in accessors {
+                    if let Some(accessor) = accessor {
+                        *index = Some(compaction.attribute(&accessor, id));
+                    }
+                }
+            }
+            json.indices = Some(compaction.indices(&indices, id, &remap.indices));
+            compaction.root.meshes[mesh.index()].primitives[primitive.index()] = json;
+        }
+    }
+
+    // Retain the views which were not referenced by accessors or images in the
+    // first place, since extensions may reference them.
+    let Compaction {
+        mut root,
+        views,
+        bin: compacted,
+        ..
+    } = compaction;
+    let before = referenced_views(&document.0);
+    let after = referenced_views(&root);
+    let mut bin = Vec::new();
+    let mut new_views = Vec::new();
+    let mut indices = vec![None; document.views().len()];
+    for (index, view) in document.views().enumerate() {
+        if before.contains(&(index as u32)) && !after.contains(&(index as u32)) {
+            continue;
+        }
+        let data = get_buffer_data(view.buffer())?;
+        let data = data.get(view.offset()..view.offset() + view.length())?;
+        let mut json = document.0.buffer_views[index].clone();
+        bin.resize((bin.len() + 3) & !3, 0);
+        json.buffer = json::Index::new(0);
+        json.byte_offset = Some(bin.len() as u32);
+        bin.extend_from_slice(data);
+        indices[index] = Some(json::Index::new(new_views.len() as u32));
+        new_views.push(json);
+    }
+    bin.resize((bin.len() + 3) & !3, 0);
+    let base = bin.len() as u32;
+    bin.extend_from_slice(&compacted);
+    for mut view in views {
+        view.byte_offset = Some(view.byte_offset.unwrap_or(0) + base);
+        new_views.push(view);
+    }
+
+    // Compacted accessors reference the appended views.
+    let original = document.views().len();
+    let retained = indices.iter().flatten().count();
+    let remap_view = |view: &mut json::Index<json::buffer::View>| {
+        *view = match indices.get(view.value()) {
+            Some(index) => index.unwrap(),
+            None => json::Index::new((view.value() - original + retained) as u32),
+        };
+    };
+    for accessor in root.accessors.iter_mut() {
+        if let Some(view) = accessor.buffer_view.as_mut() {
+            remap_view(view);
+        }
+        if let Some(sparse) = accessor.sparse.as_mut() {
+            remap_view(&mut sparse.indices.buffer_view);
+            remap_view(&mut sparse.values.buffer_view);
+        }
+    }
+    for view in root
+        .images
+        .iter_mut()
+        .filter_map(|image| image.buffer_view.as_mut())
+    {
+        remap_view(view);
+    }
+    root.buffer_views = new_views;
+    root.buffers = vec![json::Buffer {
+        byte_length: bin.len() as u32,
+        #[cfg(feature = "names")]
+        name: None,
+        uri: None,
+        extensions: None,
+        extras: Default::default(),
+    }];
+    Some((root, bin))
+}
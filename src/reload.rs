@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use crate::buffer;
+use crate::import::{self, Fetcher, Import, ResourceRequest};
+use crate::Result;
+
+/// Returns the canonical form of `path`, or `path` unchanged if it cannot be
+/// canonicalized, for example because the file no longer exists.
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Wraps a fetcher, recording the files it is asked for.
+struct Recorder<'a, F> {
+    fetcher: F,
+    dependencies: &'a mut BTreeSet<PathBuf>,
+}
+
+impl<F: Fetcher> Fetcher for Recorder<'_, F> {
+    fn fetch(&mut self, request: &ResourceRequest<'_>) -> Result<buffer::Bytes> {
+        let resolved = match request.base {
+            Some(base) => base.join(request.path),
+            None => PathBuf::from(request.path),
+        };
+        self.dependencies.insert(normalize(&resolved));
+        self.fetcher.fetch(request)
+    }
+}
+
+/// Records the files touched by imports to support hot-reloading.
+///
+/// Every asset is identified by the path of its root glTF file. The tracker
+/// records the root file itself together with every file requested through
+/// the fetcher while importing, and can then answer which assets are
+/// affected by a changed path.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let mut tracker = gltf::ReloadTracker::new();
+/// let (document, buffers, images) =
+///     tracker.import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+/// # let _ = (document, buffers, images);
+/// assert!(tracker.affects("examples/Box.gltf", "examples/Box0.bin"));
+/// assert!(!tracker.affects("examples/Box.gltf", "examples/Lantern.gltf"));
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReloadTracker {
+    /// Maps normalized asset paths to the normalized paths they depend on.
+    assets: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl ReloadTracker {
+    /// Constructs an empty `ReloadTracker`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Imports glTF 2.0 from the file system like [`import`], recording the
+    /// root file and every fetched file as dependencies of the asset.
+    ///
+    /// Any dependencies previously recorded for the asset are replaced.
+    ///
+    /// [`import`]: fn.import.html
+    pub fn import<P, F, B>(&mut self, path: P, fetcher: F) -> Result<Import>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Option<&Path>, &str) -> Result<B>,
+        B: Into<buffer::Bytes>,
+    {
+        let path = path.as_ref();
+        let asset = normalize(path);
+        let mut dependencies = BTreeSet::new();
+        dependencies.insert(asset.clone());
+        let recorder = Recorder {
+            fetcher,
+            dependencies: &mut dependencies,
+        };
+        let result = import::import_path(path, recorder, &mut |_| ControlFlow::Continue(()));
+        self.assets.insert(asset, dependencies);
+        result
+    }
+
+    /// Records that `asset` depends on `dependency`.
+    ///
+    /// This is intended for custom import paths which do not go through
+    /// [`import`](#method.import).
+    pub fn record<A, D>(&mut self, asset: A, dependency: D)
+    where
+        A: AsRef<Path>,
+        D: AsRef<Path>,
+    {
+        self.assets
+            .entry(normalize(asset.as_ref()))
+            .or_default()
+            .insert(normalize(dependency.as_ref()));
+    }
+
+    /// Removes all records of `asset`.
+    pub fn forget<A: AsRef<Path>>(&mut self, asset: A) {
+        self.assets.remove(&normalize(asset.as_ref()));
+    }
+
+    /// Returns the recorded dependencies of `asset`, including the asset itself.
+    pub fn dependencies<A: AsRef<Path>>(&self, asset: A) -> impl Iterator<Item = &Path> {
+        self.assets
+            .get(&normalize(asset.as_ref()))
+            .into_iter()
+            .flat_map(|dependencies| dependencies.iter().map(PathBuf::as_path))
+    }
+
+    /// Returns `true` if a change to `changed` affects `asset`.
+    pub fn affects<A, C>(&self, asset: A, changed: C) -> bool
+    where
+        A: AsRef<Path>,
+        C: AsRef<Path>,
+    {
+        self.assets
+            .get(&normalize(asset.as_ref()))
+            .map(|dependencies| dependencies.contains(&normalize(changed.as_ref())))
+            .unwrap_or(false)
+    }
+
+    /// Returns the assets affected by a change to `changed`.
+    pub fn affected<C: AsRef<Path>>(&self, changed: C) -> impl Iterator<Item = &Path> {
+        let changed = normalize(changed.as_ref());
+        self.assets
+            .iter()
+            .filter(move |(_, dependencies)| dependencies.contains(&changed))
+            .map(|(asset, _)| asset.as_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::TempDir;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn affected() {
+        let root = TempDir::new("reload");
+        let asset = |buffer: &str| {
+            format!(
+                r#"{{
+                    "asset": {{ "version": "2.0" }},
+                    "buffers": [{{ "byteLength": 4, "uri": "{}" }}]
+                }}"#,
+                buffer
+            )
+        };
+        fs::write(root.join("a.gltf"), asset("a.bin")).unwrap();
+        fs::write(root.join("b.gltf"), asset("b.bin")).unwrap();
+        fs::write(root.join("a.bin"), [1u8; 4]).unwrap();
+        fs::write(root.join("b.bin"), [2u8; 4]).unwrap();
+
+        let mut tracker = crate::ReloadTracker::new();
+        let (_, a, _) = tracker
+            .import(root.join("a.gltf"), crate::filesystem_fetcher)
+            .unwrap();
+        let (_, b, _) = tracker
+            .import(root.join("b.gltf"), crate::filesystem_fetcher)
+            .unwrap();
+        assert_eq!(tracker.dependencies(root.join("a.gltf")).count(), 2);
+
+        // Only the asset using the modified file is reloaded.
+        fs::write(root.join("a.bin"), [3u8; 4]).unwrap();
+        let affected: Vec<PathBuf> = tracker
+            .affected(root.join("a.bin"))
+            .map(Path::to_path_buf)
+            .collect();
+        assert_eq!(affected, [root.join("a.gltf").canonicalize().unwrap()]);
+        let mut fetched = Vec::new();
+        for path in &affected {
+            let fetcher = |base: Option<&Path>, uri: &str| {
+                fetched.push(uri.to_owned());
+                crate::filesystem_fetcher(base, uri)
+            };
+            let (_, reloaded, _) = tracker.import(path, fetcher).unwrap();
+            assert_eq!(*reloaded[0], [3u8; 4]);
+            assert_ne!(*reloaded[0], *a[0]);
+        }
+        assert_eq!(fetched, ["a.bin"]);
+        assert!(!tracker.affects(root.join("b.gltf"), root.join("a.bin")));
+        assert_eq!(*b[0], *fs::read(root.join("b.bin")).unwrap());
+        assert!(tracker.affects(root.join("b.gltf"), root.join("b.bin")));
+
+        tracker.forget(root.join("a.gltf"));
+        assert_eq!(tracker.affected(root.join("a.bin")).count(), 0);
+    }
+}
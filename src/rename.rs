@@ -0,0 +1,426 @@
+use crate::prelude::*;
+use crate::Document;
+use core::fmt;
+
+use json::Value;
+use serde_json::json;
+
+/// An object which can be renamed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Object {
+    /// The animation at the given index.
+    Animation(usize),
+
+    /// The camera at the given index.
+    Camera(usize),
+
+    /// The material at the given index.
+    Material(usize),
+
+    /// The mesh at the given index.
+    Mesh(usize),
+
+    /// A morph target of a mesh, named in the `targetNames` array of the
+    /// mesh extras, see [`Mesh::target_names`].
+    ///
+    /// [`Mesh::target_names`]: ../mesh/struct.Mesh.html#method.target_names
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    MorphTarget {
+        /// The index of the mesh.
+        mesh: usize,
+
+        /// The index of the morph target.
+        target: usize,
+    },
+
+    /// The node at the given index.
+    Node(usize),
+
+    /// The scene at the given index.
+    Scene(usize),
+
+    /// The skin at the given index.
+    Skin(usize),
+}
+
+impl Object {
+    /// Returns the name of the top-level property containing the object, for
+    /// example `"nodes"`, or `"targetNames"` for morph targets.
+    pub fn property(&self) -> &'static str {
+        match self {
+            Object::Animation(_) => "animations",
+            Object::Camera(_) => "cameras",
+            Object::Material(_) => "materials",
+            Object::Mesh(_) => "meshes",
+            #[cfg(feature = "extras")]
+            Object::MorphTarget { .. } => "targetNames",
+            Object::Node(_) => "nodes",
+            Object::Scene(_) => "scenes",
+            Object::Skin(_) => "skins",
+        }
+    }
+
+    /// Returns the index of the object in its property.
+    pub fn index(&self) -> usize {
+        match *self {
+            Object::Animation(index)
+            | Object::Camera(index)
+            | Object::Material(index)
+            | Object::Mesh(index)
+            | Object::Node(index)
+            | Object::Scene(index)
+            | Object::Skin(index) => index,
+            #[cfg(feature = "extras")]
+            Object::MorphTarget { target, .. } => target,
+        }
+    }
+
+    /// Returns `true` if `other` is of the same kind and names must be
+    /// distinct between the two.
+    fn is_sibling(&self, other: &Object) -> bool {
+        match (self, other) {
+            #[cfg(feature = "extras")]
+            (Object::MorphTarget { mesh: a, .. }, Object::MorphTarget { mesh: b, .. }) => a == b,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+
+    /// Returns the object as a JSON object of the form
+    /// `{ "property": "nodes", "index": 3 }`, with an additional `mesh`
+    /// member for morph targets.
+    pub fn to_value(&self) -> Value {
+        match *self {
+            #[cfg(feature = "extras")]
+            Object::MorphTarget { mesh, target } => json!({
+                "property": self.property(),
+                "mesh": mesh,
+                "index": target,
+            }),
+            _ => json!({
+                "property": self.property(),
+                "index": self.index(),
+            }),
+        }
+    }
+}
+
+/// A rename error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The object does not exist.
+    NotFound(Object),
+
+    /// The name is already taken by another object of the same kind, which
+    /// would make lookups by name ambiguous.
+    Duplicate {
+        /// The object which was to be renamed.
+        object: Object,
+
+        /// The requested name.
+        name: String,
+
+        /// The object already named `name`.
+        existing: Object,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotFound(object) => {
+                write!(
+                    f,
+                    "{}[{}] does not exist",
+                    object.property(),
+                    object.index()
+                )
+            }
+            Error::Duplicate { name, existing, .. } => write!(
+                f,
+                "{:?} is already the name of {}[{}]",
+                name,
+                existing.property(),
+                existing.index()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A rename which was applied.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Renamed {
+    /// The renamed object.
+    pub object: Object,
+
+    /// The previous name of the object, if it had one.
+    pub from: Option<String>,
+
+    /// The new name of the object.
+    pub to: String,
+}
+
+/// The outcome of [`Document::rename_with`].
+///
+/// [`Document::rename_with`]: ../struct.Document.html#method.rename_with
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    /// The renames which were applied, in order.
+    pub renamed: Vec<Renamed>,
+
+    /// The renames which were not applied, because the new name was taken.
+    pub conflicts: Vec<Error>,
+}
+
+impl Report {
+    /// Returns the current name of the object of the property `property`
+    /// which was named `name` before the renames, or `None` if no such
+    /// object was renamed.
+    ///
+    /// This is intended for resolving data which refers to objects by name,
+    /// such as animation clips retargeted by node name or material
+    /// assignments in external tools.
+    pub fn resolve(&self, property: &str, name: &str) -> Option<&str> {
+        let mut current: Option<&str> = None;
+        for renamed in &self.renamed {
+            let from = renamed.from.as_deref();
+            if renamed.object.property() == property && from == Some(current.unwrap_or(name)) {
+                current = Some(&renamed.to);
+            }
+        }
+        current
+    }
+
+    /// Returns the report as a JSON object of the form:
+    ///
+    /// ```json
+    /// {
+    ///   "renamed": [
+    ///     { "property": "nodes", "index": 3, "from": "Bone.001", "to": "Spine" }
+    ///   ],
+    ///   "conflicts": [
+    ///     { "property": "nodes", "index": 4, "name": "Spine", "existing": 3 }
+    ///   ]
+    /// }
+    /// ```
+    pub fn to_value(&self) -> Value {
+        let renamed: Vec<Value> = self
+            .renamed
+            .iter()
+            .map(|renamed| {
+                let mut value = renamed.object.to_value();
+                value["from"] = renamed.from.clone().into();
+                value["to"] = renamed.to.clone().into();
+                value
+            })
+            .collect();
+        let conflicts: Vec<Value> = self
+            .conflicts
+            .iter()
+            .filter_map(|error| match error {
+                Error::Duplicate {
+                    object,
+                    name,
+                    existing,
+                } => {
+                    let mut value = object.to_value();
+                    value["name"] = name.clone().into();
+                    value["existing"] = existing.index().into();
+                    Some(value)
+                }
+                Error::NotFound(_) => None,
+            })
+            .collect();
+        json!({
+            "renamed": renamed,
+            "conflicts": conflicts,
+        })
+    }
+}
+
+/// Returns every object of `root` which can be renamed, with its name.
+fn objects(root: &json::Root) -> Vec<(Object, Option<String>)> {
+    fn push<T>(
+        objects: &mut Vec<(Object, Option<String>)>,
+        items: &[T],
+        object: fn(usize) -> Object,
+        name: fn(&T) -> &Option<String>,
+    ) {
+        for (index, item) in items.iter().enumerate() {
+            objects.push((object(index), name(item).clone()));
+        }
+    }
+
+    let mut objects = Vec::new();
+    push(&mut objects, &root.animations, Object::Animation, |x| {
+        &x.name
+    });
+    push(&mut objects, &root.cameras, Object::Camera, |x| &x.name);
+    push(&mut objects, &root.materials, Object::Material, |x| &x.name);
+    push(&mut objects, &root.meshes, Object::Mesh, |x| &x.name);
+    push(&mut objects, &root.nodes, Object::Node, |x| &x.name);
+    push(&mut objects, &root.scenes, Object::Scene, |x| &x.name);
+    push(&mut objects, &root.skins, Object::Skin, |x| &x.name);
+    #[cfg(feature = "extras")]
+    for (mesh, json) in root.meshes.iter().enumerate() {
+        for (target, name) in target_names(json).into_iter().flatten().enumerate() {
+            let object = Object::MorphTarget { mesh, target };
+            objects.push((object, name.as_str().map(String::from)));
+        }
+    }
+    objects
+}
+
+/// Returns the `targetNames` array of the extras of `mesh`.
+#[cfg(feature = "extras")]
+fn target_names(mesh: &json::Mesh) -> Option<Vec<Value>> {
+    let extras: Value = json::deserialize::from_str(mesh.extras.as_ref()?.get()).ok()?;
+    match extras.get("targetNames")? {
+        Value::Array(names) => Some(names.clone()),
+        _ => None,
+    }
+}
+
+/// Sets the name of `object` in `root` and returns its previous name.
+fn set_name(root: &mut json::Root, object: Object, name: String) -> Option<Option<String>> {
+    let slot = match object {
+        Object::Animation(index) => &mut root.animations.get_mut(index)?.name,
+        Object::Camera(index) => &mut root.cameras.get_mut(index)?.name,
+        Object::Material(index) => &mut root.materials.get_mut(index)?.name,
+        Object::Mesh(index) => &mut root.meshes.get_mut(index)?.name,
+        Object::Node(index) => &mut root.nodes.get_mut(index)?.name,
+        Object::Scene(index) => &mut root.scenes.get_mut(index)?.name,
+        Object::Skin(index) => &mut root.skins.get_mut(index)?.name,
+        #[cfg(feature = "extras")]
+        Object::MorphTarget { mesh, target } => {
+            let mesh = root.meshes.get_mut(mesh)?;
+            let mut extras: Value =
+                json::deserialize::from_str(mesh.extras.as_ref()?.get()).ok()?;
+            let slot = extras.get_mut("targetNames")?.get_mut(target)?;
+            let previous = slot.as_str().map(String::from);
+            *slot = Value::from(name);
+            mesh.extras = Some(serde_json::value::to_raw_value(&extras).ok()?);
+            return Some(previous);
+        }
+    };
+    Some(slot.replace(name))
+}
+
+/// Renames `object` to `name` unless another object of the same kind is
+/// named `name`.
+fn rename(
+    root: &mut json::Root,
+    objects: &mut [(Object, Option<String>)],
+    object: Object,
+    name: String,
+) -> Result<Renamed, Error> {
+    let position = objects
+        .iter()
+        .position(|(other, _)| *other == object)
+        .ok_or(Error::NotFound(object))?;
+    let existing = objects.iter().find(|(other, other_name)| {
+        *other != object && other.is_sibling(&object) && other_name.as_deref() == Some(&name)
+    });
+    if let Some((existing, _)) = existing {
+        return Err(Error::Duplicate {
+            object,
+            name,
+            existing: *existing,
+        });
+    }
+    let from = set_name(root, object, name.clone()).ok_or(Error::NotFound(object))?;
+    objects[position].1 = Some(name.clone());
+    Ok(Renamed {
+        object,
+        from,
+        to: name,
+    })
+}
+
+impl Document {
+    /// Renames `object` to `name`.
+    ///
+    /// Names stay unique among objects of the same kind, or among the morph
+    /// targets of one mesh, so that lookups by name remain unambiguous.
+    /// Renaming to a name already taken by another object fails with
+    /// [`Error::Duplicate`], leaving the document unchanged.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use gltf::rename::Object;
+    ///
+    /// let mut gltf = gltf::Gltf::from_slice(br#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "nodes": [{ "name": "Bone.001" }, { "name": "Spine" }]
+    /// }"#)?;
+    /// let renamed = gltf.rename(Object::Node(0), "Hips")?;
+    /// assert_eq!(renamed.from.as_deref(), Some("Bone.001"));
+    /// assert!(gltf.rename(Object::Node(0), "Spine").is_err());
+    /// assert_eq!(gltf.nodes().next().unwrap().name(), Some("Hips"));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    ///
+    /// [`Error::Duplicate`]: rename/enum.Error.html#variant.Duplicate
+    pub fn rename(&mut self, object: Object, name: &str) -> Result<Renamed, Error> {
+        let mut objects = objects(&self.0);
+        rename(&mut self.0, &mut objects, object, name.to_owned())
+    }
+
+    /// Renames objects in bulk, calling `f` with every named object and its
+    /// name, and renaming the object if `f` returns a new name.
+    ///
+    /// Renames are applied in order of the top-level properties, then by
+    /// index. A new name already taken by another object of the same kind at
+    /// that point is not applied, and is recorded in [`Report::conflicts`]
+    /// instead. Regular expression replacements can be applied from within
+    /// `f`, for example with the `regex` crate.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let mut gltf = gltf::Gltf::from_slice(br#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "nodes": [{ "name": "mixamorig:Hips" }, { "name": "mixamorig:Spine" }]
+    /// }"#)?;
+    /// let report = gltf.rename_with(|_, name| {
+    ///     name.strip_prefix("mixamorig:").map(String::from)
+    /// });
+    /// assert_eq!(report.renamed.len(), 2);
+    /// assert_eq!(report.resolve("nodes", "mixamorig:Spine"), Some("Spine"));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    ///
+    /// [`Report::conflicts`]: rename/struct.Report.html#structfield.conflicts
+    pub fn rename_with<F>(&mut self, mut f: F) -> Report
+    where
+        F: FnMut(Object, &str) -> Option<String>,
+    {
+        let mut objects = objects(&self.0);
+        let mut report = Report::default();
+        for i in 0..objects.len() {
+            let (object, name) = match &objects[i] {
+                (object, Some(name)) => (*object, name.clone()),
+                (_, None) => continue,
+            };
+            let new_name = match f(object, &name) {
+                Some(new_name) if new_name != name => new_name,
+                _ => continue,
+            };
+            match rename(&mut self.0, &mut objects, object, new_name) {
+                Ok(renamed) => report.renamed.push(renamed),
+                Err(error) => report.conflicts.push(error),
+            }
+        }
+        report
+    }
+}
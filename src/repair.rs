@@ -0,0 +1,144 @@
+use crate::geometry::triangle_area;
+use crate::prelude::*;
+use crate::topology;
+use crate::{Buffer, Primitive};
+
+/// Options for [`Primitive::fill_holes`].
+///
+/// [`Primitive::fill_holes`]: ../mesh/struct.Primitive.html#method.fill_holes
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// The maximum number of edges of a filled hole.
+    max_edges: usize,
+
+    /// The maximum area of the triangles filling a hole.
+    max_area: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            max_edges: 16,
+            max_area: f32::INFINITY,
+        }
+    }
+}
+
+impl Options {
+    /// Constructs the default `Options`, which fill holes of up to 16 edges
+    /// regardless of their area.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of edges of a filled hole. Defaults to `16`.
+    pub fn with_max_edges(mut self, max_edges: usize) -> Self {
+        self.max_edges = max_edges;
+        self
+    }
+
+    /// Sets the maximum area of the triangles filling a hole, in the units of
+    /// the vertex positions. Defaults to infinity.
+    pub fn with_max_area(mut self, max_area: f32) -> Self {
+        self.max_area = max_area;
+        self
+    }
+}
+
+/// The triangles filling the holes of a primitive.
+///
+/// Created by [`Primitive::fill_holes`].
+///
+/// [`Primitive::fill_holes`]: ../mesh/struct.Primitive.html#method.fill_holes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HoleFill {
+    /// The new triangles, as indices of existing vertices.
+    pub triangles: Vec<[u32; 3]>,
+
+    /// The number of filled holes.
+    pub filled: usize,
+
+    /// The number of holes left open because they exceed the limits of the
+    /// options or do not form a closed loop.
+    pub skipped: usize,
+}
+
+/// Triangulates the closed loop of vertices `boundary` into `positions` with
+/// minimal area, and returns the triangles and their area.
+///
+/// The triangles wind against the loop, so that they wind consistently with
+/// the triangles bordering it.
+fn triangulate_loop(positions: &[[f32; 3]], boundary: &[u32]) -> (Vec<[u32; 3]>, f32) {
+    let n = boundary.len();
+    let position = |i: usize| positions[boundary[i] as usize];
+    // The minimal area and best split vertex of the polygon from `i` to `j`.
+    let mut area = vec![0.0f32; n * n];
+    let mut split = vec![0usize; n * n];
+    for gap in 2..n {
+        for i in 0..n - gap {
+            let j = i + gap;
+            let (best, k) = (i + 1..j)
+                .map(|k| {
+                    let total = area[i * n + k]
+                        + area[k * n + j]
+                        + triangle_area(position(i), position(k), position(j));
+                    (total, k)
+                })
+                .fold((f32::INFINITY, i + 1), |a, b| if b.0 < a.0 { b } else { a });
+            area[i * n + j] = best;
+            split[i * n + j] = k;
+        }
+    }
+    let mut triangles = Vec::with_capacity(n - 2);
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i, j)) = stack.pop() {
+        if j - i < 2 {
+            continue;
+        }
+        let k = split[i * n + j];
+        triangles.push([boundary[j], boundary[k], boundary[i]]);
+        stack.push((k, j));
+        stack.push((i, k));
+    }
+    (triangles, area[n - 1])
+}
+
+impl<'a> Primitive<'a> {
+    /// Computes triangles which fill the small holes of an otherwise closed
+    /// primitive.
+    ///
+    /// Holes are the boundary loops reported by [`topology`], and are
+    /// triangulated with minimal area using the existing boundary vertices.
+    /// The new triangles wind consistently with their neighbors, so appending
+    /// them to the triangle list of the primitive closes the holes. No holes
+    /// are filled if the primitive is not manifold.
+    ///
+    /// Returns `None` if the primitive has no readable positions or does not
+    /// consist of triangles.
+    ///
+    /// [`topology`]: #method.topology
+    pub fn fill_holes<'s, F>(&'a self, get_buffer_data: F, options: Options) -> Option<HoleFill>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let reader = self.reader(get_buffer_data);
+        let indices = reader.triangulate()?;
+        let positions: Vec<[f32; 3]> = reader.read_positions()?.collect();
+        let (report, closed) = topology::analyze(&positions, &indices, topology::Options::new());
+        let mut fill = HoleFill::default();
+        for (boundary, closed) in report.boundary_loops.iter().zip(closed) {
+            if !report.is_manifold() || !closed || boundary.len() > options.max_edges {
+                fill.skipped += 1;
+                continue;
+            }
+            let (triangles, area) = triangulate_loop(&positions, boundary);
+            if area > options.max_area {
+                fill.skipped += 1;
+                continue;
+            }
+            fill.triangles.extend(triangles);
+            fill.filled += 1;
+        }
+        Some(fill)
+    }
+}
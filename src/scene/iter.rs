@@ -1,5 +1,7 @@
-use std::slice;
+use crate::prelude::*;
+use core::slice;
 
+use crate::math::Matrix4;
 use crate::{Document, Node};
 
 /// An `Iterator` that visits the nodes in a scene.
@@ -22,7 +24,42 @@ pub struct Children<'a> {
     pub(crate) iter: slice::Iter<'a, json::Index<json::scene::Node>>,
 }
 
+/// An `Iterator` that visits every node in a scene together with its world
+/// transform, in depth-first hierarchy order.
+#[derive(Clone, Debug)]
+pub struct Traverse<'a> {
+    /// Nodes left to visit, paired with the world transform of their parent.
+    pub(crate) stack: Vec<(Node<'a>, Matrix4)>,
+
+    /// Marks the nodes visited so far, guarding against cyclic hierarchies.
+    pub(crate) visited: Vec<bool>,
+}
+
+impl<'a> Iterator for Traverse<'a> {
+    type Item = (Node<'a>, [[f32; 4]; 4]);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, parent) = self.stack.pop()?;
+            if core::mem::replace(&mut self.visited[node.index()], true) {
+                continue;
+            }
+            let world = parent * Matrix4::from_array(node.transform().matrix());
+            self.stack
+                .extend(node.children().rev().map(|child| (child, world)));
+            return Some((node, world.as_array()));
+        }
+    }
+}
+
 impl<'a> ExactSizeIterator for Nodes<'a> {}
+impl<'a> DoubleEndedIterator for Nodes<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|index| self.document.nodes().nth(index.value()).unwrap())
+    }
+}
+
 impl<'a> Iterator for Nodes<'a> {
     type Item = Node<'a>;
     fn next(&mut self) -> Option<Self::Item> {
@@ -37,6 +74,14 @@ impl<'a> Iterator for Nodes<'a> {
 }
 
 impl<'a> ExactSizeIterator for Children<'a> {}
+impl<'a> DoubleEndedIterator for Children<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|index| self.document.nodes().nth(index.value()).unwrap())
+    }
+}
+
 impl<'a> Iterator for Children<'a> {
     type Item = Node<'a>;
     fn next(&mut self) -> Option<Self::Item> {
@@ -1,7 +1,16 @@
+use crate::prelude::*;
 #[cfg(feature = "extensions")]
 use serde_json::{Map, Value};
 
+#[cfg(feature = "utils")]
+use crate::geometry;
+#[cfg(all(feature = "extras", feature = "import"))]
+use crate::image;
 use crate::math::*;
+#[cfg(feature = "extras")]
+use crate::Texture;
+#[cfg(feature = "utils")]
+use crate::{mesh::BoundingBox, Buffer};
 use crate::{Camera, Document, Mesh, Skin};
 
 /// Iterators.
@@ -66,7 +75,11 @@ impl Transform {
                 );
                 let sx = i.x.magnitude();
                 let sy = i.y.magnitude();
-                let sz = i.determinant().signum() * i.z.magnitude();
+                let sz = if i.determinant().is_sign_negative() {
+                    -i.z.magnitude()
+                } else {
+                    i.z.magnitude()
+                };
                 let scale = [sx, sy, sz];
                 i.x.multiply(1.0 / sx);
                 i.y.multiply(1.0 / sy);
@@ -105,7 +118,7 @@ pub struct Node<'a> {
 pub struct Scene<'a> {
     /// The parent `Document` struct.
     #[allow(dead_code)]
-    document: &'a Document,
+    pub(crate) document: &'a Document,
 
     /// The corresponding JSON index.
     index: usize,
@@ -114,6 +127,51 @@ pub struct Scene<'a> {
     json: &'a json::scene::Scene,
 }
 
+/// The background of a scene, following the extras convention described by
+/// [`json::scene::Background`].
+///
+/// [`json::scene::Background`]: ../json/scene/struct.Background.html
+#[cfg(feature = "extras")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+#[derive(Clone, Debug)]
+pub struct Background<'a> {
+    /// The parent `Document` struct.
+    document: &'a Document,
+
+    /// The background parsed from the scene extras.
+    json: json::scene::Background,
+}
+
+#[cfg(feature = "extras")]
+impl<'a> Background<'a> {
+    /// Linear RGB background color.
+    pub fn color(&self) -> Option<[f32; 3]> {
+        self.json.color
+    }
+
+    /// Brightness multiplier of the skybox.
+    pub fn intensity(&self) -> f32 {
+        self.json.intensity
+    }
+
+    /// Returns the equirectangular skybox texture.
+    ///
+    /// Returns `None` if there is no skybox or if the texture does not exist.
+    pub fn skybox(&self) -> Option<Texture<'a>> {
+        self.document.textures().nth(self.json.skybox?.value())
+    }
+
+    /// Returns the decoded skybox image given the image data returned by
+    /// [`import`].
+    ///
+    /// [`import`]: ../fn.import.html
+    #[cfg(feature = "import")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    pub fn skybox_data<'d>(&self, images: &'d [image::Data]) -> Option<&'d image::Data> {
+        images.get(self.skybox()?.source().index())
+    }
+}
+
 impl<'a> Node<'a> {
     /// Constructs a `Node`.
     pub(crate) fn new(document: &'a Document, index: usize, json: &'a json::scene::Node) -> Self {
@@ -170,16 +228,15 @@ impl<'a> Node<'a> {
     #[cfg(feature = "KHR_lights_punctual")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
     pub fn light(&self) -> Option<crate::khr_lights_punctual::Light<'a>> {
-        if let Some(extensions) = self.json.extensions.as_ref() {
-            if let Some(khr_lights_punctual) = extensions.khr_lights_punctual.as_ref() {
-                let mut lights = self.document.lights().unwrap();
-                Some(lights.nth(khr_lights_punctual.light.value()).unwrap())
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+        let index = self
+            .json
+            .extensions
+            .as_ref()?
+            .khr_lights_punctual
+            .as_ref()?
+            .light
+            .value();
+        self.document.lights()?.nth(index)
     }
 
     /// Returns the mesh referenced by this node.
@@ -228,6 +285,88 @@ impl<'a> Node<'a> {
     pub fn weights(&self) -> Option<&'a [f32]> {
         self.json.weights.as_deref()
     }
+
+    /// Returns the world transform of this node in `scene` as a 4x4
+    /// column-major matrix.
+    ///
+    /// Returns `None` if the node is not part of the scene. When computing the
+    /// transforms of many nodes, prefer [`Scene::traverse`].
+    ///
+    /// [`Scene::traverse`]: struct.Scene.html#method.traverse
+    pub fn global_transform(&self, scene: &Scene<'a>) -> Option<[[f32; 4]; 4]> {
+        scene
+            .traverse()
+            .find(|(node, _)| node.index() == self.index)
+            .map(|(_, transform)| transform)
+    }
+
+    /// Computes the surface area of the mesh referenced by this node, with
+    /// its vertex positions transformed by the column-major matrix
+    /// `transform`, such as the world transform returned by
+    /// [`Scene::traverse`].
+    ///
+    /// Returns `None` if the node has no mesh with readable triangles.
+    ///
+    /// [`Scene::traverse`]: struct.Scene.html#method.traverse
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn surface_area<'s, F>(&self, get_buffer_data: F, transform: &[[f32; 4]; 4]) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.measure(get_buffer_data, transform)
+            .map(|(area, _)| area)
+    }
+
+    /// Computes the volume enclosed by the mesh referenced by this node,
+    /// with its vertex positions transformed by the column-major matrix
+    /// `transform`.
+    ///
+    /// The triangles of all primitives are considered together, so a closed
+    /// mesh may be split into primitives by material. Returns `None` if the
+    /// node has no mesh with readable triangles or the mesh is not closed,
+    /// see [`Primitive::is_closed`].
+    ///
+    /// [`Primitive::is_closed`]: ../mesh/struct.Primitive.html#method.is_closed
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn volume<'s, F>(&self, get_buffer_data: F, transform: &[[f32; 4]; 4]) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.measure(get_buffer_data, transform)?.1
+    }
+
+    /// Measures the surface area and enclosed volume of the mesh referenced
+    /// by this node.
+    #[cfg(feature = "utils")]
+    fn measure<'s, F>(
+        &self,
+        get_buffer_data: F,
+        transform: &[[f32; 4]; 4],
+    ) -> Option<(f32, Option<f32>)>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mesh = self.mesh()?;
+
+        let buffer_data = geometry::BufferData::new(self.document, get_buffer_data);
+        let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+        let mut triangles = Vec::new();
+        let mut any = false;
+        for primitive in mesh.primitives() {
+            if let Some(more) = primitive.transformed_triangles(get_buffer_data, transform) {
+                triangles.extend(more);
+                any = true;
+            }
+        }
+        if any {
+            Some(geometry::measure(&triangles))
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a> Scene<'a> {
@@ -272,6 +411,36 @@ impl<'a> Scene<'a> {
         self.json.name.as_deref()
     }
 
+    /// Returns the background of this scene stored in the extras.
+    ///
+    /// See [`json::scene::Background`] for the convention.
+    ///
+    /// [`json::scene::Background`]: ../json/scene/struct.Background.html
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn background(&self) -> Option<Background<'a>> {
+        self.json.background().map(|json| Background {
+            document: self.document,
+            json,
+        })
+    }
+
+    /// Returns the environment light of this scene as defined by the
+    /// `EXT_lights_image_based` extension.
+    #[cfg(feature = "EXT_lights_image_based")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "EXT_lights_image_based")))]
+    pub fn image_based_light(&self) -> Option<crate::ext_lights_image_based::ImageBasedLight<'a>> {
+        let index = self
+            .json
+            .extensions
+            .as_ref()?
+            .ext_lights_image_based
+            .as_ref()?
+            .light
+            .value();
+        self.document.image_based_lights()?.nth(index)
+    }
+
     /// Returns an `Iterator` that visits each root node of the scene.
     pub fn nodes(&self) -> iter::Nodes<'a> {
         iter::Nodes {
@@ -279,13 +448,153 @@ impl<'a> Scene<'a> {
             iter: self.json.nodes.iter(),
         }
     }
+
+    /// Computes the world-space bounding box of all meshes in the scene.
+    ///
+    /// Mesh bounds are computed from the vertex positions, transformed by the
+    /// world transform of each instantiating node, and merged. Returns `None`
+    /// if the scene contains no readable geometry.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn compute_bounding_box<'s, F>(&self, get_buffer_data: F) -> Option<BoundingBox>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.traverse()
+            .filter_map(|(node, transform)| {
+                let mesh = node.mesh()?;
+                let bounds = mesh.compute_bounding_box(get_buffer_data.clone())?;
+                Some(bounds.transform(&transform))
+            })
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Computes the world-space surface area of all meshes in the scene,
+    /// counting each instantiating node separately.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn surface_area<'s, F>(&self, get_buffer_data: F) -> f32
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        self.traverse()
+            .filter_map(|(node, transform)| node.surface_area(get_buffer_data.clone(), &transform))
+            .sum()
+    }
+
+    /// Computes the world-space volume enclosed by all meshes in the scene,
+    /// counting each instantiating node separately.
+    ///
+    /// Overlapping meshes are not merged, so their shared volume is counted
+    /// repeatedly. Returns `None` if any mesh with readable triangles is not
+    /// closed, see [`Node::volume`].
+    ///
+    /// [`Node::volume`]: struct.Node.html#method.volume
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn volume<'s, F>(&self, get_buffer_data: F) -> Option<f32>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut volume = 0.0;
+        for (node, transform) in self.traverse() {
+            if let Some((_, node_volume)) = node.measure(get_buffer_data.clone(), &transform) {
+                volume += node_volume?;
+            }
+        }
+        Some(volume)
+    }
+
+    /// Returns an `Iterator` that visits every node of the scene in
+    /// depth-first hierarchy order, together with its world transform.
+    ///
+    /// The world transform is a 4x4 column-major matrix computed as the
+    /// product of the local transforms of the node and all its ancestors.
+    /// Parents are always visited before their children.
+    pub fn traverse(&self) -> iter::Traverse<'a> {
+        let identity = Matrix4::from_nonuniform_scale(1.0, 1.0, 1.0);
+        iter::Traverse {
+            stack: self.nodes().rev().map(|node| (node, identity)).collect(),
+            visited: vec![false; self.document.nodes().len()],
+        }
+    }
+}
+
+/// Checks that the node hierarchy forms disjoint strict trees, i.e. that no
+/// node has more than one parent and that there are no cycles.
+///
+/// Out of range child indices are ignored since they are reported by the
+/// regular JSON validation.
+pub(crate) fn validate_hierarchy<R>(root: &json::Root, report: &mut R)
+where
+    R: FnMut(&dyn Fn() -> json::Path, json::validation::Error),
+{
+    let nodes = &root.nodes;
+    let child_path = |parent: usize, child: usize| {
+        json::Path::new()
+            .field("nodes")
+            .index(parent)
+            .field("children")
+            .index(child)
+    };
+
+    // The parent of each node, and the position in the parent's children.
+    let mut parents: Vec<Option<(usize, usize)>> = vec![None; nodes.len()];
+    for (index, node) in nodes.iter().enumerate() {
+        for (position, child) in node.children.iter().flatten().enumerate() {
+            let child = child.value();
+            if child >= nodes.len() {
+                continue;
+            }
+            if parents[child].is_some() {
+                report(
+                    &|| child_path(index, position),
+                    json::validation::Error::Invalid,
+                );
+            } else {
+                parents[child] = Some((index, position));
+            }
+        }
+    }
+
+    // Every node has at most one parent now, so following the parents from any
+    // node either terminates at a root or runs into a cycle.
+    const UNVISITED: u8 = 0;
+    const VISITING: u8 = 1;
+    const DONE: u8 = 2;
+    let mut state = vec![UNVISITED; nodes.len()];
+    let mut walk = Vec::new();
+    for start in 0..nodes.len() {
+        let mut current = Some(start);
+        while let Some(index) = current {
+            if state[index] != UNVISITED {
+                break;
+            }
+            state[index] = VISITING;
+            walk.push(index);
+            current = parents[index].map(|(parent, _)| parent);
+        }
+        if let Some(index) = current {
+            if state[index] == VISITING {
+                // Report the reference which closes the cycle.
+                let (parent, position) = parents[index].unwrap();
+                report(
+                    &|| child_path(parent, position),
+                    json::validation::Error::Cycle,
+                );
+            }
+        }
+        for index in walk.drain(..) {
+            state[index] = DONE;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::math::*;
     use crate::scene::Transform;
-    use std::f32::consts::PI;
+    use core::f32::consts::PI;
 
     fn rotate(x: f32, y: f32, z: f32, r: f32) -> [f32; 4] {
         let r = Quaternion::from_axis_angle(Vector3::new(x, y, z).normalize(), r);
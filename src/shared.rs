@@ -0,0 +1,105 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::{buffer, image, Document};
+
+/// A cheaply clonable, thread-safe handle to imported buffer data.
+///
+/// Cloning a `SharedBuffer` only increments a reference count, so that
+/// several systems, for example a renderer and a physics engine, can hold on
+/// to the same payload without copying it.
+#[derive(Clone, Debug)]
+pub struct SharedBuffer(Arc<buffer::Data>);
+
+impl SharedBuffer {
+    /// Returns `true` if both handles refer to the same data.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the data if this is the only handle to it, or the handle
+    /// otherwise.
+    pub fn try_unwrap(self) -> Result<buffer::Data, Self> {
+        Arc::try_unwrap(self.0).map_err(SharedBuffer)
+    }
+}
+
+impl From<buffer::Data> for SharedBuffer {
+    fn from(data: buffer::Data) -> Self {
+        SharedBuffer(Arc::new(data))
+    }
+}
+
+impl ops::Deref for SharedBuffer {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A cheaply clonable, thread-safe handle to imported image data.
+///
+/// Dereferences to the underlying [`image::Data`], see [`SharedBuffer`].
+///
+/// [`image::Data`]: ../image/struct.Data.html
+/// [`SharedBuffer`]: struct.SharedBuffer.html
+#[derive(Clone, Debug)]
+pub struct SharedImage(Arc<image::Data>);
+
+impl SharedImage {
+    /// Returns `true` if both handles refer to the same data.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the data if this is the only handle to it, or the handle
+    /// otherwise.
+    pub fn try_unwrap(self) -> Result<image::Data, Self> {
+        Arc::try_unwrap(self.0).map_err(SharedImage)
+    }
+}
+
+impl From<image::Data> for SharedImage {
+    fn from(data: image::Data) -> Self {
+        SharedImage(Arc::new(data))
+    }
+}
+
+impl ops::Deref for SharedImage {
+    type Target = image::Data;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Return type of [`share`].
+///
+/// [`share`]: fn.share.html
+pub type SharedImport = (Document, Vec<SharedBuffer>, Vec<SharedImage>);
+
+/// Converts the result of an import into shared handles, without copying
+/// the buffer or image data.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, buffers, _) =
+///     gltf::shared::share(gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?);
+/// let physics = buffers[0].clone();
+/// let thread = std::thread::spawn(move || physics.len());
+/// assert_eq!(thread.join().unwrap(), buffers[0].len());
+/// # let _ = document;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+pub fn share(
+    (document, buffers, images): (Document, Vec<buffer::Data>, Vec<image::Data>),
+) -> SharedImport {
+    (
+        document,
+        buffers.into_iter().map(SharedBuffer::from).collect(),
+        images.into_iter().map(SharedImage::from).collect(),
+    )
+}
@@ -0,0 +1,341 @@
+use crate::accessor::Writer;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::prelude::*;
+use crate::Scene;
+use alloc::collections::BTreeMap;
+use json::buffer::Target;
+use json::validation::Checked::Valid;
+
+/// Options for [`write`].
+///
+/// [`write`]: fn.write.html
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// The radius of the joint markers, or `None` to derive it from the size
+    /// of the skeletons.
+    joint_radius: Option<f32>,
+
+    /// Whether labelled nodes are created at the joints.
+    labels: bool,
+
+    /// The color of the joint markers.
+    joint_color: [f32; 4],
+
+    /// The color of the bones.
+    bone_color: [f32; 4],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            joint_radius: None,
+            labels: false,
+            joint_color: [1.0, 0.6, 0.0, 1.0],
+            bone_color: [0.0, 0.8, 1.0, 1.0],
+        }
+    }
+}
+
+impl Options {
+    /// Constructs the default `Options`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the radius of the joint markers in world units. Defaults to
+    /// 1.5% of the diagonal of the bounds of the joints.
+    pub fn with_joint_radius(mut self, joint_radius: f32) -> Self {
+        self.joint_radius = Some(joint_radius);
+        self
+    }
+
+    /// Sets whether a node named after each joint is created at its
+    /// position, which viewers and editors display as a label. Defaults to
+    /// `false`.
+    ///
+    /// Without the `names` feature the nodes are unnamed.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the linear RGBA color of the joint markers. Defaults to orange.
+    pub fn with_joint_color(mut self, joint_color: [f32; 4]) -> Self {
+        self.joint_color = joint_color;
+        self
+    }
+
+    /// Sets the linear RGBA color of the bones. Defaults to light blue.
+    pub fn with_bone_color(mut self, bone_color: [f32; 4]) -> Self {
+        self.bone_color = bone_color;
+        self
+    }
+}
+
+/// The vertices of an octahedron with unit radius, followed by its triangles
+/// in counter-clockwise order.
+const OCTAHEDRON: ([[f32; 3]; 6], [[u32; 3]; 8]) = (
+    [
+        [1.0, 0.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+    ],
+    [
+        [0, 2, 4],
+        [2, 1, 4],
+        [1, 3, 4],
+        [3, 0, 4],
+        [2, 0, 5],
+        [1, 2, 5],
+        [3, 1, 5],
+        [0, 3, 5],
+    ],
+);
+
+/// Returns an unlit-looking material of `color`, which is emissive so that
+/// it is visible regardless of the lighting of the viewer.
+fn material(name: &str, color: [f32; 4]) -> json::Material {
+    #[cfg(not(feature = "names"))]
+    let _ = name;
+    json::Material {
+        #[cfg(feature = "names")]
+        name: Some(name.to_owned()),
+        pbr_metallic_roughness: json::material::PbrMetallicRoughness {
+            base_color_factor: json::material::PbrBaseColorFactor(color),
+            metallic_factor: json::material::StrengthFactor(0.0),
+            ..Default::default()
+        },
+        emissive_factor: json::material::EmissiveFactor([color[0], color[1], color[2]]),
+        ..Default::default()
+    }
+}
+
+/// Returns a primitive drawing the vertices `positions` with `indices`, or
+/// `None` if either is empty.
+fn primitive(
+    writer: &mut Writer,
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    mode: json::mesh::Mode,
+    material: json::Index<json::Material>,
+) -> Option<json::mesh::Primitive> {
+    let positions = writer.push(positions, Some(Target::ArrayBuffer))?;
+    let indices = writer.push(indices, Some(Target::ElementArrayBuffer))?;
+    let mut attributes = BTreeMap::new();
+    attributes.insert(Valid(json::mesh::Semantic::Positions), positions);
+    Some(json::mesh::Primitive {
+        attributes,
+        extensions: None,
+        extras: Default::default(),
+        indices: Some(indices),
+        material: Some(material),
+        mode: Valid(mode),
+        targets: None,
+    })
+}
+
+/// Returns a node without a transform.
+fn node(name: Option<&str>) -> json::Node {
+    #[cfg(not(feature = "names"))]
+    let _ = name;
+    json::Node {
+        camera: None,
+        children: None,
+        extensions: None,
+        extras: Default::default(),
+        matrix: None,
+        mesh: None,
+        #[cfg(feature = "names")]
+        name: name.map(String::from),
+        rotation: None,
+        scale: None,
+        translation: None,
+        skin: None,
+        weights: None,
+    }
+}
+
+/// Writes a renderable visualization of the skeletons used in `scene` as a
+/// new scene of the root of `writer`, and returns its index.
+///
+/// Every joint of every skin is drawn as an octahedron at its world
+/// position, and every bone as a line from a joint to the nearest of its
+/// ancestors that is also a joint. Skinned meshes themselves are not drawn,
+/// so the root of `writer` is typically empty, making a separate document
+/// for quality assurance and documentation imagery.
+///
+/// Returns `None` if no joint is part of `scene`.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::accessor::Writer;
+/// use gltf::skeleton::{self, Options};
+///
+/// let gltf = gltf::Gltf::from_slice(br#"{
+///     "asset": { "version": "2.0" },
+///     "scenes": [{ "nodes": [0] }],
+///     "nodes": [
+///         { "name": "hips", "children": [1] },
+///         { "name": "spine", "translation": [0, 1, 0] }
+///     ],
+///     "skins": [{ "joints": [0, 1] }]
+/// }"#)?;
+/// let scene = gltf.scenes().next().unwrap();
+///
+/// let mut root = gltf::json::Root::default();
+/// let mut writer = Writer::new(&mut root);
+/// let options = Options::new().with_labels(true);
+/// let skeleton = skeleton::write(&mut writer, &scene, &options).unwrap();
+/// let bin = writer.finish();
+/// assert_eq!(root.scenes[skeleton.value()].nodes.len(), 3);
+/// assert_eq!(root.meshes[0].primitives.len(), 2);
+/// # let _ = bin;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+pub fn write(
+    writer: &mut Writer,
+    scene: &Scene,
+    options: &Options,
+) -> Option<json::Index<json::Scene>> {
+    // The world position and parent of every node of the scene.
+    let mut world = BTreeMap::new();
+    let mut parents = BTreeMap::new();
+    for (node, transform) in scene.traverse() {
+        world.insert(
+            node.index(),
+            (node.clone(), [0, 1, 2].map(|i| transform[3][i])),
+        );
+        for child in node.children() {
+            parents.insert(child.index(), node.index());
+        }
+    }
+
+    // The joints of every skin which are part of the scene, in order.
+    let mut joints = Vec::new();
+    for skin in scene.document.skins() {
+        for joint in skin.joints() {
+            if world.contains_key(&joint.index()) && !joints.contains(&joint.index()) {
+                joints.push(joint.index());
+            }
+        }
+    }
+    if joints.is_empty() {
+        return None;
+    }
+    let positions: Vec<[f32; 3]> = joints.iter().map(|joint| world[joint].1).collect();
+
+    let radius = options.joint_radius.unwrap_or_else(|| {
+        let (mut min, mut max) = (positions[0], positions[0]);
+        for position in &positions {
+            for i in 0..3 {
+                min[i] = min[i].min(position[i]);
+                max[i] = max[i].max(position[i]);
+            }
+        }
+        let diagonal: f32 = [0, 1, 2].map(|i| (max[i] - min[i]).powi(2)).iter().sum();
+        let radius = 0.015 * diagonal.sqrt();
+        if radius > 0.0 {
+            radius
+        } else {
+            0.01
+        }
+    });
+
+    let (vertices, triangles) = OCTAHEDRON;
+    let mut joint_positions = Vec::with_capacity(positions.len() * vertices.len());
+    let mut joint_indices = Vec::with_capacity(positions.len() * triangles.len() * 3);
+    for position in &positions {
+        let first = joint_positions.len() as u32;
+        joint_positions.extend(
+            vertices
+                .iter()
+                .map(|vertex| [0, 1, 2].map(|i| position[i] + radius * vertex[i])),
+        );
+        joint_indices.extend(triangles.iter().flatten().map(|index| first + index));
+    }
+    let mut bone_indices = Vec::new();
+    for (index, joint) in joints.iter().enumerate() {
+        let mut ancestor = parents.get(joint);
+        while let Some(parent) = ancestor {
+            if let Some(parent) = joints.iter().position(|joint| joint == parent) {
+                bone_indices.extend([parent as u32, index as u32]);
+                break;
+            }
+            ancestor = parents.get(parent);
+        }
+    }
+
+    let root = writer.root_mut();
+    let joint_material = json::Index::new(root.materials.len() as u32);
+    root.materials
+        .push(material("skeleton joints", options.joint_color));
+    let bone_material = json::Index::new(root.materials.len() as u32);
+    root.materials
+        .push(material("skeleton bones", options.bone_color));
+
+    let mut primitives = vec![primitive(
+        writer,
+        &joint_positions,
+        &joint_indices,
+        json::mesh::Mode::Triangles,
+        joint_material,
+    )?];
+    if !bone_indices.is_empty() {
+        primitives.push(primitive(
+            writer,
+            &positions,
+            &bone_indices,
+            json::mesh::Mode::Lines,
+            bone_material,
+        )?);
+    }
+
+    let root = writer.root_mut();
+    let mesh = json::Index::new(root.meshes.len() as u32);
+    root.meshes.push(json::Mesh {
+        extensions: None,
+        extras: Default::default(),
+        #[cfg(feature = "names")]
+        name: Some("skeleton".to_owned()),
+        primitives,
+        weights: None,
+    });
+    let mut nodes = vec![json::Index::new(root.nodes.len() as u32)];
+    root.nodes.push(json::Node {
+        mesh: Some(mesh),
+        ..node(Some("skeleton"))
+    });
+    if options.labels {
+        for (joint, position) in joints.iter().zip(&positions) {
+            #[cfg(feature = "names")]
+            let name = world[joint].0.name();
+            #[cfg(not(feature = "names"))]
+            let name = {
+                let _ = joint;
+                None
+            };
+            nodes.push(json::Index::new(root.nodes.len() as u32));
+            root.nodes.push(json::Node {
+                translation: Some(*position),
+                ..node(name)
+            });
+        }
+    }
+    let scene = json::Index::new(root.scenes.len() as u32);
+    root.scenes.push(json::Scene {
+        extensions: None,
+        extras: Default::default(),
+        #[cfg(feature = "names")]
+        name: Some("skeleton".to_owned()),
+        nodes,
+    });
+    Some(scene)
+}
@@ -1,10 +1,19 @@
+#[cfg(feature = "utils")]
+use crate::prelude::*;
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 #[cfg(feature = "extensions")]
 use serde_json::{Map, Value};
 
 use crate::{Accessor, Document, Node};
 
 #[cfg(feature = "utils")]
-use crate::Buffer;
+use crate::accessor;
+
+#[cfg(feature = "utils")]
+use crate::math::Matrix4;
+#[cfg(feature = "utils")]
+use crate::{Buffer, Scene};
 
 /// Iterators.
 pub mod iter;
@@ -91,6 +100,45 @@ impl<'a> Skin<'a> {
         }
     }
 
+    /// Computes the joint matrices of this skin in `scene`, in joint order.
+    ///
+    /// Each matrix is the world transform of the joint multiplied by its
+    /// inverse-bind matrix, transforming vertices from the bind pose to their
+    /// skinned world space positions. As required by the specification, the
+    /// transform of the node the skinned mesh is attached to is not applied.
+    ///
+    /// Returns `None` if a joint is not part of `scene` or if the
+    /// inverse-bind matrices cannot be read.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn joint_matrices<'s, F>(
+        &self,
+        scene: &Scene<'a>,
+        get_buffer_data: F,
+    ) -> Option<Vec<[[f32; 4]; 4]>>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut world = vec![None; self.document.nodes().len()];
+        for (node, transform) in scene.traverse() {
+            world[node.index()] = Some(transform);
+        }
+        let mut inverse_bind_matrices = match self.inverse_bind_matrices() {
+            Some(accessor) => Some(accessor::Iter::new(accessor, get_buffer_data)?),
+            None => None,
+        };
+        self.joints()
+            .map(|joint| {
+                let world = Matrix4::from_array(world[joint.index()]?);
+                let inverse_bind = match inverse_bind_matrices.as_mut() {
+                    Some(iter) => Matrix4::from_array(iter.next()?),
+                    None => return Some(world.as_array()),
+                };
+                Some((world * inverse_bind).as_array())
+            })
+            .collect()
+    }
+
     /// Returns an `Iterator` that visits the skeleton nodes used as joints in
     /// this skin.
     pub fn joints(&self) -> iter::Joints<'a> {
@@ -0,0 +1,105 @@
+use crate::hash::Hasher;
+use crate::prelude::*;
+use crate::{buffer, image, Document};
+use core::fmt::Write;
+
+use json::Value;
+use serde_json::Map;
+
+/// Returns `value` with the members of every object sorted by key.
+///
+/// Member order is otherwise that of insertion when `serde_json` is built
+/// with its `preserve_order` feature, as with the `lossless` feature.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut members: Vec<(String, Value)> = object.into_iter().collect();
+            members.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut sorted = Map::new();
+            for (key, value) in members {
+                sorted.insert(key, sort_keys(value));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// Returns the glTF JSON of `document` in canonical form, pretty-printed with
+/// the members of every object sorted by key.
+///
+/// Two documents with the same content produce the same canonical JSON,
+/// regardless of the member order of their source.
+pub fn canonical_json(document: &Document) -> String {
+    // Serializing a document cannot fail.
+    let root = json::serialize::to_value(&document.0).unwrap();
+    json::serialize::to_string_pretty(&sort_keys(root)).unwrap()
+}
+
+/// Returns a normalized textual digest of an import, for golden tests of
+/// asset pipelines.
+///
+/// `buffers` and `images` are expected to be the data returned by
+/// [`import`](../fn.import.html) for `document`. The digest lists the length
+/// and content hash of every buffer, the dimensions, format and hash of the
+/// decoded pixels of every image, and the [canonical JSON] of the document:
+///
+/// ```text
+/// buffer 0: 648 bytes, hash 2cd4a7b3f4e1e2a8
+/// image 0: 256x256 R8G8B8A8, hash 0b6f0a8e5c7d1f93
+/// json:
+/// {
+///   "accessors": [
+///   ...
+/// ```
+///
+/// Hashes are stable across platforms, runs and compiler versions, so the
+/// digest may be stored alongside the test and compared with `assert_eq!`.
+/// Image hashes depend on the decoder, which may change between versions of
+/// the `image` crate.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// # let path = "examples/Box.gltf";
+/// let (document, buffers, images) = gltf::import(path, gltf::filesystem_fetcher)?;
+/// let digest = gltf::snapshot::digest(&document, &buffers, &images);
+/// assert!(digest.starts_with("buffer 0: 648 bytes, hash "));
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+///
+/// [canonical JSON]: fn.canonical_json.html
+pub fn digest(document: &Document, buffers: &[buffer::Data], images: &[image::Data]) -> String {
+    let mut digest = String::new();
+    // Writing to a `String` cannot fail.
+    for (index, data) in buffers.iter().enumerate() {
+        let mut hasher = Hasher::new();
+        hasher.write(data);
+        let _ = writeln!(
+            digest,
+            "buffer {}: {} bytes, hash {:016x}",
+            index,
+            data.len(),
+            hasher.finish()
+        );
+    }
+    for (index, data) in images.iter().enumerate() {
+        let _ = writeln!(
+            digest,
+            "image {}: {}x{} {:?}, hash {:016x}",
+            index,
+            data.width,
+            data.height,
+            data.format,
+            data.content_hash()
+        );
+    }
+    digest.push_str("json:\n");
+    digest.push_str(&canonical_json(document));
+    digest.push('\n');
+    digest
+}
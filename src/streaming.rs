@@ -0,0 +1,360 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::geometry::{transform_point, triangle_area, BufferData};
+use crate::prelude::*;
+use crate::{Buffer, Document, Material};
+
+use json::Value;
+use serde_json::json;
+
+/// The material slot a texture is used in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TextureUsage {
+    /// `pbrMetallicRoughness.baseColorTexture`.
+    BaseColor,
+
+    /// `pbrMetallicRoughness.metallicRoughnessTexture`.
+    MetallicRoughness,
+
+    /// `normalTexture`.
+    Normal,
+
+    /// `occlusionTexture`.
+    Occlusion,
+
+    /// `emissiveTexture`.
+    Emissive,
+}
+
+impl TextureUsage {
+    /// Returns the relative importance of the slot between 0 and 1.
+    ///
+    /// Slots whose detail is most noticeable when missing, like base color
+    /// and normals, weigh the most.
+    pub fn weight(self) -> f32 {
+        match self {
+            TextureUsage::BaseColor => 1.0,
+            TextureUsage::Normal => 0.8,
+            TextureUsage::Emissive => 0.6,
+            TextureUsage::MetallicRoughness => 0.5,
+            TextureUsage::Occlusion => 0.4,
+        }
+    }
+
+    /// Returns the name of the slot as used in the glTF JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TextureUsage::BaseColor => "baseColorTexture",
+            TextureUsage::MetallicRoughness => "metallicRoughnessTexture",
+            TextureUsage::Normal => "normalTexture",
+            TextureUsage::Occlusion => "occlusionTexture",
+            TextureUsage::Emissive => "emissiveTexture",
+        }
+    }
+}
+
+/// Streaming priority of a texture.
+///
+/// Created by [`Document::texture_streaming_priorities`].
+///
+/// [`Document::texture_streaming_priorities`]: ../struct.Document.html#method.texture_streaming_priorities
+#[derive(Clone, Debug, PartialEq)]
+pub struct TexturePriority {
+    /// The index of the texture.
+    pub texture: usize,
+
+    /// The priority relative to the other textures of the document, between
+    /// 0 and 1 where 1 is the most important texture.
+    pub priority: f32,
+
+    /// The distinct material slots the texture is used in.
+    pub usages: Vec<TextureUsage>,
+
+    /// The number of materials using the texture.
+    pub materials: usize,
+
+    /// The world space surface area of the triangles sampling the texture,
+    /// summed over every instance in every scene.
+    pub surface_area: f32,
+
+    /// The texture space area of the triangles sampling the texture, summed
+    /// over every instance in every scene, where 1 covers the texture once.
+    pub uv_area: f32,
+}
+
+impl TexturePriority {
+    /// Returns the average number of texels per world space unit on the
+    /// surfaces sampling the texture, given its image dimensions.
+    ///
+    /// Returns 0 if the texture does not cover any surface.
+    pub fn texels_per_unit(&self, width: u32, height: u32) -> f32 {
+        if self.surface_area > 0.0 {
+            (self.uv_area * width as f32 * height as f32 / self.surface_area).sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the finest mip level which needs to be resident to provide
+    /// `texels_per_unit` texels per world space unit, given the image
+    /// dimensions of the texture.
+    ///
+    /// Finer mip levels do not add visible detail at that density and need
+    /// not be streamed. Textures which do not cover any surface only need
+    /// their coarsest mip level.
+    pub fn mip_level(&self, width: u32, height: u32, texels_per_unit: f32) -> u32 {
+        let unused = self.surface_area <= 0.0;
+        let mut density = self.texels_per_unit(width, height);
+        let (mut width, mut height) = (width, height);
+        let mut level = 0;
+        while (width > 1 || height > 1) && (unused || density * 0.5 >= texels_per_unit) {
+            density *= 0.5;
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+            level += 1;
+        }
+        level
+    }
+
+    /// Returns the priority as a JSON object.
+    fn to_value(&self) -> Value {
+        json!({
+            "priority": self.priority,
+            "usages": self.usages.iter().map(|usage| usage.as_str()).collect::<Vec<_>>(),
+            "materials": self.materials,
+            "surfaceArea": self.surface_area,
+            "uvArea": self.uv_area,
+        })
+    }
+}
+
+/// Returns the texture slots of a material as usage, texture index, and
+/// texture co-ordinate set.
+pub(crate) fn slots(material: &Material<'_>) -> Vec<(TextureUsage, usize, u32)> {
+    let pbr = material.pbr_metallic_roughness();
+    let mut slots = Vec::new();
+    if let Some(info) = pbr.base_color_texture() {
+        slots.push((
+            TextureUsage::BaseColor,
+            info.texture().index(),
+            info.tex_coord(),
+        ));
+    }
+    if let Some(info) = pbr.metallic_roughness_texture() {
+        slots.push((
+            TextureUsage::MetallicRoughness,
+            info.texture().index(),
+            info.tex_coord(),
+        ));
+    }
+    if let Some(info) = material.normal_texture() {
+        slots.push((
+            TextureUsage::Normal,
+            info.texture().index(),
+            info.tex_coord(),
+        ));
+    }
+    if let Some(info) = material.occlusion_texture() {
+        slots.push((
+            TextureUsage::Occlusion,
+            info.texture().index(),
+            info.tex_coord(),
+        ));
+    }
+    if let Some(info) = material.emissive_texture() {
+        slots.push((
+            TextureUsage::Emissive,
+            info.texture().index(),
+            info.tex_coord(),
+        ));
+    }
+    slots
+}
+
+/// Returns the area of a triangle in 2D.
+fn uv_triangle_area(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs()
+}
+
+/// Returns the summed area of `triangles` with `positions` as vertices,
+/// skipping triangles with out of range indices.
+fn area<T: Copy>(triangles: &[u32], positions: &[T], area: impl Fn(T, T, T) -> f32) -> f32 {
+    triangles
+        .chunks_exact(3)
+        .filter_map(|triangle| {
+            let a = *positions.get(triangle[0] as usize)?;
+            let b = *positions.get(triangle[1] as usize)?;
+            let c = *positions.get(triangle[2] as usize)?;
+            Some(area(a, b, c))
+        })
+        .sum()
+}
+
+impl Document {
+    /// Estimates how important every texture is for texture streaming.
+    ///
+    /// Textures are weighted by the material slots they are used in (see
+    /// [`TextureUsage::weight`]) and the world space surface area of the
+    /// triangle primitives sampling them in every scene. Nodes which are not
+    /// part of any scene are ignored. The surface and texture space areas
+    /// are recorded too, so that the required mip levels can be derived
+    /// once the image dimensions are known, see
+    /// [`TexturePriority::mip_level`].
+    ///
+    /// Returns one entry per texture, in index order.
+    ///
+    /// [`TextureUsage::weight`]: streaming/enum.TextureUsage.html#method.weight
+    /// [`TexturePriority::mip_level`]: streaming/struct.TexturePriority.html#method.mip_level
+    pub fn texture_streaming_priorities<'a, 's, F>(
+        &'a self,
+        get_buffer_data: F,
+    ) -> Vec<TexturePriority>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut priorities: Vec<TexturePriority> = self
+            .textures()
+            .map(|texture| TexturePriority {
+                texture: texture.index(),
+                priority: 0.0,
+                usages: Vec::new(),
+                materials: 0,
+                surface_area: 0.0,
+                uv_area: 0.0,
+            })
+            .collect();
+
+        for material in self.materials() {
+            let mut counted = Vec::new();
+            for (usage, texture, _) in slots(&material) {
+                if let Some(priority) = priorities.get_mut(texture) {
+                    if !priority.usages.contains(&usage) {
+                        priority.usages.push(usage);
+                    }
+                    if !counted.contains(&texture) {
+                        counted.push(texture);
+                        priority.materials += 1;
+                    }
+                }
+            }
+        }
+
+        let buffer_data = BufferData::new(self, get_buffer_data);
+        let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+        let mut scores = vec![0.0f32; priorities.len()];
+        for scene in self.scenes() {
+            for (node, transform) in scene.traverse() {
+                let mesh = match node.mesh() {
+                    Some(mesh) => mesh,
+                    None => continue,
+                };
+                for primitive in mesh.primitives() {
+                    let slots = slots(&primitive.material());
+                    if slots.is_empty() {
+                        continue;
+                    }
+                    let reader = primitive.reader(get_buffer_data);
+                    let (positions, triangles) =
+                        match (reader.read_positions(), reader.triangulate()) {
+                            (Some(positions), Some(triangles)) => (positions, triangles),
+                            _ => continue,
+                        };
+                    let positions: Vec<_> = positions
+                        .map(|position| transform_point(&transform, position))
+                        .collect();
+                    let surface_area = area(&triangles, &positions, triangle_area);
+
+                    let mut uv_areas: Vec<(u32, f32)> = Vec::new();
+                    for (usage, texture, set) in slots {
+                        let uv_area = match uv_areas.iter().find(|(s, _)| *s == set) {
+                            Some(&(_, uv_area)) => uv_area,
+                            None => {
+                                let tex_coords = reader.read_tex_coords_or_default(set);
+                                let uv_area = area(&triangles, &tex_coords, uv_triangle_area);
+                                uv_areas.push((set, uv_area));
+                                uv_area
+                            }
+                        };
+                        if let Some(priority) = priorities.get_mut(texture) {
+                            priority.surface_area += surface_area;
+                            priority.uv_area += uv_area;
+                            scores[texture] += usage.weight() * surface_area;
+                        }
+                    }
+                }
+            }
+        }
+
+        let max_score = scores.iter().copied().fold(0.0, f32::max);
+        if max_score > 0.0 {
+            for (priority, score) in priorities.iter_mut().zip(scores) {
+                priority.priority = score / max_score;
+            }
+        }
+        priorities
+    }
+
+    /// Returns the texture streaming priorities of the document as a JSON
+    /// sidecar manifest of the form:
+    ///
+    /// ```json
+    /// {
+    ///   "textures": [
+    ///     {
+    ///       "index": 0,
+    ///       "image": 0,
+    ///       "priority": 1.0,
+    ///       "usages": ["baseColorTexture"],
+    ///       "materials": 1,
+    ///       "surfaceArea": 24.0,
+    ///       "uvArea": 6.0
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// See [`texture_streaming_priorities`](#method.texture_streaming_priorities).
+    pub fn texture_streaming_manifest(&self, priorities: &[TexturePriority]) -> Value {
+        let textures: Vec<Value> = priorities
+            .iter()
+            .map(|priority| {
+                let mut entry = priority.to_value();
+                if let Value::Object(ref mut entry) = entry {
+                    entry.insert("index".to_owned(), priority.texture.into());
+                    if let Some(texture) = self.textures().nth(priority.texture) {
+                        entry.insert("image".to_owned(), texture.source().index().into());
+                    }
+                }
+                entry
+            })
+            .collect();
+        json!({ "textures": textures })
+    }
+}
+
+/// Stores texture streaming priorities in the `extras` of the textures of
+/// `root` under the key `"streaming"`.
+///
+/// Existing `extras` objects are extended; textures whose `extras` are not
+/// an object are left unchanged.
+#[cfg(feature = "extras")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+pub fn write_extras(root: &mut json::Root, priorities: &[TexturePriority]) {
+    for priority in priorities {
+        let texture = match root.textures.get_mut(priority.texture) {
+            Some(texture) => texture,
+            None => continue,
+        };
+        let mut extras = match texture.extras {
+            Some(ref extras) => match serde_json::from_str(extras.get()) {
+                Ok(Value::Object(extras)) => extras,
+                _ => continue,
+            },
+            None => serde_json::Map::new(),
+        };
+        extras.insert("streaming".to_owned(), priority.to_value());
+        // Serializing a JSON value cannot fail.
+        texture.extras = Some(serde_json::value::to_raw_value(&extras).unwrap());
+    }
+}
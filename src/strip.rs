@@ -0,0 +1,202 @@
+use crate::prelude::*;
+use crate::Document;
+use alloc::collections::BTreeSet;
+
+use json::Value;
+use serde_json::Map;
+
+/// Options for [`Document::strip_metadata`].
+///
+/// [`Document::strip_metadata`]: ../struct.Document.html#method.strip_metadata
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Whether names are removed.
+    names: bool,
+
+    /// Whether extras are removed.
+    extras: bool,
+
+    /// Whether unused extension declarations are removed.
+    unused_extensions: bool,
+
+    /// The top-level properties whose objects keep their names.
+    kept_names: Vec<String>,
+
+    /// The extras keys which are kept.
+    kept_extras: Vec<String>,
+
+    /// The extensions which stay declared.
+    kept_extensions: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            names: true,
+            extras: true,
+            unused_extensions: true,
+            kept_names: Vec::new(),
+            kept_extras: Vec::new(),
+            kept_extensions: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// Constructs the default `Options`, which strip all names, extras and
+    /// unused extension declarations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether names are removed. Defaults to `true`.
+    pub fn with_names(mut self, names: bool) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Sets whether extras are removed. Defaults to `true`.
+    pub fn with_extras(mut self, extras: bool) -> Self {
+        self.extras = extras;
+        self
+    }
+
+    /// Sets whether extensions which are declared in `extensionsUsed` but
+    /// not used by any object are removed from the declaration. Defaults to
+    /// `true`.
+    pub fn with_unused_extensions(mut self, unused_extensions: bool) -> Self {
+        self.unused_extensions = unused_extensions;
+        self
+    }
+
+    /// Keeps the names of the objects of the top-level property `property`,
+    /// for example `"nodes"` for node names needed to retarget animations.
+    pub fn keep_names(mut self, property: &str) -> Self {
+        self.kept_names.push(property.to_owned());
+        self
+    }
+
+    /// Keeps the member `key` of extras objects, for example extras read by
+    /// the runtime.
+    pub fn keep_extras(mut self, key: &str) -> Self {
+        self.kept_extras.push(key.to_owned());
+        self
+    }
+
+    /// Keeps the declaration of the extension `extension`, even if it is
+    /// unused.
+    pub fn keep_extension(mut self, extension: &str) -> Self {
+        self.kept_extensions.push(extension.to_owned());
+        self
+    }
+}
+
+/// Strips the names and extras of `value` and the objects within it.
+///
+/// `names` is whether names are removed at this level. The names of the
+/// extensions referenced are collected into `extensions`.
+fn strip(value: &mut Value, names: bool, options: &Options, extensions: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(object) => {
+            if names {
+                object.remove("name");
+            }
+            if options.extras {
+                strip_extras(object, &options.kept_extras);
+            }
+            if let Some(Value::Object(used)) = object.get("extensions") {
+                extensions.extend(used.keys().cloned());
+            }
+            for (key, value) in object.iter_mut() {
+                if key != "extras" {
+                    strip(value, names, options, extensions);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                strip(value, names, options, extensions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes the extras of `object` except for the members `kept`.
+fn strip_extras(object: &mut Map<String, Value>, kept: &[String]) {
+    if let Some(Value::Object(extras)) = object.get_mut("extras") {
+        extras.retain(|key, _| kept.contains(key));
+        if !extras.is_empty() {
+            return;
+        }
+    }
+    object.remove("extras");
+}
+
+impl Document {
+    /// Removes metadata which is not needed at runtime, as a final step
+    /// before shipping assets.
+    ///
+    /// By default, every name, every extras member and every unused
+    /// extension declaration is removed. Extensions listed in
+    /// `extensionsRequired` are never removed, since some, such as
+    /// `KHR_mesh_quantization`, are used without being referenced by any
+    /// object. Extension data discarded on parsing because the corresponding
+    /// crate feature is disabled counts as unused. Documents which contain
+    /// invalid values, and thus cannot be serialized, are left unchanged.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// use gltf::strip::Options;
+    ///
+    /// let mut gltf = gltf::Gltf::from_slice(br#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "nodes": [{ "name": "Hip" }],
+    ///     "materials": [{ "name": "Skin" }],
+    ///     "extensionsUsed": ["KHR_materials_unlit"]
+    /// }"#)?;
+    /// gltf.strip_metadata(&Options::new().keep_names("nodes"));
+    /// assert_eq!(gltf.nodes().next().unwrap().name(), Some("Hip"));
+    /// assert_eq!(gltf.materials().next().unwrap().name(), None);
+    /// assert_eq!(gltf.extensions_used().count(), 0);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("runtime error");
+    /// # }
+    /// ```
+    pub fn strip_metadata(&mut self, options: &Options) {
+        let mut root = match json::serialize::to_value(&self.0) {
+            Ok(root) => root,
+            Err(_) => return,
+        };
+        let mut extensions = BTreeSet::new();
+        if let Value::Object(object) = &mut root {
+            if options.extras {
+                strip_extras(object, &options.kept_extras);
+            }
+            if let Some(Value::Object(used)) = object.get("extensions") {
+                extensions.extend(used.keys().cloned());
+            }
+            for (key, value) in object.iter_mut() {
+                if key != "extras" {
+                    let names = options.names && !options.kept_names.contains(key);
+                    strip(value, names, options, &mut extensions);
+                }
+            }
+        }
+        let mut root: json::Root = match json::deserialize::from_value(root) {
+            Ok(root) => root,
+            Err(_) => return,
+        };
+        if options.unused_extensions {
+            let required = root.extensions_required.clone();
+            root.extensions_used.retain(|extension| {
+                extensions.contains(extension)
+                    || required.contains(extension)
+                    || options.kept_extensions.contains(extension)
+            });
+        }
+        self.0 = root;
+    }
+}
@@ -1,12 +1,15 @@
+#[cfg(all(not(feature = "std"), feature = "KHR_texture_transform"))]
+use crate::float::Float;
+use crate::lazy::Lazy;
 use crate::{image, Document};
 
+#[cfg(feature = "extensions")]
+use alloc::string::String;
 pub use json::texture::{MagFilter, MinFilter, WrappingMode};
 #[cfg(feature = "extensions")]
 use serde_json::{Map, Value};
 
-lazy_static! {
-    static ref DEFAULT_SAMPLER: json::texture::Sampler = Default::default();
-}
+static DEFAULT_SAMPLER: Lazy<json::texture::Sampler> = Lazy::new(Default::default);
 
 /// A reference to a `Texture`.
 #[derive(Clone, Debug)]
@@ -45,6 +48,122 @@ pub struct Texture<'a> {
     json: &'a json::texture::Texture,
 }
 
+/// A texture filter, as used by graphics APIs which select the mipmap
+/// filter separately.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Filter {
+    /// Nearest neighbour filtering.
+    Nearest,
+
+    /// Linear filtering.
+    Linear,
+}
+
+/// Per-texture mipmap bias and level of detail clamps, stored in the
+/// `extras` of a texture under the key `"mipSettings"`:
+///
+/// ```json
+/// "extras": { "mipSettings": { "bias": -0.5, "minLod": 0, "maxLod": 4 } }
+/// ```
+///
+/// All members are optional. This is a convention of this crate rather
+/// than part of glTF, so that art-directed sharpness settings survive asset
+/// pipelines using it. See [`Texture::mip_settings`] and
+/// [`write_mip_settings`].
+///
+/// [`Texture::mip_settings`]: struct.Texture.html#method.mip_settings
+/// [`write_mip_settings`]: fn.write_mip_settings.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MipSettings {
+    /// The bias added to the computed level of detail, where negative values
+    /// sharpen. Defaults to `0`.
+    pub bias: f32,
+
+    /// The lowest, i.e. most detailed, level of detail used. Defaults to `0`.
+    pub min_lod: f32,
+
+    /// The highest level of detail used, or `None` for no limit.
+    pub max_lod: Option<f32>,
+}
+
+impl Default for MipSettings {
+    fn default() -> Self {
+        Self {
+            bias: 0.0,
+            min_lod: 0.0,
+            max_lod: None,
+        }
+    }
+}
+
+impl MipSettings {
+    /// Returns the settings as a JSON object of the form
+    /// `{ "bias": -0.5, "minLod": 0, "maxLod": 4 }`, omitting `maxLod` if
+    /// there is no limit.
+    pub fn to_value(&self) -> json::Value {
+        let mut value = json::Value::Object(Default::default());
+        value["bias"] = self.bias.into();
+        value["minLod"] = self.min_lod.into();
+        if let Some(max_lod) = self.max_lod {
+            value["maxLod"] = max_lod.into();
+        }
+        value
+    }
+
+    /// Parses settings from a JSON object as returned by
+    /// [`to_value`](#method.to_value), with missing members defaulted.
+    ///
+    /// Returns `None` if `value` is not an object or a member is not a
+    /// number.
+    pub fn from_value(value: &json::Value) -> Option<Self> {
+        let object = value.as_object()?;
+        let member = |key: &str| match object.get(key) {
+            Some(value) => value.as_f64().map(|x| Some(x as f32)),
+            None => Some(None),
+        };
+        Some(Self {
+            bias: member("bias")?.unwrap_or(0.0),
+            min_lod: member("minLod")?.unwrap_or(0.0),
+            max_lod: member("maxLod")?,
+        })
+    }
+}
+
+/// The sampling state of a texture in the form graphics APIs such as
+/// Vulkan, Metal and WebGPU expect.
+///
+/// See [`Sampler::descriptor`] and [`Texture::sampler_descriptor`].
+///
+/// [`Sampler::descriptor`]: struct.Sampler.html#method.descriptor
+/// [`Texture::sampler_descriptor`]: struct.Texture.html#method.sampler_descriptor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SamplerDescriptor {
+    /// The magnification filter.
+    pub mag_filter: Filter,
+
+    /// The minification filter.
+    pub min_filter: Filter,
+
+    /// The filter between mipmap levels, or `None` if only the base level is
+    /// sampled.
+    pub mipmap_filter: Option<Filter>,
+
+    /// The wrapping mode of the `s` co-ordinate.
+    pub wrap_s: WrappingMode,
+
+    /// The wrapping mode of the `t` co-ordinate.
+    pub wrap_t: WrappingMode,
+
+    /// The bias added to the computed level of detail.
+    pub mip_bias: f32,
+
+    /// The lowest level of detail used.
+    pub lod_min_clamp: f32,
+
+    /// The highest level of detail used, or `None` for no limit.
+    pub lod_max_clamp: Option<f32>,
+}
+
 impl<'a> Sampler<'a> {
     /// Constructs a `Sampler`.
     pub(crate) fn new(
@@ -64,7 +183,7 @@ impl<'a> Sampler<'a> {
         Self {
             document,
             index: None,
-            json: &DEFAULT_SAMPLER,
+            json: &*DEFAULT_SAMPLER,
         }
     }
 
@@ -101,6 +220,35 @@ impl<'a> Sampler<'a> {
         self.json.wrap_t.unwrap()
     }
 
+    /// Returns the sampling state in the form graphics APIs expect.
+    ///
+    /// Unspecified filters default to linear filtering, including between
+    /// mipmap levels, which is the common choice of renderers.
+    pub fn descriptor(&self) -> SamplerDescriptor {
+        let (min_filter, mipmap_filter) = match self.min_filter() {
+            Some(MinFilter::Nearest) => (Filter::Nearest, None),
+            Some(MinFilter::Linear) => (Filter::Linear, None),
+            Some(MinFilter::NearestMipmapNearest) => (Filter::Nearest, Some(Filter::Nearest)),
+            Some(MinFilter::LinearMipmapNearest) => (Filter::Linear, Some(Filter::Nearest)),
+            Some(MinFilter::NearestMipmapLinear) => (Filter::Nearest, Some(Filter::Linear)),
+            Some(MinFilter::LinearMipmapLinear) | None => (Filter::Linear, Some(Filter::Linear)),
+        };
+        let settings = MipSettings::default();
+        SamplerDescriptor {
+            mag_filter: match self.mag_filter() {
+                Some(MagFilter::Nearest) => Filter::Nearest,
+                Some(MagFilter::Linear) | None => Filter::Linear,
+            },
+            min_filter,
+            mipmap_filter,
+            wrap_s: self.wrap_s(),
+            wrap_t: self.wrap_t(),
+            mip_bias: settings.bias,
+            lod_min_clamp: settings.min_lod,
+            lod_max_clamp: settings.max_lod,
+        }
+    }
+
     /// Returns the extension values map
     #[cfg(feature = "extensions")]
     #[cfg_attr(docsrs, doc(cfg(feature = "extensions")))]
@@ -165,6 +313,38 @@ impl<'a> Texture<'a> {
             .unwrap()
     }
 
+    /// Returns the mipmap settings stored in the `extras` of this texture,
+    /// see [`MipSettings`].
+    ///
+    /// [`MipSettings`]: struct.MipSettings.html
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn mip_settings(&self) -> Option<MipSettings> {
+        let extras = self.json.extras.as_ref()?;
+        let extras: json::Value = json::deserialize::from_str(extras.get()).ok()?;
+        MipSettings::from_value(extras.get("mipSettings")?)
+    }
+
+    /// Returns the sampling state of this texture in the form graphics APIs
+    /// expect, see [`Sampler::descriptor`].
+    ///
+    /// With the `extras` feature, the [`MipSettings`] of this texture are
+    /// incorporated.
+    ///
+    /// [`Sampler::descriptor`]: struct.Sampler.html#method.descriptor
+    /// [`MipSettings`]: struct.MipSettings.html
+    pub fn sampler_descriptor(&self) -> SamplerDescriptor {
+        #[allow(unused_mut)]
+        let mut descriptor = self.sampler().descriptor();
+        #[cfg(feature = "extras")]
+        if let Some(settings) = self.mip_settings() {
+            descriptor.mip_bias = settings.bias;
+            descriptor.lod_min_clamp = settings.min_lod;
+            descriptor.lod_max_clamp = settings.max_lod;
+        }
+        descriptor
+    }
+
     /// Returns the extension values map
     #[cfg(feature = "extensions")]
     #[cfg_attr(docsrs, doc(cfg(feature = "extensions")))]
@@ -252,6 +432,7 @@ impl<'a> AsRef<Texture<'a>> for Info<'a> {
 /// To support this use case, this extension adds `offset`, `rotation`, and `scale` properties to textureInfo structures.
 /// These properties would typically be implemented as an affine transform on the UV coordinates.
 #[cfg(feature = "KHR_texture_transform")]
+#[derive(Clone, Debug)]
 pub struct TextureTransform<'a> {
     /// The corresponding JSON struct.
     json: &'a json::extensions::texture::TextureTransform,
@@ -285,8 +466,64 @@ impl<'a> TextureTransform<'a> {
         self.json.tex_coord
     }
 
+    /// Returns the transform as a 3x3 column-major matrix.
+    ///
+    /// The matrix applies the scale first, then the rotation, and finally the
+    /// offset, as defined by the extension.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let [offset_x, offset_y] = self.offset();
+        let [scale_x, scale_y] = self.scale();
+        let (sin, cos) = self.rotation().sin_cos();
+        [
+            [cos * scale_x, -sin * scale_x, 0.0],
+            [sin * scale_y, cos * scale_y, 0.0],
+            [offset_x, offset_y, 1.0],
+        ]
+    }
+
+    /// Applies the transform to a texture co-ordinate.
+    pub fn apply(&self, uv: [f32; 2]) -> [f32; 2] {
+        apply_matrix(&self.matrix(), uv)
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
 }
+
+/// Applies a 3x3 column-major affine transform to a texture co-ordinate.
+#[cfg(feature = "KHR_texture_transform")]
+pub(crate) fn apply_matrix(m: &[[f32; 3]; 3], [u, v]: [f32; 2]) -> [f32; 2] {
+    [
+        m[0][0] * u + m[1][0] * v + m[2][0],
+        m[0][1] * u + m[1][1] * v + m[2][1],
+    ]
+}
+
+/// Stores `settings` in the `extras` of the texture at index `texture` of
+/// `root` under the key `"mipSettings"`, see [`MipSettings`].
+///
+/// Existing `extras` objects are extended. Returns `false` if the texture
+/// does not exist or its `extras` are not an object, leaving it unchanged.
+///
+/// [`MipSettings`]: struct.MipSettings.html
+#[cfg(feature = "extras")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+pub fn write_mip_settings(root: &mut json::Root, texture: usize, settings: &MipSettings) -> bool {
+    let texture = match root.textures.get_mut(texture) {
+        Some(texture) => texture,
+        None => return false,
+    };
+    let mut extras = match texture.extras {
+        Some(ref extras) => match json::deserialize::from_str(extras.get()) {
+            Ok(json::Value::Object(extras)) => extras,
+            _ => return false,
+        },
+        None => serde_json::Map::new(),
+    };
+    extras.insert("mipSettings".into(), settings.to_value());
+    // Serializing a JSON value cannot fail.
+    texture.extras = Some(serde_json::value::to_raw_value(&extras).unwrap());
+    true
+}
@@ -0,0 +1,460 @@
+use std::io::Cursor;
+
+use crate::export::{BufferLayout, ExportHook};
+use crate::image::{Data, Encoding};
+use crate::streaming::{self, TextureUsage};
+use crate::{Document, Error, Result};
+use image_crate::{imageops::FilterType, GenericImageView, ImageOutputFormat};
+use json::validation::Error::Invalid;
+
+use json::Value;
+use serde_json::json;
+
+/// The JPEG quality used when re-encoding JPEG images.
+const JPEG_QUALITY: u8 = 90;
+
+/// The maximum image resolution of every material texture slot.
+///
+/// Every slot is unlimited by default.
+///
+/// ```
+/// use gltf::streaming::TextureUsage;
+/// use gltf::texture_budget::Budget;
+///
+/// let budget = Budget::new()
+///     .with_max_dimension(TextureUsage::Normal, 2048)
+///     .with_max_dimension(TextureUsage::MetallicRoughness, 1024)
+///     .with_max_dimension(TextureUsage::Occlusion, 1024);
+/// assert_eq!(budget.max_dimension(TextureUsage::Normal), Some(2048));
+/// assert_eq!(budget.max_dimension(TextureUsage::BaseColor), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Budget {
+    /// The maximum width and height of the images of each slot, indexed by
+    /// `slot`.
+    max_dimensions: [Option<u32>; 5],
+}
+
+/// Returns the index of the budget of `usage`.
+fn slot(usage: TextureUsage) -> usize {
+    match usage {
+        TextureUsage::BaseColor => 0,
+        TextureUsage::MetallicRoughness => 1,
+        TextureUsage::Normal => 2,
+        TextureUsage::Occlusion => 3,
+        TextureUsage::Emissive => 4,
+    }
+}
+
+impl Budget {
+    /// Constructs a `Budget` without any limit set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the width and height of the images used in `usage` to
+    /// `max_dimension` pixels.
+    pub fn with_max_dimension(mut self, usage: TextureUsage, max_dimension: u32) -> Self {
+        self.max_dimensions[slot(usage)] = Some(max_dimension.max(1));
+        self
+    }
+
+    /// Returns the maximum width and height of the images used in `usage`,
+    /// if limited.
+    pub fn max_dimension(&self, usage: TextureUsage) -> Option<u32> {
+        self.max_dimensions[slot(usage)]
+    }
+
+    /// Returns the smallest limit of `usages`, if any is limited.
+    fn limit(&self, usages: &[TextureUsage]) -> Option<u32> {
+        usages
+            .iter()
+            .filter_map(|&usage| self.max_dimension(usage))
+            .min()
+    }
+}
+
+/// A texture slot of a material, as material index, usage, and image index.
+struct Slot {
+    material: usize,
+    usage: TextureUsage,
+    image: usize,
+}
+
+impl Slot {
+    /// Returns the JSON path of the texture info of the slot.
+    fn path(&self) -> json::Path {
+        let path = json::Path::new().field("materials").index(self.material);
+        match self.usage {
+            TextureUsage::BaseColor | TextureUsage::MetallicRoughness => path
+                .field("pbrMetallicRoughness")
+                .field(self.usage.as_str()),
+            usage => path.field(usage.as_str()),
+        }
+    }
+}
+
+/// Returns the texture slots of every material of `document` whose texture
+/// has a valid image.
+fn slots(document: &Document) -> Vec<Slot> {
+    let root = &document.0;
+    let mut slots = Vec::new();
+    for (index, material) in document.materials().enumerate() {
+        for (usage, texture, _) in streaming::slots(&material) {
+            let image = root
+                .textures
+                .get(texture)
+                .map(|texture| texture.source.value())
+                .filter(|&image| image < root.images.len());
+            if let Some(image) = image {
+                slots.push(Slot {
+                    material: index,
+                    usage,
+                    image,
+                });
+            }
+        }
+    }
+    slots
+}
+
+/// Returns the encoding the image is stored in, judging by its JSON.
+fn encoding(image: &json::Image) -> Option<Encoding> {
+    match (&image.mime_type, &image.uri) {
+        (Some(mime_type), _) => Encoding::from_mime_type(&mime_type.0),
+        (None, Some(uri)) if uri.starts_with("data:") => uri["data:".len()..]
+            .split(';')
+            .next()
+            .and_then(Encoding::from_mime_type),
+        (None, Some(uri)) => Encoding::from_extension(uri),
+        (None, None) => None,
+    }
+}
+
+/// An image exceeding its budget, to be downscaled by [`Downscale`].
+///
+/// [`Downscale`]: struct.Downscale.html
+struct Job {
+    image: usize,
+    usages: Vec<TextureUsage>,
+    max_dimension: u32,
+    jpeg: bool,
+}
+
+/// A re-encoded image, as data, encoding, and width and height in pixels.
+type Encoded = (Vec<u8>, Encoding, [u32; 2]);
+
+/// An image downscaled by [`Downscale`].
+///
+/// [`Downscale`]: struct.Downscale.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+    /// The index of the image.
+    pub image: usize,
+
+    /// The distinct material slots the image is used in.
+    pub usages: Vec<TextureUsage>,
+
+    /// The previous width and height in pixels.
+    pub from: [u32; 2],
+
+    /// The new width and height in pixels.
+    pub to: [u32; 2],
+
+    /// The encoding of the new image data.
+    pub encoding: Encoding,
+
+    /// The size of the new image data in bytes.
+    pub bytes: usize,
+}
+
+impl Change {
+    /// Returns the change as a JSON object.
+    fn to_value(&self) -> Value {
+        json!({
+            "image": self.image,
+            "usages": self.usages.iter().map(|usage| usage.as_str()).collect::<Vec<_>>(),
+            "from": self.from,
+            "to": self.to,
+            "mimeType": self.encoding.mime_type(),
+            "bytes": self.bytes,
+        })
+    }
+}
+
+/// What a [`Downscale`] changed during an export.
+///
+/// [`Downscale`]: struct.Downscale.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    /// The downscaled images, in order of their index.
+    pub changes: Vec<Change>,
+
+    /// The indices of images exceeding the budget which were left as they
+    /// are, because their pixel data was missing, in a floating point
+    /// format, or failed to encode.
+    pub skipped: Vec<usize>,
+}
+
+impl Report {
+    /// Returns the report as a JSON object, for example
+    /// `{ "changes": [{ "image": 0, "from": [4096, 4096], "to": [2048, 2048], ... }], "skipped": [] }`.
+    pub fn to_value(&self) -> Value {
+        json!({
+            "changes": self.changes.iter().map(Change::to_value).collect::<Vec<_>>(),
+            "skipped": self.skipped,
+        })
+    }
+}
+
+/// An [`ExportHook`] downscaling the images which exceed a [`Budget`].
+///
+/// Each image is limited by the smallest budget of the slots it is used in.
+/// Offending images are resampled to fit, preserving the aspect ratio, and
+/// re-encoded: JPEG images without alpha stay JPEG, all others become PNG.
+/// When exporting binary glTF the new data is appended to the `BIN` chunk,
+/// otherwise it is embedded as a `data:` URI. The previous image data is left
+/// unreferenced.
+///
+/// Register the hook by mutable reference to read the [`Report`] after the
+/// export. With the `parallel` feature the images are re-encoded in parallel,
+/// see [`with_threads`](#method.with_threads).
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::export::Exporter;
+/// use gltf::streaming::TextureUsage;
+/// use gltf::texture_budget::{Budget, Downscale};
+///
+/// let (document, buffers, images) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher)?;
+/// let budget = Budget::new().with_max_dimension(TextureUsage::Normal, 2048);
+/// document.check_texture_budget(&images, &budget)?;
+///
+/// let mut downscale = Downscale::new(&images, budget);
+/// let root = document.into_json();
+/// let glb = Exporter::new()
+///     .with_hook(&mut downscale)
+///     .to_glb(root, buffers[0].to_vec())?;
+/// assert!(downscale.report().changes.is_empty());
+/// # let _ = glb;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+///
+/// [`ExportHook`]: ../export/trait.ExportHook.html
+/// [`Budget`]: struct.Budget.html
+/// [`Report`]: struct.Report.html
+#[derive(Clone, Debug)]
+pub struct Downscale<'a> {
+    /// The decoded images of the exported document, in order.
+    images: &'a [Data],
+
+    /// The resolution budget.
+    budget: Budget,
+
+    /// The changes of the last export.
+    report: Report,
+
+    /// The number of threads encoding images, or `None` for the current
+    /// thread pool.
+    #[cfg(feature = "parallel")]
+    threads: Option<usize>,
+}
+
+impl<'a> Downscale<'a> {
+    /// Constructs a `Downscale` hook for a document whose decoded images are
+    /// `images`, as returned by `import`.
+    pub fn new(images: &'a [Data], budget: Budget) -> Self {
+        Self {
+            images,
+            budget,
+            report: Report::default(),
+            #[cfg(feature = "parallel")]
+            threads: None,
+        }
+    }
+
+    /// Returns the changes made by the last export.
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    /// Encodes the downscaled images on a dedicated rayon thread pool with
+    /// `threads` threads, instead of the current thread pool.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Resamples and re-encodes the images of `jobs`, in parallel with the
+    /// `parallel` feature.
+    #[cfg(feature = "parallel")]
+    fn encode_all(&self, jobs: &[Job]) -> Vec<Option<Encoded>> {
+        use rayon::prelude::*;
+
+        let images = self.images;
+        let encode = || {
+            jobs.par_iter()
+                .map(|job| Self::encode(&images[job.image], job.max_dimension, job.jpeg))
+                .collect()
+        };
+        let pool = self.threads.and_then(|threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .ok()
+        });
+        match pool {
+            Some(pool) => pool.install(encode),
+            None => encode(),
+        }
+    }
+
+    /// Resamples and re-encodes the images of `jobs`.
+    #[cfg(not(feature = "parallel"))]
+    fn encode_all(&self, jobs: &[Job]) -> Vec<Option<Encoded>> {
+        jobs.iter()
+            .map(|job| Self::encode(&self.images[job.image], job.max_dimension, job.jpeg))
+            .collect()
+    }
+
+    /// Returns the resampled and re-encoded image, or `None` if it cannot be
+    /// re-encoded.
+    fn encode(data: &Data, max_dimension: u32, jpeg: bool) -> Option<Encoded> {
+        let image =
+            data.to_dynamic_image()?
+                .resize(max_dimension, max_dimension, FilterType::Triangle);
+        let opaque = matches!(
+            image,
+            image_crate::DynamicImage::ImageLuma8(_) | image_crate::DynamicImage::ImageRgb8(_)
+        );
+        let (encoding, format) = if jpeg && opaque {
+            (Encoding::Jpeg, ImageOutputFormat::Jpeg(JPEG_QUALITY))
+        } else {
+            (Encoding::Png, ImageOutputFormat::Png)
+        };
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).ok()?;
+        let (width, height) = image.dimensions();
+        Some((bytes.into_inner(), encoding, [width, height]))
+    }
+}
+
+impl<'a> ExportHook for Downscale<'a> {
+    fn before_serialize(&mut self, root: &mut json::Root, layout: &mut BufferLayout) {
+        self.report = Report::default();
+        let slots = slots(&Document::from_json_without_validation(root.clone()));
+        let mut jobs = Vec::new();
+        for index in 0..root.images.len() {
+            let mut usages = Vec::new();
+            for slot in slots.iter().filter(|slot| slot.image == index) {
+                if !usages.contains(&slot.usage) {
+                    usages.push(slot.usage);
+                }
+            }
+            let max_dimension = match self.budget.limit(&usages) {
+                Some(max_dimension) => max_dimension,
+                None => continue,
+            };
+            match self.images.get(index) {
+                Some(data) if data.width.max(data.height) <= max_dimension => continue,
+                Some(_) => {}
+                None => {
+                    self.report.skipped.push(index);
+                    continue;
+                }
+            }
+            let jpeg = encoding(&root.images[index]) == Some(Encoding::Jpeg);
+            jobs.push(Job {
+                image: index,
+                usages,
+                max_dimension,
+                jpeg,
+            });
+        }
+
+        let encoded = self.encode_all(&jobs);
+        for (job, encoded) in jobs.into_iter().zip(encoded) {
+            let index = job.image;
+            let (bytes, encoding, to) = match encoded {
+                Some(encoded) => encoded,
+                None => {
+                    self.report.skipped.push(index);
+                    continue;
+                }
+            };
+            let view = layout.push_view(root, &bytes);
+            let image = &mut root.images[index];
+            match view {
+                Some(view) => {
+                    image.buffer_view = Some(view);
+                    image.mime_type = Some(json::image::MimeType(encoding.mime_type().into()));
+                    image.uri = None;
+                }
+                None => {
+                    let uri = format!(
+                        "data:{};base64,{}",
+                        encoding.mime_type(),
+                        base64::encode(&bytes)
+                    );
+                    image.buffer_view = None;
+                    image.mime_type = None;
+                    image.uri = Some(uri);
+                }
+            }
+            let data = &self.images[index];
+            self.report.changes.push(Change {
+                image: index,
+                usages: job.usages,
+                from: [data.width, data.height],
+                to,
+                encoding,
+                bytes: bytes.len(),
+            });
+        }
+        self.report.skipped.sort_unstable();
+    }
+}
+
+impl<'a, 'b> ExportHook for &'b mut Downscale<'a> {
+    fn before_serialize(&mut self, root: &mut json::Root, layout: &mut BufferLayout) {
+        (**self).before_serialize(root, layout)
+    }
+}
+
+impl Document {
+    /// Checks the resolution of the images used by the materials of the
+    /// document against `budget`, given the decoded `images` as returned by
+    /// `import`.
+    ///
+    /// Returns an `Error::Validation` with an `Invalid` error at the path of
+    /// every material texture slot whose image exceeds the budget of the
+    /// slot, for example `materials[2].normalTexture`. Use [`Downscale`] to
+    /// fix the offending images on export.
+    ///
+    /// [`Downscale`]: texture_budget/struct.Downscale.html
+    pub fn check_texture_budget(&self, images: &[Data], budget: &Budget) -> Result<()> {
+        let mut errors = Vec::new();
+        for slot in slots(self) {
+            let max_dimension = match budget.max_dimension(slot.usage) {
+                Some(max_dimension) => max_dimension,
+                None => continue,
+            };
+            if let Some(data) = images.get(slot.image) {
+                if data.width.max(data.height) > max_dimension {
+                    errors.push((slot.path(), Invalid));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::geometry::{cross, dot, position_key, sub, BufferData};
+use crate::prelude::*;
+use crate::{Buffer, Document, Primitive};
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+
+/// Options for [`Primitive::topology`] and [`Document::topology`].
+///
+/// [`Primitive::topology`]: ../mesh/struct.Primitive.html#method.topology
+/// [`Document::topology`]: ../struct.Document.html#method.topology
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// Whether self-intersections are detected.
+    self_intersections: bool,
+}
+
+impl Options {
+    /// Constructs the default `Options`, which analyze edges only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether pairs of intersecting triangles are detected. Defaults to
+    /// `false`.
+    ///
+    /// Detection compares every pair of triangles with overlapping bounds,
+    /// which is considerably slower than the edge analysis.
+    pub fn with_self_intersections(mut self, self_intersections: bool) -> Self {
+        self.self_intersections = self_intersections;
+        self
+    }
+}
+
+/// The topology of the triangles of a primitive.
+///
+/// Vertices are matched by position, since vertices are commonly split along
+/// seams, and reported by the index of the first vertex drawn at each
+/// position. Edges are reported as pairs of such indices in the winding order
+/// of one of their triangles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The index of the mesh.
+    pub mesh: usize,
+
+    /// The index of the primitive within the mesh.
+    pub primitive: usize,
+
+    /// The number of triangles.
+    pub triangles: usize,
+
+    /// The number of triangles with coincident vertices, which are excluded
+    /// from the analysis.
+    pub degenerate_triangles: usize,
+
+    /// The loops of edges which belong to a single triangle, i.e. the
+    /// outlines of holes in the surface.
+    ///
+    /// Each loop lists its vertices in winding order. Loops through vertices
+    /// with several boundary edges are split arbitrarily and may not close.
+    pub boundary_loops: Vec<Vec<u32>>,
+
+    /// The edges shared by more than two triangles.
+    pub non_manifold_edges: Vec<[u32; 2]>,
+
+    /// The edges shared by two triangles winding in the same direction,
+    /// i.e. with inconsistently oriented faces.
+    pub inconsistent_edges: Vec<[u32; 2]>,
+
+    /// The pairs of triangle indices which intersect, if requested.
+    ///
+    /// Triangles sharing a vertex are not tested, nor are coplanar
+    /// triangles.
+    pub self_intersections: Option<Vec<[usize; 2]>>,
+}
+
+impl Report {
+    /// Returns `true` if every edge is shared by at most two consistently
+    /// oriented triangles.
+    pub fn is_manifold(&self) -> bool {
+        self.non_manifold_edges.is_empty() && self.inconsistent_edges.is_empty()
+    }
+
+    /// Returns `true` if the surface is manifold and has no holes.
+    ///
+    /// Self-intersections are not taken into account.
+    pub fn is_watertight(&self) -> bool {
+        self.is_manifold() && self.boundary_loops.is_empty()
+    }
+}
+
+/// The triangles of an edge, counted by direction.
+#[derive(Clone, Copy, Default)]
+struct EdgeUse {
+    /// Triangles winding from the lower to the higher vertex.
+    forward: u32,
+
+    /// Triangles winding from the higher to the lower vertex.
+    backward: u32,
+}
+
+/// Returns `true` if the segment `p`, `q` crosses the triangle `a`, `b`, `c`,
+/// using the intersection test of Möller and Trumbore.
+fn segment_crosses_triangle(p: [f32; 3], q: [f32; 3], [a, b, c]: [[f32; 3]; 3]) -> bool {
+    let direction = sub(q, p);
+    let (e1, e2) = (sub(b, a), sub(c, a));
+    let h = cross(direction, e2);
+    let det = dot(e1, h);
+    if det == 0.0 {
+        return false;
+    }
+    let s = sub(p, a);
+    let u = dot(s, h) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return false;
+    }
+    let r = cross(s, e1);
+    let v = dot(direction, r) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+    (0.0..=1.0).contains(&(dot(e2, r) / det))
+}
+
+/// Returns `true` if the triangles `a` and `b` intersect in a segment.
+fn triangles_intersect(a: [[f32; 3]; 3], b: [[f32; 3]; 3]) -> bool {
+    let crosses = |from: [[f32; 3]; 3], to: [[f32; 3]; 3]| {
+        (0..3).any(|i| segment_crosses_triangle(from[i], from[(i + 1) % 3], to))
+    };
+    crosses(a, b) || crosses(b, a)
+}
+
+/// Finds the pairs of intersecting triangles among `triangles`, skipping
+/// pairs which share one of the `welded` vertices.
+fn self_intersections(triangles: &[[[f32; 3]; 3]], welded: &[[usize; 3]]) -> Vec<[usize; 2]> {
+    let bounds: Vec<([f32; 3], [f32; 3])> = triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let min = [0, 1, 2].map(|i| a[i].min(b[i]).min(c[i]));
+            let max = [0, 1, 2].map(|i| a[i].max(b[i]).max(c[i]));
+            (min, max)
+        })
+        .collect();
+
+    // Sweep along the x axis so only triangles overlapping in x are paired.
+    let mut order: Vec<usize> = (0..triangles.len()).collect();
+    order.sort_by(|&i, &j| {
+        bounds[i].0[0]
+            .partial_cmp(&bounds[j].0[0])
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut pairs = Vec::new();
+    for (position, &i) in order.iter().enumerate() {
+        for &j in &order[position + 1..] {
+            if bounds[j].0[0] > bounds[i].1[0] {
+                break;
+            }
+            let overlaps = (1..3).all(|k| bounds[j].0[k] <= bounds[i].1[k])
+                && (1..3).all(|k| bounds[i].0[k] <= bounds[j].1[k]);
+            let adjacent = welded[i].iter().any(|vertex| welded[j].contains(vertex));
+            if overlaps && !adjacent && triangles_intersect(triangles[i], triangles[j]) {
+                pairs.push([i.min(j), i.max(j)]);
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Joins directed boundary edges between welded vertices into loops, and
+/// returns each loop together with whether it closes.
+fn boundary_loops(edges: &[[usize; 2]]) -> Vec<(Vec<usize>, bool)> {
+    let mut successors: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for &[from, to] in edges.iter().rev() {
+        successors.entry(from).or_default().push(to);
+    }
+    let mut loops = Vec::new();
+    while let Some((&start, _)) = successors.iter().next() {
+        let mut vertices = vec![start];
+        let mut current = start;
+        let mut closed = false;
+        while let Some(next) = successors.get_mut(&current).and_then(Vec::pop) {
+            if successors[&current].is_empty() {
+                successors.remove(&current);
+            }
+            if next == start {
+                closed = true;
+                break;
+            }
+            vertices.push(next);
+            current = next;
+        }
+        loops.push((vertices, closed));
+    }
+    loops
+}
+
+/// Analyzes the topology of the triangles `indices` into `positions`, and
+/// returns the report together with whether each boundary loop closes.
+///
+/// The mesh and primitive indices of the report are left zero.
+pub(crate) fn analyze(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    options: Options,
+) -> (Report, Vec<bool>) {
+    let mut report = Report::default();
+
+    // Weld the drawn vertices by position.
+    let mut welds: BTreeMap<[u32; 3], usize> = BTreeMap::new();
+    let mut representatives: Vec<u32> = Vec::new();
+    let mut triangles = Vec::new();
+    let mut welded = Vec::new();
+    for triangle in indices.chunks_exact(3) {
+        let vertex = |i: usize| positions.get(triangle[i] as usize).copied();
+        let corners = match (vertex(0), vertex(1), vertex(2)) {
+            (Some(a), Some(b), Some(c)) => [a, b, c],
+            _ => continue,
+        };
+        report.triangles += 1;
+        let mut ids = [0; 3];
+        for (id, (&corner, &index)) in ids.iter_mut().zip(corners.iter().zip(triangle)) {
+            *id = *welds.entry(position_key(corner)).or_insert_with(|| {
+                representatives.push(index);
+                representatives.len() - 1
+            });
+        }
+        if ids[0] == ids[1] || ids[1] == ids[2] || ids[2] == ids[0] {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+        triangles.push(corners);
+        welded.push(ids);
+    }
+
+    let mut edges: BTreeMap<[usize; 2], EdgeUse> = BTreeMap::new();
+    for ids in &welded {
+        for i in 0..3 {
+            let (from, to) = (ids[i], ids[(i + 1) % 3]);
+            let edge = edges.entry([from.min(to), from.max(to)]).or_default();
+            if from < to {
+                edge.forward += 1;
+            } else {
+                edge.backward += 1;
+            }
+        }
+    }
+    let vertex = |id: usize| representatives[id];
+    let mut boundary = Vec::new();
+    for (&[low, high], edge) in &edges {
+        match (edge.forward, edge.backward) {
+            (1, 0) => boundary.push([low, high]),
+            (0, 1) => boundary.push([high, low]),
+            (1, 1) => {}
+            (2, 0) | (0, 2) => report.inconsistent_edges.push([vertex(low), vertex(high)]),
+            _ => report.non_manifold_edges.push([vertex(low), vertex(high)]),
+        }
+    }
+    let (loops, closed) = boundary_loops(&boundary)
+        .into_iter()
+        .map(|(ids, closed)| (ids.into_iter().map(vertex).collect(), closed))
+        .unzip();
+    report.boundary_loops = loops;
+
+    if options.self_intersections {
+        report.self_intersections = Some(self_intersections(&triangles, &welded));
+    }
+    (report, closed)
+}
+
+impl<'a> Primitive<'a> {
+    /// Analyzes the topology of the triangles of the primitive.
+    ///
+    /// Returns `None` if the primitive has no readable positions or does not
+    /// consist of triangles. Triangles with out of range indices are ignored.
+    pub fn topology<'s, F>(&'a self, get_buffer_data: F, options: Options) -> Option<Report>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let reader = self.reader(get_buffer_data);
+        let indices = reader.triangulate()?;
+        let positions: Vec<[f32; 3]> = reader.read_positions()?.collect();
+        let (report, _) = analyze(&positions, &indices, options);
+        Some(Report {
+            mesh: self.mesh.index(),
+            primitive: self.index(),
+            ..report
+        })
+    }
+}
+
+impl Document {
+    /// Analyzes the topology of every triangle primitive, see
+    /// [`Primitive::topology`].
+    ///
+    /// Returns the reports ordered by mesh and primitive. Primitives without
+    /// readable triangles are not reported.
+    ///
+    /// [`Primitive::topology`]: mesh/struct.Primitive.html#method.topology
+    pub fn topology<'a, 's, F>(&'a self, get_buffer_data: F, options: Options) -> Vec<Report>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let buffer_data = BufferData::new(self, get_buffer_data);
+        let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+        self.meshes()
+            .flat_map(|mesh| mesh.primitives())
+            .filter_map(|primitive| primitive.topology(get_buffer_data, options))
+            .collect()
+    }
+}
@@ -0,0 +1,516 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+use crate::{Document, Error, FileKind, Gltf, Result};
+use json::{Path, Value};
+use serde_json::Map;
+
+/// The glTF 1.0 dictionaries that are converted, in conversion order.
+const DICTIONARIES: &[&str] = &[
+    "buffers",
+    "bufferViews",
+    "accessors",
+    "images",
+    "samplers",
+    "textures",
+    "materials",
+    "meshes",
+    "cameras",
+    "nodes",
+    "scenes",
+];
+
+/// Loads glTF from a slice of bytes like [`Gltf::from_slice`], upgrading
+/// glTF 1.0 JSON documents to glTF 2.0 first.
+///
+/// Binary glTF 1.0 (`KHR_binary_glTF`) is not supported.
+///
+/// [`Gltf::from_slice`]: ../struct.Gltf.html#method.from_slice
+pub fn from_slice(slice: &[u8]) -> Result<Gltf> {
+    match Gltf::from_slice(slice) {
+        Err(Error::UnsupportedVersion { found }) if is_version_1(found.as_deref()) => {
+            let value = json::deserialize::from_slice(slice)?;
+            let document = Document::from_json(upgrade(&value)?)?;
+            Ok(Gltf {
+                document,
+                blob: None,
+                container: FileKind::GltfJson,
+            })
+        }
+        result => result,
+    }
+}
+
+fn is_version_1(found: Option<&str>) -> bool {
+    match found {
+        Some(version) => version.split('.').next() == Some("1"),
+        // Legacy documents without a version are treated as glTF 1.0.
+        None => true,
+    }
+}
+
+/// Converts the common subset of a glTF 1.0 document to glTF 2.0 JSON.
+///
+/// The following objects are converted:
+///
+/// * buffers, buffer views, and accessors, moving accessor byte strides to
+///   buffer views,
+/// * images, samplers, and textures,
+/// * meshes, where the `JOINT` and `WEIGHT` semantics become `JOINTS_0` and
+///   `WEIGHTS_0` and custom semantics are prefixed with an underscore,
+/// * cameras, nodes, and scenes, where nodes referencing several meshes
+///   reference a new mesh combining their primitives,
+/// * materials, from the values of `KHR_materials_common` or the material
+///   itself, approximated by the metallic-roughness model.
+///
+/// Techniques, programs, shaders, skins, and animations are dropped. Object
+/// identifiers are kept as names unless an object has a name of its own.
+///
+/// Returns `Error::Validation` if the document references an object which
+/// does not exist.
+pub fn upgrade(document: &Value) -> Result<json::Root> {
+    let mut upgrade = Upgrade {
+        document,
+        ids: BTreeMap::new(),
+        buffer_views: Vec::new(),
+        strided_views: BTreeMap::new(),
+        meshes: Vec::new(),
+    };
+    for dictionary in DICTIONARIES {
+        for (index, (id, _)) in entries(document, dictionary).enumerate() {
+            upgrade.ids.insert((dictionary, id.as_str()), index);
+        }
+    }
+
+    let mut root = Map::new();
+    root.insert("asset".into(), upgrade.asset());
+    let buffers = upgrade.buffers()?;
+    root.insert("buffers".into(), buffers);
+    let accessors = upgrade.accessors()?;
+    root.insert(
+        "bufferViews".into(),
+        Value::from(upgrade.buffer_views.clone()),
+    );
+    root.insert("accessors".into(), accessors);
+    let images = upgrade.images();
+    root.insert("images".into(), images);
+    let samplers = upgrade.samplers();
+    root.insert("samplers".into(), samplers);
+    let textures = upgrade.textures()?;
+    root.insert("textures".into(), textures);
+    let materials = upgrade.materials()?;
+    root.insert("materials".into(), materials);
+    upgrade.meshes()?;
+    let cameras = upgrade.cameras();
+    root.insert("cameras".into(), cameras);
+    let nodes = upgrade.nodes()?;
+    root.insert("meshes".into(), Value::from(upgrade.meshes.clone()));
+    root.insert("nodes".into(), nodes);
+    let scenes = upgrade.scenes()?;
+    root.insert("scenes".into(), scenes);
+    if let Some(scene) = document.get("scene") {
+        let index = upgrade.resolve("scenes", scene, || Path::new().field("scene"))?;
+        root.insert("scene".into(), index);
+    }
+    Ok(json::deserialize::from_value(Value::Object(root))?)
+}
+
+/// Intermediate state of an upgrade.
+struct Upgrade<'a> {
+    /// The glTF 1.0 document.
+    document: &'a Value,
+
+    /// Maps dictionary names and object identifiers to glTF 2.0 indices.
+    ids: BTreeMap<(&'static str, &'a str), usize>,
+
+    /// The glTF 2.0 buffer views, growing as strided views are required.
+    buffer_views: Vec<Value>,
+
+    /// Maps buffer view indices and byte strides to strided buffer views.
+    strided_views: BTreeMap<(usize, u64), usize>,
+
+    /// The glTF 2.0 meshes, growing as meshes are combined.
+    meshes: Vec<Value>,
+}
+
+/// Returns the entries of a glTF 1.0 dictionary.
+fn entries<'a>(
+    document: &'a Value,
+    dictionary: &str,
+) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    document
+        .get(dictionary)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|map| map.iter())
+}
+
+/// Copies the given properties of `from` that are present to `to`.
+fn copy(from: &Value, to: &mut Map<String, Value>, keys: &[&str]) {
+    for key in keys {
+        if let Some(value) = from.get(key) {
+            to.insert((*key).to_string(), value.clone());
+        }
+    }
+}
+
+/// Starts a glTF 2.0 object, keeping the name or the identifier as its name.
+fn object(id: &str, json: &Value) -> Map<String, Value> {
+    let mut object = Map::new();
+    let name = json.get("name").cloned().unwrap_or_else(|| Value::from(id));
+    object.insert("name".into(), name);
+    object
+}
+
+impl<'a> Upgrade<'a> {
+    /// Returns the glTF 2.0 index of the object identified by `id`.
+    fn resolve<P>(&self, dictionary: &'static str, id: &Value, path: P) -> Result<Value>
+    where
+        P: Fn() -> Path,
+    {
+        id.as_str()
+            .and_then(|id| self.ids.get(&(dictionary, id)))
+            .map(|&index| Value::from(index))
+            .ok_or_else(|| {
+                Error::Validation(vec![(path(), json::validation::Error::IndexOutOfBounds)])
+            })
+    }
+
+    fn asset(&self) -> Value {
+        let mut asset = Map::new();
+        if let Some(json) = self.document.get("asset") {
+            copy(json, &mut asset, &["copyright", "generator"]);
+        }
+        asset.insert("version".into(), Value::from("2.0"));
+        Value::Object(asset)
+    }
+
+    fn buffers(&self) -> Result<Value> {
+        Ok(entries(self.document, "buffers")
+            .map(|(id, json)| {
+                let mut buffer = object(id, json);
+                copy(json, &mut buffer, &["uri"]);
+                let length = json.get("byteLength").cloned().unwrap_or_else(|| 0.into());
+                buffer.insert("byteLength".into(), length);
+                Value::Object(buffer)
+            })
+            .collect())
+    }
+
+    fn accessors(&mut self) -> Result<Value> {
+        for (id, json) in entries(self.document, "bufferViews") {
+            let mut view = object(id, json);
+            copy(json, &mut view, &["byteOffset", "target"]);
+            let length = json.get("byteLength").cloned().unwrap_or_else(|| 0.into());
+            view.insert("byteLength".into(), length);
+            let buffer = json.get("buffer").unwrap_or(&Value::Null);
+            let buffer = self.resolve("buffers", buffer, || {
+                Path::new().field("bufferViews").key(id).field("buffer")
+            })?;
+            view.insert("buffer".into(), buffer);
+            self.buffer_views.push(Value::Object(view));
+        }
+
+        let mut accessors = Vec::new();
+        for (id, json) in entries(self.document, "accessors") {
+            let mut accessor = object(id, json);
+            copy(
+                json,
+                &mut accessor,
+                &["byteOffset", "componentType", "count", "type", "min", "max"],
+            );
+            if let Some(view) = json.get("bufferView") {
+                let path = || Path::new().field("accessors").key(id).field("bufferView");
+                let mut view = self.resolve("bufferViews", view, path)?;
+                match json.get("byteStride").and_then(Value::as_u64) {
+                    Some(stride) if stride > 0 => view = self.strided_view(&view, stride),
+                    _ => {}
+                }
+                accessor.insert("bufferView".into(), view);
+            }
+            accessors.push(Value::Object(accessor));
+        }
+        Ok(Value::from(accessors))
+    }
+
+    /// Returns a copy of a buffer view with the given byte stride.
+    fn strided_view(&mut self, view: &Value, stride: u64) -> Value {
+        let view = view.as_u64().unwrap() as usize;
+        let buffer_views = &mut self.buffer_views;
+        let index = *self.strided_views.entry((view, stride)).or_insert_with(|| {
+            let mut strided = buffer_views[view].clone();
+            strided["byteStride"] = Value::from(stride);
+            buffer_views.push(strided);
+            buffer_views.len() - 1
+        });
+        Value::from(index)
+    }
+
+    fn images(&self) -> Value {
+        entries(self.document, "images")
+            .map(|(id, json)| {
+                let mut image = object(id, json);
+                copy(json, &mut image, &["uri"]);
+                Value::Object(image)
+            })
+            .collect()
+    }
+
+    fn samplers(&self) -> Value {
+        entries(self.document, "samplers")
+            .map(|(id, json)| {
+                let mut sampler = object(id, json);
+                copy(
+                    json,
+                    &mut sampler,
+                    &["magFilter", "minFilter", "wrapS", "wrapT"],
+                );
+                Value::Object(sampler)
+            })
+            .collect()
+    }
+
+    fn textures(&self) -> Result<Value> {
+        let mut textures = Vec::new();
+        for (id, json) in entries(self.document, "textures") {
+            let mut texture = object(id, json);
+            for (key, dictionary) in [("sampler", "samplers"), ("source", "images")] {
+                if let Some(reference) = json.get(key) {
+                    let path = || Path::new().field("textures").key(id).field(key);
+                    texture.insert(key.into(), self.resolve(dictionary, reference, path)?);
+                }
+            }
+            textures.push(Value::Object(texture));
+        }
+        Ok(Value::from(textures))
+    }
+
+    fn materials(&self) -> Result<Value> {
+        let mut materials = Vec::new();
+        for (id, json) in entries(self.document, "materials") {
+            let common = json
+                .get("extensions")
+                .and_then(|extensions| extensions.get("KHR_materials_common"));
+            let values = common
+                .or(Some(json))
+                .and_then(|json| json.get("values"))
+                .unwrap_or(&Value::Null);
+
+            let mut material = object(id, json);
+            let mut pbr = Map::new();
+            pbr.insert("metallicFactor".into(), Value::from(0.0));
+            let mut alpha = 1.0;
+            if let Some(transparency) = values.get("transparency").and_then(Value::as_f64) {
+                alpha = transparency;
+            }
+            let path = |key: &str| {
+                Path::new()
+                    .field("materials")
+                    .key(id)
+                    .field("values")
+                    .field(key)
+            };
+            match values.get("diffuse") {
+                Some(Value::String(texture)) => {
+                    let index = self.resolve("textures", &Value::from(texture.as_str()), || {
+                        path("diffuse")
+                    })?;
+                    let mut info = Map::new();
+                    info.insert("index".into(), index);
+                    pbr.insert("baseColorTexture".into(), Value::Object(info));
+                    pbr.insert(
+                        "baseColorFactor".into(),
+                        Value::from(vec![1.0, 1.0, 1.0, alpha]),
+                    );
+                }
+                Some(Value::Array(color)) => {
+                    let mut color = color.iter().filter_map(Value::as_f64).collect::<Vec<_>>();
+                    color.resize(4, 1.0);
+                    color[3] *= alpha;
+                    pbr.insert("baseColorFactor".into(), Value::from(color));
+                }
+                _ => {}
+            }
+            if let Some(shininess) = values.get("shininess").and_then(Value::as_f64) {
+                // Inverse of the common Blinn-Phong specular power mapping.
+                let roughness = ((2.0 / (shininess.max(0.0) + 2.0)) as f32).sqrt();
+                pbr.insert("roughnessFactor".into(), Value::from(roughness));
+            }
+            material.insert("pbrMetallicRoughness".into(), Value::Object(pbr));
+            match values.get("emission") {
+                Some(Value::String(texture)) => {
+                    let index = self.resolve("textures", &Value::from(texture.as_str()), || {
+                        path("emission")
+                    })?;
+                    let mut info = Map::new();
+                    info.insert("index".into(), index);
+                    material.insert("emissiveTexture".into(), Value::Object(info));
+                    material.insert("emissiveFactor".into(), Value::from(vec![1.0; 3]));
+                }
+                Some(Value::Array(color)) => {
+                    let color = color.iter().take(3).cloned().collect::<Vec<_>>();
+                    material.insert("emissiveFactor".into(), Value::from(color));
+                }
+                _ => {}
+            }
+            let transparent = common
+                .and_then(|common| common.get("transparent"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if transparent || alpha < 1.0 {
+                material.insert("alphaMode".into(), Value::from("BLEND"));
+            }
+            if let Some(double_sided) = common.and_then(|common| common.get("doubleSided")) {
+                material.insert("doubleSided".into(), double_sided.clone());
+            }
+            materials.push(Value::Object(material));
+        }
+        Ok(Value::from(materials))
+    }
+
+    fn meshes(&mut self) -> Result<()> {
+        for (id, json) in entries(self.document, "meshes") {
+            let mut mesh = object(id, json);
+            let mut primitives = Vec::new();
+            let empty = Vec::new();
+            let json_primitives = json
+                .get("primitives")
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            for (index, json) in json_primitives.iter().enumerate() {
+                let path = |key: &str| {
+                    Path::new()
+                        .field("meshes")
+                        .key(id)
+                        .field("primitives")
+                        .index(index)
+                        .field(key)
+                };
+                let mut primitive = Map::new();
+                copy(json, &mut primitive, &["mode"]);
+                let mut attributes = Map::new();
+                if let Some(json) = json.get("attributes").and_then(Value::as_object) {
+                    for (semantic, accessor) in json {
+                        let accessor = self
+                            .resolve("accessors", accessor, || path("attributes").key(semantic))?;
+                        attributes.insert(upgrade_semantic(semantic), accessor);
+                    }
+                }
+                primitive.insert("attributes".into(), Value::Object(attributes));
+                if let Some(indices) = json.get("indices") {
+                    let indices = self.resolve("accessors", indices, || path("indices"))?;
+                    primitive.insert("indices".into(), indices);
+                }
+                if let Some(material) = json.get("material") {
+                    let material = self.resolve("materials", material, || path("material"))?;
+                    primitive.insert("material".into(), material);
+                }
+                primitives.push(Value::Object(primitive));
+            }
+            mesh.insert("primitives".into(), Value::from(primitives));
+            self.meshes.push(Value::Object(mesh));
+        }
+        Ok(())
+    }
+
+    fn cameras(&self) -> Value {
+        entries(self.document, "cameras")
+            .map(|(id, json)| {
+                let mut camera = object(id, json);
+                copy(json, &mut camera, &["type", "perspective", "orthographic"]);
+                Value::Object(camera)
+            })
+            .collect()
+    }
+
+    fn nodes(&mut self) -> Result<Value> {
+        let mut nodes = Vec::new();
+        for (id, json) in entries(self.document, "nodes") {
+            let path = |key: &str| Path::new().field("nodes").key(id).field(key);
+            let mut node = object(id, json);
+            copy(
+                json,
+                &mut node,
+                &["matrix", "translation", "rotation", "scale"],
+            );
+            if let Some(camera) = json.get("camera") {
+                node.insert(
+                    "camera".into(),
+                    self.resolve("cameras", camera, || path("camera"))?,
+                );
+            }
+            if let Some(children) = json.get("children").and_then(Value::as_array) {
+                let children = children
+                    .iter()
+                    .enumerate()
+                    .map(|(i, child)| self.resolve("nodes", child, || path("children").index(i)))
+                    .collect::<Result<Vec<_>>>()?;
+                node.insert("children".into(), Value::from(children));
+            }
+            let meshes = match json.get("meshes").and_then(Value::as_array) {
+                Some(meshes) => meshes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, mesh)| self.resolve("meshes", mesh, || path("meshes").index(i)))
+                    .collect::<Result<Vec<_>>>()?,
+                None => Vec::new(),
+            };
+            match meshes.len() {
+                0 => {}
+                1 => {
+                    node.insert("mesh".into(), meshes[0].clone());
+                }
+                _ => {
+                    // glTF 2.0 nodes reference a single mesh.
+                    let primitives = meshes
+                        .iter()
+                        .flat_map(|mesh| {
+                            let mesh = &self.meshes[mesh.as_u64().unwrap() as usize];
+                            mesh["primitives"].as_array().unwrap().clone()
+                        })
+                        .collect::<Vec<_>>();
+                    let mut mesh = Map::new();
+                    mesh.insert("primitives".into(), Value::from(primitives));
+                    self.meshes.push(Value::Object(mesh));
+                    node.insert("mesh".into(), Value::from(self.meshes.len() - 1));
+                }
+            }
+            nodes.push(Value::Object(node));
+        }
+        Ok(Value::from(nodes))
+    }
+
+    fn scenes(&self) -> Result<Value> {
+        let mut scenes = Vec::new();
+        for (id, json) in entries(self.document, "scenes") {
+            let mut scene = object(id, json);
+            let mut nodes = Vec::new();
+            if let Some(json) = json.get("nodes").and_then(Value::as_array) {
+                for (i, node) in json.iter().enumerate() {
+                    let path = || Path::new().field("scenes").key(id).field("nodes").index(i);
+                    nodes.push(self.resolve("nodes", node, path)?);
+                }
+            }
+            scene.insert("nodes".into(), Value::from(nodes));
+            scenes.push(Value::Object(scene));
+        }
+        Ok(Value::from(scenes))
+    }
+}
+
+/// Converts a glTF 1.0 attribute semantic to glTF 2.0.
+fn upgrade_semantic(semantic: &str) -> String {
+    let (name, set) = match semantic.split_once('_') {
+        Some((name, set)) if set.parse::<u32>().is_ok() => (name, set),
+        _ => (semantic, "0"),
+    };
+    match name {
+        "POSITION" | "NORMAL" | "TANGENT" if semantic == name => semantic.to_string(),
+        "TEXCOORD" | "COLOR" => format!("{}_{}", name, set),
+        "JOINT" => format!("JOINTS_{}", set),
+        "WEIGHT" => format!("WEIGHTS_{}", set),
+        _ => format!("_{}", semantic),
+    }
+}
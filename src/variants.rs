@@ -0,0 +1,319 @@
+use crate::export::{BufferLayout, Exporter};
+use crate::layout::ViewReport;
+use crate::prelude::*;
+use crate::quantize;
+use crate::streaming::{self, TextureUsage};
+use crate::{buffer, Buffer, Document, Result};
+use alloc::boxed::Box;
+use core::fmt;
+
+use json::Value;
+use serde_json::json;
+
+/// Facts about the source document which every variant can use, computed
+/// once per [`Pipeline::run`].
+///
+/// [`Pipeline::run`]: struct.Pipeline.html#method.run
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+    /// How every buffer view is used, see [`Document::view_layout`].
+    ///
+    /// [`Document::view_layout`]: ../struct.Document.html#method.view_layout
+    pub views: Vec<ViewReport>,
+
+    /// The distinct material slots each image is used in, for example to
+    /// keep normal maps out of lossy codecs. Indexed by image.
+    pub image_usages: Vec<Vec<TextureUsage>>,
+}
+
+impl Analysis {
+    /// Analyzes `document`.
+    pub fn new(document: &Document) -> Self {
+        let mut image_usages = vec![Vec::new(); document.images().len()];
+        let textures: Vec<_> = document.textures().collect();
+        for material in document.materials() {
+            for (usage, texture, _) in streaming::slots(&material) {
+                let image = match textures.get(texture) {
+                    Some(texture) => texture.source().index(),
+                    None => continue,
+                };
+                if let Some(usages) = image_usages.get_mut(image) {
+                    if !usages.contains(&usage) {
+                        usages.push(usage);
+                    }
+                }
+            }
+        }
+        Self {
+            views: document.view_layout(),
+            image_usages,
+        }
+    }
+}
+
+/// A step producing a variant from the source, such as re-encoding
+/// textures or compressing meshes for the target platform.
+///
+/// Hooks are implemented for closures taking the same arguments as
+/// [`apply`](#tymethod.apply).
+pub trait VariantHook {
+    /// Called with a copy of the source JSON and `BIN` chunk.
+    fn apply(&mut self, root: &mut json::Root, layout: &mut BufferLayout, analysis: &Analysis);
+}
+
+impl<F> VariantHook for F
+where
+    F: FnMut(&mut json::Root, &mut BufferLayout, &Analysis),
+{
+    fn apply(&mut self, root: &mut json::Root, layout: &mut BufferLayout, analysis: &Analysis) {
+        self(root, layout, analysis)
+    }
+}
+
+/// A hook quantizing vertex attributes with `KHR_mesh_quantization`, for
+/// example for mobile variants.
+///
+/// The formats recommended by [`Document::quantization`] for the attributes
+/// stored in the `BIN` chunk are applied with [`quantize::apply`], and the
+/// quantized data is appended to the `BIN` chunk.
+///
+/// [`Document::quantization`]: ../struct.Document.html#method.quantization
+/// [`quantize::apply`]: ../quantize/fn.apply.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Quantize {
+    /// The error tolerances.
+    options: quantize::Options,
+}
+
+impl Quantize {
+    /// Constructs a `Quantize` hook with the error tolerances of `options`.
+    pub fn new(options: quantize::Options) -> Self {
+        Self { options }
+    }
+}
+
+impl VariantHook for Quantize {
+    fn apply(&mut self, root: &mut json::Root, layout: &mut BufferLayout, _: &Analysis) {
+        let mut bin = match layout.bin.take() {
+            Some(bin) => bin,
+            None => return,
+        };
+        let document = Document::from_json_without_validation(root.clone());
+        let get_buffer_data = |buffer: Buffer| match buffer.source() {
+            buffer::Source::Bin if buffer.index() == 0 => Some(bin.as_slice()),
+            _ => None,
+        };
+        let reports = document.quantization(get_buffer_data, &self.options);
+        if reports.iter().all(|report| report.savings() == 0) {
+            layout.bin = Some(bin);
+            return;
+        }
+        let first_view = root.buffer_views.len();
+        let data = quantize::apply(&document, get_buffer_data, &reports, root);
+
+        // Rebase the views of the appended buffer onto the `BIN` chunk.
+        let buffer = json::Index::new(root.buffers.len() as u32 - 1);
+        root.buffers.pop();
+        while bin.len() % 4 != 0 {
+            bin.push(0);
+        }
+        let offset = bin.len() as u32;
+        for view in &mut root.buffer_views[first_view..] {
+            if view.buffer == buffer {
+                view.buffer = json::Index::new(0);
+                view.byte_offset = Some(offset + view.byte_offset.unwrap_or(0));
+            }
+        }
+        bin.extend_from_slice(&data);
+        root.buffers[0].byte_length = bin.len() as u32;
+        layout.bin = Some(bin);
+    }
+}
+
+/// A platform variant of an asset, for example `"web"` with WebP textures
+/// and meshopt compression.
+pub struct Variant<'a> {
+    /// The name of the variant.
+    name: String,
+
+    /// The hooks, in order of registration.
+    hooks: Vec<Box<dyn VariantHook + 'a>>,
+
+    /// The exporter writing the variant.
+    exporter: Exporter<'a>,
+}
+
+impl<'a> fmt::Debug for Variant<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Variant")
+            .field("name", &self.name)
+            .field("hooks", &self.hooks.len())
+            .field("exporter", &self.exporter)
+            .finish()
+    }
+}
+
+impl<'a> Variant<'a> {
+    /// Constructs a variant named `name` without hooks, which is written by
+    /// a default [`Exporter`].
+    ///
+    /// [`Exporter`]: ../export/struct.Exporter.html
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            hooks: Vec::new(),
+            exporter: Exporter::new(),
+        }
+    }
+
+    /// Adds a hook, which is run after all previously added hooks.
+    pub fn with_hook<H: VariantHook + 'a>(mut self, hook: H) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Sets the exporter writing the variant after the hooks have run, for
+    /// example to align buffer views or work around viewer quirks.
+    pub fn with_exporter(mut self, exporter: Exporter<'a>) -> Self {
+        self.exporter = exporter;
+        self
+    }
+
+    /// Returns the name of the variant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A variant written by a [`Pipeline`].
+///
+/// [`Pipeline`]: struct.Pipeline.html
+#[derive(Clone, Debug)]
+pub struct Output {
+    /// The name of the variant.
+    pub name: String,
+
+    /// The binary glTF of the variant.
+    pub glb: Vec<u8>,
+
+    /// The extensions the variant declares as used.
+    pub extensions_used: Vec<String>,
+
+    /// The extensions the variant declares as required.
+    pub extensions_required: Vec<String>,
+}
+
+/// Writes several platform variants of an asset in one pass.
+///
+/// The source is analyzed once, and every variant starts from a copy of the
+/// source JSON and `BIN` chunk, which its hooks transform before its
+/// exporter writes it as binary glTF.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::export::BufferLayout;
+/// use gltf::variants::{Analysis, Pipeline, Variant};
+///
+/// let mut pipeline = Pipeline::new()
+///     .with_variant(Variant::new("desktop"))
+///     .with_variant(Variant::new("web").with_hook(
+///         |root: &mut gltf::json::Root, _: &mut BufferLayout, _: &Analysis| {
+///             root.asset.generator = Some("web pipeline".to_owned());
+///         },
+///     ));
+/// let outputs = pipeline.run(&gltf::json::Root::default(), &[])?;
+/// assert_eq!(outputs.len(), 2);
+/// let manifest = gltf::variants::manifest(&outputs, |output| format!("asset.{}.glb", output.name));
+/// assert_eq!(manifest["variants"][1]["uri"], "asset.web.glb");
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("runtime error");
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Pipeline<'a> {
+    /// The variants, in order of registration.
+    variants: Vec<Variant<'a>>,
+}
+
+impl<'a> Pipeline<'a> {
+    /// Constructs a `Pipeline` without variants.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a variant, which is written after all previously added variants.
+    pub fn with_variant(mut self, variant: Variant<'a>) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    /// Writes every variant of the source `root` with `bin` as the data of
+    /// its first buffer, returning the variants in order of registration.
+    pub fn run(&mut self, root: &json::Root, bin: &[u8]) -> Result<Vec<Output>> {
+        let analysis = Analysis::new(&Document::from_json_without_validation(root.clone()));
+        let mut outputs = Vec::with_capacity(self.variants.len());
+        for variant in &mut self.variants {
+            let mut root = root.clone();
+            let mut layout = BufferLayout {
+                bin: Some(bin.to_vec()),
+            };
+            for hook in variant.hooks.iter_mut() {
+                hook.apply(&mut root, &mut layout, &analysis);
+            }
+            let extensions_used = root.extensions_used.clone();
+            let extensions_required = root.extensions_required.clone();
+            let glb = variant
+                .exporter
+                .to_glb(root, layout.bin.unwrap_or_default())?;
+            outputs.push(Output {
+                name: variant.name.clone(),
+                glb,
+                extensions_used,
+                extensions_required,
+            });
+        }
+        Ok(outputs)
+    }
+}
+
+/// Returns a manifest tying the variants `outputs` together, with `uri`
+/// naming the file of each variant.
+///
+/// The manifest is a JSON object of the form:
+///
+/// ```json
+/// {
+///   "variants": [
+///     {
+///       "name": "web",
+///       "uri": "asset.web.glb",
+///       "byteLength": 1234,
+///       "extensionsUsed": ["EXT_meshopt_compression", "EXT_texture_webp"],
+///       "extensionsRequired": ["EXT_meshopt_compression"]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Loaders can pick the first variant whose required extensions they
+/// support.
+pub fn manifest<F>(outputs: &[Output], uri: F) -> Value
+where
+    F: Fn(&Output) -> String,
+{
+    let variants: Vec<Value> = outputs
+        .iter()
+        .map(|output| {
+            json!({
+                "name": output.name,
+                "uri": uri(output),
+                "byteLength": output.glb.len(),
+                "extensionsUsed": output.extensions_used,
+                "extensionsRequired": output.extensions_required,
+            })
+        })
+        .collect();
+    json!({ "variants": variants })
+}
@@ -0,0 +1,354 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::geometry::{cross, dot, sub, BufferData};
+use crate::mesh::BoundingBox;
+use crate::prelude::*;
+use crate::{Buffer, Scene};
+
+/// Squared distance of voxels without a nearby occupied voxel.
+const FAR: f64 = 1.0e30;
+
+/// An axis-aligned grid of cubic voxels recording which voxels intersect
+/// geometry.
+///
+/// Voxels are addressed by `[x, y, z]` indices and stored with `x` varying
+/// fastest, which is also the order of [`signed_distance_field`].
+///
+/// [`signed_distance_field`]: #method.signed_distance_field
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelGrid {
+    /// The minimum corner of the grid.
+    origin: [f32; 3],
+
+    /// The edge length of a voxel.
+    voxel_size: f32,
+
+    /// The number of voxels along each axis.
+    dimensions: [usize; 3],
+
+    /// The occupancy of every voxel.
+    occupied: Vec<bool>,
+}
+
+/// Returns `true` if the triangle `a`, `b`, `c` overlaps the box with the
+/// given `center` and `half` extent, using the separating axis theorem.
+fn triangle_overlaps_box([a, b, c]: [[f32; 3]; 3], center: [f32; 3], half: f32) -> bool {
+    let v = [sub(a, center), sub(b, center), sub(c, center)];
+    let edges = [sub(v[1], v[0]), sub(v[2], v[1]), sub(v[0], v[2])];
+    let units = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let normal = cross(edges[0], edges[1]);
+    let edge_axes = units
+        .iter()
+        .flat_map(|&unit| edges.iter().map(move |&edge| cross(unit, edge)));
+    units
+        .iter()
+        .copied()
+        .chain(core::iter::once(normal))
+        .chain(edge_axes)
+        .all(|axis| {
+            let projections = [dot(v[0], axis), dot(v[1], axis), dot(v[2], axis)];
+            let min = projections[0].min(projections[1]).min(projections[2]);
+            let max = projections[0].max(projections[1]).max(projections[2]);
+            let radius = half * (axis[0].abs() + axis[1].abs() + axis[2].abs());
+            min <= radius && max >= -radius
+        })
+}
+
+/// Computes the squared distance transform of `f` along one line, see
+/// "Distance Transforms of Sampled Functions" by Felzenszwalb and
+/// Huttenlocher.
+fn distance_transform_1d(f: &[f64], d: &mut [f64]) {
+    if f.iter().all(|&value| value >= FAR) {
+        d.copy_from_slice(f);
+        return;
+    }
+    let n = f.len();
+    let mut v = vec![0usize; n];
+    let mut z = vec![0.0f64; n + 1];
+    let mut k = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+    for q in 1..n {
+        let parabola = |p: usize| f[p] + (p * p) as f64;
+        let intersection = |p: usize| (parabola(q) - parabola(p)) / (2 * (q - p)) as f64;
+        let mut s = intersection(v[k]);
+        // `z[0]` is negative infinity, so this stops at the first parabola.
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let offset = q as f64 - v[k] as f64;
+        *d = offset * offset + f[v[k]];
+    }
+}
+
+impl VoxelGrid {
+    /// Constructs an empty grid covering `bounds` with `resolution` voxels
+    /// along its longest axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is zero.
+    pub fn new(bounds: &BoundingBox, resolution: usize) -> Self {
+        assert!(resolution != 0, "resolution must be non-zero");
+        let extent = sub(bounds.max, bounds.min);
+        let longest = extent[0].max(extent[1]).max(extent[2]);
+        let voxel_size = if longest > 0.0 {
+            longest / resolution as f32
+        } else {
+            1.0
+        };
+        let mut dimensions = [1; 3];
+        for (dimension, extent) in dimensions.iter_mut().zip(extent) {
+            *dimension = ((extent / voxel_size).ceil() as usize).clamp(1, resolution);
+        }
+        Self {
+            origin: bounds.min,
+            voxel_size,
+            dimensions,
+            occupied: vec![false; dimensions[0] * dimensions[1] * dimensions[2]],
+        }
+    }
+
+    /// Returns the minimum corner of the grid.
+    pub fn origin(&self) -> [f32; 3] {
+        self.origin
+    }
+
+    /// Returns the edge length of a voxel.
+    pub fn voxel_size(&self) -> f32 {
+        self.voxel_size
+    }
+
+    /// Returns the number of voxels along each axis.
+    pub fn dimensions(&self) -> [usize; 3] {
+        self.dimensions
+    }
+
+    /// Returns the bounds of the voxel at `voxel`.
+    pub fn voxel_bounds(&self, voxel: [usize; 3]) -> BoundingBox {
+        let min = [0, 1, 2].map(|i| self.origin[i] + voxel[i] as f32 * self.voxel_size);
+        let max = min.map(|min| min + self.voxel_size);
+        BoundingBox { min, max }
+    }
+
+    /// Returns `true` if the voxel at `voxel` is occupied, and `false` for
+    /// voxels outside of the grid.
+    pub fn is_occupied(&self, voxel: [usize; 3]) -> bool {
+        self.linear(voxel)
+            .map_or(false, |index| self.occupied[index])
+    }
+
+    /// Returns the number of occupied voxels.
+    pub fn occupied_count(&self) -> usize {
+        self.occupied.iter().filter(|&&occupied| occupied).count()
+    }
+
+    /// Marks every voxel intersecting the triangle `a`, `b`, `c` as occupied.
+    ///
+    /// Geometry outside of the grid is ignored.
+    pub fn insert_triangle(&mut self, a: [f32; 3], b: [f32; 3], c: [f32; 3]) {
+        let mut range = [(0, 0); 3];
+        for (i, range) in range.iter_mut().enumerate() {
+            let min = a[i].min(b[i]).min(c[i]);
+            let max = a[i].max(b[i]).max(c[i]);
+            let last = self.dimensions[i] as f32 - 1.0;
+            let to_voxel = |value: f32| (value - self.origin[i]) / self.voxel_size;
+            if to_voxel(max) < 0.0 || to_voxel(min) > last + 1.0 {
+                return;
+            }
+            *range = (
+                to_voxel(min).floor().clamp(0.0, last) as usize,
+                to_voxel(max).floor().clamp(0.0, last) as usize,
+            );
+        }
+        let half = 0.5 * self.voxel_size;
+        for z in range[2].0..=range[2].1 {
+            for y in range[1].0..=range[1].1 {
+                for x in range[0].0..=range[0].1 {
+                    let bounds = self.voxel_bounds([x, y, z]);
+                    let center = [
+                        bounds.min[0] + half,
+                        bounds.min[1] + half,
+                        bounds.min[2] + half,
+                    ];
+                    if triangle_overlaps_box([a, b, c], center, half) {
+                        let index = self.linear([x, y, z]).unwrap();
+                        self.occupied[index] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Marks the voxels enclosed by occupied voxels as occupied, turning a
+    /// surface voxelization into a solid one.
+    ///
+    /// Voxels are enclosed if they cannot be reached from the boundary of
+    /// the grid through face-adjacent unoccupied voxels.
+    pub fn fill_interior(&mut self) {
+        let interior = self.interior();
+        for (occupied, interior) in self.occupied.iter_mut().zip(interior) {
+            *occupied |= interior;
+        }
+    }
+
+    /// Computes a signed distance field with one value per voxel.
+    ///
+    /// Every value is the distance from the voxel center to the center of
+    /// the nearest occupied voxel, in the units of the scene. Unoccupied
+    /// voxels enclosed by occupied voxels, as determined by
+    /// [`fill_interior`], have negative distances. Returns `f32::INFINITY`
+    /// for every voxel if no voxel is occupied.
+    ///
+    /// [`fill_interior`]: #method.fill_interior
+    pub fn signed_distance_field(&self) -> Vec<f32> {
+        let [nx, ny, nz] = self.dimensions;
+        let mut field: Vec<f64> = self
+            .occupied
+            .iter()
+            .map(|&occupied| if occupied { 0.0 } else { FAR })
+            .collect();
+        let mut line = Vec::new();
+        let mut out = Vec::new();
+        let axes = [(nx, 1), (ny, nx), (nz, nx * ny)];
+        for (axis, &(length, stride)) in axes.iter().enumerate() {
+            out.resize(length, 0.0);
+            for start in 0..field.len() {
+                // Visit every line along `axis` once, from its first voxel.
+                let coordinate = [start % nx, start / nx % ny, start / (nx * ny)][axis];
+                if coordinate != 0 {
+                    continue;
+                }
+                line.clear();
+                line.extend((0..length).map(|i| field[start + i * stride]));
+                distance_transform_1d(&line, &mut out);
+                for (i, &value) in out.iter().enumerate() {
+                    field[start + i * stride] = value;
+                }
+            }
+        }
+        let interior = self.interior();
+        field
+            .iter()
+            .zip(interior)
+            .map(|(&squared, interior)| {
+                let distance = if squared >= FAR {
+                    f32::INFINITY
+                } else {
+                    (squared as f32).sqrt() * self.voxel_size
+                };
+                if interior {
+                    -distance
+                } else {
+                    distance
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the index of `voxel` into the voxel storage.
+    fn linear(&self, [x, y, z]: [usize; 3]) -> Option<usize> {
+        let [nx, ny, nz] = self.dimensions;
+        if x < nx && y < ny && z < nz {
+            Some(x + nx * (y + ny * z))
+        } else {
+            None
+        }
+    }
+
+    /// Returns which voxels are unoccupied and enclosed by occupied voxels.
+    fn interior(&self) -> Vec<bool> {
+        let [nx, ny, nz] = self.dimensions;
+        let mut outside = vec![false; self.occupied.len()];
+        let mut stack = Vec::new();
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let boundary =
+                        x == 0 || y == 0 || z == 0 || x == nx - 1 || y == ny - 1 || z == nz - 1;
+                    if boundary {
+                        stack.push([x, y, z]);
+                    }
+                }
+            }
+        }
+        while let Some(voxel) = stack.pop() {
+            let index = self.linear(voxel).unwrap();
+            if outside[index] || self.occupied[index] {
+                continue;
+            }
+            outside[index] = true;
+            let [x, y, z] = voxel;
+            let neighbors = [
+                [x.wrapping_sub(1), y, z],
+                [x + 1, y, z],
+                [x, y.wrapping_sub(1), z],
+                [x, y + 1, z],
+                [x, y, z.wrapping_sub(1)],
+                [x, y, z + 1],
+            ];
+            stack.extend(
+                neighbors
+                    .iter()
+                    .filter(|&&neighbor| self.linear(neighbor).is_some()),
+            );
+        }
+        self.occupied
+            .iter()
+            .zip(outside)
+            .map(|(&occupied, outside)| !occupied && !outside)
+            .collect()
+    }
+}
+
+impl<'a> Scene<'a> {
+    /// Voxelizes the triangle geometry of the scene in world space, with
+    /// `resolution` voxels along the longest axis of the scene bounds.
+    ///
+    /// Only the surface is voxelized; use [`VoxelGrid::fill_interior`] for a
+    /// solid voxelization of closed meshes. Returns `None` if the scene
+    /// contains no readable geometry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is zero.
+    ///
+    /// [`VoxelGrid::fill_interior`]: voxel/struct.VoxelGrid.html#method.fill_interior
+    pub fn voxelize<'s, F>(&self, get_buffer_data: F, resolution: usize) -> Option<VoxelGrid>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let bounds = self.compute_bounding_box(get_buffer_data.clone())?;
+        let mut grid = VoxelGrid::new(&bounds, resolution);
+
+        let buffer_data = BufferData::new(self.document, get_buffer_data);
+        let get_buffer_data = |buffer: Buffer<'_>| buffer_data.get(buffer);
+
+        for (node, transform) in self.traverse() {
+            let mesh = match node.mesh() {
+                Some(mesh) => mesh,
+                None => continue,
+            };
+            for primitive in mesh.primitives() {
+                let triangles = primitive
+                    .transformed_triangles(get_buffer_data, &transform)
+                    .unwrap_or_default();
+                for [a, b, c] in triangles {
+                    grid.insert_triangle(a, b, c);
+                }
+            }
+        }
+        Some(grid)
+    }
+}
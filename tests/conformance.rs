@@ -0,0 +1,176 @@
+//! Conformance test.
+//!
+//! Imports every variant of every asset of a local checkout of the Khronos
+//! sample models and checks the outcome. The directory is read from the
+//! `GLTF_SAMPLE_MODELS` environment variable, which may point at either
+//! `glTF-Sample-Models/2.0` or `glTF-Sample-Assets/Models`, and defaults to
+//! the `glTF-Sample-Models/2.0` checkout used by the other tests. The test is
+//! skipped if the directory does not exist.
+//!
+//! Assets relying on extensions in `OPTIONAL_EXTENSIONS` are allowed to fail
+//! to import, since they are not supported by the importer, but they must
+//! never cause a panic.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::{env, fs, path};
+
+const SAMPLE_MODELS_DIRECTORY_PATH: &str = "glTF-Sample-Models/2.0";
+
+/// Extensions whose assets may legitimately fail to import.
+const OPTIONAL_EXTENSIONS: &[&str] = &[
+    "EXT_meshopt_compression",
+    "KHR_draco_mesh_compression",
+    "KHR_texture_basisu",
+];
+
+/// The result of importing a single file.
+enum Outcome {
+    /// The file was imported and passed all checks.
+    Ok,
+
+    /// The file failed to import, which is acceptable for this file.
+    Tolerated(String),
+
+    /// The file failed to import or failed a check.
+    Failed(String),
+}
+
+fn check(
+    document: &gltf::Document,
+    buffer_data: &[gltf::buffer::Data],
+    image_data: &[gltf::image::Data],
+) -> Result<(), String> {
+    if document.buffers().len() != buffer_data.len() {
+        return Err("buffer count mismatch".to_string());
+    }
+    for (buffer, data) in document.buffers().zip(buffer_data) {
+        if (buffer.length() + 3) & !3 != data.len() {
+            return Err(format!("buffer {} has the wrong length", buffer.index()));
+        }
+    }
+    if document.images().len() != image_data.len() {
+        return Err("image count mismatch".to_string());
+    }
+
+    for mesh in document.meshes() {
+        for primitive in mesh.primitives() {
+            let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            let positions = primitive.get(&gltf::Semantic::Positions);
+            let readable = positions.as_ref().map_or(false, |accessor| {
+                accessor.data_type() == gltf::accessor::DataType::F32
+                    && (accessor.view().is_some() || accessor.sparse().is_some())
+            });
+            if readable {
+                let count = reader.read_positions().map_or(0, |iter| iter.count());
+                if count != positions.unwrap().count() {
+                    return Err(format!("mesh {} has unreadable positions", mesh.index()));
+                }
+            }
+            let indexed = primitive
+                .indices()
+                .map_or(false, |accessor| accessor.view().is_some());
+            if indexed {
+                let count = reader
+                    .read_indices()
+                    .map_or(0, |iter| iter.into_u32().count());
+                if count != primitive.indices().unwrap().count() {
+                    return Err(format!("mesh {} has unreadable indices", mesh.index()));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the file requires or uses an optional extension.
+fn uses_optional_extension(path: &path::Path) -> bool {
+    let gltf = match gltf::Gltf::open(path) {
+        Ok(gltf) => gltf,
+        Err(_) => return false,
+    };
+    gltf.extensions_required()
+        .chain(gltf.extensions_used())
+        .any(|extension| OPTIONAL_EXTENSIONS.contains(&extension))
+}
+
+fn test(path: &path::Path) -> Outcome {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (document, buffers, images) =
+            gltf::import(path, gltf::filesystem_fetcher).map_err(|err| err.to_string())?;
+        check(&document, &buffers, &images)
+    }));
+    match result {
+        Ok(Ok(())) => Outcome::Ok,
+        Ok(Err(err)) if uses_optional_extension(path) => Outcome::Tolerated(err),
+        Ok(Err(err)) => Outcome::Failed(err),
+        Err(_) => Outcome::Failed("panicked".to_string()),
+    }
+}
+
+fn run(directory: &path::Path) {
+    let mut passed = 0;
+    let mut tolerated = 0;
+    let mut failures = Vec::new();
+    let mut models = fs::read_dir(directory)
+        .expect("failed to read sample models directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    models.sort();
+    for model in models {
+        // Every model has one directory per variant, for example `glTF`,
+        // `glTF-Binary`, `glTF-Embedded`, `glTF-Draco`, or `glTF-Quantized`.
+        let mut files = Vec::new();
+        for variant in fs::read_dir(&model).unwrap() {
+            let variant = variant.unwrap().path();
+            if !variant.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(&variant).unwrap() {
+                let file = file.unwrap().path();
+                match file.extension().and_then(|extension| extension.to_str()) {
+                    Some("gltf") | Some("glb") => files.push(file),
+                    _ => {}
+                }
+            }
+        }
+        files.sort();
+        for file in files {
+            match test(&file) {
+                Outcome::Ok => {
+                    println!("{:?}: ok", file);
+                    passed += 1;
+                }
+                Outcome::Tolerated(err) => {
+                    println!("{:?}: tolerated: {}", file, err);
+                    tolerated += 1;
+                }
+                Outcome::Failed(err) => {
+                    println!("{:?}: error: {}", file, err);
+                    failures.push(file);
+                }
+            }
+        }
+    }
+
+    println!(
+        "{} passed, {} tolerated, {} failed",
+        passed,
+        tolerated,
+        failures.len()
+    );
+    assert!(failures.is_empty(), "failed to import {:?}", failures);
+    assert!(passed > 0, "no sample models found in {:?}", directory);
+}
+
+#[test]
+fn conformance() {
+    let directory = env::var_os("GLTF_SAMPLE_MODELS")
+        .map(path::PathBuf::from)
+        .unwrap_or_else(|| path::PathBuf::from(SAMPLE_MODELS_DIRECTORY_PATH));
+    if !directory.is_dir() {
+        println!("{:?} does not exist, skipping conformance test", directory);
+        return;
+    }
+    run(&directory);
+}
@@ -22,3 +22,2071 @@ fn test_accessor_bounds() {
         }
     );
 }
+
+#[test]
+fn test_content_hash() {
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let mesh = document.meshes().next().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    let hash = primitive.content_hash(get_buffer_data);
+    assert_eq!(hash, primitive.content_hash(get_buffer_data));
+
+    let positions = primitive.get(&gltf::Semantic::Positions).unwrap();
+    let normals = primitive.get(&gltf::Semantic::Normals).unwrap();
+    assert_ne!(
+        positions.content_hash(get_buffer_data),
+        normals.content_hash(get_buffer_data)
+    );
+
+    // The hashes are persisted by caches, so they must not change between
+    // runs or versions.
+    assert_eq!(hash, 0x8e71_8829_c5cb_5624);
+    assert_eq!(
+        positions.content_hash(get_buffer_data),
+        0xb35b_cd08_8bfc_4be4
+    );
+    let material = document.materials().next().unwrap();
+    assert_eq!(material.content_hash(), 0x0f54_f192_65ed_f209);
+    let image = gltf::image::Data {
+        pixels: vec![255, 128, 0],
+        format: gltf::image::Format::R8G8B8,
+        width: 1,
+        height: 1,
+    };
+    // FNV-1a of the little endian format, width and height, then the pixels.
+    assert_eq!(image.content_hash(), 0xb137_b5d2_4621_e208);
+}
+
+#[test]
+fn test_sparse_accessor_without_view() {
+    let (document, buffers, _) =
+        gltf::import("tests/box_sparse.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let accessor = document.accessors().nth(2).unwrap();
+    assert!(accessor.view().is_none());
+
+    let iter = gltf::accessor::Iter::<f32>::new(accessor.clone(), get_buffer_data).unwrap();
+    assert_eq!(iter.len(), 2);
+    let values: Vec<f32> = iter.collect();
+    assert_eq!(values.len(), 2);
+
+    let dense = accessor.dense_data(get_buffer_data).unwrap();
+    let expected: Vec<u8> = values.iter().flat_map(|x| x.to_le_bytes()).collect();
+    assert_eq!(dense, expected);
+}
+
+#[test]
+fn test_accessor_iter_double_ended() {
+    let (document, buffers, _) =
+        gltf::import("tests/box_sparse.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    for accessor in document.accessors() {
+        if accessor.size() != std::mem::size_of::<f32>() {
+            continue;
+        }
+        let iter = gltf::accessor::Iter::<f32>::new(accessor, get_buffer_data).unwrap();
+        let forward: Vec<f32> = iter.clone().collect();
+        let mut backward: Vec<f32> = iter.clone().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(iter.len(), forward.len());
+        for n in 0..forward.len() + 1 {
+            assert_eq!(iter.clone().nth(n), forward.get(n).copied());
+            assert_eq!(iter.clone().skip(n).collect::<Vec<_>>(), forward[n..]);
+            assert_eq!(
+                iter.clone().nth_back(n),
+                forward.iter().rev().nth(n).copied()
+            );
+        }
+
+        // Alternate between both ends.
+        let mut iter = iter;
+        let mut alternating = Vec::new();
+        while let Some(front) = iter.next() {
+            alternating.push(front);
+            if let Some(back) = iter.next_back() {
+                alternating.push(back);
+            }
+        }
+        let mut expected = Vec::new();
+        let (mut i, mut j) = (0, forward.len());
+        while i < j {
+            expected.push(forward[i]);
+            i += 1;
+            if i < j {
+                j -= 1;
+                expected.push(forward[j]);
+            }
+        }
+        assert_eq!(alternating, expected);
+    }
+}
+
+#[test]
+fn test_vertex_chunks() {
+    for path in ["examples/Box.gltf", "tests/box_sparse.gltf"] {
+        let (document, buffers, _) = gltf::import(path, gltf::filesystem_fetcher).unwrap();
+        for primitive in document.meshes().flat_map(|mesh| mesh.primitives()) {
+            let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|x| &*x.0));
+            let positions: Vec<[f32; 3]> = reader.read_positions().unwrap().collect();
+            for chunk_size in [1, 5, positions.len(), positions.len() + 1] {
+                let chunks = reader.chunks(chunk_size);
+                assert_eq!(
+                    chunks.len(),
+                    (positions.len() + chunk_size - 1) / chunk_size
+                );
+                let mut chunked = Vec::new();
+                for chunk in chunks {
+                    assert_eq!(chunk.range().start, chunked.len());
+                    let iter = chunk.read_positions().unwrap();
+                    assert_eq!(iter.len(), chunk.len());
+                    chunked.extend(iter);
+                }
+                assert_eq!(chunked, positions);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_texture_streaming_priorities() {
+    use gltf::streaming::TextureUsage;
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 92,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAACAPwAAgD8AAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAAA/AAAAAAAAAD8AAAA/AAAAAAAAAD8AAAEAAgAAAAIAAwA="
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 48, "byteLength": 32 },
+            { "buffer": 0, "byteOffset": 80, "byteLength": 12 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] },
+            { "bufferView": 1, "componentType": 5126, "count": 4, "type": "VEC2" },
+            { "bufferView": 2, "componentType": 5123, "count": 6, "type": "SCALAR" }
+        ],
+        "images": [{ "uri": "a.png" }, { "uri": "b.png" }, { "uri": "c.png" }],
+        "textures": [{ "source": 0 }, { "source": 1 }, { "source": 2 }],
+        "materials": [
+            {
+                "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } },
+                "normalTexture": { "index": 1 }
+            },
+            { "emissiveTexture": { "index": 1 } }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0, "TEXCOORD_0": 1 },
+                "indices": 2,
+                "material": 0
+            }]
+        }],
+        "nodes": [{ "mesh": 0, "scale": [2, 2, 2] }],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    let gltf::Gltf { document, blob, .. } = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&document, blob, None, gltf::empty_fetcher).unwrap();
+    let priorities =
+        document.texture_streaming_priorities(|buffer| buffers.get(buffer.index()).map(|x| &*x.0));
+    assert_eq!(priorities.len(), 3);
+
+    let base_color = &priorities[0];
+    assert_eq!(base_color.priority, 1.0);
+    assert_eq!(base_color.usages, [TextureUsage::BaseColor]);
+    assert_eq!(base_color.materials, 1);
+    assert!((base_color.surface_area - 4.0).abs() < 1e-6);
+    assert!((base_color.uv_area - 0.25).abs() < 1e-6);
+    assert!((base_color.texels_per_unit(256, 256) - 64.0).abs() < 1e-3);
+    assert_eq!(base_color.mip_level(256, 256, 16.0), 2);
+
+    let normal = &priorities[1];
+    assert!((normal.priority - 0.8).abs() < 1e-6);
+    assert_eq!(
+        normal.usages,
+        [TextureUsage::Normal, TextureUsage::Emissive]
+    );
+    assert_eq!(normal.materials, 2);
+
+    let unused = &priorities[2];
+    assert_eq!(unused.priority, 0.0);
+    assert_eq!(unused.mip_level(256, 256, 16.0), 8);
+
+    let manifest = document.texture_streaming_manifest(&priorities);
+    assert_eq!(manifest["textures"][1]["image"], 1);
+    assert_eq!(manifest["textures"][1]["usages"][1], "emissiveTexture");
+
+    #[cfg(feature = "extras")]
+    {
+        let mut root = document.into_json();
+        gltf::streaming::write_extras(&mut root, &priorities);
+        let extras = root.textures[0].extras.as_ref().unwrap().get();
+        assert!(extras.starts_with(r#"{"streaming":{"#));
+    }
+}
+
+#[test]
+fn test_wrap_dependencies() {
+    use gltf::streaming::TextureUsage;
+    use gltf::texture::WrappingMode;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 60, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAvwAAAAAAAIA/AAAAQAAAAD8AAAA/" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }, { "buffer": 0, "byteOffset": 36, "byteLength": 24 }],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] },
+            { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC2" }
+        ],
+        "images": [{ "uri": "texture.png" }],
+        "samplers": [{ "wrapS": 33071 }, { "wrapS": 33071, "wrapT": 33071 }],
+        "textures": [{ "source": 0, "sampler": 0 }, { "source": 0, "sampler": 1 }],
+        "materials": [
+            { "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } }, "normalTexture": { "index": 1 } },
+            { "emissiveTexture": { "index": 0, "texCoord": 1 } }
+        ],
+        "meshes": [{
+            "primitives": [
+                { "attributes": { "POSITION": 0, "TEXCOORD_0": 1 }, "material": 0 },
+                { "attributes": { "POSITION": 0, "TEXCOORD_0": 1 }, "material": 1 }
+            ]
+        }]
+    }"#;
+    let gltf::Gltf { document, blob, .. } = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&document, blob, None, gltf::empty_fetcher).unwrap();
+    let dependencies =
+        document.wrap_dependencies(|buffer| buffers.get(buffer.index()).map(|x| &*x.0));
+
+    assert_eq!(dependencies.len(), 1);
+    let dependency = &dependencies[0];
+    assert_eq!((dependency.material, dependency.texture), (0, 0));
+    assert_eq!(dependency.usage, TextureUsage::BaseColor);
+    assert_eq!(dependency.wrap_s, WrappingMode::ClampToEdge);
+    assert_eq!(dependency.wrap_t, WrappingMode::Repeat);
+    assert_eq!(dependency.uv_min, [-0.5, 0.0]);
+    assert_eq!(dependency.uv_max, [1.0, 2.0]);
+    assert!(!dependency.wraps_s());
+    assert!(dependency.wraps_t());
+}
+
+#[test]
+fn test_scene_traverse() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [
+            { "translation": [1.0, 0.0, 0.0], "children": [1] },
+            { "translation": [0.0, 1.0, 0.0], "scale": [2.0, 2.0, 2.0] }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let scene = gltf.scenes().next().unwrap();
+    let visited: Vec<_> = scene.traverse().collect();
+    assert_eq!(visited.len(), 2);
+    assert_eq!(visited[0].0.index(), 0);
+    assert_eq!(visited[1].0.index(), 1);
+    assert_eq!(
+        visited[1].1,
+        [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [1.0, 1.0, 0.0, 1.0],
+        ]
+    );
+    let child = gltf.nodes().nth(1).unwrap();
+    assert_eq!(child.global_transform(&scene), Some(visited[1].1));
+}
+
+#[test]
+fn test_shared_import() {
+    use gltf::shared::{share, SharedBuffer};
+
+    let import = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let length = import.1[0].len();
+    let (_, buffers, images) = share(import);
+    assert!(images.is_empty());
+
+    let renderer = buffers[0].clone();
+    assert!(renderer.ptr_eq(&buffers[0]));
+    let physics = buffers[0].clone();
+    let thread = std::thread::spawn(move || physics.len());
+    assert_eq!(thread.join().unwrap(), length);
+    assert_eq!(renderer.len(), length);
+
+    let renderer = renderer.try_unwrap().unwrap_err();
+    drop(buffers);
+    let data = SharedBuffer::try_unwrap(renderer).unwrap();
+    assert_eq!(data.len(), length);
+}
+
+#[test]
+fn test_computed_bounding_box() {
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let mesh = document.meshes().next().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    assert_eq!(
+        primitive.compute_bounding_box(get_buffer_data),
+        Some(primitive.bounding_box())
+    );
+    assert_eq!(
+        mesh.compute_bounding_box(get_buffer_data),
+        Some(primitive.bounding_box())
+    );
+
+    // The box is rotated by 90 degrees around the x axis by its parent node.
+    let scene = document.default_scene().unwrap();
+    let bounds = scene.compute_bounding_box(get_buffer_data).unwrap();
+    for i in 0..3 {
+        assert!((bounds.min[i] + 0.5).abs() < 1e-6);
+        assert!((bounds.max[i] - 0.5).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_surface_area_and_volume() {
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let mesh = document.meshes().next().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    assert!((primitive.surface_area(get_buffer_data).unwrap() - 6.0).abs() < 1e-5);
+    assert!((primitive.volume(get_buffer_data).unwrap() - 1.0).abs() < 1e-5);
+    assert!(primitive.is_closed(get_buffer_data));
+
+    let scale = [
+        [2.0, 0.0, 0.0, 0.0],
+        [0.0, 2.0, 0.0, 0.0],
+        [0.0, 0.0, 2.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+    let node = document.nodes().find(|node| node.mesh().is_some()).unwrap();
+    assert!((node.surface_area(get_buffer_data, &scale).unwrap() - 24.0).abs() < 1e-5);
+    assert!((node.volume(get_buffer_data, &scale).unwrap() - 8.0).abs() < 1e-5);
+
+    let scene = document.default_scene().unwrap();
+    assert!((scene.surface_area(get_buffer_data) - 6.0).abs() < 1e-5);
+    assert!((scene.volume(get_buffer_data).unwrap() - 1.0).abs() < 1e-5);
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 36, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+    }"#;
+    let gltf::Gltf { document, blob, .. } = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&document, blob, None, gltf::empty_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    assert!((primitive.surface_area(get_buffer_data).unwrap() - 0.5).abs() < 1e-5);
+    assert_eq!(primitive.volume(get_buffer_data), None);
+    assert!(!primitive.is_closed(get_buffer_data));
+}
+
+#[test]
+fn test_topology() {
+    use gltf::topology::Options;
+
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let options = Options::new().with_self_intersections(true);
+    let reports = document.topology(get_buffer_data, options);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].triangles, 12);
+    assert!(reports[0].is_watertight());
+    assert_eq!(reports[0].self_intersections, Some(vec![]));
+
+    // One triangle pierces the other.
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 72, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAACAPgAAgD4AAIC/AACAPgAAgD4AAIA/AAAAQAAAAEAAAAAA" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 72 }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 6, "type": "VEC3", "min": [0, 0, -1], "max": [2, 2, 1] }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+    }"#;
+    let gltf::Gltf { document, blob, .. } = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&document, blob, None, gltf::empty_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let report = primitive.topology(get_buffer_data, options).unwrap();
+    assert!(report.is_manifold());
+    assert!(!report.is_watertight());
+    assert_eq!(report.boundary_loops, [vec![0, 1, 2], vec![3, 4, 5]]);
+    assert_eq!(report.self_intersections, Some(vec![[0, 1]]));
+    let report = primitive.topology(get_buffer_data, Options::new()).unwrap();
+    assert_eq!(report.self_intersections, None);
+}
+
+#[test]
+fn test_fill_holes() {
+    use gltf::repair::Options;
+
+    // A unit cube without its top face.
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 156, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAACAPwAAgD8AAAAAAAAAAAAAAAAAAIA/AACAPwAAAAAAAIA/AAAAAAAAgD8AAIA/AACAPwAAgD8AAIA/AAACAAMAAAADAAEAAAABAAUAAAAFAAQAAgAGAAcAAgAHAAMAAAAEAAYAAAAGAAIAAQADAAcAAQAHAAUA" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 96 }, { "buffer": 0, "byteOffset": 96, "byteLength": 60 }],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 8, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 1] },
+            { "bufferView": 1, "componentType": 5123, "count": 30, "type": "SCALAR" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }] }]
+    }"#;
+    let gltf::Gltf { document, blob, .. } = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&document, blob, None, gltf::empty_fetcher).unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let fill = primitive
+        .fill_holes(get_buffer_data, Options::new())
+        .unwrap();
+    assert_eq!((fill.filled, fill.skipped), (1, 0));
+    assert_eq!(fill.triangles.len(), 2);
+
+    // Every edge of the hole is now shared by a triangle winding against it.
+    let topology = primitive
+        .topology(get_buffer_data, gltf::topology::Options::new())
+        .unwrap();
+    let hole = &topology.boundary_loops[0];
+    for (i, &from) in hole.iter().enumerate() {
+        let to = hole[(i + 1) % hole.len()];
+        assert!(fill
+            .triangles
+            .iter()
+            .any(|triangle| (0..3).any(|j| triangle[j] == to && triangle[(j + 1) % 3] == from)));
+    }
+
+    let options = Options::new().with_max_edges(3);
+    let fill = primitive.fill_holes(get_buffer_data, options).unwrap();
+    assert_eq!((fill.filled, fill.skipped), (0, 1));
+    let options = Options::new().with_max_area(0.5);
+    let fill = primitive.fill_holes(get_buffer_data, options).unwrap();
+    assert!(fill.triangles.is_empty());
+}
+
+#[test]
+fn test_print_tree_and_dot() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "scenes": [{ "name": "Main", "nodes": [0] }],
+        "nodes": [
+            { "name": "Root", "children": [1, 2], "scale": [2, 2, 2] },
+            { "name": "Body", "mesh": 0 },
+            { "camera": 0 },
+            { "name": "Spare" }
+        ],
+        "cameras": [{ "type": "perspective", "perspective": { "yfov": 1, "znear": 0.1 } }],
+        "materials": [{ "name": "Skin" }],
+        "meshes": [{
+            "name": "Body",
+            "primitives": [
+                { "attributes": { "POSITION": 0 }, "material": 0 },
+                { "attributes": { "POSITION": 0 } }
+            ]
+        }],
+        "buffers": [{ "byteLength": 36 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] }]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    assert_eq!(
+        gltf.print_tree(),
+        concat!(
+            "Scene 0 \"Main\"\n",
+            "└── Node 0 \"Root\" [scale [2.0, 2.0, 2.0]]\n",
+            "    ├── Node 1 \"Body\" [mesh 0 \"Body\" (2 primitives, materials 0, default)]\n",
+            "    └── Node 2 [camera 0 (perspective)]\n",
+            "Nodes in no scene\n",
+            "└── Node 3 \"Spare\"\n",
+        )
+    );
+
+    let dot = gltf.to_dot();
+    assert!(dot.starts_with("digraph gltf {\n"));
+    assert!(dot.contains("node1 [label=\"Node 1\\nBody\", shape=ellipse];"));
+    for edge in [
+        "scene0 -> node0;",
+        "node0 -> node1;",
+        "node1 -> mesh0;",
+        "node2 -> camera0;",
+        "mesh0 -> material0;",
+        "mesh0 -> accessor0;",
+        "accessor0 -> view0;",
+        "view0 -> buffer0;",
+    ] {
+        assert!(dot.contains(edge), "missing {}", edge);
+    }
+}
+
+#[test]
+fn test_json_size() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [{ "name": "Node" }, { "name": "A much longer node name" }, {}],
+        "scenes": [{ "nodes": [0, 1, 2] }]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let size = gltf.json_size();
+    let properties: Vec<&str> = size
+        .properties
+        .iter()
+        .map(|p| p.property.as_str())
+        .collect();
+    assert_eq!(properties, ["nodes", "scenes", "asset"]);
+
+    // The members, separating commas and braces make up the whole object.
+    let members: usize = size.properties.iter().map(|p| p.bytes).sum();
+    assert_eq!(size.total, members + size.properties.len() - 1 + 2);
+    let nodes = &size.properties[0];
+    assert_eq!(nodes.count, 3);
+    assert_eq!(
+        nodes.names,
+        r#""name":"Node""name":"A much longer node name""#.len()
+    );
+    assert_eq!(nodes.largest.map(|(index, _)| index), Some(1));
+    let value = size.to_value();
+    assert_eq!(value["total"], size.total);
+    assert_eq!(value["properties"][0]["largest"]["index"], 1);
+}
+
+#[test]
+fn test_strip_metadata() {
+    use gltf::strip::Options;
+
+    let json = r#"{
+        "asset": { "version": "2.0", "extras": { "exporter": "test" } },
+        "scenes": [{ "name": "Scene", "nodes": [0] }],
+        "nodes": [{ "name": "Hip", "extras": { "lod": 1, "notes": "draft" } }],
+        "extensionsUsed": ["KHR_materials_unlit", "KHR_mesh_quantization"],
+        "extensionsRequired": ["KHR_mesh_quantization"]
+    }"#;
+    let parse = || gltf::Gltf::from_slice(json.as_bytes()).unwrap().document;
+
+    let mut document = parse();
+    document.strip_metadata(&Options::new());
+    let root = document.into_json();
+    assert_eq!(root.scenes[0].name, None);
+    assert_eq!(root.nodes[0].name, None);
+    assert_eq!(root.extensions_used, ["KHR_mesh_quantization"]);
+
+    let mut document = parse();
+    let options = Options::new()
+        .keep_names("nodes")
+        .keep_extras("lod")
+        .keep_extension("KHR_materials_unlit");
+    document.strip_metadata(&options);
+    let root = document.into_json();
+    assert_eq!(root.scenes[0].name, None);
+    assert_eq!(root.nodes[0].name.as_deref(), Some("Hip"));
+    assert_eq!(root.extensions_used.len(), 2);
+    #[cfg(feature = "extras")]
+    {
+        let extras = root.nodes[0].extras.as_ref().unwrap();
+        assert_eq!(extras.get(), r#"{"lod":1}"#);
+        assert!(root.asset.extras.is_none());
+    }
+
+    let mut document = parse();
+    let options = Options::new()
+        .with_names(false)
+        .with_unused_extensions(false);
+    document.strip_metadata(&options);
+    let root = document.into_json();
+    assert_eq!(root.scenes[0].name.as_deref(), Some("Scene"));
+    assert_eq!(root.extensions_used.len(), 2);
+}
+
+#[test]
+fn test_snapshot_digest() {
+    let (document, buffers, images) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let digest = gltf::snapshot::digest(&document, &buffers, &images);
+    let (document, buffers, images) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    assert_eq!(digest, gltf::snapshot::digest(&document, &buffers, &images));
+    let mut lines = digest.lines();
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("buffer 0: 648 bytes, hash "));
+    assert_eq!(lines.next(), Some("json:"));
+    assert_eq!(lines.next(), Some("{"));
+    assert_eq!(lines.next(), Some("  \"accessors\": ["));
+
+    // The canonical JSON does not depend on the member order of the source.
+    let a = gltf::Gltf::from_slice(
+        br#"{"asset":{"version":"2.0"},"nodes":[{"translation":[1,2,3],"scale":[2,2,2]}]}"#,
+    )
+    .unwrap();
+    let b = gltf::Gltf::from_slice(
+        br#"{"nodes":[{"scale":[2,2,2],"translation":[1,2,3]}],"asset":{"version":"2.0"}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        gltf::snapshot::canonical_json(&a),
+        gltf::snapshot::canonical_json(&b)
+    );
+}
+
+#[test]
+fn test_fuzz_entry_points() {
+    let glb = fs::read("examples/Box.glb").unwrap();
+    gltf::fuzz::parse_glb(&glb).unwrap();
+    for len in 0..glb.len() {
+        assert!(gltf::fuzz::parse_glb(&glb[..len]).is_err());
+    }
+    gltf::fuzz::parse_json(&fs::read("examples/Box.gltf").unwrap()).unwrap();
+
+    // Out of bounds accessors are reported rather than panicking.
+    assert!(gltf::fuzz::parse_json(
+        br#"{
+            "asset": { "version": "2.0" },
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 5 } }] }]
+        }"#
+    )
+    .is_err());
+
+    // Accessors of an unexpected component type are skipped.
+    gltf::fuzz::parse_json(
+        br#"{
+            "asset": { "version": "2.0" },
+            "accessors": [
+                { "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 1] },
+                { "componentType": 5126, "count": 3, "type": "SCALAR" }
+            ],
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "JOINTS_0": 1 }, "indices": 1 }] }]
+        }"#,
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_fuzz_arbitrary_roots() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    // A fixed xorshift sequence, so that failures are reproducible.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    for len in 0..256 {
+        let bytes: Vec<u8> = (0..len * 16)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        let mut u = Unstructured::new(&bytes);
+        if let Ok((root, bin)) = <(gltf::json::Root, Vec<u8>)>::arbitrary(&mut u) {
+            let _ = gltf::fuzz::load_json(root, &bin);
+        }
+    }
+}
+
+#[cfg(feature = "names")]
+#[test]
+fn test_rename() {
+    use gltf::rename::{Error, Object};
+
+    let mut gltf = gltf::Gltf::from_slice(
+        br#"{
+            "asset": { "version": "2.0" },
+            "materials": [{ "name": "Mat.001" }, { "name": "Mat.002" }],
+            "nodes": [{ "name": "Bone.001" }, { "name": "Bone.002" }, {}]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        gltf.rename(Object::Node(5), "Hips"),
+        Err(Error::NotFound(Object::Node(5)))
+    );
+    let renamed = gltf.rename(Object::Node(2), "Bone.001");
+    assert!(matches!(
+        renamed,
+        Err(Error::Duplicate {
+            existing: Object::Node(0),
+            ..
+        })
+    ));
+    gltf.rename(Object::Node(2), "Mat.001").unwrap();
+
+    let report = gltf.rename_with(|object, name| match object {
+        Object::Node(_) => name.strip_prefix("Bone.").map(|n| format!("Mat.{}", n)),
+        Object::Material(_) => Some("Skin".to_owned()),
+        _ => None,
+    });
+    assert_eq!(report.renamed.len(), 2);
+    assert_eq!(report.conflicts.len(), 2);
+    assert_eq!(report.resolve("materials", "Mat.001"), Some("Skin"));
+    assert_eq!(report.resolve("nodes", "Bone.002"), Some("Mat.002"));
+    assert_eq!(report.resolve("nodes", "Mat.001"), None);
+    assert_eq!(report.to_value()["conflicts"][1]["existing"], 2);
+    let names: Vec<_> = gltf.nodes().map(|node| node.name()).collect();
+    assert_eq!(names, [Some("Bone.001"), Some("Mat.002"), Some("Mat.001")]);
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_mip_settings() {
+    use gltf::texture::{self, Filter, MipSettings, WrappingMode};
+
+    let gltf = gltf::Gltf::from_slice(
+        br#"{
+            "asset": { "version": "2.0" },
+            "images": [{ "uri": "a.png" }],
+            "samplers": [{ "magFilter": 9728, "minFilter": 9985, "wrapS": 33071 }],
+            "textures": [
+                { "source": 0, "sampler": 0, "extras": { "author": "me" } },
+                { "source": 0, "extras": { "mipSettings": { "bias": -0.5, "maxLod": 4 } } }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let textures: Vec<_> = gltf.textures().collect();
+    let descriptor = textures[0].sampler_descriptor();
+    assert_eq!(descriptor.mag_filter, Filter::Nearest);
+    assert_eq!(descriptor.min_filter, Filter::Linear);
+    assert_eq!(descriptor.mipmap_filter, Some(Filter::Nearest));
+    assert_eq!(descriptor.wrap_s, WrappingMode::ClampToEdge);
+    assert_eq!(descriptor.mip_bias, 0.0);
+    assert_eq!(textures[0].mip_settings(), None);
+    let settings = MipSettings {
+        bias: -0.5,
+        min_lod: 0.0,
+        max_lod: Some(4.0),
+    };
+    assert_eq!(textures[1].mip_settings(), Some(settings));
+    let descriptor = textures[1].sampler_descriptor();
+    assert_eq!(descriptor.mipmap_filter, Some(Filter::Linear));
+    assert_eq!(descriptor.mip_bias, -0.5);
+    assert_eq!(descriptor.lod_max_clamp, Some(4.0));
+
+    let mut root = gltf.document.into_json();
+    let sharp = MipSettings {
+        bias: -1.0,
+        ..Default::default()
+    };
+    assert!(texture::write_mip_settings(&mut root, 0, &sharp));
+    assert!(!texture::write_mip_settings(&mut root, 2, &sharp));
+    let document = gltf::Document::from_json(root).unwrap();
+    let texture = document.textures().next().unwrap();
+    assert_eq!(texture.mip_settings(), Some(sharp));
+    assert!(texture.extras().as_ref().unwrap().get().contains("author"));
+}
+
+#[test]
+fn test_complexity() {
+    use gltf::complexity::Limits;
+
+    let gltf = gltf::Gltf::from_slice(
+        br#"{
+            "asset": { "version": "2.0" },
+            "nodes": [
+                { "children": [1, 2, 3] },
+                { "children": [4] },
+                {},
+                {},
+                { "children": [5] },
+                {},
+                {}
+            ],
+            "skins": [{ "joints": [0, 1, 4, 5] }]
+        }"#,
+    )
+    .unwrap();
+    let complexity = gltf.complexity();
+    assert_eq!(complexity.nodes, 7);
+    assert_eq!(complexity.depth, 4);
+    assert_eq!(complexity.max_children, 3);
+    assert_eq!(complexity.max_joints, 4);
+    assert_eq!(complexity.to_value()["maxChildren"], 3);
+
+    let limits = Limits::new()
+        .with_max_depth(4)
+        .with_max_children(3)
+        .with_max_joints(4);
+    assert!(gltf.check_complexity(&limits).is_ok());
+    let limits = Limits::new()
+        .with_max_depth(2)
+        .with_max_children(2)
+        .with_max_joints(3)
+        .with_max_nodes(6);
+    let errors = match gltf.check_complexity(&limits) {
+        Err(gltf::Error::Validation(errors)) => errors,
+        result => panic!("unexpected result: {:?}", result),
+    };
+    let paths: Vec<_> = errors.iter().map(|(path, _)| path.to_string()).collect();
+    assert_eq!(
+        paths,
+        ["nodes", "nodes[5]", "nodes[0].children", "skins[0].joints"]
+    );
+}
+
+#[test]
+fn test_gltf_slice_borrows_blob() {
+    let bytes = fs::read("examples/Box.glb").unwrap();
+    let gltf = gltf::GltfSlice::from_slice(&bytes).unwrap();
+    let blob = gltf.blob.unwrap();
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&blob.as_ptr()));
+    assert_eq!(gltf.meshes().count(), 1);
+
+    let owned = gltf.into_owned();
+    assert_eq!(owned.blob.as_deref(), Some(blob));
+}
+
+#[test]
+fn test_webp_data_uri() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{
+            "uri": "data:image/webp;base64,UklGRhoAAABXRUJQVlA4TA0AAAAvAAAAEAcQERGIiP4HAA=="
+        }]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let images = gltf::import_encoded_images(&gltf, &[], None, gltf::empty_fetcher).unwrap();
+    assert_eq!(images[0].encoding, gltf::image::Encoding::WebP);
+
+    let data = images[0].decode().unwrap();
+    assert_eq!((data.width, data.height), (1, 1));
+}
+
+#[test]
+fn test_decode_with_max_dimension() {
+    let encoded = gltf::image::Encoded {
+        bytes: std::fs::read("tests/orange_64x32.jpg").unwrap(),
+        encoding: gltf::image::Encoding::Jpeg,
+    };
+    let full = encoded.decode().unwrap();
+    assert_eq!((full.width, full.height), (64, 32));
+    let unchanged = encoded.decode_with_max_dimension(64).unwrap();
+    assert_eq!((unchanged.width, unchanged.height), (64, 32));
+    // Scaled while decoding.
+    let quarter = encoded.decode_with_max_dimension(16).unwrap();
+    assert_eq!((quarter.width, quarter.height), (16, 8));
+    // Scaled while decoding to 1/2, then resampled.
+    let resampled = encoded.decode_with_max_dimension(20).unwrap();
+    assert_eq!((resampled.width, resampled.height), (20, 10));
+    assert_eq!(resampled.pixels.len(), 20 * 10 * 3);
+}
+
+#[test]
+fn test_memory_fetcher() {
+    let mut fetcher = gltf::MemoryFetcher::default();
+    fetcher.insert(
+        "assets/./Box.gltf",
+        std::fs::read("examples/Box.gltf").unwrap(),
+    );
+    fetcher.insert(
+        "assets/Box0.bin",
+        std::fs::read("examples/Box0.bin").unwrap(),
+    );
+
+    let (_, expected, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let (document, buffers, _) = fetcher.import("assets/Box.gltf").unwrap();
+    assert_eq!(document.buffers().count(), 1);
+    assert_eq!(&*buffers[0], &*expected[0]);
+
+    let base = std::path::Path::new("assets/textures");
+    assert!(fetcher.read(Some(base), "../Box0.bin").is_ok());
+    match fetcher.read(Some(base), "Box0.bin") {
+        Err(gltf::Error::Io(error)) => assert_eq!(error.kind(), std::io::ErrorKind::NotFound),
+        _ => panic!("expected a missing file"),
+    }
+
+    let slice = fetcher.get("assets/Box.gltf").unwrap();
+    let (_, buffers, _) =
+        gltf::import_slice(slice, Some(std::path::Path::new("assets")), |base, path| {
+            fetcher.read(base, path)
+        })
+        .unwrap();
+    assert_eq!(&*buffers[0], &*expected[0]);
+}
+
+/// Returns a ustar entry holding `data` at `name`.
+fn tar_entry(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = [0u8; 512];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[100..107].copy_from_slice(b"0000644");
+    header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+    header[148..156].copy_from_slice(b"        ");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+    header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+
+    let mut entry = header.to_vec();
+    entry.extend_from_slice(data);
+    entry.resize((entry.len() + 511) / 512 * 512, 0);
+    entry
+}
+
+#[test]
+fn test_import_streams() {
+    let (_, expected, _) = gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+
+    let glb = fs::File::open("examples/Box.glb").unwrap();
+    let (document, buffers, _) = gltf::import_reader(glb, None, gltf::empty_fetcher).unwrap();
+    assert_eq!(document.meshes().count(), 1);
+    assert_eq!(&*buffers[0], &*expected[0]);
+
+    let mut tar = tar_entry("model/Box0.bin", &fs::read("examples/Box0.bin").unwrap());
+    tar.extend(tar_entry(
+        "model/Box.gltf",
+        &fs::read("examples/Box.gltf").unwrap(),
+    ));
+    tar.extend_from_slice(&[0; 1024]);
+    let (document, buffers, _) = gltf::import_tar(tar.as_slice()).unwrap();
+    assert_eq!(document.meshes().count(), 1);
+    assert_eq!(&*buffers[0], &*expected[0]);
+
+    let tar = tar_entry("Box0.bin", &[]);
+    match gltf::import_tar(tar.as_slice()) {
+        Err(gltf::Error::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::NotFound),
+        _ => panic!("expected a missing glTF file"),
+    }
+    let mut tar = tar_entry("Box.gltf", b"{}");
+    tar[0] = b'X';
+    match gltf::MemoryFetcher::from_tar(tar.as_slice()) {
+        Err(gltf::Error::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::InvalidData),
+        _ => panic!("expected a checksum mismatch"),
+    }
+}
+
+#[test]
+fn test_detect() {
+    use gltf::FileKind;
+
+    let glb = fs::read("examples/Box.glb").unwrap();
+    let json = fs::read("examples/Box.gltf").unwrap();
+    assert_eq!(gltf::detect(&glb), FileKind::Glb { version: 2 });
+    assert_eq!(gltf::detect(&json), FileKind::GltfJson);
+    assert_eq!(gltf::detect(b"\xEF\xBB\xBF {}"), FileKind::GltfJson);
+    assert_eq!(gltf::detect(b"glTF"), FileKind::Unknown);
+    assert_eq!(gltf::detect(b""), FileKind::Unknown);
+
+    let gltf = gltf::Gltf::from_slice(&glb).unwrap();
+    assert_eq!(gltf.container, FileKind::Glb { version: 2 });
+    let gltf = gltf::Gltf::from_reader(io::Cursor::new(&json)).unwrap();
+    assert_eq!(gltf.container, FileKind::GltfJson);
+    let gltf = gltf::GltfSlice::from_slice(&glb).unwrap();
+    assert!(gltf.into_owned().container.is_glb());
+}
+
+#[test]
+fn test_glb_options() {
+    use gltf::binary::{Glb, Options, Warning};
+
+    let json = br#"{"asset":{"version":"2.0"}} "#;
+    let glb = Glb {
+        header: gltf::binary::Header {
+            magic: *b"glTF",
+            version: 2,
+            length: 0,
+        },
+        json: json.to_vec().into(),
+        bin: Some(vec![1, 2, 3, 4].into()),
+    };
+    let mut data = glb.to_vec().unwrap();
+    data[4] = 3;
+    data.extend_from_slice(&4u32.to_le_bytes());
+    data.extend_from_slice(b"XTRA");
+    data.extend_from_slice(&[5, 6, 7, 8]);
+    let length = data.len() as u32;
+    data[8..12].copy_from_slice(&length.to_le_bytes());
+
+    assert!(Glb::from_slice(&data).is_err());
+    assert!(Glb::from_slice_with_options(&data, Options::new()).is_err());
+    let options = Options::new().with_unknown_versions(true);
+    let parsed = Glb::from_slice_with_options(&data, options).unwrap();
+    assert_eq!(parsed.warnings, [Warning::Version(3)]);
+    assert!(parsed.chunks.is_empty());
+
+    let parsed = Glb::from_slice_with_options(&data, options.with_unknown_chunks(true)).unwrap();
+    assert_eq!(&*parsed.glb.json, &json[..]);
+    assert_eq!(parsed.glb.bin.as_deref(), Some(&[1, 2, 3, 4][..]));
+    assert_eq!(
+        parsed.warnings,
+        [
+            Warning::Version(3),
+            Warning::UnknownChunk {
+                ty: *b"XTRA",
+                length: 4
+            }
+        ]
+    );
+    assert_eq!(&parsed.chunks[0].ty, b"XTRA");
+    assert_eq!(&*parsed.chunks[0].data, &[5, 6, 7, 8]);
+
+    let mut data = b"glTF".to_vec();
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&(20 + json.len() as u32 + 4).to_le_bytes());
+    data.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(json);
+    data.extend_from_slice(&[1, 2, 3, 4]);
+    let parsed = Glb::from_slice_with_options(&data, options).unwrap();
+    assert_eq!(parsed.glb.header.version, 1);
+    assert_eq!(&*parsed.glb.json, &json[..]);
+    assert_eq!(parsed.glb.bin.as_deref(), Some(&[1, 2, 3, 4][..]));
+}
+
+#[test]
+fn test_metadata_only() {
+    let json = br#"{
+        "asset": { "version": "2.0", "generator": "test" },
+        "extensionsUsed": ["KHR_materials_unlit"],
+        "accessors": [{ "not": "an accessor" }],
+        "meshes": [{ "name": "Cube", "primitives": [] }],
+        "nodes": [{ "name": "Root", "children": [1] }, { "mesh": 0 }],
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    assert!(gltf::Gltf::from_slice(json).is_err());
+
+    let metadata = gltf::Document::from_json_metadata_only(json).unwrap();
+    assert_eq!(metadata.asset.generator.as_deref(), Some("test"));
+    assert_eq!(metadata.extensions_used, ["KHR_materials_unlit"]);
+    assert!(metadata.extensions_required.is_empty());
+    assert_eq!(metadata.meshes[0].name.as_deref(), Some("Cube"));
+    let names: Vec<_> = metadata
+        .nodes
+        .iter()
+        .map(|node| node.name.as_deref())
+        .collect();
+    assert_eq!(names, [Some("Root"), None]);
+    assert_eq!(metadata.scene.map(|scene| scene.value()), Some(0));
+    assert_eq!(metadata.scenes.len(), 1);
+
+    let json = br#"{ "asset": { "version": "1.0" } }"#;
+    assert!(matches!(
+        gltf::Document::from_json_metadata_only(json),
+        Err(gltf::Error::UnsupportedVersion { .. })
+    ));
+}
+
+#[test]
+fn test_animation_bindings() {
+    use gltf::animation::Property;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 16, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAAAAAA==" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 16 }],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR", "min": [0], "max": [0] },
+            { "bufferView": 0, "byteOffset": 4, "componentType": 5126, "count": 1, "type": "VEC3" },
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC4" }
+        ],
+        "nodes": [{ "children": [1] }, {}, {}],
+        "animations": [
+            {
+                "samplers": [{ "input": 0, "output": 1 }, { "input": 0, "output": 2 }],
+                "channels": [
+                    { "sampler": 0, "target": { "node": 1, "path": "translation" } },
+                    { "sampler": 1, "target": { "node": 0, "path": "rotation" } }
+                ]
+            },
+            {
+                "samplers": [{ "input": 0, "output": 1 }],
+                "channels": [{ "sampler": 0, "target": { "node": 1, "path": "scale" } }]
+            }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let bindings = gltf.animation_bindings();
+    assert_eq!(bindings.len(), 3);
+
+    let node = bindings.get(1);
+    let properties: Vec<_> = node.iter().map(|binding| binding.property).collect();
+    assert_eq!(properties, [Property::Translation, Property::Scale]);
+    assert_eq!((node[1].animation, node[1].channel), (1, 0));
+    let channel = node[1].channel(&gltf).unwrap();
+    assert_eq!(channel.target().node().index(), 1);
+
+    assert_eq!(bindings.get(0)[0].property, Property::Rotation);
+    assert!(bindings.get(2).is_empty());
+    assert!(bindings.get(3).is_empty());
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_morph_target_names() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 12, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAA" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 12 }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3", "min": [0, 0, 0], "max": [0, 0, 0] }],
+        "meshes": [
+            {
+                "primitives": [
+                    { "attributes": { "POSITION": 0 }, "targets": [{ "POSITION": 0 }, { "POSITION": 0 }] },
+                    { "attributes": { "POSITION": 0 }, "targets": [{ "POSITION": 0 }] }
+                ],
+                "extras": { "targetNames": ["jawOpen", "smile"] }
+            },
+            { "primitives": [{ "attributes": { "POSITION": 0 } }] }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let mesh = gltf.meshes().next().unwrap();
+    assert_eq!(mesh.target_names().unwrap(), ["jawOpen", "smile"]);
+
+    let primitives: Vec<_> = mesh.primitives().collect();
+    assert_eq!(primitives[0].target_index_by_name("smile"), Some(1));
+    assert_eq!(primitives[0].target_index_by_name("frown"), None);
+    assert_eq!(primitives[1].target_index_by_name("jawOpen"), Some(0));
+    assert_eq!(primitives[1].target_index_by_name("smile"), None);
+
+    let mesh = gltf.meshes().nth(1).unwrap();
+    assert!(mesh.target_names().is_none());
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_blend_shape_resolution() {
+    use gltf::blend_shape::{BlendShape, Scheme};
+
+    assert_eq!(BlendShape::of_scheme(Scheme::ArKit).count(), 52);
+    assert_eq!(BlendShape::of_scheme(Scheme::Oculus).count(), 15);
+    for shape in BlendShape::ALL {
+        assert_eq!(BlendShape::from_name(shape.name()), Some(*shape));
+    }
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "meshes": [
+            { "primitives": [], "extras": { "targetNames": ["blendShape1.jawOpen", "mouthSmile_L", "Wink"] } },
+            { "primitives": [] }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let resolutions = gltf.resolve_blend_shapes();
+    assert_eq!(resolutions.len(), 1);
+
+    let resolution = &resolutions[0];
+    assert_eq!(resolution.mesh, 0);
+    assert_eq!(resolution.target(BlendShape::MouthSmileLeft), Some(1));
+    assert_eq!(resolution.target(BlendShape::JawOpen), Some(0));
+    assert_eq!(resolution.unresolved, [(2, "Wink".to_owned())]);
+    assert_eq!(resolution.schemes().collect::<Vec<_>>(), [Scheme::ArKit]);
+    assert_eq!(resolution.missing.len(), 50);
+    assert!(!resolution.missing.contains(&BlendShape::JawOpen));
+}
+
+#[test]
+fn test_shared_buffer_storage() {
+    use gltf::buffer::Bytes;
+    let (_, buffers, _) = gltf::import("examples/Box.gltf", |base, path| {
+        let bytes = gltf::filesystem_fetcher(base, path)?;
+        Ok(Bytes::from_storage(bytes.into_boxed_slice()))
+    })
+    .unwrap();
+    assert!(matches!(buffers[0].0, Bytes::Shared(_)));
+    assert_eq!(buffers[0].len(), 648);
+}
+
+#[test]
+fn test_fetcher_request_and_progress() {
+    use gltf::{Fetcher, ResourceKind, ResourceRequest};
+    use std::ops::ControlFlow;
+
+    struct Checked;
+
+    impl Fetcher for Checked {
+        fn fetch(&mut self, request: &ResourceRequest<'_>) -> gltf::Result<gltf::buffer::Bytes> {
+            assert_eq!(request.kind, ResourceKind::Buffer);
+            assert_eq!(request.index, Some(0));
+            assert_eq!(request.uri, "Box0.bin");
+            assert_eq!(request.expected_length, Some(648));
+            gltf::filesystem_fetcher(request.base, request.path).map(Into::into)
+        }
+    }
+
+    let mut reports = Vec::new();
+    gltf::import_with_progress("examples/Box.gltf", Checked, |progress| {
+        reports.push((progress.completed, progress.total));
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+    assert_eq!(reports, vec![(1, 1)]);
+
+    let result =
+        gltf::import_with_progress("examples/Box.gltf", Checked, |_| ControlFlow::Break(()));
+    assert!(matches!(result, Err(gltf::Error::Cancelled)));
+}
+
+#[test]
+fn test_mock_scheme_fixtures() {
+    use gltf::mock::{FailNth, MockScheme, ShortRead, Slow};
+    use gltf::{Error, ResourceKind};
+    use std::io::ErrorKind;
+    use std::ops::ControlFlow;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    let gltf = |buffers: &str, images: &str| {
+        format!(
+            r#"{{ "asset": {{ "version": "2.0" }}, "buffers": [{}], "images": [{}] }}"#,
+            buffers, images
+        )
+        .into_bytes()
+    };
+    let jpeg = std::fs::read("tests/orange_64x32.jpg").unwrap();
+    let image = |uri: &str| format!(r#"{{ "uri": "{}" }}"#, uri);
+    let buffer = |uri: &str| format!(r#"{{ "uri": "{}", "byteLength": 4 }}"#, uri);
+    let scheme = || {
+        MockScheme::new()
+            .with_file("assets/data file.bin", vec![1, 2, 3, 4])
+            .with_file("/abs/data.bin", vec![1, 2, 3, 4])
+            .with_file("assets/image.jpg", jpeg.clone())
+            .with_error("assets/locked.bin", ErrorKind::PermissionDenied)
+    };
+    let import = |slice: &[u8], base: Option<&str>, fetcher: &mut MockScheme| {
+        let base = base.map(Path::new);
+        gltf::import_slice_with_progress(slice, base, fetcher, |_| ControlFlow::Continue(()))
+    };
+    fn io_kind<T>(result: gltf::Result<T>) -> Option<ErrorKind> {
+        match result {
+            Err(Error::Io(error)) => Some(error.kind()),
+            _ => None,
+        }
+    }
+
+    // Relative, percent-encoded, `file:` and `data:` references.
+    let mut fetcher = scheme();
+    let slice = gltf(
+        &[
+            buffer("data%20file.bin"),
+            buffer("file:///abs/data.bin"),
+            buffer("file:/abs/data.bin"),
+            buffer("data:application/octet-stream;base64,AQIDBA=="),
+        ]
+        .join(","),
+        "",
+    );
+    let (_, buffers, _) = import(&slice, Some("assets"), &mut fetcher).unwrap();
+    assert!(buffers.iter().all(|data| **data == [1, 2, 3, 4]));
+    let requests = fetcher.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].path, "assets/data file.bin");
+    assert_eq!(requests[0].uri, "data%20file.bin");
+    assert_eq!(requests[1].path, "/abs/data.bin");
+    assert_eq!(
+        (requests[2].kind, requests[2].index),
+        (ResourceKind::Buffer, Some(2))
+    );
+
+    // Scheme errors.
+    let result = import(
+        &gltf(&buffer("https://example.com/data.bin"), ""),
+        Some("assets"),
+        &mut scheme(),
+    );
+    assert!(matches!(result, Err(Error::UnsupportedScheme)));
+    let result = import(&gltf(&buffer("data:;base64,!!"), ""), None, &mut scheme());
+    assert!(matches!(result, Err(Error::Base64(_))));
+    let result = import(&gltf(&buffer("data.bin"), ""), None, &mut scheme());
+    assert!(matches!(result, Err(Error::ExternalReferenceInSliceImport)));
+    let result = import(&gltf(r#"{ "byteLength": 4 }"#, ""), None, &mut scheme());
+    assert!(matches!(result, Err(Error::MissingBlob)));
+
+    // Fetcher errors.
+    let result = import(
+        &gltf(&buffer("missing.bin"), ""),
+        Some("assets"),
+        &mut scheme(),
+    );
+    assert_eq!(io_kind(result), Some(ErrorKind::NotFound));
+    let result = import(
+        &gltf(&buffer("locked.bin"), ""),
+        Some("assets"),
+        &mut scheme(),
+    );
+    assert_eq!(io_kind(result), Some(ErrorKind::PermissionDenied));
+
+    // Injected errors.
+    fn import_with<F: gltf::Fetcher>(slice: &[u8], fetcher: F) -> gltf::Result<gltf::Document> {
+        let base = Some(Path::new("assets"));
+        gltf::import_slice_with_progress(slice, base, fetcher, |_| ControlFlow::Continue(()))
+            .map(|(document, _, _)| document)
+    }
+    let slice = gltf(&buffer("data%20file.bin"), &image("image.jpg"));
+    let mut mock = scheme();
+    let result = import_with(&slice, FailNth::new(&mut mock, 1, ErrorKind::TimedOut));
+    assert_eq!(io_kind(result), Some(ErrorKind::TimedOut));
+    assert_eq!(mock.requests()[0].kind, ResourceKind::Buffer);
+    assert_eq!(mock.requests().len(), 1);
+    let result = import_with(&slice, ShortRead::new(scheme(), 4));
+    assert!(matches!(
+        result,
+        Err(Error::BufferLength {
+            buffer: 0,
+            expected: 4,
+            actual: 0,
+        })
+    ));
+    let start = Instant::now();
+    let delay = Duration::from_millis(5);
+    import_with(&slice, Slow::new(scheme(), delay)).unwrap();
+    assert!(start.elapsed() >= 2 * delay);
+
+    // Path based reads for `import_buffers`.
+    let mut mock = scheme();
+    let document = gltf::Gltf::from_slice(&gltf(&buffer("data%20file.bin"), "")).unwrap();
+    let base = Some(Path::new("assets"));
+    let buffers = gltf::import_buffers(&document, None, base, |base, path| mock.read(base, path));
+    assert_eq!(*buffers.unwrap()[0], [1, 2, 3, 4]);
+    let mut failing = FailNth::new(scheme(), 0, ErrorKind::UnexpectedEof);
+    let result = gltf::import_buffers(&document, None, base, |base, path| failing.read(base, path));
+    assert_eq!(io_kind(result), Some(ErrorKind::UnexpectedEof));
+    assert_eq!(failing.count(), 1);
+}
+
+#[test]
+fn test_node_hierarchy_validation() {
+    let errors = |nodes: &str| {
+        let json = format!(
+            r#"{{ "asset": {{ "version": "2.0" }}, "nodes": {} }}"#,
+            nodes
+        );
+        match gltf::Gltf::from_slice(json.as_bytes()) {
+            Ok(_) => Vec::new(),
+            Err(gltf::Error::Validation(errors)) => errors
+                .into_iter()
+                .map(|(path, error)| (path.to_string(), error))
+                .collect(),
+            Err(error) => panic!("unexpected error: {}", error),
+        }
+    };
+    use gltf::json::validation::Error;
+
+    assert!(errors(r#"[{ "children": [1, 2] }, {}, { "children": [3] }, {}]"#).is_empty());
+    assert_eq!(
+        errors(r#"[{ "children": [1] }, { "children": [2] }, { "children": [0] }]"#),
+        vec![("nodes[2].children[0]".to_string(), Error::Cycle)]
+    );
+    assert_eq!(
+        errors(r#"[{ "children": [0] }]"#),
+        vec![("nodes[0].children[0]".to_string(), Error::Cycle)]
+    );
+    assert_eq!(
+        errors(r#"[{ "children": [2] }, { "children": [2] }, {}]"#),
+        vec![("nodes[1].children[0]".to_string(), Error::Invalid)]
+    );
+    assert_eq!(
+        errors(r#"[{ "children": [5] }]"#),
+        vec![("nodes[0].children[0]".to_string(), Error::IndexOutOfBounds)]
+    );
+}
+
+#[test]
+fn test_accessor_without_view_reads_zeros() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "accessors": [{
+            "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [0, 0, 0]
+        }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    let reader = primitive.reader(|_| None);
+    let positions = reader.read_positions().unwrap();
+    assert_eq!(positions.len(), 3);
+    assert!(positions.eq(vec![[0.0f32; 3]; 3]));
+}
+
+#[cfg(all(feature = "KHR_lights_punctual", feature = "KHR_materials_variants"))]
+#[test]
+fn test_lights_and_variants() {
+    use gltf::khr_lights_punctual::Kind;
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_lights_punctual", "KHR_materials_variants"],
+        "extensions": {
+            "KHR_lights_punctual": {
+                "lights": [
+                    { "type": "directional" },
+                    { "type": "spot", "intensity": 2.0, "spot": { "outerConeAngle": 0.5 } }
+                ]
+            },
+            "KHR_materials_variants": { "variants": [{ "name": "red" }, { "name": "blue" }] }
+        },
+        "accessors": [{
+            "componentType": 5126, "count": 1, "type": "VEC3",
+            "min": [0, 0, 0], "max": [0, 0, 0]
+        }],
+        "materials": [{}, {}],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "material": 0,
+                "extensions": {
+                    "KHR_materials_variants": { "mappings": [{ "material": 1, "variants": [1] }] }
+                }
+            }]
+        }],
+        "nodes": [{ "extensions": { "KHR_lights_punctual": { "light": 1 } } }, {}]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+
+    let lights = gltf.lights().unwrap().collect::<Vec<_>>();
+    assert_eq!(lights.len(), 2);
+    assert_eq!(lights[0].kind(), Kind::Directional);
+    let light = gltf.nodes().next().unwrap().light().unwrap();
+    assert_eq!(light.index(), 1);
+    assert_eq!(light.intensity(), 2.0);
+    assert_eq!(
+        light.kind(),
+        Kind::Spot {
+            inner_cone_angle: 0.0,
+            outer_cone_angle: 0.5
+        }
+    );
+    assert!(gltf.nodes().nth(1).unwrap().light().is_none());
+
+    let variants = gltf
+        .variants()
+        .unwrap()
+        .map(|v| v.name())
+        .collect::<Vec<_>>();
+    assert_eq!(variants, ["red", "blue"]);
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    assert_eq!(primitive.mappings().count(), 1);
+    assert_eq!(primitive.variant_material(0).index(), Some(0));
+    assert_eq!(primitive.variant_material(1).index(), Some(1));
+}
+
+#[test]
+fn test_reader_defaults() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 36,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA"
+        }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 0]
+        }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+    }"#;
+    let (document, buffers, _) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+    assert_eq!(reader.read_tex_coords_or_default(0), vec![[0.0; 2]; 3]);
+    assert_eq!(reader.read_colors_or_white(0), vec![[1.0; 4]; 3]);
+    let positions = reader.read_positions().unwrap().collect::<Vec<_>>();
+    assert_eq!(
+        reader.read_normals_or_flat(&positions),
+        vec![[0.0, 0.0, 1.0]; 3]
+    );
+}
+
+#[test]
+fn test_reader_flat_normals_indexed() {
+    // Two triangles folded along a shared edge.
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 60,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAIA/AAABAAIAAAADAAEA"
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 48, "byteLength": 12 }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 1]
+            },
+            { "bufferView": 1, "componentType": 5123, "count": 6, "type": "SCALAR" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }] }]
+    }"#;
+    let (document, buffers, _) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let positions = reader.read_positions().unwrap().collect::<Vec<_>>();
+    let normals = reader.read_normals_or_flat(&positions);
+    assert_eq!(normals.len(), reader.deindex(&positions).len());
+    assert_eq!(
+        normals,
+        [[[0.0, 0.0, 1.0]; 3], [[0.0, 1.0, 0.0]; 3]].concat()
+    );
+}
+
+#[test]
+fn test_attribute_flags() {
+    use gltf::AttributeFlags;
+    let gltf = gltf::Gltf::open("examples/Box.gltf").unwrap();
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    assert_eq!(
+        primitive.attribute_flags(),
+        AttributeFlags::POSITION | AttributeFlags::NORMAL
+    );
+    assert_eq!(
+        AttributeFlags::from_semantic(&gltf::Semantic::TexCoords(1)),
+        Some(AttributeFlags::TEXCOORD_1)
+    );
+    assert_eq!(
+        AttributeFlags::from_semantic(&gltf::Semantic::Colors(2)),
+        None
+    );
+}
+
+#[test]
+fn test_material_reflectance() {
+    let json = br#"{
+        "asset": { "version": "2.0" },
+        "materials": [
+            { "pbrMetallicRoughness": { "baseColorFactor": [0.5, 0.5, 0.5, 1.0], "metallicFactor": 0.0 } },
+            { "pbrMetallicRoughness": { "baseColorFactor": [0.5, 0.25, 0.0, 1.0] } }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json).unwrap();
+    let mut materials = gltf.materials();
+    let dielectric = materials.next().unwrap().reflectance();
+    for component in dielectric.f0.iter() {
+        assert!((component - 0.04).abs() < 1e-6);
+    }
+    assert_eq!(dielectric.f90, 1.0);
+    let metal = materials.next().unwrap().reflectance();
+    assert_eq!(metal.f0, [0.5, 0.25, 0.0]);
+}
+
+#[test]
+fn test_skin_joint_matrices() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 64,
+            "uri": "data:application/octet-stream;base64,AACAPwAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAAAAAAAAAAAAgD8AAAAAAACAvwAAAMAAAEDAAACAPw=="
+        }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 64 }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 1, "type": "MAT4" }],
+        "nodes": [
+            { "translation": [1, 2, 3], "children": [1] },
+            { "translation": [0, 1, 0] }
+        ],
+        "skins": [
+            { "joints": [0], "inverseBindMatrices": 0 },
+            { "joints": [0, 1] }
+        ],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    let (document, buffers, _) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let scene = document.scenes().next().unwrap();
+    let get_buffer_data = |buffer: gltf::Buffer| Some(&*buffers[buffer.index()]);
+    let mut skins = document.skins();
+
+    let identity = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+    let bound = skins.next().unwrap();
+    assert_eq!(
+        bound.joint_matrices(&scene, get_buffer_data),
+        Some(vec![identity])
+    );
+
+    let unbound = skins.next().unwrap();
+    let matrices = unbound.joint_matrices(&scene, get_buffer_data).unwrap();
+    assert_eq!(matrices[0][3], [1.0, 2.0, 3.0, 1.0]);
+    assert_eq!(matrices[1][3], [1.0, 3.0, 3.0, 1.0]);
+}
+
+#[cfg(feature = "KHR_lights_punctual")]
+#[test]
+fn test_light_units() {
+    use gltf::khr_lights_punctual::{candela_to_watts, watts_to_candela};
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_lights_punctual"],
+        "extensions": {
+            "KHR_lights_punctual": {
+                "lights": [
+                    { "type": "directional", "intensity": 683.0 },
+                    { "type": "point", "intensity": 100.0, "range": 5.0 }
+                ]
+            }
+        }
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let lights = gltf.lights().unwrap().collect::<Vec<_>>();
+    assert_eq!(lights[0].watts(), 1.0);
+    assert_eq!(lights[0].attenuation(100.0), 1.0);
+    assert!((watts_to_candela(lights[1].watts()) - 100.0).abs() < 1e-3);
+    assert!((candela_to_watts(100.0) - lights[1].watts()).abs() < 1e-6);
+    assert_eq!(lights[1].attenuation(5.0), 0.0);
+    assert_eq!(lights[1].cutoff_distance(1.0), 5.0);
+    assert_eq!(lights[1].cutoff_distance(25.0), 2.0);
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+#[test]
+fn test_transformed_tex_coords() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_texture_transform"],
+        "buffers": [{ "byteLength": 8, "uri": "data:application/octet-stream;base64,AACAPwAAAAA=" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 8 }],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC2" },
+            { "componentType": 5126, "count": 1, "type": "VEC3", "min": [0, 0, 0], "max": [0, 0, 0] }
+        ],
+        "images": [{ "uri": "data:image/png;base64," }],
+        "textures": [{ "source": 0 }],
+        "materials": [{
+            "pbrMetallicRoughness": {
+                "baseColorTexture": {
+                    "index": 0,
+                    "extensions": {
+                        "KHR_texture_transform": {
+                            "offset": [0.5, 0.0], "rotation": 1.5707964, "scale": [2.0, 1.0]
+                        }
+                    }
+                }
+            }
+        }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 1, "TEXCOORD_0": 0 }, "material": 0 }] }]
+    }"#;
+    let gltf = gltf::Gltf::from_slice(json.as_bytes()).unwrap();
+    let buffers = gltf::import_buffers(&gltf, None, None, gltf::empty_fetcher).unwrap();
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    let transform = primitive
+        .material()
+        .pbr_metallic_roughness()
+        .base_color_texture()
+        .unwrap()
+        .texture_transform()
+        .unwrap();
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let uvs = reader
+        .read_tex_coords_transformed(0, &transform)
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(uvs.len(), 1);
+    assert!((uvs[0][0] - 0.5).abs() < 1e-6);
+    assert!((uvs[0][1] + 2.0).abs() < 1e-6);
+    assert_eq!(transform.apply([0.0, 0.0]), [0.5, 0.0]);
+}
+
+#[cfg(feature = "EXT_lights_image_based")]
+#[test]
+fn test_image_based_light() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["EXT_lights_image_based"],
+        "extensions": {
+            "EXT_lights_image_based": {
+                "lights": [{
+                    "intensity": 2.0,
+                    "irradianceCoefficients": [
+                        [1, 1, 1], [0, 0, 0], [0, 0, 0], [0, 0, 0], [0, 0, 0],
+                        [0, 0, 0], [0, 0, 0], [0, 0, 0], [0, 0, 0]
+                    ],
+                    "specularImageSize": 1,
+                    "specularImages": [[0, 0, 0, 0, 0, 0]]
+                }]
+            }
+        },
+        "images": [{ "uri": "data:image/webp;base64,UklGRhoAAABXRUJQVlA4TA0AAAAvAAAAEAcQERGIiP4HAA==" }],
+        "scenes": [{ "nodes": [], "extensions": { "EXT_lights_image_based": { "light": 0 } } }]
+    }"#;
+    let (document, _, images) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let light = document
+        .scenes()
+        .next()
+        .unwrap()
+        .image_based_light()
+        .unwrap();
+    assert_eq!(light.intensity(), 2.0);
+    assert_eq!(light.rotation(), [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(light.irradiance_coefficients()[0], [1.0; 3]);
+    let levels = light.specular_images().collect::<Vec<_>>();
+    assert_eq!(levels.len(), 1);
+    assert!(levels[0].iter().all(|face| face.index() == 0));
+    let data = light.specular_data(&images).unwrap();
+    assert_eq!(data[0][5].width, 1);
+}
+
+#[test]
+fn test_unsupported_version() {
+    let legacy = br#"{
+        "asset": { "version": "1.0" },
+        "meshes": {},
+        "nodes": {}
+    }"#;
+    match gltf::Gltf::from_slice(legacy) {
+        Err(gltf::Error::UnsupportedVersion { found }) => assert_eq!(found.as_deref(), Some("1.0")),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    let future = br#"{ "asset": { "version": "3.0" } }"#;
+    assert!(matches!(
+        gltf::Gltf::from_slice(future),
+        Err(gltf::Error::UnsupportedVersion { .. })
+    ));
+
+    // Readers are inspected from their current position.
+    let mut reader = io::Cursor::new([&b"prefix"[..], legacy].concat());
+    reader.set_position(6);
+    match gltf::Gltf::from_reader(reader) {
+        Err(gltf::Error::UnsupportedVersion { found }) => assert_eq!(found.as_deref(), Some("1.0")),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    let reader = io::Cursor::new(fs::read("examples/Box.gltf").unwrap());
+    assert!(gltf::Gltf::from_reader(reader).is_ok());
+}
+
+#[cfg(feature = "voxelize")]
+#[test]
+fn test_voxelize() {
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let scene = document.default_scene().unwrap();
+    let mut grid = scene
+        .voxelize(|buffer| buffers.get(buffer.index()).map(|x| &*x.0), 8)
+        .unwrap();
+    assert_eq!(grid.dimensions(), [8, 8, 8]);
+    assert_eq!(grid.voxel_size(), 0.125);
+    assert_eq!(grid.occupied_count(), 8 * 8 * 8 - 6 * 6 * 6);
+    assert!(grid.is_occupied([0, 3, 4]));
+    assert!(!grid.is_occupied([3, 3, 4]));
+
+    let field = grid.signed_distance_field();
+    let at = |[x, y, z]: [usize; 3]| field[x + 8 * (y + 8 * z)];
+    assert_eq!(at([0, 0, 0]), 0.0);
+    assert_eq!(at([1, 3, 4]), -0.125);
+    assert_eq!(at([3, 3, 3]), -0.375);
+
+    grid.fill_interior();
+    assert_eq!(grid.occupied_count(), 8 * 8 * 8);
+}
+
+#[cfg(feature = "upgrade")]
+#[test]
+fn test_upgrade() {
+    let legacy = br#"{
+        "asset": { "version": "1.0" },
+        "buffers": {
+            "data": { "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAA", "byteLength": 12 }
+        },
+        "bufferViews": {
+            "positions": { "buffer": "data", "byteLength": 12, "target": 34962 }
+        },
+        "accessors": {
+            "position": {
+                "bufferView": "positions", "byteStride": 12, "componentType": 5126,
+                "count": 1, "type": "VEC3", "min": [0, 0, 0], "max": [0, 0, 0]
+            }
+        },
+        "materials": {
+            "red": {
+                "extensions": {
+                    "KHR_materials_common": {
+                        "technique": "BLINN",
+                        "values": { "diffuse": [1, 0, 0, 1], "shininess": 0 }
+                    }
+                }
+            }
+        },
+        "meshes": {
+            "point": {
+                "primitives": [{ "attributes": { "POSITION": "position" }, "material": "red", "mode": 0 }]
+            }
+        },
+        "nodes": {
+            "root": { "meshes": ["point", "point"] }
+        },
+        "scenes": { "main": { "nodes": ["root"] } },
+        "scene": "main"
+    }"#;
+    let gltf = gltf::upgrade::from_slice(legacy).unwrap();
+    let node = gltf.default_scene().unwrap().nodes().next().unwrap();
+    assert_eq!(node.name(), Some("root"));
+    let mesh = node.mesh().unwrap();
+    assert_eq!(mesh.primitives().len(), 2);
+    let primitive = mesh.primitives().next().unwrap();
+    let material = primitive.material();
+    assert_eq!(material.name(), Some("red"));
+    let pbr = material.pbr_metallic_roughness();
+    assert_eq!(pbr.base_color_factor(), [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(pbr.roughness_factor(), 1.0);
+    let position = primitive.get(&gltf::Semantic::Positions).unwrap();
+    assert_eq!(position.view().unwrap().stride(), Some(12));
+
+    let broken = br#"{
+        "asset": { "version": "1.0" },
+        "scenes": { "main": { "nodes": ["missing"] } }
+    }"#;
+    assert!(matches!(
+        gltf::upgrade::from_slice(broken),
+        Err(gltf::Error::Validation(_))
+    ));
+}
+
+#[test]
+fn test_generate_averaged_tangents() {
+    // One triangle in the XY plane with the U axis mirrored.
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 60,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAIC/AAAAAAAAAAAAAIA/"
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 24 }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 0]
+            },
+            { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC2" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "TEXCOORD_0": 1 } }] }]
+    }"#;
+    let (document, buffers, _) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let tangents = primitive
+        .generate_averaged_tangents(|buffer| Some(&buffers[buffer.index()]))
+        .unwrap();
+    assert_eq!(tangents, vec![[-1.0, 0.0, 0.0, -1.0]; 3]);
+
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let positions = reader.read_positions().unwrap().collect::<Vec<_>>();
+    let normals = vec![[0.0, 0.0, 1.0]; 3];
+    let tex_coords = reader.read_tex_coords_or_default(0);
+    let generated = reader.read_tangents_or_averaged(&positions, &normals, &tex_coords);
+    assert_eq!(generated, tangents);
+
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    assert!(primitive
+        .generate_averaged_tangents(|buffer| Some(&buffers[buffer.index()]))
+        .is_none());
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_scene_background() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "data:image/webp;base64,UklGRhoAAABXRUJQVlA4TA0AAAAvAAAAEAcQERGIiP4HAA==" }],
+        "textures": [{ "source": 0 }],
+        "scenes": [{
+            "nodes": [],
+            "extras": { "author": "test", "background": { "color": [0.5, 0.5, 0.5], "skybox": 0 } }
+        }]
+    }"#;
+    let (document, _, images) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let background = document.scenes().next().unwrap().background().unwrap();
+    assert_eq!(background.color(), Some([0.5; 3]));
+    assert_eq!(background.intensity(), 1.0);
+    assert_eq!(background.skybox().unwrap().index(), 0);
+    assert_eq!(background.skybox_data(&images).unwrap().width, 1);
+
+    let mut scene = document.into_json().scenes.remove(0);
+    let mut json = scene.background().unwrap();
+    json.skybox = None;
+    scene.set_background(Some(json));
+    assert!(scene.background().unwrap().skybox.is_none());
+    scene.set_background(None);
+    assert!(scene.background().is_none());
+    assert_eq!(scene.extras.unwrap().get(), r#"{"author":"test"}"#);
+}
+
+#[cfg(feature = "schema-validate")]
+#[test]
+fn test_schema_validation() {
+    for path in [
+        "examples/Box.gltf",
+        "examples/Box.glb",
+        "examples/Lantern.gltf",
+        "tests/minimal_accessor_min_max.gltf",
+    ] {
+        let slice = std::fs::read(path).unwrap();
+        if let Err(error) = gltf::Gltf::from_slice_with_schema_validation(&slice) {
+            panic!("{}: {}", path, error);
+        }
+    }
+
+    // The sparse accessor has a `byteOffset` without a `bufferView`.
+    let slice = std::fs::read("tests/box_sparse.gltf").unwrap();
+    assert!(matches!(
+        gltf::Gltf::from_slice_with_schema_validation(&slice),
+        Err(gltf::Error::Schema(_))
+    ));
+
+    // Semantically valid, but the roughness factor is out of range.
+    let json = br#"{
+        "asset": { "version": "2.0" },
+        "materials": [{ "pbrMetallicRoughness": { "roughnessFactor": 2.0 } }]
+    }"#;
+    assert!(gltf::Gltf::from_slice(json).is_ok());
+    match gltf::Gltf::from_slice_with_schema_validation(json) {
+        Err(gltf::Error::Schema(violations)) => {
+            assert_eq!(violations.len(), 1);
+            assert_eq!(
+                violations[0].path.as_str(),
+                "materials[0].pbrMetallicRoughness.roughnessFactor"
+            );
+            assert_eq!(violations[0].keyword, "maximum");
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_triangulate_and_deindex() {
+    let (document, buffers, _) =
+        gltf::import("examples/Box.gltf", gltf::filesystem_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let indices = reader.read_indices_u32();
+    assert_eq!(indices.len(), 36);
+    assert_eq!(reader.triangulate().unwrap(), indices);
+    let positions = reader.read_positions().unwrap().collect::<Vec<_>>();
+    let flat = reader.deindex(&positions);
+    assert_eq!(flat.len(), 36);
+    assert_eq!(flat[35], positions[indices[35] as usize]);
+
+    // Four vertices forming a quad as a non-indexed triangle strip.
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 48,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAACAPwAAgD8AAAAA"
+        }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 48 }],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 0]
+        }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 }, "mode": 5 }] }]
+    }"#;
+    let (document, buffers, _) = gltf::import_slice(json, None, gltf::empty_fetcher).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    assert_eq!(reader.read_indices_u32(), vec![0, 1, 2, 3]);
+    assert_eq!(reader.triangulate().unwrap(), vec![0, 1, 2, 2, 1, 3]);
+    assert_eq!(
+        reader.deindex(&['a', 'b', 'c', 'd']),
+        vec!['a', 'b', 'c', 'c', 'b', 'd']
+    );
+}
+
+#[cfg(feature = "import")]
+#[test]
+fn test_import_embedded() {
+    let bytes = include_bytes!("box_sparse.glb");
+    let (document, buffers) = gltf::import_embedded(bytes).unwrap();
+    let (_, expected, _) = gltf::import_slice(bytes, None, gltf::empty_fetcher).unwrap();
+    assert_eq!(buffers.len(), document.buffers().len());
+    for (buffer, expected) in buffers.iter().zip(&expected) {
+        assert_eq!(&buffer[..], &expected[..]);
+    }
+    let external = std::fs::read("tests/box_sparse.gltf").unwrap();
+    assert!(matches!(
+        gltf::import_embedded(external),
+        Err(gltf::Error::ExternalReferenceInSliceImport)
+    ));
+}
+
+#[cfg(feature = "KHR_materials_iridescence")]
+#[test]
+fn test_materials_iridescence() {
+    let gltf = gltf::Gltf::open("tests/iridescence.gltf").unwrap();
+    let materials: Vec<_> = gltf.materials().collect();
+
+    let defaults = materials[0].iridescence().unwrap();
+    assert_eq!(defaults.iridescence_factor(), 0.0);
+    assert_eq!(defaults.iridescence_ior(), 1.3);
+    assert_eq!(defaults.iridescence_thickness_minimum(), 100.0);
+    assert_eq!(defaults.iridescence_thickness_maximum(), 400.0);
+    assert!(defaults.iridescence_texture().is_none());
+    assert!(defaults.iridescence_thickness_texture().is_none());
+
+    let bubble = materials[1].iridescence().unwrap();
+    assert_eq!(bubble.iridescence_factor(), 0.8);
+    assert_eq!(bubble.iridescence_ior(), 1.5);
+    assert_eq!(bubble.iridescence_thickness_minimum(), 200.0);
+    assert_eq!(bubble.iridescence_thickness_maximum(), 800.0);
+    let texture = bubble.iridescence_texture().unwrap();
+    assert_eq!((texture.texture().index(), texture.tex_coord()), (0, 0));
+    let thickness = bubble.iridescence_thickness_texture().unwrap();
+    assert_eq!((thickness.texture().index(), thickness.tex_coord()), (1, 1));
+
+    assert!(materials[2].iridescence().is_none());
+}
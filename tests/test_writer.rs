@@ -0,0 +1,893 @@
+use gltf::accessor::{Iter, Writer};
+use gltf::json::buffer::Target;
+
+#[test]
+fn test_accessor_writer_roundtrip() {
+    let positions = [[0.0f32, 0.5, 0.0], [-0.5, -0.5, 0.0], [0.5, -0.5, 1.0]];
+    let colors = [[255u8, 0, 0], [0, 255, 0], [0, 0, 255]];
+    let indices = [0u16, 1, 2];
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    let positions_index = writer.push(&positions, Some(Target::ArrayBuffer)).unwrap();
+    let colors_index = writer
+        .push_normalized(&colors, Some(Target::ArrayBuffer))
+        .unwrap();
+    let indices_index = writer
+        .push(&indices, Some(Target::ElementArrayBuffer))
+        .unwrap();
+    let bin = writer.finish();
+    assert_eq!(bin.len() % 4, 0);
+
+    // Three byte colors must be padded to a four byte stride.
+    assert_eq!(root.buffer_views[1].byte_stride, Some(4));
+    assert_eq!(root.buffer_views[1].byte_offset, Some(36));
+    assert_eq!(
+        root.accessors[positions_index.value()].max,
+        Some(gltf::json::Value::from(vec![0.5, 0.5, 1.0]))
+    );
+
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+    let accessor = |index: usize| document.accessors().nth(index).unwrap();
+
+    let read: Vec<[f32; 3]> = Iter::new(accessor(positions_index.value()), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(read, positions);
+
+    let colors_accessor = accessor(colors_index.value());
+    assert!(colors_accessor.normalized());
+    let read: Vec<[u8; 3]> = Iter::new(colors_accessor, get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(read, colors);
+
+    let read: Vec<u16> = Iter::new(accessor(indices_index.value()), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(read, indices);
+}
+
+#[test]
+fn test_accessor_writer_empty() {
+    use gltf::accessor::packed::Format;
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    assert_eq!(
+        writer.push::<[f32; 3]>(&[], Some(Target::ArrayBuffer)),
+        None
+    );
+    assert_eq!(writer.push_normalized::<[u8; 4]>(&[], None), None);
+    let empty: [[f32; 3]; 0] = [];
+    assert_eq!(writer.push_packed(&empty, Format::Snorm8, None), None);
+    assert!(writer.push(&[1u16], None).is_some());
+    assert_eq!(writer.finish().len(), 4);
+    assert_eq!(root.accessors.len(), 1);
+    assert_eq!(root.buffer_views.len(), 1);
+    assert!(root.extensions_required.is_empty());
+    assert!(gltf::Document::from_json(root).is_ok());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_accessor_writer_par_push() {
+    let positions: Vec<[f32; 3]> = (0..10_000)
+        .map(|i| [i as f32, -(i as f32), (i % 7) as f32])
+        .collect();
+    let colors: Vec<[u8; 3]> = (0..10_000).map(|i| [i as u8, (i / 3) as u8, 7]).collect();
+
+    let mut sequential = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut sequential);
+    writer.push(&positions, Some(Target::ArrayBuffer));
+    writer.push_normalized(&colors, Some(Target::ArrayBuffer));
+    let sequential_bin = writer.finish();
+
+    let mut parallel = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut parallel);
+    writer.par_push(&positions, Some(Target::ArrayBuffer));
+    writer.par_push_normalized(&colors, Some(Target::ArrayBuffer));
+    let parallel_bin = writer.finish();
+
+    assert_eq!(parallel_bin, sequential_bin);
+    assert_eq!(
+        gltf::json::serialize::to_string(&parallel).unwrap(),
+        gltf::json::serialize::to_string(&sequential).unwrap()
+    );
+}
+
+#[test]
+fn test_export_hooks() {
+    use gltf::export::{BufferLayout, Exporter};
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    writer.push(&[1.0f32, 2.0, 3.0], None);
+    let bin = writer.finish();
+
+    let mut calls = Vec::new();
+    let glb = Exporter::new()
+        .with_hook(|root: &mut gltf::json::Root, layout: &mut BufferLayout| {
+            calls.push("first");
+            let view = layout.push_view(root, b"payload").unwrap();
+            assert_eq!(view.value(), 1);
+            root.extensions_used.push("EXT_example".to_owned());
+        })
+        .with_hook(|root: &mut gltf::json::Root, _: &mut BufferLayout| {
+            assert_eq!(root.buffer_views.len(), 2);
+            root.asset.generator = Some("test".to_owned());
+        })
+        .to_glb(root, bin)
+        .unwrap();
+    assert_eq!(calls, ["first"]);
+
+    let (document, buffers, _) = gltf::import_slice(&glb, None, gltf::empty_fetcher).unwrap();
+    assert_eq!(
+        document.extensions_used().collect::<Vec<_>>(),
+        ["EXT_example"]
+    );
+    let view = document.views().nth(1).unwrap();
+    let data = &buffers[0][view.offset()..view.offset() + view.length()];
+    assert_eq!(data, b"payload");
+    assert_eq!(
+        document.into_json().asset.generator.as_deref(),
+        Some("test")
+    );
+
+    let mut standard =
+        Exporter::new().with_hook(|_: &mut gltf::json::Root, layout: &mut BufferLayout| {
+            assert!(layout.bin.is_none());
+        });
+    let json = standard.to_gltf(gltf::json::Root::default()).unwrap();
+    assert!(gltf::Gltf::from_slice(&json).is_ok());
+}
+
+#[test]
+fn test_export_alignment() {
+    use gltf::export::Exporter;
+
+    let positions = [[0.0f32, 0.5, 0.0], [-0.5, -0.5, 0.0], [0.5, -0.5, 1.0]];
+    let indices = [0u16, 1, 2];
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    let indices_index = writer
+        .push(&indices, Some(Target::ElementArrayBuffer))
+        .unwrap();
+    let positions_index = writer.push(&positions, Some(Target::ArrayBuffer)).unwrap();
+    let bin = writer.finish();
+
+    let glb = Exporter::new()
+        .with_alignment(64)
+        .to_glb(root, bin)
+        .unwrap();
+
+    // The BIN chunk data follows the file header, the JSON chunk, and the
+    // BIN chunk header.
+    let json_length = u32::from_le_bytes([glb[12], glb[13], glb[14], glb[15]]) as usize;
+    let bin_start = 12 + 8 + json_length + 8;
+    assert_eq!(bin_start % 64, 0);
+    assert_eq!(&glb[bin_start - 4..bin_start], b"BIN\0");
+
+    let (document, buffers, _) = gltf::import_slice(&glb, None, gltf::empty_fetcher).unwrap();
+    let views: Vec<_> = document.views().collect();
+    assert_eq!(views[1].offset(), 0);
+    assert_eq!(views[0].offset(), 64);
+
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(|x| &*x.0);
+    let accessor = |index: usize| document.accessors().nth(index).unwrap();
+    let read: Vec<[f32; 3]> = Iter::new(accessor(positions_index.value()), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(read, positions);
+    let read: Vec<u16> = Iter::new(accessor(indices_index.value()), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(read, indices);
+}
+
+#[test]
+fn test_compact_vertex_ranges() {
+    // Two primitives referencing small windows of a shared vertex pool.
+    let positions: Vec<[f32; 3]> = (0..100).map(|i| [i as f32, 0.0, 1.0]).collect();
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    writer.push(&positions, Some(Target::ArrayBuffer));
+    writer.push(&[10u16, 11, 12], Some(Target::ElementArrayBuffer));
+    writer.push(&[92u16, 90, 91, 92], Some(Target::ElementArrayBuffer));
+    let bin = writer.finish();
+    root.meshes = gltf::json::deserialize::from_str(
+        r#"[{ "primitives": [
+            { "attributes": { "POSITION": 0 }, "indices": 1 },
+            { "attributes": { "POSITION": 0 }, "indices": 2, "mode": 1 }
+        ] }]"#,
+    )
+    .unwrap();
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .nth(1)
+        .unwrap();
+    let remap = primitive.vertex_remap(get_buffer_data).unwrap();
+    assert_eq!(remap.vertices, [90, 91, 92]);
+    assert_eq!(remap.indices, [2, 0, 1, 2]);
+
+    let (root, compacted) = gltf::reindex::compact(&document, get_buffer_data).unwrap();
+    assert!(compacted.len() < bin.len());
+    assert_eq!(root.buffers.len(), 1);
+    assert_eq!(root.buffer_views.len(), 4);
+    assert_eq!(root.accessors.len(), 4);
+
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(compacted.as_slice());
+    let read = |index: usize| {
+        let primitive = document
+            .meshes()
+            .next()
+            .unwrap()
+            .primitives()
+            .nth(index)
+            .unwrap();
+        let reader = primitive.reader(get_buffer_data);
+        let positions: Vec<[f32; 3]> = reader.read_positions().unwrap().collect();
+        let indices: Vec<u32> = reader.read_indices().unwrap().into_u32().collect();
+        let max = primitive.get(&gltf::Semantic::Positions).unwrap().max();
+        (positions, indices, max)
+    };
+    let (positions, indices, max) = read(0);
+    assert_eq!(
+        positions,
+        [[10.0, 0.0, 1.0], [11.0, 0.0, 1.0], [12.0, 0.0, 1.0]]
+    );
+    assert_eq!(indices, [0, 1, 2]);
+    assert_eq!(max, Some(gltf::json::Value::from(vec![12.0, 0.0, 1.0])));
+    let (positions, indices, _) = read(1);
+    assert_eq!(
+        positions,
+        [[90.0, 0.0, 1.0], [91.0, 0.0, 1.0], [92.0, 0.0, 1.0]]
+    );
+    assert_eq!(indices, [2, 0, 1, 2]);
+}
+
+#[test]
+fn test_split_and_merge_views() {
+    use gltf::layout::Implication;
+
+    let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    writer.push(&positions, Some(Target::ArrayBuffer));
+    writer.push(&[0u16, 1, 2], Some(Target::ElementArrayBuffer));
+    writer.push(&[2u16, 1, 0], Some(Target::ElementArrayBuffer));
+    let bin = writer.finish();
+    root.meshes = gltf::json::deserialize::from_str(
+        r#"[{ "primitives": [
+            { "attributes": { "POSITION": 0 }, "indices": 1 },
+            { "attributes": { "POSITION": 0 }, "indices": 2 }
+        ] }]"#,
+    )
+    .unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+    let read = |root: gltf::json::Root, index: usize| {
+        let document = gltf::Document::from_json(root).unwrap();
+        let primitive = document
+            .meshes()
+            .next()
+            .unwrap()
+            .primitives()
+            .nth(index)
+            .unwrap();
+        let reader = primitive.reader(get_buffer_data);
+        let positions: Vec<[f32; 3]> = reader.read_positions().unwrap().collect();
+        let indices: Vec<u32> = reader.read_indices().unwrap().into_u32().collect();
+        (positions, indices)
+    };
+
+    // The two index views are adjacent and can share one GPU buffer.
+    let reports = gltf::Document::from_json(root.clone())
+        .unwrap()
+        .view_layout();
+    assert!(reports[0].implications.is_empty());
+    assert_eq!(reports[1].implications, [Implication::Fragmented]);
+    assert_eq!(reports[2].implications, [Implication::Fragmented]);
+    let mut merged = root.clone();
+    assert_eq!(gltf::layout::merge_views(&mut merged), 1);
+    assert_eq!(merged.buffer_views.len(), 2);
+    assert_eq!(read(merged.clone(), 0), (positions.to_vec(), vec![0, 1, 2]));
+    assert_eq!(read(merged, 1), (positions.to_vec(), vec![2, 1, 0]));
+
+    // Move the first indices into the vertex view.
+    let mut shared = root;
+    shared.accessors[1].buffer_view = Some(gltf::json::Index::new(0));
+    shared.accessors[1].byte_offset = Some(36);
+    shared.buffer_views[0].byte_length = 42;
+    shared.buffer_views[0].target = None;
+    let reports = gltf::Document::from_json(shared.clone())
+        .unwrap()
+        .view_layout();
+    assert_eq!(reports[0].vertex_accessors, [0]);
+    assert_eq!(reports[0].index_accessors, [1]);
+    assert_eq!(reports[0].implications, [Implication::MixedTargets]);
+    assert_eq!(gltf::layout::split_shared_views(&mut shared), 1);
+    let reports = gltf::Document::from_json(shared.clone())
+        .unwrap()
+        .view_layout();
+    assert!(reports[0].implications.is_empty());
+    assert_eq!(reports[3].index_accessors, [1]);
+    assert_eq!(read(shared, 0), (positions.to_vec(), vec![0, 1, 2]));
+}
+
+#[test]
+fn test_export_viewer_workarounds() {
+    use gltf::export::{Exporter, Viewer};
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    writer.push(
+        &[[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        Some(Target::ArrayBuffer),
+    );
+    writer.push(&[0u8, 1, 2], Some(Target::ElementArrayBuffer));
+    writer.push(&[[0.5f32, 0.5, 0.5, 0.5]; 3], Some(Target::ArrayBuffer));
+    writer.push(&[[0.0f32, 0.0]; 3], Some(Target::ArrayBuffer));
+    let bin = writer.finish();
+    root.meshes = gltf::json::deserialize::from_str(
+        r#"[{ "primitives": [{
+            "attributes": { "POSITION": 0, "WEIGHTS_0": 2, "TEXCOORD_0": 3, "TEXCOORD_4": 3 },
+            "indices": 1
+        }] }]"#,
+    )
+    .unwrap();
+
+    let glb = Exporter::new()
+        .with_viewer(Viewer::ThreeJs)
+        .to_glb(root, bin)
+        .unwrap();
+    let gltf = gltf::Gltf::from_slice(&glb).unwrap();
+    let blob = gltf.blob.as_deref();
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    assert!(primitive.get(&gltf::Semantic::TexCoords(0)).is_some());
+    assert!(primitive.get(&gltf::Semantic::TexCoords(4)).is_none());
+    let reader = primitive.reader(|_| blob);
+    match reader.read_indices().unwrap() {
+        gltf::mesh::util::ReadIndices::U16(indices) => {
+            assert_eq!(indices.collect::<Vec<_>>(), [0, 1, 2])
+        }
+        _ => panic!("indices were not widened"),
+    }
+    let weights: Vec<[f32; 4]> = reader.read_weights(0).unwrap().into_f32().collect();
+    assert_eq!(weights, [[0.25; 4]; 3]);
+}
+
+#[test]
+fn test_accessor_writer_packed_formats() {
+    use gltf::accessor::packed::{self, Format};
+
+    assert_eq!(packed::f32_to_f16(1.0), 0x3c00);
+    assert_eq!(packed::f32_to_f16(-2.0), 0xc000);
+    assert_eq!(packed::f32_to_f16(65504.0), 0x7bff);
+    assert_eq!(packed::f32_to_f16(1.0e6), 0x7c00);
+    assert_eq!(packed::f32_to_f16(1.0e-9), 0);
+    // The smallest subnormal half float, and a tie rounded to even.
+    assert_eq!(packed::f32_to_f16(5.9604645e-8), 1);
+    assert_eq!(packed::f16_to_f32(1), 5.9604645e-8);
+    assert_eq!(packed::f32_to_f16(1234.5), packed::f32_to_f16(1234.0));
+    for value in [0.1f32, -0.333, 6.1035156e-5, 1234.5] {
+        let half = packed::f16_to_f32(packed::f32_to_f16(value));
+        assert!((half - value).abs() <= value.abs() / 1024.0);
+    }
+    let tangent = packed::unpack_snorm10(packed::pack_snorm10([0.6, -0.8, 0.0, -1.0]));
+    assert!((tangent[0] - 0.6).abs() < 1e-2 && (tangent[1] + 0.8).abs() < 1e-2);
+    assert_eq!([tangent[2], tangent[3]], [0.0, -1.0]);
+
+    let normals = [[0.0f32, 0.0, 1.0], [0.0, -1.0, 0.0], [0.6, 0.8, 0.0]];
+    let uvs = [[0.25f32, 0.5], [1.0, 0.0], [0.0, 1.0]];
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    let quantized = writer
+        .push_packed(&normals, Format::Snorm8, Some(Target::ArrayBuffer))
+        .unwrap();
+    let compact = writer
+        .push_packed(&normals, Format::Snorm10_10_10_2, Some(Target::ArrayBuffer))
+        .unwrap();
+    let half = writer
+        .push_packed(&uvs, Format::Float16, Some(Target::ArrayBuffer))
+        .unwrap();
+    let bin = writer.finish();
+    assert_eq!(bin.len(), 3 * 4 + 3 * 4 + 3 * 4);
+    assert_eq!(root.extensions_required, ["KHR_mesh_quantization"]);
+    assert_eq!(
+        root.accessors[quantized.value()].max,
+        Some(gltf::json::Value::from(vec![76, 102, 127]))
+    );
+
+    let document = gltf::Document::from_json_without_validation(root);
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+    let accessor = |index: gltf::json::Index<gltf::json::Accessor>| {
+        document.accessors().nth(index.value()).unwrap()
+    };
+    let snorm: Vec<[i8; 3]> = Iter::new(accessor(quantized), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(snorm, [[0, 0, 127], [0, -127, 0], [76, 102, 0]]);
+    let packed_normals: Vec<[f32; 4]> = Iter::<[u8; 4]>::new(accessor(compact), get_buffer_data)
+        .unwrap()
+        .map(|bytes| packed::unpack_snorm10(u32::from_le_bytes(bytes)))
+        .collect();
+    for (normal, packed) in normals.iter().zip(&packed_normals) {
+        assert!((0..3).all(|i| (normal[i] - packed[i]).abs() < 2e-3));
+    }
+    let halves: Vec<[u16; 2]> = Iter::new(accessor(half), get_buffer_data)
+        .unwrap()
+        .collect();
+    assert_eq!(halves[0].map(packed::f16_to_f32), uvs[0]);
+    #[cfg(feature = "extras")]
+    {
+        assert_eq!(Format::of(&accessor(half)), Some(Format::Float16));
+        assert_eq!(Format::of(&accessor(quantized)), None);
+    }
+}
+
+#[test]
+fn test_platform_variants() {
+    use gltf::export::{BufferLayout, Exporter};
+    use gltf::streaming::TextureUsage;
+    use gltf::variants::{Analysis, Pipeline, Variant};
+
+    let bin = b"not a png".to_vec();
+    let root: gltf::json::Root = gltf::json::deserialize::from_str(
+        r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 9 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 9 }],
+            "images": [{ "bufferView": 0, "mimeType": "image/png" }],
+            "textures": [{ "source": 0 }],
+            "materials": [{ "normalTexture": { "index": 0 } }]
+        }"#,
+    )
+    .unwrap();
+
+    let webp = |root: &mut gltf::json::Root, layout: &mut BufferLayout, analysis: &Analysis| {
+        assert_eq!(analysis.image_usages, [vec![TextureUsage::Normal]]);
+        let view = layout.push_view(root, b"not a webp").unwrap();
+        root.images[0].buffer_view = Some(view);
+        root.images[0].mime_type = Some(gltf::json::image::MimeType("image/webp".to_owned()));
+        root.extensions_used.push("EXT_texture_webp".to_owned());
+    };
+    let mut pipeline = Pipeline::new()
+        .with_variant(Variant::new("desktop"))
+        .with_variant(Variant::new("web").with_hook(webp))
+        .with_variant(Variant::new("mobile").with_exporter(Exporter::new().with_alignment(16)));
+    let outputs = pipeline.run(&root, &bin).unwrap();
+    let names: Vec<&str> = outputs.iter().map(|output| output.name.as_str()).collect();
+    assert_eq!(names, ["desktop", "web", "mobile"]);
+
+    let web = gltf::Gltf::from_slice(&outputs[1].glb).unwrap();
+    match web.images().next().unwrap().source() {
+        gltf::image::Source::View { view, mime_type } => {
+            assert_eq!(mime_type, "image/webp");
+            let blob = web.blob.as_deref().unwrap();
+            assert_eq!(&blob[view.offset()..][..view.length()], b"not a webp");
+        }
+        _ => panic!("image is not stored in a buffer view"),
+    }
+    // The source is not affected by the hooks of other variants.
+    let desktop = gltf::Gltf::from_slice(&outputs[0].glb).unwrap();
+    assert_eq!(desktop.images().count(), 1);
+    assert_eq!(desktop.views().count(), 1);
+
+    let manifest = gltf::variants::manifest(&outputs, |output| format!("{}.glb", output.name));
+    assert_eq!(manifest["variants"][1]["uri"], "web.glb");
+    assert_eq!(
+        manifest["variants"][1]["extensionsUsed"][0],
+        "EXT_texture_webp"
+    );
+    assert_eq!(manifest["variants"][2]["byteLength"], outputs[2].glb.len());
+}
+
+#[test]
+fn test_quantized_variant() {
+    use gltf::quantize::Options;
+    use gltf::variants::{Pipeline, Quantize, Variant};
+
+    let gltf = gltf::Gltf::open("examples/Box.glb").unwrap();
+    let bin = gltf.blob.clone().unwrap();
+    let root = gltf.document.into_json();
+    let mut pipeline = Pipeline::new()
+        .with_variant(Variant::new("desktop"))
+        .with_variant(Variant::new("mobile").with_hook(Quantize::new(Options::new())));
+    let outputs = pipeline.run(&root, &bin).unwrap();
+    assert!(outputs[0].extensions_required.is_empty());
+    assert_eq!(outputs[1].extensions_required, ["KHR_mesh_quantization"]);
+
+    let mobile = gltf::Gltf::from_slice(&outputs[1].glb).unwrap();
+    let blob = mobile.blob.as_deref().unwrap();
+    assert_eq!(mobile.buffers().count(), 1);
+    assert_eq!(mobile.buffers().next().unwrap().length(), blob.len());
+    let primitive = mobile.meshes().next().unwrap().primitives().next().unwrap();
+    let normals = primitive.get(&gltf::Semantic::Normals).unwrap();
+    assert_eq!(normals.data_type(), gltf::accessor::DataType::I8);
+    let view = normals.view().unwrap();
+    assert!(view.offset() + view.length() <= blob.len());
+    assert!(view.offset() >= bin.len());
+}
+
+#[test]
+fn test_camera_paths() {
+    use gltf::animation::util::PropertyValue;
+    use gltf::camera_path::{self, Options};
+    use gltf::mesh::BoundingBox;
+
+    fn rotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+        let cross = |a: [f32; 3], b: [f32; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let axis = [q[0], q[1], q[2]];
+        let t = cross(axis, v).map(|x| 2.0 * x);
+        let u = cross(axis, t);
+        [0, 1, 2].map(|i| v[i] + q[3] * t[i] + u[i])
+    }
+    let close = |a: [f32; 3], b: [f32; 3]| (0..3).all(|i| (a[i] - b[i]).abs() < 1e-3);
+
+    let bounds = BoundingBox {
+        min: [0.0, 0.0, 0.0],
+        max: [2.0, 2.0, 2.0],
+    };
+    let options = Options::new().with_duration(4.0).with_sweep(0.0);
+    let mut root = gltf::json::Root {
+        scenes: gltf::json::deserialize::from_str(r#"[{ "nodes": [] }]"#).unwrap(),
+        ..Default::default()
+    };
+    let mut writer = Writer::new(&mut root);
+    let scene = gltf::json::Index::new(0);
+    assert!(
+        camera_path::orbit(&mut writer, gltf::json::Index::new(1), &bounds, &options).is_none()
+    );
+    let orbit = camera_path::orbit(&mut writer, scene, &bounds, &options).unwrap();
+    let turntable = camera_path::turntable(&mut writer, scene, &bounds, &options).unwrap();
+    let bin = writer.finish();
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+    let sample = |animation: usize, channel: usize, time: f32| {
+        let animation = document.animations().nth(animation).unwrap();
+        let channel = animation.channels().nth(channel).unwrap();
+        channel.sample(get_buffer_data, time).unwrap()
+    };
+
+    // The orbiting camera keeps looking at the center from the same distance.
+    let center = [1.0, 1.0, 1.0];
+    let mut distance = None;
+    for time in [0.0, 1.0, 2.5] {
+        let (translation, rotation) = match (
+            sample(orbit.animation.value(), 0, time),
+            sample(orbit.animation.value(), 1, time),
+        ) {
+            (PropertyValue::Translation(t), PropertyValue::Rotation(r)) => (t, r),
+            _ => panic!("unexpected channels"),
+        };
+        let offset = [0, 1, 2].map(|i| center[i] - translation[i]);
+        let length = offset.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let forward = rotate(rotation, [0.0, 0.0, -1.0]);
+        assert!(close(forward, offset.map(|x| x / length)));
+        assert!((length - *distance.get_or_insert(length)).abs() < 1e-2);
+        assert!(translation[1] > center[1]);
+    }
+
+    // After a quarter turn the turntable camera looks along -X.
+    let rotation = match sample(turntable.animation.value(), 0, 1.0) {
+        PropertyValue::Rotation(rotation) => rotation,
+        _ => panic!("unexpected channel"),
+    };
+    let camera = document.nodes().nth(turntable.node.value()).unwrap();
+    let (translation, local, _) = camera.transform().decomposed();
+    let position = rotate(rotation, translation);
+    assert!(position[0] > 0.0 && position[2].abs() < 1e-3);
+    let forward = rotate(rotation, rotate(local, [0.0, 0.0, -1.0]));
+    assert!(forward[0] < 0.0 && forward[1] < 0.0);
+    assert_eq!(document.scenes().next().unwrap().nodes().count(), 2);
+    assert_eq!(camera.camera().unwrap().index(), turntable.camera.value());
+}
+
+#[test]
+fn test_quantization() {
+    use gltf::accessor::packed::Format;
+    use gltf::quantize::{self, Options};
+    use gltf::Semantic;
+
+    let count = 64;
+    let angle = |i: usize| i as f32 * 0.7;
+    let positions: Vec<[f32; 3]> = (0..count)
+        .map(|i| [angle(i).cos(), angle(i).sin(), i as f32 / 63.0 - 0.5])
+        .collect();
+    let normals: Vec<[f32; 3]> = (0..count)
+        .map(|i| [angle(i).cos(), angle(i).sin(), 0.0])
+        .collect();
+    let tex_coords: Vec<[f32; 2]> = (0..count).map(|i| [i as f32 / 63.0, 0.5]).collect();
+    let tiled: Vec<[f32; 2]> = tex_coords.iter().map(|&[u, v]| [4.0 * u, v]).collect();
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    let target = Some(Target::ArrayBuffer);
+    writer.push(&positions, target);
+    writer.push(&normals, target);
+    writer.push(&tex_coords, target);
+    writer.push(&tiled, target);
+    let bin = writer.finish();
+    root.meshes = gltf::json::deserialize::from_str(
+        r#"[{ "primitives": [{ "attributes": {
+            "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2, "TEXCOORD_1": 3
+        } }] }]"#,
+    )
+    .unwrap();
+    root.nodes = gltf::json::deserialize::from_str(r#"[{ "mesh": 0 }]"#).unwrap();
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+
+    let reports = document.quantization(get_buffer_data, &Options::new());
+    let formats: Vec<_> = reports[0]
+        .attributes
+        .iter()
+        .map(|attribute| (attribute.semantic.clone(), attribute.format))
+        .collect();
+    assert_eq!(
+        formats,
+        [
+            (Semantic::Positions, Some(Format::Snorm16)),
+            (Semantic::Normals, Some(Format::Snorm8)),
+            (Semantic::TexCoords(0), Some(Format::Snorm16)),
+            (Semantic::TexCoords(1), None),
+        ]
+    );
+    assert!(reports[0].attributes[0].max_error <= 1.0 / 4096.0);
+    assert_eq!(reports[0].savings(), count * (4 + 8 + 4));
+    let coarse = Options::new().with_position_error(0.01);
+    let reports_coarse = document.quantization(get_buffer_data, &coarse);
+    assert_eq!(reports_coarse[0].attributes[0].format, Some(Format::Snorm8));
+
+    let mut root = document.clone().into_json();
+    let quantized = quantize::apply(&document, get_buffer_data, &reports, &mut root);
+    assert_eq!(quantized.len(), count * (8 + 4 + 4));
+    assert!(root
+        .extensions_required
+        .contains(&"KHR_mesh_quantization".to_owned()));
+    let document = gltf::Document::from_json(root).unwrap();
+    let primitive = document
+        .meshes()
+        .next()
+        .unwrap()
+        .primitives()
+        .next()
+        .unwrap();
+    let positions = primitive.get(&Semantic::Positions).unwrap();
+    assert!(positions.normalized());
+    assert_eq!(positions.data_type(), gltf::accessor::DataType::I16);
+    assert_eq!(primitive.get(&Semantic::TexCoords(1)).unwrap().index(), 3);
+    let node = document.nodes().next().unwrap();
+    assert!(node.mesh().is_none());
+    let child = node.children().next().unwrap();
+    assert_eq!(child.mesh().unwrap().index(), 0);
+    let (translation, _, scale) = child.transform().decomposed();
+    assert!(scale.iter().all(|&s| (s - 0.99).abs() < 1e-3));
+    assert!(translation[2].abs() < 1e-6);
+}
+
+#[test]
+fn test_skeleton_visualization() {
+    use gltf::skeleton::{self, Options};
+
+    let gltf = gltf::Gltf::from_slice(
+        br#"{
+            "asset": { "version": "2.0" },
+            "scenes": [{ "nodes": [0] }, { "nodes": [4] }],
+            "nodes": [
+                { "name": "root", "children": [1], "translation": [0, 0, 2] },
+                { "name": "hips", "children": [2] },
+                { "name": "offset", "children": [3], "translation": [0, 1, 0] },
+                { "name": "spine", "translation": [0, 1, 0] },
+                {}
+            ],
+            "skins": [{ "joints": [1, 3] }, { "joints": [3] }]
+        }"#,
+    )
+    .unwrap();
+    let scenes: Vec<_> = gltf.scenes().collect();
+
+    let mut root = gltf::json::Root::default();
+    let mut writer = Writer::new(&mut root);
+    assert!(skeleton::write(&mut writer, &scenes[1], &Options::new()).is_none());
+    let options = Options::new().with_labels(true).with_joint_radius(0.1);
+    let scene = skeleton::write(&mut writer, &scenes[0], &options).unwrap();
+    let bin = writer.finish();
+    let document = gltf::Document::from_json(root).unwrap();
+    let get_buffer_data = |_: gltf::Buffer| Some(bin.as_slice());
+
+    let scene = document.scenes().nth(scene.value()).unwrap();
+    let labels: Vec<_> = scene
+        .nodes()
+        .skip(1)
+        .map(|node| (node.name(), node.transform().decomposed().0))
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            (Some("hips"), [0.0, 0.0, 2.0]),
+            (Some("spine"), [0.0, 2.0, 2.0])
+        ]
+    );
+    let mesh = document.meshes().next().unwrap();
+    let primitives: Vec<_> = mesh.primitives().collect();
+    let reader = primitives[0].reader(get_buffer_data);
+    let positions: Vec<_> = reader.read_positions().unwrap().collect();
+    assert_eq!(positions.len(), 12);
+    assert_eq!(positions[0], [0.1, 0.0, 2.0]);
+    assert_eq!(primitives[1].mode(), gltf::mesh::Mode::Lines);
+    let reader = primitives[1].reader(get_buffer_data);
+    let bones: Vec<_> = reader.read_indices().unwrap().into_u32().collect();
+    assert_eq!(bones, [0, 1]);
+}
+
+#[test]
+fn test_texture_budget_downscale() {
+    use gltf::export::Exporter;
+    use gltf::streaming::TextureUsage;
+    use gltf::texture_budget::{Budget, Downscale};
+
+    let slice = br#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "orange_64x32.jpg" }],
+        "textures": [{ "source": 0 }],
+        "materials": [
+            { "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } },
+            { "normalTexture": { "index": 0 } }
+        ]
+    }"#;
+    let base = std::path::Path::new("tests");
+    let (document, _, images) =
+        gltf::import_slice(slice, Some(base), gltf::filesystem_fetcher).unwrap();
+
+    let budget = Budget::new().with_max_dimension(TextureUsage::Normal, 16);
+    match document.check_texture_budget(&images, &budget) {
+        Err(gltf::Error::Validation(errors)) => {
+            let paths: Vec<_> = errors.iter().map(|(path, _)| path.as_str()).collect();
+            assert_eq!(paths, ["materials[1].normalTexture"]);
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    let unlimited = Budget::new().with_max_dimension(TextureUsage::BaseColor, 64);
+    assert!(document.check_texture_budget(&images, &unlimited).is_ok());
+
+    let mut downscale = Downscale::new(&images, budget);
+    let glb = Exporter::new()
+        .with_hook(&mut downscale)
+        .to_glb(document.clone().into_json(), Vec::new())
+        .unwrap();
+    let report = downscale.report();
+    assert!(report.skipped.is_empty());
+    assert_eq!(report.changes.len(), 1);
+    let change = &report.changes[0];
+    assert_eq!(
+        change.usages,
+        [TextureUsage::BaseColor, TextureUsage::Normal]
+    );
+    assert_eq!((change.from, change.to), ([64, 32], [16, 8]));
+    assert_eq!(change.encoding, gltf::image::Encoding::Jpeg);
+    #[cfg(feature = "parallel")]
+    {
+        let mut parallel = Downscale::new(&images, budget).with_threads(2);
+        let parallel_glb = Exporter::new()
+            .with_hook(&mut parallel)
+            .to_glb(document.clone().into_json(), Vec::new())
+            .unwrap();
+        assert_eq!(parallel.report(), report);
+        assert_eq!(parallel_glb, glb);
+    }
+
+    let (downscaled, _, downscaled_images) =
+        gltf::import_slice(&glb, None, gltf::filesystem_fetcher).unwrap();
+    assert!(downscaled
+        .check_texture_budget(&downscaled_images, &budget)
+        .is_ok());
+    assert_eq!(
+        (downscaled_images[0].width, downscaled_images[0].height),
+        (16, 8)
+    );
+
+    // Standard glTF embeds the new image as a data URI.
+    let mut downscale = Downscale::new(
+        &images,
+        Budget::new().with_max_dimension(TextureUsage::Normal, 4),
+    );
+    let gltf = Exporter::new()
+        .with_hook(&mut downscale)
+        .to_gltf(document.into_json())
+        .unwrap();
+    assert_eq!(downscale.report().changes[0].to, [4, 2]);
+    let (_, _, images) = gltf::import_slice(&gltf, None, gltf::filesystem_fetcher).unwrap();
+    assert_eq!((images[0].width, images[0].height), (4, 2));
+}
+
+#[test]
+fn test_glb_appender() {
+    use gltf::json::mesh::Mode;
+    use gltf::GlbAppender;
+    use std::io::Cursor;
+
+    let file = Cursor::new(std::fs::read("examples/Box.glb").unwrap());
+    let mut appender = GlbAppender::open(file).unwrap();
+    let bin_length = appender.root().buffers[0].byte_length;
+    match appender.append_mesh(&[], None, Mode::Points) {
+        Err(gltf::Error::Validation(errors)) => {
+            assert_eq!(
+                errors[0].0.as_str(),
+                "meshes[1].primitives[0].attributes[\"POSITION\"]"
+            );
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    let node = appender
+        .append_mesh(&[[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]], None, Mode::Points)
+        .unwrap();
+    let glb = appender.finish().unwrap().into_inner();
+
+    let gltf = gltf::Gltf::from_slice(&glb).unwrap();
+    let blob = gltf.blob.as_deref().unwrap();
+    assert!(blob.len() > bin_length as usize);
+    let nodes: Vec<_> = gltf.default_scene().unwrap().nodes().collect();
+    assert_eq!(nodes.last().unwrap().index(), node.value());
+    let points = nodes.last().unwrap().mesh().unwrap();
+    let primitive = points.primitives().next().unwrap();
+    assert_eq!(primitive.mode(), gltf::mesh::Mode::Points);
+    let reader = primitive.reader(|_| Some(blob));
+    let positions: Vec<_> = reader.read_positions().unwrap().collect();
+    assert_eq!(positions, [[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]]);
+    let box_count = |gltf: &gltf::Gltf| {
+        let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+        let reader = primitive.reader(|_| gltf.blob.as_deref());
+        reader.read_positions().unwrap().count()
+    };
+    assert_eq!(box_count(&gltf), 24);
+
+    // Outgrowing the JSON chunk moves the `BIN` chunk, doubling the capacity
+    // of the JSON chunk each time.
+    let mut appender = GlbAppender::open(Cursor::new(glb)).unwrap();
+    for frame in 0..100 {
+        let indices = [0, 1, 2];
+        let triangle = [[frame as f32, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        appender
+            .append_mesh(&triangle, Some(&indices), Mode::Triangles)
+            .unwrap();
+        appender.flush().unwrap();
+    }
+    assert!((1..8).contains(&appender.relocations()));
+    let glb = appender.finish().unwrap().into_inner();
+    let gltf = gltf::Gltf::from_slice(&glb).unwrap();
+    let blob = gltf.blob.as_deref().unwrap();
+    assert_eq!(gltf.meshes().count(), 102);
+    let mesh = gltf.meshes().last().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    let reader = primitive.reader(|_| Some(blob));
+    assert_eq!(
+        reader.read_positions().unwrap().next(),
+        Some([99.0, 0.0, 0.0])
+    );
+    assert_eq!(box_count(&gltf), 24);
+
+    // Trailing chunks cannot be appended to.
+    let mut glb = glb;
+    glb.extend_from_slice(&[0, 0, 0, 0, b'X', b'T', b'R', b'A']);
+    let length = glb.len() as u32;
+    glb[8..12].copy_from_slice(&length.to_le_bytes());
+    assert!(matches!(
+        GlbAppender::open(Cursor::new(glb)),
+        Err(gltf::Error::Binary(gltf::binary::Error::ChunkLength { .. }))
+    ));
+}